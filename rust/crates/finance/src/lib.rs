@@ -0,0 +1,51 @@
+//! Unified facade for the financial calculation libraries
+//!
+//! This crate re-exports the workspace libraries under a single namespace so
+//! downstream users depend on one crate version instead of keeping several
+//! in sync. Each subsystem sits behind a feature flag of the same name:
+//!
+//! | Feature     | Module        | Enabled by default |
+//! |-------------|---------------|--------------------|
+//! | `pricing`   | [`pricing`]   | yes                |
+//! | `indicator` | [`indicator`] | yes                |
+//!
+//! The `serde` feature forwards to `pricing/serde` (when `pricing` is enabled)
+//! so facade users can serialize scenarios without naming `pricing` directly.
+//!
+//! # Example
+//!
+//! ```
+//! use finance::prelude::*;
+//!
+//! let params = OptionParams {
+//!     spot_price: 100.0,
+//!     strike_price: 100.0,
+//!     time_to_expiry: 1.0,
+//!     risk_free_rate: 0.05,
+//!     volatility: 0.2,
+//!     dividend_yield: 0.0,
+//! };
+//! let option = BlackScholes::price(&params, OptionType::Call)?;
+//!
+//! let ema = EMA::new(3)?;
+//! let smoothed = ema.calculate(&[10.0, 11.0, 12.0, 13.0])?;
+//!
+//! println!("Call price: {:.2}, last EMA: {:?}", option.price, smoothed.last());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+/// Option pricing models and Greeks
+#[cfg(feature = "pricing")]
+pub use pricing;
+
+/// Technical analysis indicators
+#[cfg(feature = "indicator")]
+pub use indicator;
+
+/// Commonly used types from every enabled subsystem
+pub mod prelude {
+    #[cfg(feature = "indicator")]
+    pub use indicator::{IndicatorError, EMA};
+    #[cfg(feature = "pricing")]
+    pub use pricing::{BlackScholes, OptionParams, OptionType, PricingError, PricingResult};
+}
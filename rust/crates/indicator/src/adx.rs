@@ -0,0 +1,316 @@
+//! Average Directional Index (ADX) with directional indicators
+
+use crate::atr::true_range;
+use crate::error::check_equal_lengths;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Directional indicators and ADX for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdxOutput {
+    /// Positive directional indicator (+DI), 0-100
+    pub plus_di: f64,
+    /// Negative directional indicator (−DI), 0-100
+    pub minus_di: f64,
+    /// Average directional index; `None` until `period` DX values exist
+    pub adx: Option<f64>,
+}
+
+/// Average Directional Index indicator
+///
+/// Wilder's trend-strength measure. For each bar after the first:
+///
+/// * +DM = H − H_prev if it exceeds L_prev − L and is positive, else 0
+/// * −DM = L_prev − L if it exceeds H − H_prev and is positive, else 0
+/// * +DI = 100 × smoothed(+DM) / smoothed(TR), likewise −DI
+/// * DX = 100 × |+DI − −DI| / (+DI + −DI)
+///
+/// The sums are Wilder-smoothed (`S = S − S / period + x`, seeded with the
+/// sum of the first `period` values) and ADX is the Wilder average of DX.
+/// DI values are available from bar `period`, ADX from bar `2 × period − 1`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ADX;
+///
+/// // A steady uptrend
+/// let high: Vec<f64> = (0..30).map(|i| 101.0 + i as f64).collect();
+/// let low: Vec<f64> = (0..30).map(|i| 99.0 + i as f64).collect();
+/// let close: Vec<f64> = (0..30).map(|i| 100.5 + i as f64).collect();
+///
+/// let result = ADX::new(14)?.calculate(&high, &low, &close)?;
+/// let last = result[29].unwrap();
+/// assert!(last.plus_di > last.minus_di);
+/// assert!(last.adx.unwrap() > 25.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ADX {
+    period: usize,
+    /// Previous bar's high, low and close
+    prev: Option<(f64, f64, f64)>,
+    /// Bars with directional movement seen so far (capped once seeded)
+    count: usize,
+    /// Wilder-smoothed true range, +DM and −DM
+    smoothed: [f64; 3],
+    /// Sum of DX values during ADX warmup
+    dx_sum: f64,
+    dx_count: usize,
+    adx: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ADX {
+    /// Creates a new ADX indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Wilder smoothing period (must be > 0, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        Ok(Self {
+            period,
+            prev: None,
+            count: 0,
+            smoothed: [0.0; 3],
+            dx_sum: 0.0,
+            dx_count: 0,
+            adx: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates DI and ADX values for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<AdxOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        self.check_length(len)?;
+
+        let mut state = Self::new(self.period)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates DI and ADX values for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<AdxOutput>>, IndicatorError> {
+        self.check_length(candles.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<AdxOutput> {
+        self.undo = Snapshot::of(self);
+        let (prev_high, prev_low, prev_close) = self.prev.replace((high, low, close))?;
+
+        let up = high - prev_high;
+        let down = prev_low - low;
+        let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+        let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+        let values = [true_range(high, low, Some(prev_close)), plus_dm, minus_dm];
+
+        let period = self.period as f64;
+        if self.count < self.period {
+            self.count += 1;
+            for (sum, value) in self.smoothed.iter_mut().zip(values) {
+                *sum += value;
+            }
+            if self.count < self.period {
+                return None;
+            }
+        } else {
+            for (sum, value) in self.smoothed.iter_mut().zip(values) {
+                *sum += value - *sum / period;
+            }
+        }
+
+        let [tr, plus, minus] = self.smoothed;
+        let (plus_di, minus_di) = if tr > 0.0 {
+            (100.0 * plus / tr, 100.0 * minus / tr)
+        } else {
+            (0.0, 0.0)
+        };
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum > 0.0 {
+            100.0 * (plus_di - minus_di).abs() / di_sum
+        } else {
+            0.0
+        };
+
+        self.adx = match self.adx {
+            Some(adx) => Some((adx * (period - 1.0) + dx) / period),
+            None => {
+                self.dx_sum += dx;
+                self.dx_count += 1;
+                (self.dx_count == self.period).then(|| self.dx_sum / period)
+            }
+        };
+
+        Some(AdxOutput {
+            plus_di,
+            minus_di,
+            adx: self.adx,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<AdxOutput> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.count = 0;
+        self.smoothed = [0.0; 3];
+        self.dx_sum = 0.0;
+        self.dx_count = 0;
+        self.adx = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the smoothing period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    fn check_length(&self, len: usize) -> Result<(), IndicatorError> {
+        if len <= self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period + 1,
+                actual: len,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trend(len: usize, step: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let mid: Vec<f64> = (0..len).map(|i| 100.0 + step * i as f64).collect();
+        (
+            mid.iter().map(|m| m + 1.0).collect(),
+            mid.iter().map(|m| m - 1.0).collect(),
+            mid,
+        )
+    }
+
+    #[test]
+    fn test_adx_warmup() {
+        let (high, low, close) = trend(20, 0.5);
+        let result = ADX::new(5).unwrap().calculate(&high, &low, &close).unwrap();
+
+        assert!(result[4].is_none());
+        assert!(result[5].is_some());
+        assert_eq!(result[8].unwrap().adx, None);
+        assert!(result[9].unwrap().adx.is_some());
+    }
+
+    #[test]
+    fn test_downtrend_favours_minus_di() {
+        let (high, low, close) = trend(40, -0.8);
+        let result = ADX::new(14)
+            .unwrap()
+            .calculate(&high, &low, &close)
+            .unwrap();
+        let last = result[39].unwrap();
+
+        // Pure downtrend: no positive directional movement at all
+        assert_eq!(last.plus_di, 0.0);
+        assert!(last.minus_di > 0.0);
+        assert!((last.adx.unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_directional_movement_values() {
+        // One bar: high up 2, low up 1 -> +DM 2, -DM 0, TR 3
+        let result = ADX::new(1)
+            .unwrap()
+            .calculate(&[10.0, 12.0], &[8.0, 9.0], &[9.0, 11.0])
+            .unwrap();
+        let output = result[1].unwrap();
+        assert!((output.plus_di - 100.0 * 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(output.minus_di, 0.0);
+        assert_eq!(output.adx, Some(100.0));
+    }
+
+    #[test]
+    fn test_adx_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = (0..30)
+            .map(|i| {
+                let base = 50.0 + (i as f64 * 0.5).sin() * 4.0;
+                Candle::new(i, base, base + 1.2, base - 0.8, base + 0.3, 1.0)
+            })
+            .collect();
+        let adx = ADX::new(7).unwrap();
+        let batch = adx.calculate_candles(&candles).unwrap();
+
+        let mut streaming = adx.clone();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| streaming.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        streaming.reset();
+        assert_eq!(streaming.next(1.0, 0.0, 0.5), None);
+    }
+
+    #[test]
+    fn test_adx_invalid_inputs() {
+        assert!(ADX::new(0).is_err());
+        let adx = ADX::new(3).unwrap();
+        assert!(adx.calculate(&[1.0; 3], &[1.0; 3], &[1.0; 3]).is_err());
+        assert!(adx.calculate(&[1.0; 5], &[1.0; 4], &[1.0; 5]).is_err());
+    }
+}
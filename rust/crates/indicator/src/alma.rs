@@ -0,0 +1,252 @@
+//! Arnaud Legoux Moving Average (ALMA)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Arnaud Legoux Moving Average indicator
+///
+/// Weights the window with a Gaussian curve whose peak sits `offset` of the
+/// way from the oldest to the newest value:
+///
+/// m = offset × (window − 1)
+/// s = window / sigma
+/// w(i) = exp(−(i − m)² / (2s²)),  i = 0..window, oldest first
+/// ALMA = Σ w(i) × Price(i) / Σ w(i)
+///
+/// An offset near 1 tracks price closely, one near 0 smooths heavily; a
+/// larger `sigma` narrows the curve. The normalised weights are computed
+/// once in [`new`](Self::new), so batch evaluation is a plain dot product
+/// per window.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ALMA;
+///
+/// let alma = ALMA::new(9, 0.85, 6.0)?;
+/// let prices: Vec<f64> = (0..12).map(|i| i as f64).collect();
+/// let result = alma.calculate(&prices)?;
+///
+/// assert!(result[7].is_none());
+/// // On a straight line ALMA sits at the weights' centre of mass
+/// let centre: f64 = alma.weights().iter().enumerate().map(|(i, w)| i as f64 * w).sum();
+/// assert!((result[8].unwrap() - centre).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ALMA {
+    offset: f64,
+    sigma: f64,
+    /// Normalised Gaussian weights, oldest value first
+    weights: Vec<f64>,
+    window: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ALMA {
+    /// Creates a new ALMA indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of values in the window (must be > 0, typically 9)
+    /// * `offset` - Position of the Gaussian peak within the window (must be
+    ///   between 0 and 1, typically 0.85)
+    /// * `sigma` - Sharpness of the Gaussian curve (must be > 0, typically 6)
+    pub fn new(window: usize, offset: f64, sigma: f64) -> Result<Self, IndicatorError> {
+        if window == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "window",
+                value: window as f64,
+                constraint: "> 0",
+            });
+        }
+        if !(0.0..=1.0).contains(&offset) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "offset",
+                value: offset,
+                constraint: "between 0 and 1",
+            });
+        }
+        if sigma.is_nan() || sigma <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "sigma",
+                value: sigma,
+                constraint: "> 0",
+            });
+        }
+
+        let m = offset * (window - 1) as f64;
+        let s = window as f64 / sigma;
+        let mut weights: Vec<f64> = (0..window)
+            .map(|i| (-(i as f64 - m).powi(2) / (2.0 * s * s)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        weights.iter_mut().for_each(|w| *w /= total);
+
+        Ok(Self {
+            offset,
+            sigma,
+            weights,
+            window: VecDeque::with_capacity(window),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates ALMA for a batch of prices
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `window - 1`
+    /// values are `None` as there isn't enough data.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let window = self.weights.len();
+        check_min_length(window, prices.len())?;
+
+        let mut result = vec![None; window - 1];
+        result.extend(
+            prices
+                .windows(window)
+                .map(|values| Some(dot(&self.weights, values))),
+        );
+        Ok(result)
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `window` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.weights.len() {
+            self.window.pop_front();
+        }
+        self.window.push_back(price);
+        if self.window.len() < self.weights.len() {
+            return None;
+        }
+
+        Some(dot(&self.weights, &self.window))
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the window length
+    pub fn period(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns the Gaussian peak offset
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Returns the Gaussian sharpness
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Returns the normalised weights, oldest value first
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+fn dot<'a>(weights: &[f64], values: impl IntoIterator<Item = &'a f64>) -> f64 {
+    weights.iter().zip(values).map(|(w, v)| w * v).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alma_weights() {
+        let alma = ALMA::new(9, 0.85, 6.0).unwrap();
+        let weights = alma.weights();
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // Peak at offset × (window − 1) = 6.8, so bar 7 weighs the most
+        let peak = (0..9)
+            .max_by(|&a, &b| weights[a].total_cmp(&weights[b]))
+            .unwrap();
+        assert_eq!(peak, 7);
+
+        let raw = |i: f64| (-(i - 6.8_f64).powi(2) / (2.0 * 1.5_f64.powi(2))).exp();
+        let total: f64 = (0..9).map(|i| raw(i as f64)).sum();
+        assert!((weights[0] - raw(0.0) / total).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_alma_offset_controls_lag() {
+        let prices: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let last = |offset: f64| {
+            ALMA::new(10, offset, 6.0)
+                .unwrap()
+                .calculate(&prices)
+                .unwrap()[19]
+                .unwrap()
+        };
+        // Smaller offsets centre the weights on older prices
+        assert!(last(0.1) < last(0.5));
+        assert!(last(0.5) < last(0.9));
+        assert!((last(0.5) - 14.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_alma_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..25).map(|i| 40.0 + (i as f64 * 0.6).sin()).collect();
+        let mut alma = ALMA::new(7, 0.85, 6.0).unwrap();
+        let batch = alma.calculate(&prices).unwrap();
+        for (price, expected) in prices.iter().zip(&batch) {
+            match (alma.next(*price), expected) {
+                (Some(a), Some(b)) => assert!((a - b).abs() < 1e-12),
+                (a, b) => assert_eq!(a, *b),
+            }
+        }
+
+        alma.reset();
+        assert_eq!(alma.next(1.0), None);
+        assert_eq!((alma.period(), alma.offset(), alma.sigma()), (7, 0.85, 6.0));
+    }
+
+    #[test]
+    fn test_alma_invalid_inputs() {
+        assert!(ALMA::new(0, 0.85, 6.0).is_err());
+        assert!(ALMA::new(9, 1.5, 6.0).is_err());
+        assert!(ALMA::new(9, f64::NAN, 6.0).is_err());
+        assert!(ALMA::new(9, 0.85, 0.0).is_err());
+        assert!(matches!(
+            ALMA::new(9, 0.85, 6.0).unwrap().calculate(&[1.0; 8]),
+            Err(IndicatorError::InsufficientData {
+                required: 9,
+                actual: 8
+            })
+        ));
+    }
+}
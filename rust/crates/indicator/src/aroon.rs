@@ -0,0 +1,253 @@
+//! Aroon Up/Down and Aroon Oscillator
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, RollingMax, RollingMin};
+
+/// Aroon values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AroonOutput {
+    /// 100 × (period − bars since the highest high) / period
+    pub up: f64,
+    /// 100 × (period − bars since the lowest low) / period
+    pub down: f64,
+    /// Aroon Up minus Aroon Down, −100 to 100
+    pub oscillator: f64,
+}
+
+/// Aroon indicator
+///
+/// Measures how recently the window's extremes were set. The window covers
+/// the current bar and the `period` bars before it, so the first value is
+/// available at bar `period`. Ties resolve to the most recent bar.
+///
+/// The extremes are tracked with [`RollingMax`] and [`RollingMin`], so each
+/// update is amortised O(1) regardless of the period.
+///
+/// # Example
+///
+/// ```
+/// use indicator::Aroon;
+///
+/// let high = [10.0, 11.0, 12.0, 11.5, 11.0];
+/// let low = [9.0, 9.5, 10.5, 10.0, 8.5];
+///
+/// let result = Aroon::new(4)?.calculate(&high, &low)?;
+/// let last = result[4].unwrap();
+/// assert_eq!(last.up, 50.0); // highest high two bars ago
+/// assert_eq!(last.down, 100.0); // lowest low on this bar
+/// assert_eq!(last.oscillator, -50.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Aroon {
+    period: usize,
+    /// Highs and lows of the current bar and the `period` bars before it
+    highs: RollingMax,
+    lows: RollingMin,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Aroon {
+    /// Creates a new Aroon indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Lookback in bars (must be > 0, typically 25)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            highs: RollingMax::new(period + 1)?,
+            lows: RollingMin::new(period + 1)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates Aroon values for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+    ) -> Result<Vec<Option<AroonOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[("high", high.len()), ("low", low.len())])?;
+        check_min_length(self.period + 1, len)?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(high
+            .iter()
+            .zip(low)
+            .map(|(&high, &low)| state.next(high, low))
+            .collect())
+    }
+
+    /// Calculates Aroon values for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<AroonOutput>>, IndicatorError> {
+        check_min_length(self.period + 1, candles.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period + 1` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64) -> Option<AroonOutput> {
+        self.undo = Snapshot::of(self);
+        // Both windows fill on the same bar
+        let full = self.highs.next(high).is_some();
+        self.lows.next(low);
+        if !full {
+            return None;
+        }
+        let (high_age, low_age) = self.highs.age().zip(self.lows.age())?;
+
+        let period = self.period as f64;
+        let up = 100.0 * (period - high_age as f64) / period;
+        let down = 100.0 * (period - low_age as f64) / period;
+        Some(AroonOutput {
+            up,
+            down,
+            oscillator: up - down,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64) -> Option<AroonOutput> {
+        self.rollback();
+        self.next(high, low)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the lookback period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Direct O(period) computation for comparison
+    fn naive(high: &[f64], low: &[f64], period: usize, i: usize) -> (f64, f64) {
+        let window = i - period..=i;
+        let high_index = window
+            .clone()
+            .max_by(|&a, &b| high[a].partial_cmp(&high[b]).unwrap())
+            .unwrap();
+        let low_index = window
+            .rev()
+            .min_by(|&a, &b| low[a].partial_cmp(&low[b]).unwrap())
+            .unwrap();
+        let p = period as f64;
+        (
+            100.0 * (p - (i - high_index) as f64) / p,
+            100.0 * (p - (i - low_index) as f64) / p,
+        )
+    }
+
+    #[test]
+    fn test_aroon_matches_naive_window_scan() {
+        let high: Vec<f64> = (0..60)
+            .map(|i| 20.0 + (i as f64 * 0.37).sin() * 3.0 + ((i * 7) % 5) as f64 * 0.2)
+            .collect();
+        let low: Vec<f64> = high.iter().map(|h| h - 1.0).collect();
+        let period = 7;
+        let result = Aroon::new(period).unwrap().calculate(&high, &low).unwrap();
+
+        assert!(result[period - 1].is_none());
+        for (i, output) in result.iter().enumerate().skip(period) {
+            let output = output.unwrap();
+            let (up, down) = naive(&high, &low, period, i);
+            assert_eq!((output.up, output.down), (up, down), "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_aroon_ties_use_most_recent_bar() {
+        let mut aroon = Aroon::new(2).unwrap();
+        aroon.next(5.0, 1.0);
+        aroon.next(5.0, 1.0);
+        let output = aroon.next(4.0, 2.0).unwrap();
+        assert_eq!(output.up, 50.0);
+        assert_eq!(output.down, 50.0);
+    }
+
+    #[test]
+    fn test_aroon_candles_and_reset() {
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| {
+                let base = 100.0 + i as f64;
+                Candle::new(i, base, base + 1.0, base - 1.0, base, 1.0)
+            })
+            .collect();
+        let aroon = Aroon::new(5).unwrap();
+        let result = aroon.calculate_candles(&candles).unwrap();
+        let last = result[9].unwrap();
+        assert_eq!((last.up, last.down, last.oscillator), (100.0, 0.0, 100.0));
+
+        let mut streaming = aroon.clone();
+        for c in &candles {
+            streaming.next(c.high, c.low);
+        }
+        streaming.reset();
+        assert_eq!(streaming.next(1.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_aroon_invalid_inputs() {
+        assert!(Aroon::new(0).is_err());
+        let aroon = Aroon::new(5).unwrap();
+        assert!(matches!(
+            aroon.calculate(&[1.0; 5], &[1.0; 5]),
+            Err(IndicatorError::InsufficientData {
+                required: 6,
+                actual: 5
+            })
+        ));
+        assert!(aroon.calculate(&[1.0; 6], &[1.0; 7]).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Average True Range (ATR)
+
+use crate::error::check_equal_lengths;
+use crate::{Candle, IndicatorError};
+
+/// True range and ATR for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtrOutput {
+    /// True range of the bar
+    pub true_range: f64,
+    /// Wilder-smoothed ATR; `None` until `period` bars have been seen
+    pub atr: Option<f64>,
+}
+
+/// Average True Range indicator with Wilder smoothing
+///
+/// The true range extends the bar's high-low range to include any gap from
+/// the previous close:
+///
+/// TR = max(High − Low, |High − Close_prev|, |Low − Close_prev|)
+///
+/// The first ATR is the simple average of the first `period` true ranges;
+/// later values use `ATR = (ATR_prev × (period − 1) + TR) / period`. The
+/// first bar has no previous close, so its true range is `High − Low`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ATR;
+///
+/// let atr = ATR::new(3)?;
+/// let high = [10.0, 11.0, 12.0, 11.5];
+/// let low = [9.0, 10.0, 10.5, 10.0];
+/// let close = [9.5, 10.8, 11.0, 10.2];
+///
+/// let result = atr.calculate(&high, &low, &close)?;
+/// assert_eq!(result[1].true_range, 1.5); // gap up from 9.5 to a high of 11.0
+/// assert!(result[1].atr.is_none());
+/// assert!(result[2].atr.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ATR {
+    period: usize,
+    prev_close: Option<f64>,
+    /// Sum of true ranges during warmup
+    sum: f64,
+    count: usize,
+    value: Option<f64>,
+    /// `prev_close`, `sum`, `count` and `value` before the last `next`, or
+    /// `None` when there is nothing to roll back
+    undo: Option<(Option<f64>, f64, usize, Option<f64>)>,
+}
+
+impl ATR {
+    /// Creates a new ATR indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Wilder smoothing period (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        Ok(Self {
+            period,
+            prev_close: None,
+            sum: 0.0,
+            count: 0,
+            value: None,
+            undo: None,
+        })
+    }
+
+    /// Calculates true range and ATR for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<AtrOutput>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        if len < self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period,
+                actual: len,
+            });
+        }
+
+        let mut state = Self::new(self.period)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates true range and ATR for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(&self, candles: &[Candle]) -> Result<Vec<AtrOutput>, IndicatorError> {
+        if candles.len() < self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period,
+                actual: candles.len(),
+            });
+        }
+
+        let mut state = Self::new(self.period)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> AtrOutput {
+        self.undo = Some((self.prev_close, self.sum, self.count, self.value));
+        let true_range = true_range(high, low, self.prev_close);
+        self.prev_close = Some(close);
+
+        let period = self.period as f64;
+        self.value = match self.value {
+            Some(atr) => Some((atr * (period - 1.0) + true_range) / period),
+            None => {
+                self.sum += true_range;
+                self.count += 1;
+                (self.count == self.period).then(|| self.sum / period)
+            }
+        };
+        AtrOutput {
+            true_range,
+            atr: self.value,
+        }
+    }
+
+    /// Current ATR value
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> AtrOutput {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until the
+    /// next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some((prev_close, sum, count, value)) = self.undo.take() {
+            self.prev_close = prev_close;
+            self.sum = sum;
+            self.count = count;
+            self.value = value;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev_close = None;
+        self.sum = 0.0;
+        self.count = 0;
+        self.value = None;
+        self.undo = None;
+    }
+
+    /// Returns the smoothing period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+/// True range of a bar given the previous close, if any
+pub(crate) fn true_range(high: f64, low: f64, prev_close: Option<f64>) -> f64 {
+    match prev_close {
+        Some(prev) => (high - low)
+            .max((high - prev).abs())
+            .max((low - prev).abs()),
+        None => high - low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_true_range_includes_gaps() {
+        assert_eq!(true_range(12.0, 10.0, None), 2.0);
+        // Gap up: previous close below the bar's low
+        assert_eq!(true_range(12.0, 11.0, Some(9.0)), 3.0);
+        // Gap down: previous close above the bar's high
+        assert_eq!(true_range(8.0, 7.5, Some(10.0)), 2.5);
+    }
+
+    #[test]
+    fn test_atr_wilder_smoothing() {
+        let high = [10.0, 11.0, 12.0, 13.0, 12.0];
+        let low = [9.0, 10.0, 11.0, 11.0, 10.0];
+        let close = [9.5, 10.5, 11.5, 12.0, 11.0];
+        let result = ATR::new(3).unwrap().calculate(&high, &low, &close).unwrap();
+
+        let ranges: Vec<f64> = result.iter().map(|o| o.true_range).collect();
+        assert_eq!(ranges, vec![1.0, 1.5, 1.5, 2.0, 2.0]);
+        assert_eq!(result[1].atr, None);
+        assert!((result[2].atr.unwrap() - 4.0 / 3.0).abs() < 1e-12);
+        let expected = (4.0 / 3.0 * 2.0 + 2.0) / 3.0;
+        assert!((result[3].atr.unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_atr_candles_match_slices() {
+        let candles: Vec<Candle> = (0..6)
+            .map(|i| {
+                let base = 100.0 + (i as f64).sin() * 3.0;
+                Candle::new(i, base, base + 1.5, base - 1.0, base + 0.5, 1_000.0)
+            })
+            .collect();
+        let high: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let low: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        let atr = ATR::new(4).unwrap();
+        assert_eq!(
+            atr.calculate_candles(&candles).unwrap(),
+            atr.calculate(&high, &low, &close).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_atr_update_last_revises_current_bar() {
+        let mut atr = ATR::new(2).unwrap();
+        atr.next(11.0, 9.0, 10.0);
+        atr.next(14.0, 10.0, 13.0);
+        let expected = atr.clone().next(13.0, 12.0, 12.5);
+
+        atr.next(20.0, 12.0, 19.0);
+        assert_eq!(atr.update_last(13.0, 12.0, 12.5), expected);
+        atr.rollback();
+        assert_eq!(atr.value(), Some(3.0));
+    }
+
+    #[test]
+    fn test_atr_invalid_inputs() {
+        assert!(ATR::new(0).is_err());
+        let atr = ATR::new(3).unwrap();
+        assert!(matches!(
+            atr.calculate(&[1.0, 2.0, 3.0], &[1.0, 2.0], &[1.0, 2.0, 3.0]),
+            Err(IndicatorError::LengthMismatch { field: "low", .. })
+        ));
+        assert!(atr.calculate(&[1.0], &[1.0], &[1.0]).is_err());
+    }
+}
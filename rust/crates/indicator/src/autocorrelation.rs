@@ -0,0 +1,422 @@
+//! Autocorrelation and Ljung-Box diagnostics for serial dependence
+//!
+//! [`acf`] gives the sample autocorrelation of a whole series at lags
+//! `1..=max_lag`, [`ljung_box`] tests whether those autocorrelations are
+//! jointly zero, and [`RollingAutocorrelation`] tracks chosen lags over a
+//! sliding window. Returns with no serial dependence have autocorrelations
+//! within about `±2/√n` of zero and a large Ljung-Box p-value.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::autocorrelation::{acf, ljung_box};
+//!
+//! // Returns that alternate in sign are strongly negatively autocorrelated
+//! let returns: Vec<f64> = (0..100)
+//!     .map(|i| if i % 2 == 0 { 0.01 } else { -0.008 })
+//!     .collect();
+//!
+//! let rho = acf(&returns, 2)?;
+//! assert!(rho[0] < -0.9 && rho[1] > 0.9);
+//!
+//! let test = ljung_box(&returns, 5)?;
+//! assert!(test.p_value < 0.01);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Result of a Ljung-Box test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LjungBox {
+    /// Q statistic, `n(n + 2) Σₖ ρₖ² / (n − k)`
+    pub statistic: f64,
+    /// Number of lags tested, the degrees of freedom of the χ² reference
+    pub lags: usize,
+    /// Probability of a statistic at least this large if there is no autocorrelation
+    pub p_value: f64,
+}
+
+/// Sample autocorrelations at lags `1..=max_lag`
+///
+/// Uses the standard estimator `ρₖ = Σ (xₜ − x̄)(xₜ₊ₖ − x̄) / Σ (xₜ − x̄)²`
+/// with the full-sample mean. A constant series has no variance and
+/// reports `0.0` at every lag.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `max_lag` is zero and `InsufficientData`
+/// for fewer than `max_lag + 1` values.
+pub fn acf(values: &[f64], max_lag: usize) -> Result<Vec<f64>, IndicatorError> {
+    check_lag("max_lag", max_lag)?;
+    check_min_length(max_lag + 1, values.len())?;
+    Ok(autocorrelations(values, 1..=max_lag))
+}
+
+/// Ljung-Box test for autocorrelation up to `lags`
+///
+/// The p-value comes from a χ² distribution with `lags` degrees of freedom.
+/// When testing the residuals of a fitted ARMA(p, q) model, compare the
+/// statistic against `lags − p − q` degrees of freedom instead.
+///
+/// # Errors
+///
+/// Same as [`acf`] with `max_lag = lags`.
+pub fn ljung_box(values: &[f64], lags: usize) -> Result<LjungBox, IndicatorError> {
+    let rho = acf(values, lags)?;
+    let n = values.len() as f64;
+    let statistic = n
+        * (n + 2.0)
+        * rho
+            .iter()
+            .enumerate()
+            .map(|(i, r)| r * r / (n - (i + 1) as f64))
+            .sum::<f64>();
+
+    Ok(LjungBox {
+        statistic,
+        lags,
+        p_value: chi_squared_survival(statistic, lags as f64),
+    })
+}
+
+/// Rolling autocorrelation at a fixed set of lags
+///
+/// Recomputes [`acf`]-style autocorrelations over the last `period` values
+/// on every update, which is O(period × lags).
+///
+/// # Example
+///
+/// ```
+/// use indicator::autocorrelation::RollingAutocorrelation;
+///
+/// let rolling = RollingAutocorrelation::new(20, &[1, 5])?;
+/// let values: Vec<f64> = (0..30).map(|i| (i as f64 * 0.3).sin()).collect();
+/// let result = rolling.calculate(&values)?;
+///
+/// assert!(result[18].is_none());
+/// let lags = result[29].as_ref().unwrap();
+/// assert!(lags[0] > lags[1]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingAutocorrelation {
+    period: usize,
+    lags: Vec<usize>,
+    window: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingAutocorrelation {
+    /// Creates a new rolling autocorrelation
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be >= 2)
+    /// * `lags` - Lags to report, in output order (must be non-empty, each
+    ///   in `1..period`)
+    pub fn new(period: usize, lags: &[usize]) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+        if lags.is_empty() {
+            return Err(IndicatorError::InvalidParameter {
+                field: "lags",
+                value: 0.0,
+                constraint: "non-empty",
+            });
+        }
+        for &lag in lags {
+            check_lag("lags", lag)?;
+            if lag >= period {
+                return Err(IndicatorError::InvalidParameter {
+                    field: "lags",
+                    value: lag as f64,
+                    constraint: "< period",
+                });
+            }
+        }
+
+        Ok(Self {
+            period,
+            lags: lags.to_vec(),
+            window: VecDeque::with_capacity(period),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates rolling autocorrelations for a batch of values on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None`; later ones hold one autocorrelation per lag.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<Vec<f64>>>, IndicatorError> {
+        check_min_length(self.period, values.len())?;
+
+        let mut state = Self::new(self.period, &self.lags)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value (streaming mode)
+    pub fn next(&mut self, value: f64) -> Option<Vec<f64>> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return None;
+        }
+        let window = self.window.make_contiguous();
+        Some(autocorrelations(window, self.lags.iter().copied()))
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<Vec<f64>> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the number of values in the window
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the reported lags
+    pub fn lags(&self) -> &[usize] {
+        &self.lags
+    }
+}
+
+fn check_lag(field: &'static str, lag: usize) -> Result<(), IndicatorError> {
+    if lag == 0 {
+        return Err(IndicatorError::InvalidParameter {
+            field,
+            value: 0.0,
+            constraint: ">= 1",
+        });
+    }
+    Ok(())
+}
+
+/// Autocorrelations of `values` at each of `lags` (all < `values.len()`)
+fn autocorrelations(values: &[f64], lags: impl Iterator<Item = usize>) -> Vec<f64> {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let centered: Vec<f64> = values.iter().map(|v| v - mean).collect();
+    let variance: f64 = centered.iter().map(|c| c * c).sum();
+
+    lags.map(|lag| {
+        if variance == 0.0 {
+            return 0.0;
+        }
+        let covariance: f64 = centered
+            .iter()
+            .zip(&centered[lag..])
+            .map(|(a, b)| a * b)
+            .sum();
+        covariance / variance
+    })
+    .collect()
+}
+
+/// P(X ≥ x) for a χ² variable with `dof` degrees of freedom
+fn chi_squared_survival(x: f64, dof: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    upper_incomplete_gamma(dof / 2.0, x / 2.0)
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`
+///
+/// Uses the power series for `x < a + 1` and Lentz's continued fraction
+/// otherwise, as in Numerical Recipes.
+fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    const EPS: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        let (mut term, mut sum, mut denom) = (1.0 / a, 1.0 / a, a);
+        for _ in 0..1000 {
+            denom += 1.0;
+            term *= x / denom;
+            sum += term;
+            if term.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        return (1.0 - sum * prefactor).clamp(0.0, 1.0);
+    }
+
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    (prefactor * h).clamp(0.0, 1.0)
+}
+
+/// Natural log of the gamma function (Lanczos approximation, g = 7)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + (i + 1) as f64)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acf_matches_hand_computation() {
+        let values = [1.0, 3.0, 2.0, 5.0, 4.0];
+        let rho = acf(&values, 2).unwrap();
+        // Mean 3, deviations [−2, 0, −1, 2, 1], Σ d² = 10
+        assert!((rho[0] - (0.0 + 0.0 - 2.0 + 2.0) / 10.0).abs() < 1e-12);
+        assert!((rho[1] - (2.0 + 0.0 - 1.0) / 10.0).abs() < 1e-12);
+
+        assert_eq!(acf(&[2.0; 6], 3).unwrap(), vec![0.0; 3]);
+        assert!(acf(&values, 0).is_err());
+        assert!(matches!(
+            acf(&values, 5),
+            Err(IndicatorError::InsufficientData {
+                required: 6,
+                actual: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn test_chi_squared_survival_reference_values() {
+        // Tabulated χ² critical values at the 5% level
+        for (x, dof) in [(3.841_458_8, 1.0), (11.070_497_7, 5.0), (18.307_038, 10.0)] {
+            assert!(
+                (chi_squared_survival(x, dof) - 0.05).abs() < 1e-6,
+                "dof {dof}"
+            );
+        }
+        // With 2 degrees of freedom the survival function is exp(−x/2)
+        assert!((chi_squared_survival(3.0, 2.0) - (-1.5_f64).exp()).abs() < 1e-12);
+        assert_eq!(chi_squared_survival(0.0, 4.0), 1.0);
+    }
+
+    #[test]
+    fn test_ljung_box_on_ar_and_white_noise() {
+        let mut state: u64 = 42;
+        let noise: Vec<f64> = (0..500)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect();
+        let ar: Vec<f64> = noise
+            .iter()
+            .scan(0.0, |prev, e| {
+                *prev = 0.7 * *prev + e;
+                Some(*prev)
+            })
+            .collect();
+
+        let dependent = ljung_box(&ar, 10).unwrap();
+        assert!(dependent.p_value < 1e-6, "{dependent:?}");
+        assert_eq!(dependent.lags, 10);
+
+        let independent = ljung_box(&noise, 10).unwrap();
+        assert!(independent.p_value > 0.01, "{independent:?}");
+        assert!(independent.statistic < dependent.statistic);
+    }
+
+    #[test]
+    fn test_rolling_matches_acf_and_streaming() {
+        let values: Vec<f64> = (0..40)
+            .map(|i| (i as f64 * 0.9).sin() + (i % 3) as f64 * 0.2)
+            .collect();
+        let mut rolling = RollingAutocorrelation::new(15, &[3, 1]).unwrap();
+        let batch = rolling.calculate(&values).unwrap();
+
+        assert!(batch[..14].iter().all(Option::is_none));
+        for (i, output) in batch.iter().enumerate().skip(14) {
+            let full = acf(&values[i - 14..=i], 3).unwrap();
+            assert_eq!(output.as_deref(), Some(&[full[2], full[0]][..]));
+        }
+        let streamed: Vec<_> = values.iter().map(|&v| rolling.next(v)).collect();
+        assert_eq!(batch, streamed);
+
+        rolling.reset();
+        assert_eq!(rolling.next(1.0), None);
+        assert!(RollingAutocorrelation::new(1, &[1]).is_err());
+        assert!(RollingAutocorrelation::new(10, &[]).is_err());
+        assert!(RollingAutocorrelation::new(10, &[0]).is_err());
+        assert!(RollingAutocorrelation::new(10, &[10]).is_err());
+    }
+}
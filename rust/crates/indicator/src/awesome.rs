@@ -0,0 +1,326 @@
+//! Bill Williams' Awesome Oscillator (AO) and Accelerator Oscillator (AC)
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::sma::RollingMean;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Histogram bar colour: whether a value rose from the previous bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarColor {
+    /// Higher than the previous value
+    Green,
+    /// Equal to or lower than the previous value
+    Red,
+}
+
+/// Direction in which an oscillator crossed its zero line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZeroCross {
+    /// Moved from at or below zero to above it
+    Bullish,
+    /// Moved from at or above zero to below it
+    Bearish,
+}
+
+/// One oscillator value with its bar colour and zero-line cross
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OscillatorBar {
+    /// Oscillator value
+    pub value: f64,
+    /// Colour against the previous value; `None` on the first value
+    pub color: Option<BarColor>,
+    /// Zero-line cross since the previous value, if any
+    pub zero_cross: Option<ZeroCross>,
+}
+
+/// Awesome and Accelerator Oscillator values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AwesomeOutput {
+    /// Awesome Oscillator
+    pub ao: OscillatorBar,
+    /// Accelerator Oscillator; `None` during its warmup
+    pub ac: Option<OscillatorBar>,
+}
+
+/// Awesome Oscillator with the Accelerator Oscillator on top
+///
+/// # Formula
+///
+/// Median = (High + Low) / 2
+/// AO = SMA_fast(Median) − SMA_slow(Median)
+/// AC = AO − SMA_signal(AO)
+///
+/// Each value carries its histogram colour (green when above the previous
+/// value, red otherwise) and any zero-line cross, the inputs to Williams'
+/// saucer, twin-peaks and zero-cross signals.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{AwesomeOscillator, BarColor, ZeroCross};
+///
+/// // A steady decline, then a sharp rally
+/// let high = [20.0, 19.0, 18.0, 17.0, 16.0, 15.0, 14.0, 13.0, 30.0, 33.0];
+/// let low = high.map(|h| h - 1.0);
+///
+/// let result = AwesomeOscillator::new(2, 4, 2)?.calculate(&high, &low)?;
+/// assert!(result[2].is_none());
+/// assert!(result[7].unwrap().ao.value < 0.0);
+/// let ao = result[8].unwrap().ao;
+/// assert_eq!(ao.color, Some(BarColor::Green));
+/// assert_eq!(ao.zero_cross, Some(ZeroCross::Bullish));
+/// assert!(result[4].unwrap().ac.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AwesomeOscillator {
+    fast: RollingMean,
+    slow: RollingMean,
+    signal: RollingMean,
+    prev_ao: Option<f64>,
+    prev_ac: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl AwesomeOscillator {
+    /// Creates a new Awesome/Accelerator Oscillator
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - Fast median-price SMA (must be > 0 and < `slow_period`,
+    ///   typically 5)
+    /// * `slow_period` - Slow median-price SMA (typically 34)
+    /// * `signal_period` - SMA of AO subtracted to form AC (must be > 0,
+    ///   typically 5)
+    pub fn new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if fast_period >= slow_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "fast_period",
+                value: fast_period as f64,
+                constraint: "< slow_period",
+            });
+        }
+
+        Ok(Self {
+            fast: RollingMean::new("fast_period", fast_period)?,
+            slow: RollingMean::new("slow_period", slow_period)?,
+            signal: RollingMean::new("signal_period", signal_period)?,
+            prev_ao: None,
+            prev_ac: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates AO and AC for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `slow_period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+    ) -> Result<Vec<Option<AwesomeOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[("high", high.len()), ("low", low.len())])?;
+        check_min_length(self.slow.period, len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(high
+            .iter()
+            .zip(low)
+            .map(|(&high, &low)| state.next(high, low))
+            .collect())
+    }
+
+    /// Calculates AO and AC for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `slow_period`
+    /// candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<AwesomeOutput>>, IndicatorError> {
+        check_min_length(self.slow.period, candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64) -> Option<AwesomeOutput> {
+        self.undo = Snapshot::of(self);
+        let median = (high + low) / 2.0;
+        let fast = self.fast.next(median);
+        let ao = self
+            .slow
+            .next(median)
+            .zip(fast)
+            .map(|(slow, fast)| fast - slow)?;
+
+        let ac = self
+            .signal
+            .next(ao)
+            .map(|signal| describe(ao - signal, self.prev_ac.replace(ao - signal)));
+        Some(AwesomeOutput {
+            ao: describe(ao, self.prev_ao.replace(ao)),
+            ac,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64) -> Option<AwesomeOutput> {
+        self.rollback();
+        self.next(high, low)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.prev_ao = None;
+        self.prev_ac = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the fast, slow and signal periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (self.fast.period, self.slow.period, self.signal.period)
+    }
+}
+
+fn describe(value: f64, prev: Option<f64>) -> OscillatorBar {
+    let color = prev.map(|prev| {
+        if value > prev {
+            BarColor::Green
+        } else {
+            BarColor::Red
+        }
+    });
+    let zero_cross = prev.and_then(|prev| {
+        if prev <= 0.0 && value > 0.0 {
+            Some(ZeroCross::Bullish)
+        } else if prev >= 0.0 && value < 0.0 {
+            Some(ZeroCross::Bearish)
+        } else {
+            None
+        }
+    });
+    OscillatorBar {
+        value,
+        color,
+        zero_cross,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sma(values: &[f64], period: usize, i: usize) -> f64 {
+        values[i + 1 - period..=i].iter().sum::<f64>() / period as f64
+    }
+
+    #[test]
+    fn test_awesome_values() {
+        let high: Vec<f64> = (0..30)
+            .map(|i| 15.0 + (i as f64 * 0.4).sin() * 2.0)
+            .collect();
+        let low: Vec<f64> = high.iter().map(|h| h - 0.8).collect();
+        let median: Vec<f64> = high.iter().zip(&low).map(|(h, l)| (h + l) / 2.0).collect();
+
+        let result = AwesomeOscillator::new(3, 8, 4)
+            .unwrap()
+            .calculate(&high, &low)
+            .unwrap();
+        assert!(result[6].is_none());
+        let ao: Vec<f64> = (7..30)
+            .map(|i| sma(&median, 3, i) - sma(&median, 8, i))
+            .collect();
+        for i in 7..30 {
+            let output = result[i].unwrap();
+            assert!((output.ao.value - ao[i - 7]).abs() < 1e-9);
+            match output.ac {
+                Some(ac) => {
+                    let expected = ao[i - 7] - sma(&ao, 4, i - 7);
+                    assert!((ac.value - expected).abs() < 1e-9);
+                }
+                None => assert!(i < 10),
+            }
+        }
+    }
+
+    #[test]
+    fn test_awesome_colors_and_crosses() {
+        assert_eq!(describe(1.0, None).color, None);
+        let up = describe(0.5, Some(-0.2));
+        assert_eq!(up.color, Some(BarColor::Green));
+        assert_eq!(up.zero_cross, Some(ZeroCross::Bullish));
+        let down = describe(-0.1, Some(0.0));
+        assert_eq!(down.color, Some(BarColor::Red));
+        assert_eq!(down.zero_cross, Some(ZeroCross::Bearish));
+        let flat = describe(0.3, Some(0.3));
+        assert_eq!((flat.color, flat.zero_cross), (Some(BarColor::Red), None));
+    }
+
+    #[test]
+    fn test_awesome_streaming_matches_batch() {
+        let candles: Vec<Candle> = (0..40)
+            .map(|i| {
+                let base = 60.0 + (i as f64 * 0.3).cos() * 4.0;
+                Candle::new(i, base, base + 1.0, base - 1.0, base, 1.0)
+            })
+            .collect();
+        let mut ao = AwesomeOscillator::new(5, 34, 5).unwrap();
+        let batch = ao.calculate_candles(&candles).unwrap();
+        let streamed: Vec<_> = candles.iter().map(|c| ao.next(c.high, c.low)).collect();
+        assert_eq!(batch, streamed);
+
+        ao.reset();
+        assert_eq!(ao.next(1.0, 0.0), None);
+        assert_eq!(ao.periods(), (5, 34, 5));
+    }
+
+    #[test]
+    fn test_awesome_invalid_inputs() {
+        assert!(AwesomeOscillator::new(34, 5, 5).is_err());
+        assert!(AwesomeOscillator::new(0, 34, 5).is_err());
+        assert!(AwesomeOscillator::new(5, 34, 0).is_err());
+        let ao = AwesomeOscillator::new(5, 34, 5).unwrap();
+        assert!(matches!(
+            ao.calculate(&[1.0; 33], &[1.0; 33]),
+            Err(IndicatorError::InsufficientData {
+                required: 34,
+                actual: 33
+            })
+        ));
+        assert!(ao.calculate(&[1.0; 40], &[1.0; 39]).is_err());
+    }
+}
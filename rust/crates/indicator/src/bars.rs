@@ -0,0 +1,471 @@
+//! Aggregation of trade ticks into bars
+//!
+//! [`BarAggregator`] turns a stream of trades into completed [`Candle`]s,
+//! sampled by clock time, traded volume, or traded notional. Volume and
+//! dollar bars sample more often when activity is high, which gives returns
+//! closer to i.i.d. than fixed time bars.
+//!
+//! [`Resampler`] rolls candles (or trades) up into a higher timeframe, e.g.
+//! 1-minute bars into 5-minute, hourly or daily bars, with an optional
+//! offset so bars can start at a session open instead of midnight UTC.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::bars::{BarAggregator, BarType, Trade};
+//!
+//! let mut bars = BarAggregator::new(BarType::Volume(100.0))?;
+//!
+//! assert_eq!(bars.next(Trade::new(1, 10.0, 60.0)), None);
+//! let bar = bars.next(Trade::new(2, 10.5, 50.0)).unwrap();
+//!
+//! assert_eq!(bar.high, 10.5);
+//! assert_eq!(bar.volume, 110.0);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::{Candle, IndicatorError};
+
+/// A single executed trade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    /// Execution time as a Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// Execution price
+    pub price: f64,
+    /// Executed quantity
+    pub size: f64,
+}
+
+impl Trade {
+    /// Creates a new trade
+    pub fn new(timestamp: i64, price: f64, size: f64) -> Self {
+        Self {
+            timestamp,
+            price,
+            size,
+        }
+    }
+}
+
+/// Sampling rule that decides when a bar is complete
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarType {
+    /// Bars covering fixed clock intervals, aligned to multiples of the interval
+    Time {
+        /// Bar length in milliseconds
+        interval_ms: i64,
+    },
+    /// Bars closing once their traded quantity reaches the threshold
+    Volume(f64),
+    /// Bars closing once their traded notional (price × size) reaches the threshold
+    Dollar(f64),
+}
+
+/// Streaming tick-to-bar aggregator
+///
+/// Time bars are emitted when the first trade of a later interval arrives;
+/// intervals without trades produce no bar. Volume and dollar bars close on
+/// the trade that reaches the threshold, and that trade is included in full
+/// rather than split across bars.
+#[derive(Debug, Clone)]
+pub struct BarAggregator {
+    bar_type: BarType,
+    /// Bar currently being built
+    current: Option<Candle>,
+    /// Notional traded in the current bar (dollar bars)
+    notional: f64,
+}
+
+impl BarAggregator {
+    /// Creates a new aggregator
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the interval or threshold is not positive.
+    pub fn new(bar_type: BarType) -> Result<Self, IndicatorError> {
+        match bar_type {
+            BarType::Time { interval_ms } if interval_ms <= 0 => {
+                return Err(IndicatorError::InvalidParameter {
+                    field: "interval_ms",
+                    value: interval_ms as f64,
+                    constraint: "> 0",
+                });
+            }
+            BarType::Volume(threshold) | BarType::Dollar(threshold)
+                if threshold.is_nan() || threshold <= 0.0 =>
+            {
+                return Err(IndicatorError::InvalidParameter {
+                    field: "threshold",
+                    value: threshold,
+                    constraint: "> 0",
+                });
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            bar_type,
+            current: None,
+            notional: 0.0,
+        })
+    }
+
+    /// Aggregates a batch of trades on a fresh state
+    ///
+    /// Only completed bars are returned; a trailing partial bar is dropped.
+    pub fn aggregate(&self, trades: &[Trade]) -> Vec<Candle> {
+        let mut state = Self {
+            bar_type: self.bar_type,
+            current: None,
+            notional: 0.0,
+        };
+        trades
+            .iter()
+            .filter_map(|&trade| state.next(trade))
+            .collect()
+    }
+
+    /// Processes the next trade, returning a bar if one completed
+    pub fn next(&mut self, trade: Trade) -> Option<Candle> {
+        if let BarType::Time { interval_ms } = self.bar_type {
+            let bucket = trade.timestamp.div_euclid(interval_ms) * interval_ms;
+            let completed = match self.current {
+                Some(bar) if bar.timestamp != bucket => self.flush(),
+                _ => None,
+            };
+            self.add(trade, bucket);
+            return completed;
+        }
+
+        self.add(trade, trade.timestamp);
+        let reached = match self.bar_type {
+            BarType::Volume(threshold) => self.current.is_some_and(|bar| bar.volume >= threshold),
+            BarType::Dollar(threshold) => self.notional >= threshold,
+            BarType::Time { .. } => false,
+        };
+        if reached {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// Closes and returns the bar in progress, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.notional = 0.0;
+        self.current.take()
+    }
+
+    /// Returns the bar in progress without closing it
+    pub fn partial(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// Discards the bar in progress
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.notional = 0.0;
+    }
+
+    /// Returns the sampling rule
+    pub fn bar_type(&self) -> BarType {
+        self.bar_type
+    }
+
+    fn add(&mut self, trade: Trade, timestamp: i64) {
+        self.notional += trade.price * trade.size;
+        match &mut self.current {
+            Some(bar) => {
+                bar.high = bar.high.max(trade.price);
+                bar.low = bar.low.min(trade.price);
+                bar.close = trade.price;
+                bar.volume += trade.size;
+            }
+            None => {
+                self.current = Some(Candle::new(
+                    timestamp,
+                    trade.price,
+                    trade.price,
+                    trade.price,
+                    trade.price,
+                    trade.size,
+                ));
+            }
+        }
+    }
+}
+
+/// Streaming higher-timeframe resampler
+///
+/// Each input candle is assigned to the interval containing its open time,
+/// and candles of the same interval merge into one bar: first open, highest
+/// high, lowest low, last close and summed volume, stamped with the
+/// interval start. As with [`BarAggregator`] time bars, a bar is emitted
+/// when the first input of a later interval arrives, and intervals without
+/// input produce no bar.
+///
+/// Intervals are aligned to multiples of `interval_ms` since the Unix epoch,
+/// shifted by `offset_ms`: daily bars starting at 13:30 UTC use an offset of
+/// 13.5 hours.
+///
+/// # Example
+///
+/// ```
+/// use indicator::bars::Resampler;
+/// use indicator::Candle;
+///
+/// const MINUTE: i64 = 60_000;
+///
+/// let minutes: Vec<Candle> = (0..12)
+///     .map(|i| {
+///         let price = 100.0 + i as f64;
+///         Candle::new(i * MINUTE, price, price + 0.5, price - 0.5, price + 0.2, 10.0)
+///     })
+///     .collect();
+///
+/// let five_minute = Resampler::new(5 * MINUTE)?.resample(&minutes);
+///
+/// assert_eq!(five_minute.len(), 2); // minutes 10-11 are still open
+/// assert_eq!(five_minute[1], Candle::new(5 * MINUTE, 105.0, 109.5, 104.5, 109.2, 50.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    interval_ms: i64,
+    offset_ms: i64,
+    /// Bar currently being built
+    current: Option<Candle>,
+}
+
+impl Resampler {
+    /// Creates a resampler with intervals aligned to the Unix epoch
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the interval is not positive.
+    pub fn new(interval_ms: i64) -> Result<Self, IndicatorError> {
+        if interval_ms <= 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "interval_ms",
+                value: interval_ms as f64,
+                constraint: "> 0",
+            });
+        }
+        Ok(Self {
+            interval_ms,
+            offset_ms: 0,
+            current: None,
+        })
+    }
+
+    /// Shifts interval boundaries by `offset_ms` from the epoch alignment
+    ///
+    /// Offsets are taken modulo the interval, so negative values work too:
+    /// `-3_600_000` starts daily bars at 23:00.
+    pub fn with_offset(mut self, offset_ms: i64) -> Self {
+        self.offset_ms = offset_ms.rem_euclid(self.interval_ms);
+        self
+    }
+
+    /// Resamples a batch of candles on a fresh state
+    ///
+    /// Only completed bars are returned; a trailing partial bar is dropped.
+    pub fn resample(&self, candles: &[Candle]) -> Vec<Candle> {
+        let mut state = self.clone();
+        state.reset();
+        candles
+            .iter()
+            .filter_map(|&candle| state.next(candle))
+            .collect()
+    }
+
+    /// Processes the next lower-timeframe candle, returning a bar if one completed
+    pub fn next(&mut self, candle: Candle) -> Option<Candle> {
+        let bucket = self.bucket(candle.timestamp);
+        match &mut self.current {
+            Some(bar) if bar.timestamp == bucket => {
+                bar.high = bar.high.max(candle.high);
+                bar.low = bar.low.min(candle.low);
+                bar.close = candle.close;
+                bar.volume += candle.volume;
+                None
+            }
+            _ => self.current.replace(Candle {
+                timestamp: bucket,
+                ..candle
+            }),
+        }
+    }
+
+    /// Processes the next trade, returning a bar if one completed
+    pub fn next_trade(&mut self, trade: Trade) -> Option<Candle> {
+        self.next(Candle::new(
+            trade.timestamp,
+            trade.price,
+            trade.price,
+            trade.price,
+            trade.price,
+            trade.size,
+        ))
+    }
+
+    /// Closes and returns the bar in progress, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.current.take()
+    }
+
+    /// Returns the bar in progress without closing it
+    pub fn partial(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// Discards the bar in progress
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+
+    /// Returns the target bar length in milliseconds
+    pub fn interval_ms(&self) -> i64 {
+        self.interval_ms
+    }
+
+    /// Returns the boundary offset in milliseconds, within `[0, interval_ms)`
+    pub fn offset_ms(&self) -> i64 {
+        self.offset_ms
+    }
+
+    /// Start of the interval containing `timestamp`
+    fn bucket(&self, timestamp: i64) -> i64 {
+        (timestamp - self.offset_ms).div_euclid(self.interval_ms) * self.interval_ms
+            + self.offset_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_bars() {
+        let aggregator = BarAggregator::new(BarType::Time { interval_ms: 1_000 }).unwrap();
+        let trades = [
+            Trade::new(1_100, 10.0, 1.0),
+            Trade::new(1_500, 12.0, 2.0),
+            Trade::new(1_900, 11.0, 1.0),
+            Trade::new(4_200, 9.0, 5.0), // skips two empty intervals
+            Trade::new(5_000, 9.5, 1.0),
+        ];
+        let bars = aggregator.aggregate(&trades);
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0], Candle::new(1_000, 10.0, 12.0, 10.0, 11.0, 4.0));
+        assert_eq!(bars[1], Candle::new(4_000, 9.0, 9.0, 9.0, 9.0, 5.0));
+    }
+
+    #[test]
+    fn test_volume_bars_include_threshold_trade() {
+        let mut aggregator = BarAggregator::new(BarType::Volume(10.0)).unwrap();
+
+        assert_eq!(aggregator.next(Trade::new(1, 5.0, 4.0)), None);
+        let bar = aggregator.next(Trade::new(2, 4.0, 30.0)).unwrap();
+        assert_eq!(bar, Candle::new(1, 5.0, 5.0, 4.0, 4.0, 34.0));
+        assert_eq!(aggregator.partial(), None);
+
+        aggregator.next(Trade::new(3, 6.0, 1.0));
+        assert_eq!(aggregator.partial().unwrap().timestamp, 3);
+    }
+
+    #[test]
+    fn test_dollar_bars() {
+        let aggregator = BarAggregator::new(BarType::Dollar(1_000.0)).unwrap();
+        let trades = [
+            Trade::new(1, 100.0, 4.0),
+            Trade::new(2, 100.0, 6.0), // 1,000 reached
+            Trade::new(3, 200.0, 4.0),
+            Trade::new(4, 200.0, 1.0), // 1,000 reached
+            Trade::new(5, 200.0, 1.0),
+        ];
+        let bars = aggregator.aggregate(&trades);
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].volume, 10.0);
+        assert_eq!(bars[1].open, 200.0);
+        assert_eq!(bars[1].volume, 5.0);
+    }
+
+    #[test]
+    fn test_flush_and_invalid_parameters() {
+        let mut aggregator = BarAggregator::new(BarType::Time {
+            interval_ms: 60_000,
+        })
+        .unwrap();
+        aggregator.next(Trade::new(61_000, 1.0, 1.0));
+        assert_eq!(aggregator.flush().unwrap().timestamp, 60_000);
+        assert_eq!(aggregator.flush(), None);
+
+        assert!(BarAggregator::new(BarType::Time { interval_ms: 0 }).is_err());
+        assert!(BarAggregator::new(BarType::Volume(-1.0)).is_err());
+        assert!(BarAggregator::new(BarType::Dollar(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_resampler_chains_timeframes() {
+        const MINUTE: i64 = 60_000;
+        let minutes: Vec<Candle> = (0..130)
+            .map(|i| {
+                let price = 50.0 + (i as f64 * 0.3).sin();
+                Candle::new(
+                    i * MINUTE,
+                    price,
+                    price + 0.2,
+                    price - 0.3,
+                    price + 0.1,
+                    1.0,
+                )
+            })
+            .collect();
+
+        let five = Resampler::new(5 * MINUTE).unwrap().resample(&minutes);
+        let hourly_direct = Resampler::new(60 * MINUTE).unwrap().resample(&minutes);
+        let hourly_chained = Resampler::new(60 * MINUTE).unwrap().resample(&five);
+
+        assert_eq!(five.len(), 26 - 1);
+        assert_eq!(hourly_direct, hourly_chained);
+        assert_eq!(hourly_direct.len(), 2);
+
+        let first_hour = &minutes[..60];
+        let bar = hourly_direct[0];
+        assert_eq!(bar.open, first_hour[0].open);
+        assert_eq!(bar.close, first_hour[59].close);
+        assert_eq!(bar.volume, 60.0);
+        let high = first_hour.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+        assert_eq!(bar.high, high);
+    }
+
+    #[test]
+    fn test_resampler_offset_and_streaming() {
+        const HOUR: i64 = 3_600_000;
+        let mut daily = Resampler::new(24 * HOUR).unwrap().with_offset(-HOUR);
+        assert_eq!(daily.offset_ms(), 23 * HOUR);
+
+        // 22:00 falls in the session that opened at 23:00 the previous day
+        assert_eq!(
+            daily.next(Candle::new(22 * HOUR, 1.0, 2.0, 0.5, 1.5, 3.0)),
+            None
+        );
+        assert_eq!(daily.partial().unwrap().timestamp, -HOUR);
+        let completed = daily
+            .next_trade(Trade::new(23 * HOUR + 5, 1.7, 2.0))
+            .unwrap();
+        assert_eq!(completed, Candle::new(-HOUR, 1.0, 2.0, 0.5, 1.5, 3.0));
+        assert_eq!(
+            daily.flush(),
+            Some(Candle::new(23 * HOUR, 1.7, 1.7, 1.7, 1.7, 2.0))
+        );
+        assert_eq!(daily.flush(), None);
+
+        assert!(Resampler::new(0).is_err());
+        assert_eq!(Resampler::new(5).unwrap().interval_ms(), 5);
+    }
+}
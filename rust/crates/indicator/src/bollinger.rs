@@ -0,0 +1,227 @@
+//! Bollinger Bands with %B and Bandwidth
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, RollingMoments};
+
+/// Bollinger Band values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerOutput {
+    /// Simple moving average of the window
+    pub middle: f64,
+    /// Middle band plus `multiplier` standard deviations
+    pub upper: f64,
+    /// Middle band minus `multiplier` standard deviations
+    pub lower: f64,
+    /// Position of the price within the bands: 0 at the lower band, 1 at the
+    /// upper band
+    pub percent_b: f64,
+    /// Band width relative to the middle band
+    pub bandwidth: f64,
+}
+
+/// Bollinger Bands indicator
+///
+/// # Formula
+///
+/// Middle = SMA(period)
+/// Upper/Lower = Middle ± multiplier × σ
+/// %B = (Price − Lower) / (Upper − Lower)
+/// Bandwidth = (Upper − Lower) / Middle
+///
+/// σ is the population standard deviation of the window, maintained in O(1)
+/// per bar by [`RollingMoments`]. When the bands collapse to a single line
+/// %B is 0.5 and Bandwidth is 0, so volatility squeezes show up as
+/// Bandwidth lows without recomputing the bands.
+///
+/// # Example
+///
+/// ```
+/// use indicator::BollingerBands;
+///
+/// let prices = [10.0, 12.0, 10.0, 12.0];
+/// let result = BollingerBands::new(4, 2.0)?.calculate(&prices)?;
+///
+/// assert!(result[2].is_none());
+/// let bands = result[3].unwrap();
+/// assert_eq!((bands.middle, bands.upper, bands.lower), (11.0, 13.0, 9.0));
+/// assert_eq!(bands.percent_b, 0.75);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    moments: RollingMoments,
+    multiplier: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl BollingerBands {
+    /// Creates a new Bollinger Bands indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Moving average window (must be >= 2, typically 20)
+    /// * `multiplier` - Standard deviations between the middle and outer bands
+    ///   (must be > 0, typically 2)
+    pub fn new(period: usize, multiplier: f64) -> Result<Self, IndicatorError> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "multiplier",
+                value: multiplier,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            moments: RollingMoments::new(period)?,
+            multiplier,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the bands, %B and Bandwidth for a batch of prices on a
+    /// fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` prices.
+    pub fn calculate(
+        &self,
+        prices: &[f64],
+    ) -> Result<Vec<Option<BollingerOutput>>, IndicatorError> {
+        check_min_length(self.period(), prices.len())?;
+
+        let mut state = Self::new(self.period(), self.multiplier)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<BollingerOutput> {
+        self.undo = Snapshot::of(self);
+        let moments = self.moments.next(price)?;
+        let offset = self.multiplier * moments.std_dev;
+        let (upper, lower) = (moments.mean + offset, moments.mean - offset);
+        let width = upper - lower;
+
+        let (percent_b, bandwidth) = if width > 0.0 {
+            ((price - lower) / width, width / moments.mean)
+        } else {
+            (0.5, 0.0)
+        };
+        Some(BollingerOutput {
+            middle: moments.mean,
+            upper,
+            lower,
+            percent_b,
+            bandwidth,
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<BollingerOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.moments.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the moving average window
+    pub fn period(&self) -> usize {
+        self.moments.period()
+    }
+
+    /// Returns the standard deviation multiplier
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bollinger_matches_window_statistics() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.1)
+            .collect();
+        let result = BollingerBands::new(10, 2.5)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+
+        assert!(result[8].is_none());
+        for (output, window) in result[9..].iter().zip(prices.windows(10)) {
+            let mean = window.iter().sum::<f64>() / 10.0;
+            let variance = window.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / 10.0;
+            let std_dev = variance.sqrt();
+            let price = window[9];
+
+            let output = output.unwrap();
+            assert!((output.middle - mean).abs() < 1e-9);
+            assert!((output.upper - (mean + 2.5 * std_dev)).abs() < 1e-9);
+            assert!((output.lower - (mean - 2.5 * std_dev)).abs() < 1e-9);
+            let percent_b = (price - output.lower) / (5.0 * std_dev);
+            assert!((output.percent_b - percent_b).abs() < 1e-9);
+            assert!((output.bandwidth - 5.0 * std_dev / mean).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bollinger_flat_window() {
+        let result = BollingerBands::new(3, 2.0)
+            .unwrap()
+            .calculate(&[7.0; 4])
+            .unwrap();
+        let bands = result[3].unwrap();
+        assert_eq!((bands.upper, bands.lower), (7.0, 7.0));
+        assert_eq!((bands.percent_b, bands.bandwidth), (0.5, 0.0));
+    }
+
+    #[test]
+    fn test_bollinger_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..30).map(|i| 9.0 + (i as f64 * 0.9).cos()).collect();
+        let mut bands = BollingerBands::new(5, 2.0).unwrap();
+        let batch = bands.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| bands.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        bands.reset();
+        assert_eq!(bands.next(1.0), None);
+        assert_eq!((bands.period(), bands.multiplier()), (5, 2.0));
+    }
+
+    #[test]
+    fn test_bollinger_invalid_inputs() {
+        assert!(BollingerBands::new(1, 2.0).is_err());
+        assert!(BollingerBands::new(20, 0.0).is_err());
+        assert!(BollingerBands::new(20, f64::NAN).is_err());
+        assert!(matches!(
+            BollingerBands::new(20, 2.0).unwrap().calculate(&[1.0; 19]),
+            Err(IndicatorError::InsufficientData {
+                required: 20,
+                actual: 19
+            })
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+//! Balance of Power (BOP)
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::sma::RollingMean;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Balance of Power indicator
+///
+/// Measures how far buyers carried price from the open across the bar's
+/// range:
+///
+/// BOP = (Close − Open) / (High − Low)
+///
+/// Values run from −1 (opened at the high, closed at the low) to +1, and a
+/// bar with no range scores zero. The raw series is noisy, so it is commonly
+/// smoothed with a simple moving average (14 bars by convention).
+///
+/// # Example
+///
+/// ```
+/// use indicator::BalanceOfPower;
+///
+/// let open = [10.0, 11.0, 12.0];
+/// let high = [12.0, 12.0, 13.0];
+/// let low = [10.0, 10.0, 11.0];
+/// let close = [12.0, 10.5, 12.5];
+///
+/// let raw = BalanceOfPower::new(None)?.calculate(&open, &high, &low, &close)?;
+/// assert_eq!(raw, vec![Some(1.0), Some(-0.25), Some(0.25)]);
+///
+/// let smoothed = BalanceOfPower::new(Some(2))?.calculate(&open, &high, &low, &close)?;
+/// assert_eq!(smoothed, vec![None, Some(0.375), Some(0.0)]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BalanceOfPower {
+    smoothing: Option<RollingMean>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl BalanceOfPower {
+    /// Creates a new Balance of Power indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `smoothing` - Optional SMA period applied to the raw values (must be
+    ///   > 0, typically 14); `None` returns the raw per-bar values
+    pub fn new(smoothing: Option<usize>) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            smoothing: smoothing
+                .map(|period| RollingMean::new("smoothing", period))
+                .transpose()?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates BOP for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer bars than the smoothing period
+    /// (or none at all when unsmoothed).
+    pub fn calculate(
+        &self,
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("open", open.len()),
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.required(), len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok((0..len)
+            .map(|i| state.next(open[i], high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates BOP for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer candles than the
+    /// smoothing period (or none at all when unsmoothed).
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.required(), candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|c| state.next(c.open, c.high, c.low, c.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Always returns a value when unsmoothed; otherwise `None` until the
+    /// smoothing window fills.
+    pub fn next(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let range = high - low;
+        let bop = if range > 0.0 {
+            (close - open) / range
+        } else {
+            0.0
+        };
+        match &mut self.smoothing {
+            Some(sma) => sma.next(bop),
+            None => Some(bop),
+        }
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.rollback();
+        self.next(open, high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        if let Some(sma) = &mut self.smoothing {
+            sma.reset();
+        }
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the smoothing period, if any
+    pub fn smoothing(&self) -> Option<usize> {
+        self.smoothing.as_ref().map(|sma| sma.period)
+    }
+
+    fn required(&self) -> usize {
+        self.smoothing().unwrap_or(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bop_raw_and_smoothed() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| {
+                let open = 50.0 + (i as f64 * 0.6).sin() * 3.0;
+                let close = open + (i as f64 * 1.3).cos();
+                let high = open.max(close) + 0.5;
+                let low = open.min(close) - 0.25;
+                Candle::new(i, open, high, low, close, 1.0)
+            })
+            .collect();
+        let raw = BalanceOfPower::new(None)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        for (value, c) in raw.iter().zip(&candles) {
+            let expected = (c.close - c.open) / (c.high - c.low);
+            assert!((value.unwrap() - expected).abs() < 1e-12);
+        }
+
+        let smoothed = BalanceOfPower::new(Some(5))
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        assert!(smoothed[3].is_none());
+        for i in 4..candles.len() {
+            let mean = raw[i - 4..=i].iter().map(|v| v.unwrap()).sum::<f64>() / 5.0;
+            assert!((smoothed[i].unwrap() - mean).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bop_zero_range() {
+        let mut bop = BalanceOfPower::new(None).unwrap();
+        assert_eq!(bop.next(5.0, 5.0, 5.0, 5.0), Some(0.0));
+    }
+
+    #[test]
+    fn test_bop_streaming_matches_batch() {
+        let open = [1.0, 2.0, 3.0, 2.5, 2.0, 2.8];
+        let high = [2.5, 3.5, 3.2, 3.0, 3.0, 3.1];
+        let low = [0.5, 1.5, 2.0, 1.8, 1.5, 2.5];
+        let close = [2.0, 3.0, 2.2, 2.0, 2.9, 3.0];
+        let mut bop = BalanceOfPower::new(Some(3)).unwrap();
+        let batch = bop.calculate(&open, &high, &low, &close).unwrap();
+        let streamed: Vec<_> = (0..6)
+            .map(|i| bop.next(open[i], high[i], low[i], close[i]))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        bop.reset();
+        assert_eq!(bop.next(1.0, 2.0, 0.0, 1.5), None);
+        assert_eq!(bop.smoothing(), Some(3));
+    }
+
+    #[test]
+    fn test_bop_invalid_inputs() {
+        assert!(BalanceOfPower::new(Some(0)).is_err());
+        assert!(matches!(
+            BalanceOfPower::new(Some(14))
+                .unwrap()
+                .calculate_candles(&[Candle::default(); 13]),
+            Err(IndicatorError::InsufficientData {
+                required: 14,
+                actual: 13
+            })
+        ));
+        let bop = BalanceOfPower::new(None).unwrap();
+        assert!(bop.calculate(&[], &[], &[], &[]).is_err());
+        assert!(bop
+            .calculate(&[1.0; 3], &[1.0; 3], &[1.0; 2], &[1.0; 3])
+            .is_err());
+    }
+}
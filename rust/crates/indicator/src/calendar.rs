@@ -0,0 +1,460 @@
+//! Exchange trading calendars
+//!
+//! A [`TradingCalendar`] knows which dates an exchange trades, its regular
+//! session hours, and the days it closes early. It drives the session resets
+//! of [`SessionVwap`](crate::SessionVwap), trading-day counts for expiry
+//! calculations, and bar clocks that must skip non-trading periods.
+//!
+//! All dates and times are in the exchange's local time; converting bar
+//! timestamps (including daylight-saving transitions) is left to the caller.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use indicator::calendar::TradingCalendar;
+//!
+//! let nyse = TradingCalendar::nyse(2024..=2024);
+//! let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+//!
+//! assert!(!nyse.is_trading_day(date(7, 4))); // Independence Day
+//! assert!(nyse.session(date(11, 29)).unwrap().is_early_close); // Black Friday
+//! assert_eq!(nyse.next_trading_day(date(3, 28)), date(4, 1)); // Good Friday
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+use crate::IndicatorError;
+
+/// Trading days per year used to annualize trading-day counts
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Trading hours of a single day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Session {
+    /// Session open (inclusive)
+    pub open: NaiveTime,
+    /// Session close (exclusive)
+    pub close: NaiveTime,
+    /// Whether the session closes before the regular close
+    pub is_early_close: bool,
+}
+
+impl Session {
+    /// Returns true if `time` falls within the session
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        time >= self.open && time < self.close
+    }
+
+    /// Length of the session
+    pub fn duration(&self) -> Duration {
+        self.close - self.open
+    }
+}
+
+/// Trading calendar of a single exchange
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradingCalendar {
+    name: String,
+    open: NaiveTime,
+    close: NaiveTime,
+    weekend: Vec<Weekday>,
+    holidays: BTreeSet<NaiveDate>,
+    early_closes: BTreeMap<NaiveDate, NaiveTime>,
+}
+
+impl TradingCalendar {
+    /// Starts building a custom calendar
+    pub fn builder(name: &str) -> TradingCalendarBuilder {
+        TradingCalendarBuilder::new(name)
+    }
+
+    /// New York Stock Exchange calendar for the given years
+    ///
+    /// Regular session 09:30–16:00 with 13:00 early closes, using the NYSE
+    /// holiday rules in effect since 2022 (Juneteenth is observed from 2022).
+    /// Ad-hoc closures such as national days of mourning are not included and
+    /// can be added through [`TradingCalendarBuilder::holiday`].
+    pub fn nyse(years: RangeInclusive<i32>) -> Self {
+        let mut builder = Self::builder("NYSE")
+            .regular_session(time(9, 30), time(16, 0))
+            .expect("NYSE session is valid");
+
+        for year in years {
+            for holiday in nyse_holidays(year) {
+                builder = builder.holiday(holiday);
+            }
+            let early_close = time(13, 0);
+            // Day before Independence Day, day after Thanksgiving, Christmas Eve
+            let candidates = [
+                NaiveDate::from_ymd_opt(year, 7, 3),
+                nth_weekday(year, 11, Weekday::Thu, 4).map(|d| d + Duration::days(1)),
+                NaiveDate::from_ymd_opt(year, 12, 24),
+            ];
+            for date in candidates.into_iter().flatten() {
+                builder = builder.early_close(date, early_close);
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Name of the exchange
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns true if the exchange trades on `date`
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !self.weekend.contains(&date.weekday()) && !self.holidays.contains(&date)
+    }
+
+    /// Trading hours on `date`, or `None` if the exchange is closed
+    pub fn session(&self, date: NaiveDate) -> Option<Session> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+        let early_close = self.early_closes.get(&date).copied();
+        Some(Session {
+            open: self.open,
+            close: early_close.unwrap_or(self.close),
+            is_early_close: early_close.is_some(),
+        })
+    }
+
+    /// Returns true if the exchange is in session at `datetime`
+    pub fn is_open(&self, datetime: NaiveDateTime) -> bool {
+        self.session(datetime.date())
+            .is_some_and(|session| session.contains(datetime.time()))
+    }
+
+    /// First trading day strictly after `date`
+    pub fn next_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut next = date + Duration::days(1);
+        while !self.is_trading_day(next) {
+            next += Duration::days(1);
+        }
+        next
+    }
+
+    /// Last trading day strictly before `date`
+    pub fn previous_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut previous = date - Duration::days(1);
+        while !self.is_trading_day(previous) {
+            previous -= Duration::days(1);
+        }
+        previous
+    }
+
+    /// Trading days in the half-open interval `(start, end]`
+    pub fn trading_days(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        start
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date <= end)
+            .filter(|date| self.is_trading_day(*date))
+            .collect()
+    }
+
+    /// Number of trading days in `(start, end]`; zero if `end <= start`
+    pub fn trading_days_between(&self, start: NaiveDate, end: NaiveDate) -> usize {
+        self.trading_days(start, end).len()
+    }
+
+    /// Time from `start` to `end` in years of [`TRADING_DAYS_PER_YEAR`] trading days
+    ///
+    /// Suitable as the time to expiry of an option priced on a trading-day clock.
+    pub fn trading_year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.trading_days_between(start, end) as f64 / TRADING_DAYS_PER_YEAR
+    }
+}
+
+/// Builder for custom [`TradingCalendar`]s
+///
+/// Defaults to a 09:00–17:00 session with Saturday and Sunday as weekend days.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{NaiveDate, NaiveTime, Weekday};
+/// use indicator::calendar::TradingCalendar;
+///
+/// let calendar = TradingCalendar::builder("TASE")
+///     .regular_session(
+///         NaiveTime::from_hms_opt(9, 59, 0).unwrap(),
+///         NaiveTime::from_hms_opt(17, 25, 0).unwrap(),
+///     )?
+///     .weekend(&[Weekday::Sat, Weekday::Sun])?
+///     .holiday(NaiveDate::from_ymd_opt(2024, 10, 3).unwrap())
+///     .build();
+///
+/// assert!(!calendar.is_trading_day(NaiveDate::from_ymd_opt(2024, 10, 3).unwrap()));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TradingCalendarBuilder {
+    calendar: TradingCalendar,
+}
+
+impl TradingCalendarBuilder {
+    fn new(name: &str) -> Self {
+        Self {
+            calendar: TradingCalendar {
+                name: name.to_string(),
+                open: time(9, 0),
+                close: time(17, 0),
+                weekend: vec![Weekday::Sat, Weekday::Sun],
+                holidays: BTreeSet::new(),
+                early_closes: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Sets the regular session hours
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `close` is not after `open`.
+    pub fn regular_session(
+        mut self,
+        open: NaiveTime,
+        close: NaiveTime,
+    ) -> Result<Self, IndicatorError> {
+        if close <= open {
+            return Err(IndicatorError::InvalidParameter {
+                field: "close",
+                value: close.num_seconds_from_midnight() as f64,
+                constraint: "after the session open",
+            });
+        }
+        self.calendar.open = open;
+        self.calendar.close = close;
+        Ok(self)
+    }
+
+    /// Replaces the set of weekly non-trading days
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `days` covers the whole week, which
+    /// would leave no trading day to step to.
+    pub fn weekend(mut self, days: &[Weekday]) -> Result<Self, IndicatorError> {
+        let mut distinct = days.to_vec();
+        distinct.sort_by_key(Weekday::num_days_from_monday);
+        distinct.dedup();
+        if distinct.len() == 7 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "weekend",
+                value: 7.0,
+                constraint: "at most 6 days",
+            });
+        }
+        self.calendar.weekend = distinct;
+        Ok(self)
+    }
+
+    /// Adds a full-day closure
+    pub fn holiday(mut self, date: NaiveDate) -> Self {
+        self.calendar.holidays.insert(date);
+        self
+    }
+
+    /// Adds a day on which the session closes early at `close`
+    ///
+    /// Early closes falling on non-trading days are ignored.
+    pub fn early_close(mut self, date: NaiveDate, close: NaiveTime) -> Self {
+        self.calendar.early_closes.insert(date, close);
+        self
+    }
+
+    /// Finishes the calendar
+    pub fn build(self) -> TradingCalendar {
+        self.calendar
+    }
+}
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).expect("valid time of day")
+}
+
+/// `n`-th occurrence of `weekday` in a month; `n = 5` means the last one
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+        .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n - 1))
+}
+
+/// Easter Sunday (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Shifts a fixed-date holiday off the weekend: Saturday to Friday, Sunday to Monday
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+fn nyse_holidays(year: i32) -> Vec<NaiveDate> {
+    let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+    let mut holidays = Vec::with_capacity(10);
+
+    // New Year's Day falling on a Saturday is not observed on the Friday before
+    if let Some(new_year) = fixed(1, 1) {
+        if new_year.weekday() != Weekday::Sat {
+            holidays.push(observed(new_year));
+        }
+    }
+    holidays.extend(nth_weekday(year, 1, Weekday::Mon, 3)); // Martin Luther King Jr. Day
+    holidays.extend(nth_weekday(year, 2, Weekday::Mon, 3)); // Presidents' Day
+    holidays.extend(easter_sunday(year).map(|d| d - Duration::days(2))); // Good Friday
+    holidays.extend(nth_weekday(year, 5, Weekday::Mon, 5)); // Memorial Day
+    if year >= 2022 {
+        holidays.extend(fixed(6, 19).map(observed)); // Juneteenth
+    }
+    holidays.extend(fixed(7, 4).map(observed)); // Independence Day
+    holidays.extend(nth_weekday(year, 9, Weekday::Mon, 1)); // Labor Day
+    holidays.extend(nth_weekday(year, 11, Weekday::Thu, 4)); // Thanksgiving
+    holidays.extend(fixed(12, 25).map(observed)); // Christmas
+    holidays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_nyse_holidays() {
+        let nyse = TradingCalendar::nyse(2021..=2024);
+
+        assert!(!nyse.is_trading_day(date(2024, 1, 15))); // MLK Day
+        assert!(!nyse.is_trading_day(date(2024, 3, 29))); // Good Friday
+        assert!(!nyse.is_trading_day(date(2024, 5, 27))); // Memorial Day
+        assert!(!nyse.is_trading_day(date(2023, 6, 19))); // Juneteenth
+        assert!(!nyse.is_trading_day(date(2022, 12, 26))); // Christmas observed
+        assert!(!nyse.is_trading_day(date(2024, 12, 21))); // Saturday
+
+        // New Year's Day 2022 fell on a Saturday: Friday 2021-12-31 traded
+        assert!(nyse.is_trading_day(date(2021, 12, 31)));
+        assert!(nyse.is_trading_day(date(2024, 7, 5)));
+    }
+
+    #[test]
+    fn test_nyse_early_closes() {
+        let nyse = TradingCalendar::nyse(2024..=2024);
+
+        let black_friday = nyse.session(date(2024, 11, 29)).unwrap();
+        assert!(black_friday.is_early_close);
+        assert_eq!(black_friday.close, time(13, 0));
+        assert_eq!(black_friday.duration(), Duration::minutes(210));
+
+        // Christmas Eve 2024 was a Tuesday
+        assert!(nyse.session(date(2024, 12, 24)).unwrap().is_early_close);
+        assert!(!nyse.session(date(2024, 12, 23)).unwrap().is_early_close);
+        // July 3rd early close does not apply when it is a weekend
+        assert_eq!(nyse.session(date(2021, 7, 3)), None);
+    }
+
+    #[test]
+    fn test_is_open() {
+        let nyse = TradingCalendar::nyse(2024..=2024);
+        let at = |d: NaiveDate, h, m| d.and_hms_opt(h, m, 0).unwrap();
+
+        assert!(nyse.is_open(at(date(2024, 6, 3), 9, 30)));
+        assert!(!nyse.is_open(at(date(2024, 6, 3), 16, 0)));
+        assert!(!nyse.is_open(at(date(2024, 11, 29), 14, 0)));
+        assert!(!nyse.is_open(at(date(2024, 12, 25), 12, 0)));
+    }
+
+    #[test]
+    fn test_trading_day_counting() {
+        let nyse = TradingCalendar::nyse(2024..=2024);
+
+        // Week of Thanksgiving 2024: Mon-Wed and Fri trade
+        assert_eq!(
+            nyse.trading_days_between(date(2024, 11, 24), date(2024, 11, 30)),
+            4
+        );
+        assert_eq!(
+            nyse.trading_days_between(date(2024, 1, 1), date(2024, 12, 31)),
+            252
+        );
+        assert_eq!(
+            nyse.trading_days_between(date(2024, 6, 5), date(2024, 6, 1)),
+            0
+        );
+        assert_eq!(
+            nyse.previous_trading_day(date(2024, 7, 5)),
+            date(2024, 7, 3)
+        );
+        assert!(
+            (nyse.trading_year_fraction(date(2024, 1, 1), date(2024, 12, 31)) - 1.0).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_custom_calendar_builder() {
+        let calendar = TradingCalendar::builder("Test")
+            .weekend(&[Weekday::Fri, Weekday::Sat])
+            .unwrap()
+            .early_close(date(2024, 6, 2), time(12, 0))
+            .build();
+
+        assert_eq!(calendar.name(), "Test");
+        assert!(!calendar.is_trading_day(date(2024, 6, 7))); // Friday
+        assert!(calendar.is_trading_day(date(2024, 6, 9))); // Sunday
+        assert_eq!(
+            calendar.session(date(2024, 6, 2)).unwrap().close,
+            time(12, 0)
+        );
+
+        assert!(TradingCalendar::builder("Bad")
+            .regular_session(time(16, 0), time(9, 30))
+            .is_err());
+
+        let every_day = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+            Weekday::Sun,
+        ];
+        assert!(matches!(
+            TradingCalendar::builder("Closed").weekend(&every_day),
+            Err(IndicatorError::InvalidParameter {
+                field: "weekend",
+                ..
+            })
+        ));
+        // Repeated days do not count twice
+        assert!(TradingCalendar::builder("Open")
+            .weekend(&every_day[1..])
+            .is_ok());
+    }
+}
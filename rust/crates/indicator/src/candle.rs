@@ -0,0 +1,32 @@
+//! OHLCV bar type shared by multi-input indicators
+
+/// A single OHLCV price bar
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Candle {
+    /// Bar open time as a Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// First traded price of the bar
+    pub open: f64,
+    /// Highest traded price of the bar
+    pub high: f64,
+    /// Lowest traded price of the bar
+    pub low: f64,
+    /// Last traded price of the bar
+    pub close: f64,
+    /// Traded volume during the bar
+    pub volume: f64,
+}
+
+impl Candle {
+    /// Creates a new candle
+    pub fn new(timestamp: i64, open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+        Self {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Commodity Channel Index (CCI)
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Commodity Channel Index indicator
+///
+/// Measures how far the typical price has moved from its moving average,
+/// in units of its mean absolute deviation:
+///
+/// TP = (High + Low + Close) / 3
+/// CCI = (TP − SMA(TP)) / (constant × MeanDeviation(TP))
+///
+/// With Lambert's constant of 0.015, roughly 70-80% of values fall between
+/// −100 and +100. A window with no deviation reports zero.
+///
+/// # Example
+///
+/// ```
+/// use indicator::CCI;
+///
+/// let high = [24.2, 24.1, 24.4, 24.9, 25.3];
+/// let low = [23.9, 23.7, 23.9, 24.3, 24.6];
+/// let close = [24.0, 23.9, 24.3, 24.8, 25.2];
+///
+/// let result = CCI::new(3, 0.015)?.calculate(&high, &low, &close)?;
+/// assert!(result[1].is_none());
+/// assert!(result[4].unwrap() > 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CCI {
+    period: usize,
+    constant: f64,
+    /// Typical prices in the window, oldest first
+    window: VecDeque<f64>,
+    sum: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl CCI {
+    /// Creates a new CCI indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of bars in the window (must be > 0, typically 20)
+    /// * `constant` - Scaling constant (must be > 0, typically 0.015)
+    pub fn new(period: usize, constant: f64) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        if constant.is_nan() || constant <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "constant",
+                value: constant,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            constant,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates CCI for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period, len)?;
+
+        let mut state = Self::new(self.period, self.constant)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates CCI for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period, candles.len())?;
+
+        let mut state = Self::new(self.period, self.constant)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let typical = (high + low + close) / 3.0;
+        if self.window.len() == self.period {
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        }
+        self.window.push_back(typical);
+        self.sum += typical;
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let mean = self.sum / self.period as f64;
+        let mean_deviation =
+            self.window.iter().map(|tp| (tp - mean).abs()).sum::<f64>() / self.period as f64;
+        if mean_deviation == 0.0 {
+            return Some(0.0);
+        }
+        Some((typical - mean) / (self.constant * mean_deviation))
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the scaling constant
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cci_known_value() {
+        // Typical prices 1, 2, 3, 4 with period 4: mean 2.5, mean deviation 1
+        let prices = [1.0, 2.0, 3.0, 4.0];
+        let result = CCI::new(4, 0.015)
+            .unwrap()
+            .calculate(&prices, &prices, &prices)
+            .unwrap();
+
+        assert!(result[2].is_none());
+        assert!((result[3].unwrap() - 1.5 / 0.015).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cci_constant_window_is_zero() {
+        let prices = [5.0; 6];
+        let result = CCI::new(3, 0.015)
+            .unwrap()
+            .calculate(&prices, &prices, &prices)
+            .unwrap();
+        assert_eq!(result[5], Some(0.0));
+    }
+
+    #[test]
+    fn test_cci_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = (0..15)
+            .map(|i| {
+                let base = 20.0 + (i as f64 * 0.7).cos() * 2.0;
+                Candle::new(i, base, base + 0.5, base - 0.4, base + 0.1, 1.0)
+            })
+            .collect();
+        let cci = CCI::new(5, 0.015).unwrap();
+        let batch = cci.calculate_candles(&candles).unwrap();
+
+        let mut streaming = cci.clone();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| streaming.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+    }
+
+    #[test]
+    fn test_cci_invalid_inputs() {
+        assert!(CCI::new(0, 0.015).is_err());
+        assert!(CCI::new(20, 0.0).is_err());
+        assert!(matches!(
+            CCI::new(5, 0.015)
+                .unwrap()
+                .calculate(&[1.0; 3], &[1.0; 3], &[1.0; 3]),
+            Err(IndicatorError::InsufficientData {
+                required: 5,
+                actual: 3
+            })
+        ));
+    }
+}
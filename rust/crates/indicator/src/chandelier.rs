@@ -0,0 +1,254 @@
+//! Chandelier Exit
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, RollingMax, RollingMin, ATR};
+
+/// Chandelier Exit stop levels for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChandelierOutput {
+    /// Stop for long positions: highest high minus the ATR multiple
+    pub long_stop: f64,
+    /// Stop for short positions: lowest low plus the ATR multiple
+    pub short_stop: f64,
+}
+
+/// Chandelier Exit volatility stop
+///
+/// Hangs a stop from the extreme of the lookback window, a multiple of the
+/// average true range away:
+///
+/// Long stop = HighestHigh(period) − multiplier × ATR(period)
+/// Short stop = LowestLow(period) + multiplier × ATR(period)
+///
+/// The ATR uses Wilder smoothing as in [`ATR`], so the first levels appear
+/// at bar `period − 1`. The levels are recomputed on every bar; trailing-stop
+/// logic typically holds a long stop at its running maximum (and a short stop
+/// at its running minimum) for as long as the position is open.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ChandelierExit;
+///
+/// let high = [10.0, 12.0, 11.0, 13.0];
+/// let low = [9.0, 10.0, 10.0, 11.0];
+/// let close = [9.5, 11.5, 10.5, 12.5];
+///
+/// let result = ChandelierExit::new(3, 2.0)?.calculate(&high, &low, &close)?;
+/// assert!(result[1].is_none());
+/// // ATR = (1 + 2.5 + 1.5) / 3
+/// let stops = result[2].unwrap();
+/// assert!((stops.long_stop - (12.0 - 2.0 * 5.0 / 3.0)).abs() < 1e-12);
+/// assert!((stops.short_stop - (9.0 + 2.0 * 5.0 / 3.0)).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChandelierExit {
+    atr: ATR,
+    multiplier: f64,
+    highs: RollingMax,
+    lows: RollingMin,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ChandelierExit {
+    /// Creates a new Chandelier Exit
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Lookback for the extremes and the ATR (must be > 0,
+    ///   typically 22)
+    /// * `multiplier` - ATR multiple between extreme and stop (must be > 0,
+    ///   typically 3.0)
+    pub fn new(period: usize, multiplier: f64) -> Result<Self, IndicatorError> {
+        let atr = ATR::new(period)?;
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "multiplier",
+                value: multiplier,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            atr,
+            multiplier,
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the stop levels for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<ChandelierOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period(), len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates the stop levels for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<ChandelierOutput>>, IndicatorError> {
+        check_min_length(self.period(), candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|c| state.next(c.high, c.low, c.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<ChandelierOutput> {
+        self.undo = Snapshot::of(self);
+        let highest = self.highs.next(high);
+        let lowest = self.lows.next(low);
+        let offset = self.multiplier * self.atr.next(high, low, close).atr?;
+
+        let (highest, lowest) = highest.zip(lowest)?;
+        Some(ChandelierOutput {
+            long_stop: highest - offset,
+            short_stop: lowest + offset,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<ChandelierOutput> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.atr.reset();
+        self.highs.reset();
+        self.lows.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the lookback and ATR period
+    pub fn period(&self) -> usize {
+        self.atr.period()
+    }
+
+    /// Returns the ATR multiple
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        (0..50)
+            .map(|i| {
+                let base = 40.0 + (i as f64 * 0.25).sin() * 5.0;
+                let spread = 0.5 + (i % 4) as f64 * 0.2;
+                Candle::new(i, base, base + spread, base - spread, base + 0.1, 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chandelier_levels() {
+        let candles = candles();
+        let atr = ATR::new(10).unwrap().calculate_candles(&candles).unwrap();
+        let result = ChandelierExit::new(10, 3.0)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+
+        assert!(result[8].is_none());
+        for i in 9..candles.len() {
+            let window = &candles[i - 9..=i];
+            let highest = window.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+            let lowest = window.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+            let offset = 3.0 * atr[i].atr.unwrap();
+            let stops = result[i].unwrap();
+            assert!((stops.long_stop - (highest - offset)).abs() < 1e-12);
+            assert!((stops.short_stop - (lowest + offset)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_chandelier_streaming_matches_batch() {
+        let candles = candles();
+        let high: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let low: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let mut exit = ChandelierExit::new(22, 3.0).unwrap();
+        let batch = exit.calculate(&high, &low, &close).unwrap();
+        assert_eq!(batch, exit.calculate_candles(&candles).unwrap());
+
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| exit.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        exit.reset();
+        assert_eq!(exit.next(2.0, 1.0, 1.5), None);
+        assert_eq!((exit.period(), exit.multiplier()), (22, 3.0));
+    }
+
+    #[test]
+    fn test_chandelier_invalid_inputs() {
+        assert!(ChandelierExit::new(0, 3.0).is_err());
+        assert!(ChandelierExit::new(22, 0.0).is_err());
+        assert!(ChandelierExit::new(22, f64::NAN).is_err());
+        let exit = ChandelierExit::new(22, 3.0).unwrap();
+        assert!(matches!(
+            exit.calculate_candles(&[Candle::default(); 21]),
+            Err(IndicatorError::InsufficientData {
+                required: 22,
+                actual: 21
+            })
+        ));
+        assert!(exit.calculate(&[1.0; 22], &[1.0; 22], &[1.0; 21]).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+//! Online changepoint detection
+//!
+//! [`Cusum`] watches a return or volatility series for persistent shifts in
+//! its mean and emits a [`ChangePoint`] when one is detected, which can gate
+//! strategies or trigger risk reviews. After each detection the baseline is
+//! re-estimated from the new regime.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::changepoint::{ChangeDirection, Cusum};
+//!
+//! // Absolute returns: calm, then volatile
+//! let mut values = vec![0.010, 0.012, 0.008, 0.011, 0.009, 0.010, 0.012, 0.009];
+//! values.extend([0.030, 0.035, 0.028, 0.040]);
+//!
+//! let detector = Cusum::new(8, 0.5, 5.0)?;
+//! let changes: Vec<_> = detector.calculate(&values)?.into_iter().flatten().collect();
+//!
+//! assert_eq!(changes[0].direction, ChangeDirection::Increase);
+//! assert_eq!(changes[0].index, 8);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::IndicatorError;
+
+/// Baseline accumulators (Welford): observations, mean, sum of squared deviations
+type Baseline = (usize, f64, f64);
+
+/// Direction of a detected mean shift
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeDirection {
+    /// The series moved above its baseline
+    Increase,
+    /// The series moved below its baseline
+    Decrease,
+}
+
+/// A detected regime change
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangePoint {
+    /// Index of the bar at which the change was signalled
+    pub index: usize,
+    /// Direction of the shift
+    pub direction: ChangeDirection,
+    /// Cumulative sum that crossed the threshold, in baseline standard deviations
+    pub statistic: f64,
+}
+
+/// Two-sided CUSUM changepoint detector
+///
+/// The first `warmup` values of each regime estimate a baseline mean and
+/// standard deviation. Subsequent values are standardized against it and
+/// accumulated into
+///
+/// ```text
+/// S⁺ = max(0, S⁺ + z − k)    S⁻ = max(0, S⁻ − z − k)
+/// ```
+///
+/// A change is signalled when either sum exceeds the threshold `h`. Larger
+/// `k` ignores smaller shifts; larger `h` trades detection delay for fewer
+/// false alarms.
+#[derive(Debug, Clone)]
+pub struct Cusum {
+    warmup: usize,
+    drift: f64,
+    threshold: f64,
+    count: usize,
+    baseline: Baseline,
+    upper: f64,
+    lower: f64,
+    /// `count`, `baseline`, `upper` and `lower` before the last `next`, or
+    /// `None` when there is nothing to roll back
+    undo: Option<(usize, Baseline, f64, f64)>,
+}
+
+impl Cusum {
+    /// Creates a new CUSUM detector
+    ///
+    /// # Arguments
+    ///
+    /// * `warmup` - Values used to estimate each regime's baseline (must be >= 2)
+    /// * `drift` - Allowance `k` in standard deviations (must be >= 0)
+    /// * `threshold` - Decision threshold `h` in standard deviations (must be > 0)
+    pub fn new(warmup: usize, drift: f64, threshold: f64) -> Result<Self, IndicatorError> {
+        if warmup < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "warmup",
+                value: warmup as f64,
+                constraint: ">= 2",
+            });
+        }
+        if drift.is_nan() || drift < 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "drift",
+                value: drift,
+                constraint: ">= 0",
+            });
+        }
+        if threshold.is_nan() || threshold <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "threshold",
+                value: threshold,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            warmup,
+            drift,
+            threshold,
+            count: 0,
+            baseline: (0, 0.0, 0.0),
+            upper: 0.0,
+            lower: 0.0,
+            undo: None,
+        })
+    }
+
+    /// Runs the detector over a series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if the series is not longer than `warmup`.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<ChangePoint>>, IndicatorError> {
+        if values.len() <= self.warmup {
+            return Err(IndicatorError::InsufficientData {
+                required: self.warmup + 1,
+                actual: values.len(),
+            });
+        }
+        let mut state = Self::new(self.warmup, self.drift, self.threshold)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Processes the next value, returning a change point if one is signalled
+    pub fn next(&mut self, value: f64) -> Option<ChangePoint> {
+        self.undo = Some((self.count, self.baseline, self.upper, self.lower));
+        let index = self.count;
+        self.count += 1;
+
+        let (n, mean, m2) = self.baseline;
+        if n < self.warmup {
+            let n = n + 1;
+            let delta = value - mean;
+            let mean = mean + delta / n as f64;
+            self.baseline = (n, mean, m2 + delta * (value - mean));
+            return None;
+        }
+
+        // Floor the deviation so a flat baseline still yields finite scores
+        let std_dev = (m2 / (n - 1) as f64)
+            .sqrt()
+            .max(f64::EPSILON * mean.abs().max(1.0));
+        let z = (value - mean) / std_dev;
+        self.upper = (self.upper + z - self.drift).max(0.0);
+        self.lower = (self.lower - z - self.drift).max(0.0);
+
+        let (direction, statistic) = if self.upper > self.threshold {
+            (ChangeDirection::Increase, self.upper)
+        } else if self.lower > self.threshold {
+            (ChangeDirection::Decrease, self.lower)
+        } else {
+            return None;
+        };
+
+        // The signalling value opens the new regime's baseline
+        self.baseline = (1, value, 0.0);
+        self.upper = 0.0;
+        self.lower = 0.0;
+        Some(ChangePoint {
+            index,
+            direction,
+            statistic,
+        })
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<ChangePoint> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some((count, baseline, upper, lower)) = self.undo.take() {
+            self.count = count;
+            self.baseline = baseline;
+            self.upper = upper;
+            self.lower = lower;
+        }
+    }
+
+    /// Resets the detector state
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.baseline = (0, 0.0, 0.0);
+        self.upper = 0.0;
+        self.lower = 0.0;
+        self.undo = None;
+    }
+
+    /// Returns the warmup length
+    pub fn warmup(&self) -> usize {
+        self.warmup
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noisy(level: f64, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| level + [0.3, -0.2, 0.1, -0.4, 0.2][i % 5])
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_mean_shift() {
+        let mut values = noisy(0.0, 50);
+        values.extend(noisy(2.0, 20));
+        let detector = Cusum::new(20, 0.5, 8.0).unwrap();
+        let changes: Vec<_> = detector
+            .calculate(&values)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].direction, ChangeDirection::Increase);
+        assert!((50..53).contains(&changes[0].index));
+        assert!(changes[0].statistic > 8.0);
+    }
+
+    #[test]
+    fn test_stationary_series_has_no_changes() {
+        let detector = Cusum::new(20, 0.5, 8.0).unwrap();
+        let changes = detector.calculate(&noisy(1.0, 500)).unwrap();
+        assert!(changes.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_detects_successive_regimes() {
+        let mut values = noisy(5.0, 30);
+        values.extend(noisy(1.0, 30));
+        values.extend(noisy(5.0, 30));
+        let mut detector = Cusum::new(10, 1.0, 10.0).unwrap();
+        let changes: Vec<_> = values.iter().filter_map(|&v| detector.next(v)).collect();
+
+        let directions: Vec<_> = changes.iter().map(|c| c.direction).collect();
+        assert_eq!(
+            directions,
+            vec![ChangeDirection::Decrease, ChangeDirection::Increase]
+        );
+
+        detector.reset();
+        assert_eq!(detector.next(100.0), None);
+    }
+
+    #[test]
+    fn test_update_last_withdraws_provisional_change() {
+        let values = noisy(0.0, 20);
+        let mut detector = Cusum::new(20, 0.5, 8.0).unwrap();
+        for &value in &values {
+            detector.next(value);
+        }
+
+        // An outlier print signals a change until the bar is revised
+        assert!(detector.next(50.0).is_some());
+        assert_eq!(detector.update_last(0.1), None);
+        let expected = detector.clone().next(0.2);
+        detector.next(40.0);
+        assert_eq!(detector.update_last(0.2), expected);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(Cusum::new(1, 0.5, 5.0).is_err());
+        assert!(Cusum::new(10, -0.1, 5.0).is_err());
+        assert!(Cusum::new(10, 0.5, 0.0).is_err());
+        assert!(Cusum::new(10, 0.5, 5.0)
+            .unwrap()
+            .calculate(&[1.0; 10])
+            .is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Choppiness Index (CHOP)
+
+use std::collections::VecDeque;
+
+use crate::atr::true_range;
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Choppiness Index indicator
+///
+/// Compares the path length of price (the summed true ranges) with the net
+/// distance it spanned over the last `period` bars:
+///
+/// CHOP = 100 × log10(Σ TR / (HighestHigh − LowestLow)) / log10(period)
+///
+/// Values near 100 indicate a sideways, choppy market and values near 0 a
+/// strong trend; 61.8 and 38.2 are the conventional thresholds. The first
+/// bar's true range is its high-low range, as in [`ATR`](crate::ATR). A
+/// window with no range at all reports 100.
+///
+/// # Example
+///
+/// ```
+/// use indicator::Choppiness;
+///
+/// // Bars stacked end to end: the path equals the span
+/// let high: Vec<f64> = (0..5).map(|i| 11.0 + i as f64).collect();
+/// let low: Vec<f64> = (0..5).map(|i| 10.0 + i as f64).collect();
+///
+/// let result = Choppiness::new(4)?.calculate(&high, &low, &high)?;
+/// assert!(result[2].is_none());
+/// assert!(result[3].unwrap().abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Choppiness {
+    period: usize,
+    prev_close: Option<f64>,
+    /// True range, high and low per bar in the window, oldest first
+    window: VecDeque<(f64, f64, f64)>,
+    tr_sum: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Choppiness {
+    /// Creates a new Choppiness Index indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Lookback in bars (must be >= 2, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+
+        Ok(Self {
+            period,
+            prev_close: None,
+            window: VecDeque::with_capacity(period),
+            tr_sum: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates CHOP for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period, len)?;
+
+        let mut state = Self::new(self.period)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates CHOP for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period, candles.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let tr = true_range(high, low, self.prev_close);
+        self.prev_close = Some(close);
+
+        if self.window.len() == self.period {
+            if let Some((oldest, _, _)) = self.window.pop_front() {
+                self.tr_sum -= oldest;
+            }
+        }
+        self.window.push_back((tr, high, low));
+        self.tr_sum += tr;
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let (highest, lowest) = self.window.iter().fold(
+            (f64::NEG_INFINITY, f64::INFINITY),
+            |(hh, ll), &(_, h, l)| (hh.max(h), ll.min(l)),
+        );
+        let range = highest - lowest;
+        if range <= 0.0 {
+            return Some(100.0);
+        }
+        Some(100.0 * (self.tr_sum / range).log10() / (self.period as f64).log10())
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev_close = None;
+        self.window.clear();
+        self.tr_sum = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the lookback period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chop_fully_overlapping_bars() {
+        // Every bar spans the whole range: path = period × range
+        let result = Choppiness::new(5)
+            .unwrap()
+            .calculate(&[11.0; 6], &[10.0; 6], &[10.5; 6])
+            .unwrap();
+        assert!((result[4].unwrap() - 100.0).abs() < 1e-12);
+        assert!((result[5].unwrap() - 100.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_chop_ranging_above_trending() {
+        let trend: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        let range: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 1.3).sin() * 3.0)
+            .collect();
+        let chop = Choppiness::new(14).unwrap();
+        let last = |prices: &[f64]| {
+            let high: Vec<f64> = prices.iter().map(|p| p + 0.5).collect();
+            let low: Vec<f64> = prices.iter().map(|p| p - 0.5).collect();
+            chop.calculate(&high, &low, prices).unwrap()[29].unwrap()
+        };
+        assert!(last(&trend) < 38.2);
+        assert!(last(&range) > 61.8);
+    }
+
+    #[test]
+    fn test_chop_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| {
+                let base = 30.0 + (i as f64 * 0.5).cos() * 2.0;
+                Candle::new(i, base, base + 0.4, base - 0.7, base - 0.1, 1.0)
+            })
+            .collect();
+        let chop = Choppiness::new(6).unwrap();
+        let batch = chop.calculate_candles(&candles).unwrap();
+
+        let mut streaming = chop.clone();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| streaming.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        streaming.reset();
+        assert_eq!(streaming.next(1.0, 0.0, 0.5), None);
+    }
+
+    #[test]
+    fn test_chop_invalid_inputs() {
+        assert!(Choppiness::new(1).is_err());
+        let chop = Choppiness::new(3).unwrap();
+        assert!(matches!(
+            chop.calculate(&[1.0; 2], &[1.0; 2], &[1.0; 2]),
+            Err(IndicatorError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        ));
+        assert!(chop.calculate(&[1.0; 3], &[1.0; 3], &[1.0; 4]).is_err());
+    }
+}
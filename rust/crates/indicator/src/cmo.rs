@@ -0,0 +1,209 @@
+//! Chande Momentum Oscillator (CMO)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Chande Momentum Oscillator indicator
+///
+/// # Formula
+///
+/// CMO = 100 × (ΣUp − ΣDown) / (ΣUp + ΣDown)
+///
+/// where ΣUp and ΣDown are the summed gains and losses of the last `period`
+/// one-bar price changes. Unlike RSI the sums are not smoothed, so CMO
+/// reacts faster and spans −100 to 100 symmetrically around zero. A window
+/// without any price change reports zero.
+///
+/// # Example
+///
+/// ```
+/// use indicator::CMO;
+///
+/// let prices = [10.0, 11.0, 12.0, 11.0, 13.0];
+/// let result = CMO::new(4)?.calculate(&prices)?;
+///
+/// assert!(result[3].is_none());
+/// // Gains 1 + 1 + 2, loss 1
+/// assert_eq!(result[4], Some(60.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CMO {
+    period: usize,
+    prev: Option<f64>,
+    /// One-bar price changes in the window, oldest first
+    changes: VecDeque<f64>,
+    gains: f64,
+    losses: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl CMO {
+    /// Creates a new CMO indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of price changes summed (must be > 0, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            prev: None,
+            changes: VecDeque::with_capacity(period),
+            gains: 0.0,
+            losses: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates CMO for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period + 1, prices.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` price changes have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let change = price - self.prev.replace(price)?;
+
+        if self.changes.len() == self.period {
+            if let Some(oldest) = self.changes.pop_front() {
+                self.remove(oldest);
+            }
+        }
+        self.changes.push_back(change);
+        if change > 0.0 {
+            self.gains += change;
+        } else {
+            self.losses -= change;
+        }
+        if self.changes.len() < self.period {
+            return None;
+        }
+
+        let total = self.gains + self.losses;
+        if total <= 0.0 {
+            return Some(0.0);
+        }
+        Some(100.0 * (self.gains - self.losses) / total)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.changes.clear();
+        self.gains = 0.0;
+        self.losses = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the lookback period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    fn remove(&mut self, change: f64) {
+        // Clamp so rounding drift never leaves a tiny negative sum behind
+        if change > 0.0 {
+            self.gains = (self.gains - change).max(0.0);
+        } else {
+            self.losses = (self.losses + change).max(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmo_matches_window_sums() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 25.0 + (i as f64 * 0.45).sin() * 3.0 + i as f64 * 0.02)
+            .collect();
+        let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let result = CMO::new(9).unwrap().calculate(&prices).unwrap();
+        assert!(result[8].is_none());
+        for (value, window) in result[9..].iter().zip(changes.windows(9)) {
+            let up: f64 = window.iter().filter(|c| **c > 0.0).sum();
+            let down: f64 = -window.iter().filter(|c| **c < 0.0).sum::<f64>();
+            let expected = 100.0 * (up - down) / (up + down);
+            assert!((value.unwrap() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_cmo_extremes_and_flat() {
+        let rising: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        let cmo = CMO::new(5).unwrap();
+        assert_eq!(cmo.calculate(&rising).unwrap()[9], Some(100.0));
+        assert_eq!(cmo.calculate(&falling).unwrap()[9], Some(-100.0));
+        assert_eq!(cmo.calculate(&[3.0; 8]).unwrap()[7], Some(0.0));
+    }
+
+    #[test]
+    fn test_cmo_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..30).map(|i| 5.0 + (i as f64 * 0.8).cos()).collect();
+        let mut cmo = CMO::new(6).unwrap();
+        let batch = cmo.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| cmo.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        cmo.reset();
+        assert_eq!(cmo.next(1.0), None);
+        assert_eq!(cmo.period(), 6);
+    }
+
+    #[test]
+    fn test_cmo_invalid_inputs() {
+        assert!(CMO::new(0).is_err());
+        assert!(matches!(
+            CMO::new(14).unwrap().calculate(&[1.0; 14]),
+            Err(IndicatorError::InsufficientData {
+                required: 15,
+                actual: 14
+            })
+        ));
+    }
+}
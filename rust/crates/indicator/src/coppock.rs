@@ -0,0 +1,206 @@
+//! Coppock Curve
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, WMA};
+
+/// Coppock Curve indicator
+///
+/// A long-term momentum gauge built from two rates of change:
+///
+/// ROC(n) = 100 × (P(t) − P(t−n)) / P(t−n)
+/// Coppock = WMA(ROC(long) + ROC(short), wma_period)
+///
+/// Upturns from below zero are the classic buy signal. The summed ROC is
+/// available once `max(long, short) + 1` prices have been seen, and the
+/// curve `wma_period − 1` bars after that.
+///
+/// # Example
+///
+/// ```
+/// use indicator::Coppock;
+///
+/// let prices: Vec<f64> = (0..30).map(|i| 100.0 * 1.01_f64.powi(i)).collect();
+/// let result = Coppock::new(14, 11, 10)?.calculate(&prices)?;
+///
+/// assert!(result[22].is_none());
+/// assert!(result[23].unwrap() > 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Coppock {
+    long_roc: usize,
+    short_roc: usize,
+    /// The last `max(long, short) + 1` prices, oldest first
+    window: VecDeque<f64>,
+    wma: WMA,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Coppock {
+    /// Creates a new Coppock Curve indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `long_roc` - Longer rate-of-change lookback (must be > 0, typically 14)
+    /// * `short_roc` - Shorter rate-of-change lookback (must be > 0, typically 11)
+    /// * `wma_period` - Period of the smoothing WMA (must be > 0, typically 10)
+    pub fn new(
+        long_roc: usize,
+        short_roc: usize,
+        wma_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        for (field, value) in [("long_roc", long_roc), ("short_roc", short_roc)] {
+            if value == 0 {
+                return Err(IndicatorError::InvalidParameter {
+                    field,
+                    value: value as f64,
+                    constraint: "> 0",
+                });
+            }
+        }
+        let lookback = long_roc.max(short_roc);
+
+        Ok(Self {
+            long_roc,
+            short_roc,
+            window: VecDeque::with_capacity(lookback + 1),
+            wma: WMA::new(wma_period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the Coppock Curve for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than
+    /// `max(long, short) + wma_period` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.lookback() + self.wma.period(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// A zero reference price contributes a rate of change of zero.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let lookback = self.lookback();
+        if self.window.len() == lookback + 1 {
+            self.window.pop_front();
+        }
+        self.window.push_back(price);
+        if self.window.len() <= lookback {
+            return None;
+        }
+
+        let roc = |n: usize| {
+            let base = self.window[lookback - n];
+            if base != 0.0 {
+                100.0 * (price - base) / base
+            } else {
+                0.0
+            }
+        };
+        let summed = roc(self.long_roc) + roc(self.short_roc);
+        self.wma.next(summed)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.wma.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the long ROC, short ROC and WMA periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (self.long_roc, self.short_roc, self.wma.period())
+    }
+
+    fn lookback(&self) -> usize {
+        self.long_roc.max(self.short_roc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roc(prices: &[f64], i: usize, n: usize) -> f64 {
+        100.0 * (prices[i] - prices[i - n]) / prices[i - n]
+    }
+
+    #[test]
+    fn test_coppock_is_wma_of_summed_roc() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 50.0 + (i as f64 * 0.2).sin() * 5.0 + i as f64 * 0.1)
+            .collect();
+        let summed: Vec<f64> = (6..prices.len())
+            .map(|i| roc(&prices, i, 6) + roc(&prices, i, 4))
+            .collect();
+        let expected = WMA::new(5).unwrap().calculate(&summed).unwrap();
+
+        let result = Coppock::new(6, 4, 5).unwrap().calculate(&prices).unwrap();
+        assert!(result[..10].iter().all(Option::is_none));
+        for (value, expected) in result[6..].iter().zip(&expected) {
+            match (value, expected) {
+                (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-9),
+                _ => assert_eq!(value, expected),
+            }
+        }
+    }
+
+    #[test]
+    fn test_coppock_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64).cos()).collect();
+        let mut coppock = Coppock::new(5, 3, 4).unwrap();
+        let batch = coppock.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| coppock.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        coppock.reset();
+        assert_eq!(coppock.next(1.0), None);
+        assert_eq!(coppock.periods(), (5, 3, 4));
+    }
+
+    #[test]
+    fn test_coppock_invalid_inputs() {
+        assert!(Coppock::new(0, 11, 10).is_err());
+        assert!(Coppock::new(14, 0, 10).is_err());
+        assert!(Coppock::new(14, 11, 0).is_err());
+        assert!(matches!(
+            Coppock::new(14, 11, 10).unwrap().calculate(&[1.0; 23]),
+            Err(IndicatorError::InsufficientData {
+                required: 24,
+                actual: 23
+            })
+        ));
+    }
+}
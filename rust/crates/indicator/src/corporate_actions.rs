@@ -0,0 +1,272 @@
+//! Split and dividend adjustment of candle series
+//!
+//! Raw price history jumps on split and ex-dividend dates even though holders
+//! lose no value, which corrupts every indicator computed across the event.
+//! These utilities rescale the bars on one side of each event so the series is
+//! continuous, and record the factor applied for each action.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::corporate_actions::{adjust, AdjustmentMode, CorporateAction};
+//! use indicator::Candle;
+//!
+//! let candles = vec![
+//!     Candle::new(1, 200.0, 202.0, 198.0, 200.0, 1_000.0),
+//!     Candle::new(2, 101.0, 102.0, 99.0, 100.0, 2_000.0), // 2-for-1 split
+//! ];
+//! let actions = [CorporateAction::Split { timestamp: 2, ratio: 2.0 }];
+//!
+//! let adjusted = adjust(&candles, &actions, AdjustmentMode::Backward)?;
+//!
+//! assert_eq!(adjusted.candles[0].close, 100.0);
+//! assert_eq!(adjusted.candles[0].volume, 2_000.0);
+//! assert_eq!(adjusted.log[0].price_factor, 0.5);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::{Candle, IndicatorError};
+
+/// A corporate action effective from a given bar timestamp
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CorporateAction {
+    /// Stock split; `ratio` is new shares per old share (2.0 for a 2-for-1
+    /// split, 0.1 for a 1-for-10 reverse split)
+    Split {
+        /// Timestamp of the first bar trading on a post-split basis
+        timestamp: i64,
+        /// New shares per old share
+        ratio: f64,
+    },
+    /// Cash dividend per share
+    CashDividend {
+        /// Timestamp of the ex-dividend bar
+        timestamp: i64,
+        /// Dividend amount per share
+        amount: f64,
+    },
+}
+
+impl CorporateAction {
+    /// Timestamp from which the action is effective
+    pub fn timestamp(&self) -> i64 {
+        match *self {
+            CorporateAction::Split { timestamp, .. } => timestamp,
+            CorporateAction::CashDividend { timestamp, .. } => timestamp,
+        }
+    }
+}
+
+/// Which side of each action is rescaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdjustmentMode {
+    /// Rescale bars before each action; the latest prices stay as traded
+    Backward,
+    /// Rescale bars from each action onwards; the earliest prices stay as traded
+    Forward,
+}
+
+/// Factor applied for one corporate action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdjustmentFactor {
+    /// The action the factor was derived from
+    pub action: CorporateAction,
+    /// Multiplier applied to the open/high/low/close of the rescaled bars
+    pub price_factor: f64,
+    /// Multiplier applied to the volume of the rescaled bars
+    pub volume_factor: f64,
+    /// Number of bars the factor was applied to
+    pub bars_adjusted: usize,
+}
+
+/// Adjusted candles together with the factor log
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustedCandles {
+    /// Candles after all adjustments
+    pub candles: Vec<Candle>,
+    /// One entry per action, in chronological order
+    pub log: Vec<AdjustmentFactor>,
+}
+
+/// Adjusts a candle series for splits and cash dividends
+///
+/// Candles must be sorted by timestamp. For a split with ratio `r`, prices on
+/// the rescaled side are multiplied by `1/r` (backward) or `r` (forward) and
+/// volumes by the inverse. For a dividend `D`, the price factor is
+/// `1 − D / C`, where `C` is the close of the last bar before the ex-date
+/// (backward) or its inverse (forward); volumes are unchanged.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for a non-positive split ratio, a negative
+/// dividend, or a dividend at least as large as the prior close.
+pub fn adjust(
+    candles: &[Candle],
+    actions: &[CorporateAction],
+    mode: AdjustmentMode,
+) -> Result<AdjustedCandles, IndicatorError> {
+    let mut actions = actions.to_vec();
+    actions.sort_by_key(CorporateAction::timestamp);
+
+    let mut adjusted = candles.to_vec();
+    let mut log = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        // First bar on the post-action basis
+        let split_at = candles.partition_point(|c| c.timestamp < action.timestamp());
+
+        let (price_factor, volume_factor) = match action {
+            CorporateAction::Split { ratio, .. } => {
+                if ratio.is_nan() || ratio <= 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "ratio",
+                        value: ratio,
+                        constraint: "> 0",
+                    });
+                }
+                (1.0 / ratio, ratio)
+            }
+            CorporateAction::CashDividend { amount, .. } => {
+                if amount.is_nan() || amount < 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "amount",
+                        value: amount,
+                        constraint: ">= 0",
+                    });
+                }
+                // Use the raw close: the factor must not depend on earlier adjustments
+                let prior_close = split_at
+                    .checked_sub(1)
+                    .map_or(f64::INFINITY, |i| candles[i].close);
+                if amount >= prior_close {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "amount",
+                        value: amount,
+                        constraint: "< prior close",
+                    });
+                }
+                (1.0 - amount / prior_close, 1.0)
+            }
+        };
+
+        let (range, price_factor, volume_factor) = match mode {
+            AdjustmentMode::Backward => (0..split_at, price_factor, volume_factor),
+            AdjustmentMode::Forward => (
+                split_at..candles.len(),
+                1.0 / price_factor,
+                1.0 / volume_factor,
+            ),
+        };
+        let bars_adjusted = range.len();
+        for candle in &mut adjusted[range] {
+            candle.open *= price_factor;
+            candle.high *= price_factor;
+            candle.low *= price_factor;
+            candle.close *= price_factor;
+            candle.volume *= volume_factor;
+        }
+
+        log.push(AdjustmentFactor {
+            action,
+            price_factor,
+            volume_factor,
+            bars_adjusted,
+        });
+    }
+
+    Ok(AdjustedCandles {
+        candles: adjusted,
+        log,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(timestamp: i64, price: f64, volume: f64) -> Candle {
+        Candle::new(timestamp, price, price, price, price, volume)
+    }
+
+    #[test]
+    fn test_split_backward_adjustment() {
+        let candles = vec![
+            flat(1, 300.0, 100.0),
+            flat(2, 303.0, 100.0),
+            flat(3, 101.0, 300.0),
+        ];
+        let actions = [CorporateAction::Split {
+            timestamp: 3,
+            ratio: 3.0,
+        }];
+        let adjusted = adjust(&candles, &actions, AdjustmentMode::Backward).unwrap();
+
+        assert!((adjusted.candles[0].close - 100.0).abs() < 1e-10);
+        assert!((adjusted.candles[1].high - 101.0).abs() < 1e-10);
+        assert_eq!(adjusted.candles[0].volume, 300.0);
+        assert_eq!(adjusted.candles[2], candles[2]);
+        assert_eq!(adjusted.log[0].bars_adjusted, 2);
+    }
+
+    #[test]
+    fn test_split_forward_adjustment() {
+        let candles = vec![flat(1, 300.0, 100.0), flat(2, 100.0, 300.0)];
+        let actions = [CorporateAction::Split {
+            timestamp: 2,
+            ratio: 3.0,
+        }];
+        let adjusted = adjust(&candles, &actions, AdjustmentMode::Forward).unwrap();
+
+        assert_eq!(adjusted.candles[0], candles[0]);
+        assert!((adjusted.candles[1].close - 300.0).abs() < 1e-10);
+        assert!((adjusted.candles[1].volume - 100.0).abs() < 1e-10);
+        assert!((adjusted.log[0].price_factor - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dividend_and_split_combined() {
+        let candles = vec![
+            flat(1, 100.0, 10.0),
+            flat(2, 98.0, 10.0), // ex-dividend, $2 paid
+            flat(3, 49.0, 20.0), // 2-for-1 split
+        ];
+        let actions = [
+            CorporateAction::Split {
+                timestamp: 3,
+                ratio: 2.0,
+            },
+            CorporateAction::CashDividend {
+                timestamp: 2,
+                amount: 2.0,
+            },
+        ];
+        let adjusted = adjust(&candles, &actions, AdjustmentMode::Backward).unwrap();
+
+        // Log is chronological regardless of input order
+        assert!(matches!(
+            adjusted.log[0].action,
+            CorporateAction::CashDividend { .. }
+        ));
+        assert!((adjusted.log[0].price_factor - 0.98).abs() < 1e-12);
+        // 100 × 0.98 × 0.5 = 49, continuous with the post-split price
+        assert!((adjusted.candles[0].close - 49.0).abs() < 1e-10);
+        assert!((adjusted.candles[1].close - 49.0).abs() < 1e-10);
+        assert_eq!(adjusted.candles[0].volume, 20.0);
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let candles = vec![flat(1, 10.0, 1.0), flat(2, 10.0, 1.0)];
+        let bad_split = [CorporateAction::Split {
+            timestamp: 2,
+            ratio: 0.0,
+        }];
+        assert!(adjust(&candles, &bad_split, AdjustmentMode::Backward).is_err());
+
+        let oversized_dividend = [CorporateAction::CashDividend {
+            timestamp: 2,
+            amount: 10.0,
+        }];
+        assert!(adjust(&candles, &oversized_dividend, AdjustmentMode::Backward).is_err());
+    }
+}
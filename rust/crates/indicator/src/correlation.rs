@@ -0,0 +1,297 @@
+//! Rolling correlation and beta between two aligned series
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Rolling co-movement statistics for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorrelationOutput {
+    /// Pearson correlation of the asset with the benchmark, in [−1, 1]
+    pub correlation: f64,
+    /// Beta of the asset on the benchmark: `cov(asset, benchmark) / var(benchmark)`
+    pub beta: f64,
+    /// Sample covariance of the two series
+    pub covariance: f64,
+}
+
+/// Rolling Pearson correlation and beta of one series against another
+///
+/// Takes two bar-aligned series, typically the returns of a stock and of an
+/// index, and keeps the means, variances and co-moment of the last `period`
+/// pairs with Welford-style add/remove updates, so each step is O(1).
+///
+/// Feed returns rather than prices: correlating price levels mostly measures
+/// a shared trend. A window in which either series is constant has no
+/// defined correlation and reports `0.0`; beta is likewise `0.0` when the
+/// benchmark is constant.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingCorrelation;
+///
+/// let benchmark = [0.01, -0.02, 0.015, 0.005, -0.01];
+/// let asset: Vec<f64> = benchmark.iter().map(|r| 1.5 * r + 0.001).collect();
+///
+/// let result = RollingCorrelation::new(4)?.calculate(&asset, &benchmark)?;
+/// let last = result[4].unwrap();
+/// assert!((last.correlation - 1.0).abs() < 1e-9);
+/// assert!((last.beta - 1.5).abs() < 1e-9);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingCorrelation {
+    period: usize,
+    /// `(asset, benchmark)` pairs in the window, oldest first
+    window: VecDeque<(f64, f64)>,
+    mean_asset: f64,
+    mean_benchmark: f64,
+    /// Sum of squared deviations of the asset
+    m2_asset: f64,
+    /// Sum of squared deviations of the benchmark
+    m2_benchmark: f64,
+    /// Sum of cross deviations
+    co_moment: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingCorrelation {
+    /// Creates a new rolling correlation
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of pairs in the window (must be >= 2)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            mean_asset: 0.0,
+            mean_benchmark: 0.0,
+            m2_asset: 0.0,
+            m2_benchmark: 0.0,
+            co_moment: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates rolling correlation and beta for aligned series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` pairs.
+    pub fn calculate(
+        &self,
+        asset: &[f64],
+        benchmark: &[f64],
+    ) -> Result<Vec<Option<CorrelationOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[("asset", asset.len()), ("benchmark", benchmark.len())])?;
+        check_min_length(self.period, len)?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(asset
+            .iter()
+            .zip(benchmark)
+            .map(|(&asset, &benchmark)| state.next(asset, benchmark))
+            .collect())
+    }
+
+    /// Adds the next pair of values (streaming mode)
+    ///
+    /// Returns `None` until `period` pairs have been seen.
+    pub fn next(&mut self, asset: f64, benchmark: f64) -> Option<CorrelationOutput> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                self.remove(oldest);
+            }
+        }
+        self.window.push_back((asset, benchmark));
+        self.add(asset, benchmark);
+
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let denominator = (self.m2_asset * self.m2_benchmark).sqrt();
+        let correlation = if denominator > 0.0 {
+            (self.co_moment / denominator).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        let beta = if self.m2_benchmark > 0.0 {
+            self.co_moment / self.m2_benchmark
+        } else {
+            0.0
+        };
+        Some(CorrelationOutput {
+            correlation,
+            beta,
+            covariance: self.co_moment / (self.period - 1) as f64,
+        })
+    }
+
+    /// Replaces the most recent pair, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, asset: f64, benchmark: f64) -> Option<CorrelationOutput> {
+        self.rollback();
+        self.next(asset, benchmark)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest pair can be undone; further calls do nothing until
+    /// the next pair arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.mean_asset = 0.0;
+        self.mean_benchmark = 0.0;
+        self.m2_asset = 0.0;
+        self.m2_benchmark = 0.0;
+        self.co_moment = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Incorporates a pair that has just been pushed onto the window
+    fn add(&mut self, asset: f64, benchmark: f64) {
+        let n = self.window.len() as f64;
+        let delta_asset = asset - self.mean_asset;
+        let delta_benchmark = benchmark - self.mean_benchmark;
+        self.mean_asset += delta_asset / n;
+        self.mean_benchmark += delta_benchmark / n;
+        self.m2_asset += delta_asset * (asset - self.mean_asset);
+        self.m2_benchmark += delta_benchmark * (benchmark - self.mean_benchmark);
+        self.co_moment += delta_asset * (benchmark - self.mean_benchmark);
+    }
+
+    /// Removes a pair that has just been popped from the window
+    fn remove(&mut self, (asset, benchmark): (f64, f64)) {
+        let remaining = self.window.len() as f64;
+        if remaining == 0.0 {
+            self.reset();
+            return;
+        }
+
+        let mean_asset = self.mean_asset - (asset - self.mean_asset) / remaining;
+        let mean_benchmark = self.mean_benchmark - (benchmark - self.mean_benchmark) / remaining;
+        self.m2_asset = (self.m2_asset - (asset - mean_asset) * (asset - self.mean_asset)).max(0.0);
+        self.m2_benchmark = (self.m2_benchmark
+            - (benchmark - mean_benchmark) * (benchmark - self.mean_benchmark))
+            .max(0.0);
+        self.co_moment -= (asset - mean_asset) * (benchmark - self.mean_benchmark);
+        self.mean_asset = mean_asset;
+        self.mean_benchmark = mean_benchmark;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(asset: &[f64], benchmark: &[f64]) -> (f64, f64) {
+        let n = asset.len() as f64;
+        let mean_a = asset.iter().sum::<f64>() / n;
+        let mean_b = benchmark.iter().sum::<f64>() / n;
+        let (mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0);
+        for (&a, &b) in asset.iter().zip(benchmark) {
+            saa += (a - mean_a).powi(2);
+            sbb += (b - mean_b).powi(2);
+            sab += (a - mean_a) * (b - mean_b);
+        }
+        (sab / (saa * sbb).sqrt(), sab / sbb)
+    }
+
+    #[test]
+    fn test_correlation_matches_naive_window() {
+        let benchmark: Vec<f64> = (0..60).map(|i| (i as f64 * 0.7).sin() * 0.02).collect();
+        let asset: Vec<f64> = benchmark
+            .iter()
+            .enumerate()
+            .map(|(i, b)| 1.2 * b + (i as f64 * 1.9).cos() * 0.01)
+            .collect();
+        let period = 12;
+        let result = RollingCorrelation::new(period)
+            .unwrap()
+            .calculate(&asset, &benchmark)
+            .unwrap();
+
+        assert!(result[..period - 1].iter().all(Option::is_none));
+        for (i, output) in result.iter().enumerate().skip(period - 1) {
+            let output = output.unwrap();
+            let window = i + 1 - period..=i;
+            let (correlation, beta) = naive(&asset[window.clone()], &benchmark[window]);
+            assert!((output.correlation - correlation).abs() < 1e-9, "bar {i}");
+            assert!((output.beta - beta).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_negative_and_degenerate_correlation() {
+        let benchmark = [1.0, 2.0, 3.0, 4.0];
+        let inverse = [8.0, 6.0, 4.0, 2.0];
+        let result = RollingCorrelation::new(4)
+            .unwrap()
+            .calculate(&inverse, &benchmark)
+            .unwrap();
+        let last = result[3].unwrap();
+        assert!((last.correlation + 1.0).abs() < 1e-12);
+        assert!((last.beta + 2.0).abs() < 1e-12);
+
+        let flat = RollingCorrelation::new(3)
+            .unwrap()
+            .calculate(&[1.0, 2.0, 3.0], &[5.0; 3])
+            .unwrap();
+        assert_eq!(flat[2].unwrap().correlation, 0.0);
+        assert_eq!(flat[2].unwrap().beta, 0.0);
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let asset: Vec<f64> = (0..30).map(|i| (i as f64 * 0.3).sin()).collect();
+        let benchmark: Vec<f64> = (0..30).map(|i| (i as f64 * 0.5).cos()).collect();
+        let mut correlation = RollingCorrelation::new(6).unwrap();
+        let batch = correlation.calculate(&asset, &benchmark).unwrap();
+        let streamed: Vec<Option<CorrelationOutput>> = asset
+            .iter()
+            .zip(&benchmark)
+            .map(|(&a, &b)| correlation.next(a, b))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        assert!(RollingCorrelation::new(1).is_err());
+        assert!(matches!(
+            correlation.calculate(&asset, &benchmark[1..]),
+            Err(IndicatorError::LengthMismatch {
+                field: "benchmark",
+                ..
+            })
+        ));
+        assert!(correlation.calculate(&asset[..5], &benchmark[..5]).is_err());
+    }
+}
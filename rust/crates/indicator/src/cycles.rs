@@ -0,0 +1,408 @@
+//! Ehlers cycle indicators based on the Hilbert Transform
+//!
+//! John Ehlers' Hilbert Transform decomposes a price series into in-phase and
+//! quadrature components, from which the dominant market cycle and its phase
+//! can be measured bar by bar. This module provides:
+//!
+//! * the **dominant cycle period** (HT_DCPERIOD),
+//! * the **instantaneous trendline** (HT_TRENDLINE), a moving average whose
+//!   length tracks the measured cycle, and
+//! * the **MESA sine wave** (HT_SINE) with its 45° lead line, whose crossings
+//!   mark cycle turning points.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::cycles::HilbertTransform;
+//!
+//! let prices: Vec<f64> = (0..120)
+//!     .map(|i| 100.0 + 5.0 * (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+//!     .collect();
+//!
+//! let ht = HilbertTransform::new();
+//! let result = ht.calculate(&prices)?;
+//!
+//! let last = result.last().copied().flatten().unwrap();
+//! println!("Dominant cycle: {:.1} bars", last.dominant_period);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use crate::IndicatorError;
+
+/// Number of bars before the Hilbert Transform outputs are considered settled
+const WARMUP: usize = 63;
+/// Shortest cycle period the transform will report
+const MIN_PERIOD: f64 = 6.0;
+/// Longest cycle period the transform will report
+const MAX_PERIOD: f64 = 50.0;
+
+/// Per-bar output of the Hilbert Transform cycle indicators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HilbertCycle {
+    /// Smoothed dominant cycle period in bars (HT_DCPERIOD)
+    pub dominant_period: f64,
+    /// Dominant cycle phase in degrees, in the range (-45, 315]
+    pub phase: f64,
+    /// Instantaneous trendline (HT_TRENDLINE)
+    pub trendline: f64,
+    /// MESA sine wave, `sin(phase)` (HT_SINE)
+    pub sine: f64,
+    /// MESA lead sine wave, `sin(phase + 45°)`
+    pub lead_sine: f64,
+}
+
+/// Hilbert Transform dominant-cycle, trendline and sine wave indicator
+///
+/// The transform needs a long warmup for its recursive filters to settle, so
+/// the first 63 outputs are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct HilbertTransform {
+    /// Number of prices seen so far
+    count: usize,
+    /// Raw prices, most recent first
+    prices: Lagged,
+    /// 4-bar WMA smoothed prices, most recent first
+    smooth: Lagged,
+    detrender: Lagged,
+    in_phase: Lagged,
+    quadrature: Lagged,
+    /// Instantaneous trend values feeding the trendline smoother
+    trend: Lagged,
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+    /// `count` and the recursive values `i2`, `q2`, `re`, `im`, `period`
+    /// and `smooth_period` before the last `next`, or `None` when there is
+    /// nothing to roll back
+    undo: Option<(usize, [f64; 6])>,
+}
+
+impl HilbertTransform {
+    /// Creates a new Hilbert Transform indicator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calculates the cycle indicators for a batch of prices
+    ///
+    /// The calculation starts from a fresh state regardless of any streaming
+    /// state held by `self`.
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input whose first 63 values are
+    /// `None`, or `InsufficientData` if fewer than 64 prices are supplied.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<HilbertCycle>>, IndicatorError> {
+        if prices.len() <= WARMUP {
+            return Err(IndicatorError::InsufficientData {
+                required: WARMUP + 1,
+                actual: prices.len(),
+            });
+        }
+
+        let mut state = Self::new();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` during the warmup period.
+    pub fn next(&mut self, price: f64) -> Option<HilbertCycle> {
+        self.undo = Some((
+            self.count,
+            [
+                self.i2,
+                self.q2,
+                self.re,
+                self.im,
+                self.period,
+                self.smooth_period,
+            ],
+        ));
+        self.count += 1;
+        self.prices.push(price);
+
+        let smooth = (4.0 * self.prices.get(0)
+            + 3.0 * self.prices.get(1)
+            + 2.0 * self.prices.get(2)
+            + self.prices.get(3))
+            / 10.0;
+        self.smooth.push(smooth);
+
+        // Hilbert Transform of the smoothed price into in-phase and quadrature parts
+        let adjustment = 0.075 * self.period + 0.54;
+        let detrender = hilbert(&self.smooth) * adjustment;
+        self.detrender.push(detrender);
+
+        let q1 = hilbert(&self.detrender) * adjustment;
+        let i1 = self.detrender.get(3);
+        self.in_phase.push(i1);
+        self.quadrature.push(q1);
+
+        // Advance the phase of I1 and Q1 by 90 degrees
+        let j_i = hilbert(&self.in_phase) * adjustment;
+        let j_q = hilbert(&self.quadrature) * adjustment;
+
+        let i2 = 0.2 * (i1 - j_q) + 0.8 * self.i2;
+        let q2 = 0.2 * (q1 + j_i) + 0.8 * self.q2;
+
+        // Homodyne discriminator
+        let re = 0.2 * (i2 * self.i2 + q2 * self.q2) + 0.8 * self.re;
+        let im = 0.2 * (i2 * self.q2 - q2 * self.i2) + 0.8 * self.im;
+        self.i2 = i2;
+        self.q2 = q2;
+        self.re = re;
+        self.im = im;
+
+        let mut period = self.period;
+        if im != 0.0 && re != 0.0 {
+            period = 360.0 / (im / re).atan().to_degrees();
+        }
+        period = period
+            .min(1.5 * self.period)
+            .max(0.67 * self.period)
+            .clamp(MIN_PERIOD, MAX_PERIOD);
+        self.period = 0.2 * period + 0.8 * self.period;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+
+        let cycle_length = self.cycle_length();
+        let phase = self.phase(cycle_length);
+
+        let instantaneous_trend = (0..cycle_length)
+            .map(|lag| self.prices.get(lag))
+            .sum::<f64>()
+            / cycle_length as f64;
+        self.trend.push(instantaneous_trend);
+        let trendline = (4.0 * self.trend.get(0)
+            + 3.0 * self.trend.get(1)
+            + 2.0 * self.trend.get(2)
+            + self.trend.get(3))
+            / 10.0;
+
+        if self.count <= WARMUP {
+            return None;
+        }
+
+        Some(HilbertCycle {
+            dominant_period: self.smooth_period,
+            phase,
+            trendline,
+            sine: phase.to_radians().sin(),
+            lead_sine: (phase + 45.0).to_radians().sin(),
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<HilbertCycle> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        let Some((count, [i2, q2, re, im, period, smooth_period])) = self.undo.take() else {
+            return;
+        };
+        self.count = count;
+        self.i2 = i2;
+        self.q2 = q2;
+        self.re = re;
+        self.im = im;
+        self.period = period;
+        self.smooth_period = smooth_period;
+        for series in [
+            &mut self.prices,
+            &mut self.smooth,
+            &mut self.detrender,
+            &mut self.in_phase,
+            &mut self.quadrature,
+            &mut self.trend,
+        ] {
+            series.rollback();
+        }
+    }
+
+    /// Clears all state so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Dominant cycle period rounded to whole bars
+    fn cycle_length(&self) -> usize {
+        ((self.smooth_period + 0.5) as usize).clamp(1, MAX_PERIOD as usize)
+    }
+
+    /// Measures the dominant cycle phase by correlating the smoothed price
+    /// with sine and cosine waves of the dominant cycle length
+    fn phase(&self, cycle_length: usize) -> f64 {
+        let (real, imag) = (0..cycle_length).fold((0.0, 0.0), |(real, imag), lag| {
+            let angle = 2.0 * PI * lag as f64 / cycle_length as f64;
+            let value = self.smooth.get(lag);
+            (real + angle.sin() * value, imag + angle.cos() * value)
+        });
+
+        let mut phase = if imag.abs() > 0.0 {
+            (real / imag).atan().to_degrees()
+        } else {
+            90.0 * real.signum()
+        };
+        phase += 90.0;
+        // Compensate for the one-bar lag of the smoother
+        if self.smooth_period > 0.0 {
+            phase += 360.0 / self.smooth_period;
+        }
+        if imag < 0.0 {
+            phase += 180.0;
+        }
+        if phase > 315.0 {
+            phase -= 360.0;
+        }
+        phase
+    }
+}
+
+/// Ehlers' four-tap Hilbert Transform FIR filter over lags 0, 2, 4 and 6
+fn hilbert(series: &Lagged) -> f64 {
+    0.0962 * series.get(0) + 0.5769 * series.get(2)
+        - 0.5769 * series.get(4)
+        - 0.0962 * series.get(6)
+}
+
+/// Fixed-length history of a series indexed by lag (0 = most recent)
+///
+/// Lags beyond the available history read as zero, matching the zero-initialised
+/// filter state used by Ehlers' reference implementation.
+#[derive(Debug, Clone, Default)]
+struct Lagged {
+    values: VecDeque<f64>,
+    /// Value evicted by the last push (inner `None` if nothing was evicted),
+    /// or `None` when there is no push to undo
+    undo: Option<Option<f64>>,
+}
+
+impl Lagged {
+    fn push(&mut self, value: f64) {
+        let evicted = if self.values.len() == MAX_PERIOD as usize {
+            self.values.pop_back()
+        } else {
+            None
+        };
+        self.values.push_front(value);
+        self.undo = Some(evicted);
+    }
+
+    fn rollback(&mut self) {
+        if let Some(evicted) = self.undo.take() {
+            self.values.pop_front();
+            if let Some(oldest) = evicted {
+                self.values.push_back(oldest);
+            }
+        }
+    }
+
+    fn get(&self, lag: usize) -> f64 {
+        self.values.get(lag).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine_wave(period: f64, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| 100.0 + 10.0 * (2.0 * PI * i as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_hilbert_insufficient_data() {
+        let ht = HilbertTransform::new();
+        let result = ht.calculate(&[100.0; 10]);
+        assert_eq!(
+            result,
+            Err(IndicatorError::InsufficientData {
+                required: 64,
+                actual: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_hilbert_warmup() {
+        let ht = HilbertTransform::new();
+        let result = ht.calculate(&sine_wave(20.0, 100)).unwrap();
+
+        assert_eq!(result.len(), 100);
+        assert!(result[..WARMUP].iter().all(Option::is_none));
+        assert!(result[WARMUP..].iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_hilbert_detects_dominant_period() {
+        let ht = HilbertTransform::new();
+        let result = ht.calculate(&sine_wave(20.0, 300)).unwrap();
+
+        let last = result.last().copied().flatten().unwrap();
+        assert!(
+            (last.dominant_period - 20.0).abs() < 2.0,
+            "period = {}",
+            last.dominant_period
+        );
+        // Trendline averages out the cycle
+        assert!((last.trendline - 100.0).abs() < 2.0);
+        assert!(last.sine.abs() <= 1.0 && last.lead_sine.abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_hilbert_constant_series_trendline() {
+        let ht = HilbertTransform::new();
+        let result = ht.calculate(&[50.0; 80]).unwrap();
+
+        let last = result.last().copied().flatten().unwrap();
+        assert!((last.trendline - 50.0).abs() < 1e-9);
+        assert!(
+            last.dominant_period >= MIN_PERIOD * 0.9 && last.dominant_period <= MAX_PERIOD,
+            "period = {}",
+            last.dominant_period
+        );
+    }
+
+    #[test]
+    fn test_hilbert_streaming_matches_batch() {
+        let prices = sine_wave(15.0, 90);
+        let mut streaming = HilbertTransform::new();
+        let batch = streaming.calculate(&prices).unwrap();
+
+        for (price, expected) in prices.iter().zip(batch) {
+            assert_eq!(streaming.next(*price), expected);
+        }
+    }
+
+    #[test]
+    fn test_hilbert_update_last_matches_final_series() {
+        let prices = sine_wave(15.0, 90);
+        let mut streaming = HilbertTransform::new();
+        let batch = streaming.calculate(&prices).unwrap();
+
+        for (price, expected) in prices.iter().zip(&batch) {
+            streaming.next(price + 3.0);
+            assert_eq!(streaming.update_last(*price), *expected);
+        }
+
+        streaming.rollback();
+        streaming.rollback();
+        assert_eq!(streaming.next(prices[89]), batch[89]);
+    }
+}
@@ -0,0 +1,279 @@
+//! Double and Triple Exponential Moving Averages (DEMA, TEMA)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// Double Exponential Moving Average indicator
+///
+/// Cancels most of an EMA's lag by subtracting the EMA of the EMA:
+///
+/// DEMA = 2 × EMA(price) − EMA(EMA(price))
+///
+/// Both EMAs are seeded with the simple average of their first `period`
+/// inputs, as [`EMA::calculate`] does, so the first value appears at bar
+/// `2 × (period − 1)`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::DEMA;
+///
+/// let prices: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+/// let result = DEMA::new(3)?.calculate(&prices)?;
+///
+/// assert!(result[3].is_none());
+/// // A linear trend is tracked without lag
+/// assert!((result[4].unwrap() - 104.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DEMA {
+    ema: EMA,
+    ema_of_ema: EMA,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl DEMA {
+    /// Creates a new DEMA indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Period of both EMAs (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        let ema = EMA::new(period)?;
+        Ok(Self {
+            ema: ema.clone(),
+            ema_of_ema: ema,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates DEMA for a batch of prices on a fresh state
+    ///
+    /// Returns one value per input; the first `2 × (period − 1)` are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `2 × period − 1`
+    /// prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(2 * self.period() - 1, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let ema = self.ema.next(price)?;
+        let ema_of_ema = self.ema_of_ema.next(ema)?;
+        Some(2.0 * ema - ema_of_ema)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.ema_of_ema.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the EMA period
+    pub fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+/// Triple Exponential Moving Average indicator
+///
+/// Extends [`DEMA`] with a third EMA to remove the remaining lag:
+///
+/// TEMA = 3 × EMA1 − 3 × EMA2 + EMA3
+///
+/// where EMA2 is the EMA of EMA1 and EMA3 the EMA of EMA2. The first value
+/// appears at bar `3 × (period − 1)`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::TEMA;
+///
+/// let prices: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+/// let result = TEMA::new(3)?.calculate(&prices)?;
+///
+/// assert!(result[5].is_none());
+/// assert!((result[6].unwrap() - 106.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TEMA {
+    emas: [EMA; 3],
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl TEMA {
+    /// Creates a new TEMA indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Period of all three EMAs (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        let ema = EMA::new(period)?;
+        Ok(Self {
+            emas: [ema.clone(), ema.clone(), ema],
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates TEMA for a batch of prices on a fresh state
+    ///
+    /// Returns one value per input; the first `3 × (period − 1)` are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `3 × period − 2`
+    /// prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(3 * self.period() - 2, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let [first, second, third] = &mut self.emas;
+        let ema1 = first.next(price)?;
+        let ema2 = second.next(ema1)?;
+        let ema3 = third.next(ema2)?;
+        Some(3.0 * ema1 - 3.0 * ema2 + ema3)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        for ema in &mut self.emas {
+            ema.reset();
+        }
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the EMA period
+    pub fn period(&self) -> usize {
+        self.emas[0].period()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices() -> Vec<f64> {
+        (0..30)
+            .map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.1)
+            .collect()
+    }
+
+    #[test]
+    fn test_dema_composes_batch_emas() {
+        let prices = prices();
+        let ema = EMA::new(5).unwrap();
+        let first = ema.calculate(&prices).unwrap();
+        let first_values: Vec<f64> = first.iter().flatten().copied().collect();
+        let second = ema.calculate(&first_values).unwrap();
+
+        let result = DEMA::new(5).unwrap().calculate(&prices).unwrap();
+        assert!(result[..8].iter().all(Option::is_none));
+        for (i, value) in result.iter().enumerate().skip(8) {
+            let expected = 2.0 * first[i].unwrap() - second[i - 4].unwrap();
+            assert!((value.unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_tema_warmup_and_streaming() {
+        let prices = prices();
+        let mut tema = TEMA::new(4).unwrap();
+        let batch = tema.calculate(&prices).unwrap();
+        assert!(batch[8].is_none());
+        assert!(batch[9].is_some());
+
+        let streamed: Vec<_> = prices.iter().map(|&p| tema.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        tema.reset();
+        assert_eq!(tema.next(1.0), None);
+        assert_eq!(tema.period(), 4);
+    }
+
+    #[test]
+    fn test_period_one_passes_prices_through() {
+        let prices = [3.0, 1.0, 4.0];
+        let expected: Vec<_> = prices.iter().copied().map(Some).collect();
+        assert_eq!(DEMA::new(1).unwrap().calculate(&prices).unwrap(), expected);
+        assert_eq!(TEMA::new(1).unwrap().calculate(&prices).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(DEMA::new(0).is_err());
+        assert!(TEMA::new(0).is_err());
+        assert!(matches!(
+            DEMA::new(5).unwrap().calculate(&[1.0; 8]),
+            Err(IndicatorError::InsufficientData {
+                required: 9,
+                actual: 8
+            })
+        ));
+        assert!(matches!(
+            TEMA::new(5).unwrap().calculate(&[1.0; 12]),
+            Err(IndicatorError::InsufficientData {
+                required: 13,
+                actual: 12
+            })
+        ));
+    }
+}
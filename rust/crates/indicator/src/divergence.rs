@@ -0,0 +1,295 @@
+//! Divergence between price and an oscillator
+//!
+//! [`DivergenceDetector`] finds pivot highs and lows in the price series and
+//! compares each pivot with the previous one of the same kind against the
+//! oscillator values at the same bars:
+//!
+//! | Kind            | Price       | Oscillator  |
+//! |-----------------|-------------|-------------|
+//! | Regular bullish | lower low   | higher low  |
+//! | Hidden bullish  | higher low  | lower low   |
+//! | Regular bearish | higher high | lower high  |
+//! | Hidden bearish  | lower high  | higher high |
+//!
+//! Regular divergences warn of a reversal; hidden ones suggest the trend
+//! continues. A pivot needs `right` later bars to be confirmed, so each
+//! event carries the bar it becomes known on and events are ordered by it.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::divergence::{DivergenceDetector, DivergenceKind};
+//!
+//! // Price makes a lower low at bar 5 while the oscillator makes a higher low
+//! let price = [10.0, 9.0, 8.0, 9.0, 8.5, 7.5, 8.5, 9.0];
+//! let oscillator = [50.0, 40.0, 25.0, 45.0, 38.0, 32.0, 45.0, 55.0].map(Some);
+//!
+//! let detector = DivergenceDetector::new(1, 1, 20)?;
+//! let events = detector.detect(&price, &oscillator)?;
+//!
+//! assert_eq!(events.len(), 1);
+//! assert_eq!(events[0].kind, DivergenceKind::RegularBullish);
+//! assert_eq!((events[0].first, events[0].second), (2, 5));
+//! assert_eq!(events[0].confirmed_at, 6);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::cmp::Ordering::{Greater, Less};
+
+use crate::error::check_equal_lengths;
+use crate::{IndicatorError, SwingDetector, SwingKind};
+
+/// Type of divergence between price and oscillator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// Price lower low, oscillator higher low: possible upside reversal
+    RegularBullish,
+    /// Price higher low, oscillator lower low: uptrend continuation
+    HiddenBullish,
+    /// Price higher high, oscillator lower high: possible downside reversal
+    RegularBearish,
+    /// Price lower high, oscillator higher high: downtrend continuation
+    HiddenBearish,
+}
+
+impl DivergenceKind {
+    /// Whether the divergence points to higher prices
+    pub fn is_bullish(self) -> bool {
+        matches!(self, Self::RegularBullish | Self::HiddenBullish)
+    }
+}
+
+/// A divergence between two price pivots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// Type of divergence
+    pub kind: DivergenceKind,
+    /// Index of the earlier pivot
+    pub first: usize,
+    /// Index of the later pivot
+    pub second: usize,
+    /// Index of the bar that confirms the later pivot (`second + right`)
+    pub confirmed_at: usize,
+}
+
+/// Detector for regular and hidden divergences
+///
+/// Pivots come from a [`SwingDetector`]: a pivot high is a bar whose price
+/// is strictly above the `left` bars before it and the `right` bars after
+/// it; a pivot low mirrors it. Only
+/// consecutive pivots of the same kind at most `max_gap` bars apart are
+/// compared, and pivots where the oscillator has no value yet are skipped.
+#[derive(Debug, Clone)]
+pub struct DivergenceDetector {
+    left: usize,
+    right: usize,
+    max_gap: usize,
+}
+
+impl DivergenceDetector {
+    /// Creates a new divergence detector
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Bars before a pivot that must be lower (highs) or higher (lows) (must be > 0)
+    /// * `right` - Bars after a pivot required to confirm it (must be > 0)
+    /// * `max_gap` - Largest distance in bars between the two compared pivots (must be > 0)
+    pub fn new(left: usize, right: usize, max_gap: usize) -> Result<Self, IndicatorError> {
+        for (field, value) in [("left", left), ("right", right), ("max_gap", max_gap)] {
+            if value == 0 {
+                return Err(IndicatorError::InvalidParameter {
+                    field,
+                    value: value as f64,
+                    constraint: "> 0",
+                });
+            }
+        }
+        Ok(Self {
+            left,
+            right,
+            max_gap,
+        })
+    }
+
+    /// Finds divergences between a price series and an aligned oscillator
+    ///
+    /// `oscillator` takes the output of an indicator such as
+    /// [`RSI`](crate::RSI) directly; `None` warmup values are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length.
+    pub fn detect(
+        &self,
+        price: &[f64],
+        oscillator: &[Option<f64>],
+    ) -> Result<Vec<Divergence>, IndicatorError> {
+        check_equal_lengths(&[("price", price.len()), ("oscillator", oscillator.len())])?;
+
+        if price.len() < self.left + self.right + 1 {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        let mut last_high: Option<(usize, f64)> = None;
+        let mut last_low: Option<(usize, f64)> = None;
+        let pivots = SwingDetector::new(self.left, self.right)?.points(price, price)?;
+        for pivot in pivots {
+            let index = pivot.index;
+            let Some(osc) = oscillator[index] else {
+                continue;
+            };
+            let (last, high) = match pivot.kind {
+                SwingKind::High => (&mut last_high, true),
+                SwingKind::Low => (&mut last_low, false),
+            };
+            let Some((prev, prev_osc)) = last.replace((index, osc)) else {
+                continue;
+            };
+            if index - prev > self.max_gap {
+                continue;
+            }
+
+            let price_move = price[index].partial_cmp(&price[prev]);
+            let osc_move = osc.partial_cmp(&prev_osc);
+            let kind = match (high, price_move, osc_move) {
+                (false, Some(Less), Some(Greater)) => DivergenceKind::RegularBullish,
+                (false, Some(Greater), Some(Less)) => DivergenceKind::HiddenBullish,
+                (true, Some(Greater), Some(Less)) => DivergenceKind::RegularBearish,
+                (true, Some(Less), Some(Greater)) => DivergenceKind::HiddenBearish,
+                _ => continue,
+            };
+            events.push(Divergence {
+                kind,
+                first: prev,
+                second: index,
+                confirmed_at: index + self.right,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Returns the number of bars checked before a pivot
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// Returns the number of bars needed after a pivot to confirm it
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    /// Returns the largest distance between compared pivots
+    pub fn max_gap(&self) -> usize {
+        self.max_gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RSI;
+
+    fn detect(price: &[f64], oscillator: &[f64]) -> Vec<Divergence> {
+        let oscillator: Vec<Option<f64>> = oscillator.iter().copied().map(Some).collect();
+        DivergenceDetector::new(1, 1, 10)
+            .unwrap()
+            .detect(price, &oscillator)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_all_divergence_kinds() {
+        let valleys = [10.0, 8.0, 10.0, 9.0, 10.0];
+        let peaks = [5.0, 8.0, 5.0, 9.0, 5.0];
+        let cases = [
+            (
+                valleys,
+                [50.0, 30.0, 50.0, 20.0, 50.0],
+                DivergenceKind::HiddenBullish,
+            ),
+            (
+                peaks,
+                [50.0, 70.0, 50.0, 60.0, 50.0],
+                DivergenceKind::RegularBearish,
+            ),
+        ];
+        for (price, oscillator, kind) in cases {
+            let events = detect(&price, &oscillator);
+            assert_eq!(
+                events,
+                vec![Divergence {
+                    kind,
+                    first: 1,
+                    second: 3,
+                    confirmed_at: 4
+                }]
+            );
+        }
+
+        let lower_high = [5.0, 9.0, 5.0, 8.0, 5.0];
+        let events = detect(&lower_high, &[50.0, 60.0, 50.0, 70.0, 50.0]);
+        assert_eq!(events[0].kind, DivergenceKind::HiddenBearish);
+        assert!(!events[0].kind.is_bullish());
+
+        // Price and oscillator agree: no divergence
+        assert!(detect(&lower_high, &[50.0, 70.0, 50.0, 60.0, 50.0]).is_empty());
+    }
+
+    #[test]
+    fn test_gap_limit_and_missing_oscillator() {
+        let price = [10.0, 8.0, 10.0, 10.5, 11.0, 10.5, 10.0, 7.0, 10.0];
+        let oscillator = [50.0, 30.0, 50.0, 55.0, 60.0, 55.0, 50.0, 35.0, 50.0];
+        assert_eq!(
+            detect(&price, &oscillator)[0].kind,
+            DivergenceKind::RegularBullish
+        );
+
+        let narrow = DivergenceDetector::new(1, 1, 5).unwrap();
+        let osc: Vec<Option<f64>> = oscillator.iter().copied().map(Some).collect();
+        assert!(narrow.detect(&price, &osc).unwrap().is_empty());
+
+        let mut warmup = osc.clone();
+        warmup[1] = None;
+        let wide = DivergenceDetector::new(1, 1, 10).unwrap();
+        assert!(wide.detect(&price, &warmup).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rsi_divergence_on_fading_decline() {
+        // Two sell-offs, the second reaching lower on a smaller final drop
+        let mut price = vec![100.0; 5];
+        price.extend([
+            96.0, 92.0, 88.0, 84.0, 80.0, 82.0, 84.0, 86.0, 85.0, 84.0, 83.0,
+        ]);
+        price.extend([82.0, 81.0, 80.5, 80.0, 79.5, 81.0, 83.0, 85.0]);
+        let rsi = RSI::new(5).unwrap().calculate(&price).unwrap();
+
+        let events = DivergenceDetector::new(2, 2, 20)
+            .unwrap()
+            .detect(&price, &rsi)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, DivergenceKind::RegularBullish);
+        assert_eq!((events[0].first, events[0].second), (9, 20));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(DivergenceDetector::new(0, 1, 5).is_err());
+        assert!(DivergenceDetector::new(1, 0, 5).is_err());
+        assert!(DivergenceDetector::new(1, 1, 0).is_err());
+        let detector = DivergenceDetector::new(2, 2, 5).unwrap();
+        assert!(matches!(
+            detector.detect(&[1.0, 2.0], &[Some(1.0)]),
+            Err(IndicatorError::LengthMismatch {
+                field: "oscillator",
+                ..
+            })
+        ));
+        assert!(detector
+            .detect(&[1.0, 2.0], &[None, None])
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+//! Detrended Price Oscillator (DPO)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Detrended Price Oscillator indicator
+///
+/// Removes the trend by comparing each price with a simple moving average
+/// displaced `shift = period / 2 + 1` bars into the future, which centres
+/// the average on that price:
+///
+/// DPO(t) = P(t) − SMA(period) ending at t + shift
+///
+/// The modes differ only in alignment:
+///
+/// * [`next`](Self::next) and [`calculate`](Self::calculate) cannot see the
+///   future: on bar `t` they give the value for bar `t − shift`, i.e.
+///   `P(t − shift) − SMA(t)`, so they are safe to use in a backtest.
+/// * [`calculate_centered`](Self::calculate_centered) writes each value at
+///   the bar whose price it uses, so the last `shift` bars are `None` (their
+///   average lies in the future) as well as the first `period − 1 − shift`.
+///   Every value looks ahead by `shift` bars; use it for charting or cycle
+///   analysis only.
+///
+/// # Example
+///
+/// ```
+/// use indicator::DPO;
+///
+/// let prices = [10.0, 11.0, 12.0, 11.0, 10.0, 11.0, 12.0, 11.0];
+/// let dpo = DPO::new(4)?;
+/// assert_eq!(dpo.shift(), 3);
+///
+/// let centered = dpo.calculate_centered(&prices)?;
+/// // Bar 1: 11.0 minus the SMA of bars 1..=4
+/// assert_eq!(centered[1], Some(11.0 - 11.0));
+/// assert!(centered[5..].iter().all(Option::is_none));
+///
+/// // The causal series reports the same value once bar 4 has closed
+/// let causal = dpo.calculate(&prices)?;
+/// assert_eq!(causal[4], centered[1]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DPO {
+    period: usize,
+    /// The last `max(period, shift + 1)` prices, oldest first
+    window: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl DPO {
+    /// Creates a new DPO indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - SMA period (must be > 0, typically 20)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            window: VecDeque::new(),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates DPO for a batch of prices on a fresh state
+    ///
+    /// Matches streaming through [`next`](Self::next): the value at bar `t`
+    /// belongs to bar `t − shift` and uses no later prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than
+    /// `max(period, shift + 1)` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.window_len(), prices.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Calculates the centred DPO for a batch of prices on a fresh state
+    ///
+    /// Each value is aligned with the bar whose price it uses, which means it
+    /// depends on the `shift` prices after that bar. Not suitable for
+    /// backtesting; see the type-level docs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than
+    /// `max(period, shift + 1)` prices.
+    pub fn calculate_centered(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let mut result = self.calculate(prices)?;
+        result.drain(..self.shift().min(result.len()));
+        result.resize(prices.len(), None);
+        Ok(result)
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns the DPO of the bar `shift` bars ago, or `None` until enough
+    /// prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let len = self.window_len();
+        if self.window.len() == len {
+            self.window.pop_front();
+        }
+        self.window.push_back(price);
+        if self.window.len() < len {
+            return None;
+        }
+
+        let sma = self.window.iter().rev().take(self.period).sum::<f64>() / self.period as f64;
+        let displaced = self.window[len - 1 - self.shift()];
+        Some(displaced - sma)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the SMA period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the displacement in bars, `period / 2 + 1`
+    pub fn shift(&self) -> usize {
+        self.period / 2 + 1
+    }
+
+    fn window_len(&self) -> usize {
+        self.period.max(self.shift() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Indicator;
+
+    fn sma_ending_at(prices: &[f64], end: usize, period: usize) -> f64 {
+        prices[end + 1 - period..=end].iter().sum::<f64>() / period as f64
+    }
+
+    #[test]
+    fn test_dpo_centred_alignment() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 0.5).sin() * 4.0 + i as f64)
+            .collect();
+        let period = 10;
+        let shift = 6;
+        let result = DPO::new(period)
+            .unwrap()
+            .calculate_centered(&prices)
+            .unwrap();
+
+        assert_eq!(result.len(), prices.len());
+        assert!(result[..3].iter().all(Option::is_none));
+        assert!(result[prices.len() - shift..].iter().all(Option::is_none));
+        for (i, value) in result.iter().enumerate().take(prices.len() - shift).skip(3) {
+            let expected = prices[i] - sma_ending_at(&prices, i + shift, period);
+            assert!((value.unwrap() - expected).abs() < 1e-9, "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_dpo_streaming_lags_by_shift() {
+        let prices: Vec<f64> = (0..20).map(|i| 5.0 + (i as f64).sin()).collect();
+        let mut dpo = DPO::new(5).unwrap();
+        let centered = dpo.calculate_centered(&prices).unwrap();
+
+        for (t, &price) in prices.iter().enumerate() {
+            let streamed = dpo.next(price);
+            let expected = t.checked_sub(dpo.shift()).and_then(|i| centered[i]);
+            assert_eq!(streamed, expected, "bar {t}");
+        }
+
+        dpo.reset();
+        assert_eq!(dpo.next(1.0), None);
+    }
+
+    #[test]
+    fn test_dpo_batch_matches_stream_and_trait() {
+        let prices: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.7).sin()).collect();
+        let mut dpo = DPO::new(8).unwrap();
+        let batch = dpo.calculate(&prices).unwrap();
+        let streamed: Vec<Option<f64>> = prices.iter().map(|&p| dpo.next(p)).collect();
+
+        assert_eq!(batch, streamed);
+        assert_eq!(Indicator::calculate(&dpo, &prices).unwrap(), streamed);
+        // Bar t only ever depends on prices up to t
+        let truncated = dpo.calculate(&prices[..20]).unwrap();
+        assert_eq!(truncated, batch[..20]);
+    }
+
+    #[test]
+    fn test_dpo_short_periods() {
+        // Period 1 and 2 have a shift reaching past the SMA window
+        let prices = [1.0, 2.0, 4.0, 8.0];
+        let result = DPO::new(1).unwrap().calculate_centered(&prices).unwrap();
+        assert_eq!(result, vec![Some(-1.0), Some(-2.0), Some(-4.0), None]);
+
+        let result = DPO::new(2).unwrap().calculate_centered(&prices).unwrap();
+        assert_eq!(result[0], Some(1.0 - 3.0));
+        assert_eq!(result[2..], [None, None]);
+    }
+
+    #[test]
+    fn test_dpo_invalid_inputs() {
+        assert!(DPO::new(0).is_err());
+        assert!(matches!(
+            DPO::new(20).unwrap().calculate(&[1.0; 19]),
+            Err(IndicatorError::InsufficientData {
+                required: 20,
+                actual: 19
+            })
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+//! Drawdowns of an equity or price curve
+//!
+//! The drawdown at a bar is the fractional decline from the running peak,
+//! `1 − value / peak`, so `0.25` means 25% below the high-water mark. The
+//! duration counts bars since that peak was set and is `0` on a new high.
+//!
+//! [`drawdown_series`] and [`max_drawdown`] work on a whole curve;
+//! [`DrawdownTracker`] produces the same figures one bar at a time.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::drawdown::max_drawdown;
+//!
+//! let equity = [100.0, 120.0, 90.0, 110.0, 130.0, 117.0];
+//! let summary = max_drawdown(&equity)?;
+//!
+//! assert!((summary.max_drawdown - 0.25).abs() < 1e-12);
+//! assert_eq!((summary.peak_index, summary.trough_index), (1, 2));
+//! assert_eq!(summary.recovery_index, Some(4));
+//! assert_eq!(summary.max_duration, 2);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::IndicatorError;
+
+/// Drawdown state at one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawdownPoint {
+    /// Fractional decline from the running peak, in [0, 1)
+    pub drawdown: f64,
+    /// Running peak (high-water mark) up to and including this bar
+    pub peak: f64,
+    /// Bars since the running peak was set
+    pub duration: usize,
+}
+
+/// Worst drawdown and longest underwater stretch of a curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawdownSummary {
+    /// Largest fractional decline from a running peak
+    pub max_drawdown: f64,
+    /// Index of the peak the maximum drawdown started from
+    pub peak_index: usize,
+    /// Index of the bar at the bottom of the maximum drawdown
+    pub trough_index: usize,
+    /// First bar after the trough back at or above the peak, if the curve recovered
+    pub recovery_index: Option<usize>,
+    /// Longest number of consecutive bars spent below a peak, including an
+    /// unfinished drawdown at the end of the curve
+    pub max_duration: usize,
+}
+
+/// Running drawdown of a curve, one bar at a time
+#[derive(Debug, Clone, Default)]
+pub struct DrawdownTracker {
+    /// Number of values seen so far
+    count: usize,
+    /// Running peak and its index
+    peak: Option<(usize, f64)>,
+    summary: Option<DrawdownSummary>,
+}
+
+impl DrawdownTracker {
+    /// Creates a new tracker with no history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the next equity value (streaming mode)
+    ///
+    /// Values must be positive for the drawdown fraction to be meaningful;
+    /// [`drawdown_series`] and [`max_drawdown`] check this for whole curves.
+    pub fn next(&mut self, value: f64) -> DrawdownPoint {
+        let index = self.count;
+        self.count += 1;
+
+        let (peak_index, peak) = match self.peak {
+            Some(peak) if value < peak.1 => peak,
+            _ => {
+                self.peak = Some((index, value));
+                (index, value)
+            }
+        };
+        let drawdown = 1.0 - value / peak;
+        let duration = index - peak_index;
+
+        let summary = self.summary.get_or_insert(DrawdownSummary {
+            max_drawdown: 0.0,
+            peak_index: 0,
+            trough_index: 0,
+            recovery_index: None,
+            max_duration: 0,
+        });
+        if drawdown > summary.max_drawdown {
+            summary.max_drawdown = drawdown;
+            summary.peak_index = peak_index;
+            summary.trough_index = index;
+            summary.recovery_index = None;
+        } else if summary.recovery_index.is_none()
+            && summary.max_drawdown > 0.0
+            && peak_index == index
+        {
+            summary.recovery_index = Some(index);
+        }
+        summary.max_duration = summary.max_duration.max(duration);
+
+        DrawdownPoint {
+            drawdown,
+            peak,
+            duration,
+        }
+    }
+
+    /// Summary of the values seen so far; `None` before the first value
+    pub fn summary(&self) -> Option<&DrawdownSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Resets the tracker so the next value starts a new curve
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Drawdown at every bar of an equity or price curve
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for an empty curve and `CalculationError` if a
+/// value is not positive.
+pub fn drawdown_series(equity: &[f64]) -> Result<Vec<DrawdownPoint>, IndicatorError> {
+    validate(equity)?;
+    let mut tracker = DrawdownTracker::new();
+    Ok(equity.iter().map(|&value| tracker.next(value)).collect())
+}
+
+/// Maximum drawdown and longest drawdown duration of an equity or price curve
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for an empty curve and `CalculationError` if a
+/// value is not positive.
+pub fn max_drawdown(equity: &[f64]) -> Result<DrawdownSummary, IndicatorError> {
+    validate(equity)?;
+    let mut tracker = DrawdownTracker::new();
+    for &value in equity {
+        tracker.next(value);
+    }
+    tracker.summary.ok_or(IndicatorError::InsufficientData {
+        required: 1,
+        actual: 0,
+    })
+}
+
+fn validate(equity: &[f64]) -> Result<(), IndicatorError> {
+    if equity.is_empty() {
+        return Err(IndicatorError::InsufficientData {
+            required: 1,
+            actual: 0,
+        });
+    }
+    if let Some((index, value)) = equity
+        .iter()
+        .enumerate()
+        .find(|(_, v)| v.is_nan() || **v <= 0.0)
+    {
+        return Err(IndicatorError::CalculationError {
+            operation: "drawdown",
+            reason: format!("equity must be positive, got {value} at index {index}"),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drawdown_series() {
+        let series = drawdown_series(&[100.0, 80.0, 90.0, 100.0, 50.0]).unwrap();
+        let drawdowns: Vec<f64> = series.iter().map(|p| p.drawdown).collect();
+        let durations: Vec<usize> = series.iter().map(|p| p.duration).collect();
+
+        for (actual, expected) in drawdowns.iter().zip([0.0, 0.2, 0.1, 0.0, 0.5]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert_eq!(durations, vec![0, 1, 2, 0, 1]);
+        assert!(series.iter().all(|p| p.peak == 100.0));
+    }
+
+    #[test]
+    fn test_max_drawdown_without_recovery() {
+        let summary = max_drawdown(&[10.0, 12.0, 11.0, 13.0, 9.1, 10.0, 12.0]).unwrap();
+        assert!((summary.max_drawdown - 0.3).abs() < 1e-12);
+        assert_eq!((summary.peak_index, summary.trough_index), (3, 4));
+        assert_eq!(summary.recovery_index, None);
+        assert_eq!(summary.max_duration, 3);
+
+        let rising = max_drawdown(&[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(rising.max_drawdown, 0.0);
+        assert_eq!(rising.max_duration, 0);
+    }
+
+    #[test]
+    fn test_tracker_matches_batch_and_reset() {
+        let equity: Vec<f64> = (0..50)
+            .map(|i| 100.0 + i as f64 * 0.5 + (i as f64 * 0.4).sin() * 8.0)
+            .collect();
+        let mut tracker = DrawdownTracker::new();
+        let streamed: Vec<DrawdownPoint> = equity.iter().map(|&v| tracker.next(v)).collect();
+
+        assert_eq!(streamed, drawdown_series(&equity).unwrap());
+        assert_eq!(tracker.summary(), Some(&max_drawdown(&equity).unwrap()));
+
+        tracker.reset();
+        assert_eq!(tracker.summary(), None);
+    }
+
+    #[test]
+    fn test_invalid_equity() {
+        assert!(matches!(
+            max_drawdown(&[]),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+        assert!(matches!(
+            drawdown_series(&[1.0, 0.0]),
+            Err(IndicatorError::CalculationError { .. })
+        ));
+        assert!(drawdown_series(&[1.0, f64::NAN]).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+//! Elder's Force Index and Elder Ray (Bull/Bear Power)
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, EMA};
+
+/// Elder's Force Index
+///
+/// Weighs each price change by the volume behind it and smooths the result:
+///
+/// Force = EMA((Close − Close_prev) × Volume)
+///
+/// The raw force needs a previous close and the EMA is seeded with the
+/// simple average of its first `period` inputs, so the first value is
+/// available at bar `period`. A period of 1 gives the raw force.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ForceIndex;
+///
+/// let close = [10.0, 10.5, 10.2, 10.8];
+/// let volume = [1_000.0, 2_000.0, 1_500.0, 1_000.0];
+///
+/// let result = ForceIndex::new(1)?.calculate(&close, &volume)?;
+/// assert_eq!(result[0], None);
+/// assert_eq!(result[1], Some(1_000.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ForceIndex {
+    ema: EMA,
+    prev_close: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ForceIndex {
+    /// Creates a new Force Index indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Smoothing EMA period (must be > 0, typically 13)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            ema: EMA::new(period)?,
+            prev_close: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the Force Index for a batch of closes and volumes on a
+    /// fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period + 1` bars.
+    pub fn calculate(
+        &self,
+        close: &[f64],
+        volume: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[("close", close.len()), ("volume", volume.len())])?;
+        check_min_length(self.period() + 1, len)?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(close
+            .iter()
+            .zip(volume)
+            .map(|(&close, &volume)| state.next(close, volume))
+            .collect())
+    }
+
+    /// Calculates the Force Index for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period() + 1, candles.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.close, candle.volume))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, close: f64, volume: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let prev_close = self.prev_close.replace(close)?;
+        self.ema.next((close - prev_close) * volume)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, close: f64, volume: f64) -> Option<f64> {
+        self.rollback();
+        self.next(close, volume)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.prev_close = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the smoothing period
+    pub fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+/// Elder Ray values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElderRayOutput {
+    /// High minus the close EMA
+    pub bull_power: f64,
+    /// Low minus the close EMA
+    pub bear_power: f64,
+}
+
+/// Elder Ray indicator
+///
+/// Measures how far buyers and sellers pushed price away from consensus
+/// value, taken as an EMA of the close:
+///
+/// Bull Power = High − EMA(Close)
+/// Bear Power = Low − EMA(Close)
+///
+/// The EMA is seeded like [`EMA::calculate`], so the first value is
+/// available at bar `period − 1`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ElderRay;
+///
+/// let high = [11.0, 12.0, 13.0];
+/// let low = [9.0, 10.0, 11.0];
+/// let close = [10.0, 11.0, 12.0];
+///
+/// let result = ElderRay::new(3)?.calculate(&high, &low, &close)?;
+/// let output = result[2].unwrap();
+/// assert_eq!(output.bull_power, 2.0);
+/// assert_eq!(output.bear_power, 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElderRay {
+    ema: EMA,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ElderRay {
+    /// Creates a new Elder Ray indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Period of the close EMA (must be > 0, typically 13)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            ema: EMA::new(period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates Bull and Bear Power for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<ElderRayOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period(), len)?;
+
+        let mut state = Self::new(self.period())?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates Bull and Bear Power for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<ElderRayOutput>>, IndicatorError> {
+        check_min_length(self.period(), candles.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<ElderRayOutput> {
+        self.undo = Snapshot::of(self);
+        let ema = self.ema.next(close)?;
+        Some(ElderRayOutput {
+            bull_power: high - ema,
+            bear_power: low - ema,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<ElderRayOutput> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the EMA period
+    pub fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        (0..20)
+            .map(|i| {
+                let base = 60.0 + (i as f64 * 0.5).sin() * 3.0;
+                let volume = 1_000.0 + (i % 4) as f64 * 250.0;
+                Candle::new(i, base, base + 0.7, base - 0.6, base + 0.2, volume)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_force_index_is_ema_of_raw_force() {
+        let candles = candles();
+        let raw: Vec<f64> = candles
+            .windows(2)
+            .map(|pair| (pair[1].close - pair[0].close) * pair[1].volume)
+            .collect();
+        let expected = EMA::new(5).unwrap().calculate(&raw).unwrap();
+
+        let result = ForceIndex::new(5)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        assert_eq!(result[0], None);
+        for (value, expected) in result[1..].iter().zip(&expected) {
+            match (value, expected) {
+                (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-9),
+                _ => assert_eq!(value, expected),
+            }
+        }
+    }
+
+    #[test]
+    fn test_elder_ray_uses_close_ema() {
+        let candles = candles();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let ema = EMA::new(4).unwrap().calculate(&close).unwrap();
+
+        let result = ElderRay::new(4)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        for ((output, ema), candle) in result.iter().zip(&ema).zip(&candles) {
+            assert_eq!(output.is_some(), ema.is_some());
+            if let (Some(output), Some(ema)) = (output, ema) {
+                assert!((output.bull_power - (candle.high - ema)).abs() < 1e-12);
+                assert!((output.bear_power - (candle.low - ema)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let candles = candles();
+        let mut force = ForceIndex::new(3).unwrap();
+        let mut ray = ElderRay::new(3).unwrap();
+        let force_batch = force.calculate_candles(&candles).unwrap();
+        let ray_batch = ray.calculate_candles(&candles).unwrap();
+
+        for (i, c) in candles.iter().enumerate() {
+            assert_eq!(force.next(c.close, c.volume), force_batch[i]);
+            assert_eq!(ray.next(c.high, c.low, c.close), ray_batch[i]);
+        }
+
+        force.reset();
+        ray.reset();
+        assert_eq!(force.next(1.0, 1.0), None);
+        assert_eq!(ray.next(1.0, 1.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(ForceIndex::new(0).is_err());
+        assert!(ElderRay::new(0).is_err());
+        assert!(matches!(
+            ForceIndex::new(3).unwrap().calculate(&[1.0; 3], &[1.0; 3]),
+            Err(IndicatorError::InsufficientData {
+                required: 4,
+                actual: 3
+            })
+        ));
+        assert!(ElderRay::new(3)
+            .unwrap()
+            .calculate(&[1.0; 3], &[1.0; 2], &[1.0; 3])
+            .is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! Exponential Moving Average (EMA)
+
+use crate::IndicatorError;
+
+/// Exponential Moving Average (EMA) indicator
+///
+/// EMA is a type of moving average that places greater weight on recent data points.
+/// It responds more quickly to price changes than a simple moving average (SMA).
+///
+/// # Formula
+///
+/// EMA(t) = Price(t) × α + EMA(t-1) × (1 - α)
+///
+/// where α = 2 / (period + 1) is the smoothing factor
+///
+/// # Example
+///
+/// ```
+/// use indicator::EMA;
+///
+/// let ema = EMA::new(5)?;
+/// let prices = vec![10.0, 11.0, 12.0, 13.0, 14.0, 15.0];
+/// let result = ema.calculate(&prices)?;
+///
+/// // EMA starts from the first SMA value and applies exponential smoothing
+/// assert_eq!(result.len(), prices.len());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EMA {
+    /// Period for the EMA calculation
+    period: usize,
+    /// Smoothing factor (alpha)
+    alpha: f64,
+    /// Number of prices seen during warmup
+    count: usize,
+    /// Sum of the warmup prices, used for the SMA seed
+    sum: f64,
+    /// Current EMA value, `None` until `period` prices have been seen
+    value: Option<f64>,
+    /// `count`, `sum` and `value` before the last `next`, or `None` when
+    /// there is nothing to roll back
+    undo: Option<(usize, f64, Option<f64>)>,
+}
+
+impl EMA {
+    /// Creates a new EMA indicator with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of periods for the EMA calculation (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Returns a configured `EMA` instance or an error if the period is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::EMA;
+    ///
+    /// let ema = EMA::new(20)?;
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        // Calculate smoothing factor: α = 2 / (period + 1)
+        let alpha = 2.0 / (period as f64 + 1.0);
+
+        Ok(Self {
+            period,
+            alpha,
+            count: 0,
+            sum: 0.0,
+            value: None,
+            undo: None,
+        })
+    }
+
+    /// Calculates EMA for a batch of price data
+    ///
+    /// The first EMA value is initialized as the simple moving average (SMA)
+    /// of the first `period` values. Subsequent values use the exponential formula.
+    ///
+    /// # Arguments
+    ///
+    /// * `prices` - Slice of price data (must have at least `period` values)
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of EMA values with the same length as the input.
+    /// The first `period - 1` values will be `None` as there isn't enough data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::EMA;
+    ///
+    /// let ema = EMA::new(3)?;
+    /// let prices = vec![10.0, 11.0, 12.0, 13.0, 14.0];
+    /// let result = ema.calculate(&prices)?;
+    ///
+    /// assert_eq!(result.len(), 5);
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        if prices.len() < self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period,
+                actual: prices.len(),
+            });
+        }
+
+        let mut result = Vec::with_capacity(prices.len());
+
+        // Fill first period-1 values with None
+        for _ in 0..self.period - 1 {
+            result.push(None);
+        }
+
+        // Calculate initial SMA for the first EMA value
+        let initial_sma: f64 = prices[..self.period].iter().sum::<f64>() / self.period as f64;
+        result.push(Some(initial_sma));
+
+        // Calculate subsequent EMA values
+        let mut prev_ema = initial_sma;
+        for &price in &prices[self.period..] {
+            let ema = self.alpha * price + (1.0 - self.alpha) * prev_ema;
+            result.push(Some(ema));
+            prev_ema = ema;
+        }
+
+        Ok(result)
+    }
+
+    /// Updates a caller-held EMA value with a new price
+    ///
+    /// Unlike [`next`](Self::next), the first price is used as the starting
+    /// value instead of the SMA seed, so the results differ from
+    /// [`calculate`](Self::calculate) during the first bars. Prefer `next`
+    /// for streaming.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_ema` - The current EMA value (or None if this is the start)
+    /// * `new_price` - The new price to incorporate
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated EMA value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::EMA;
+    ///
+    /// let ema = EMA::new(10)?;
+    /// let mut current_ema = None;
+    ///
+    /// // In streaming mode, update EMA as new prices arrive
+    /// current_ema = Some(ema.update(current_ema, 100.0));
+    /// current_ema = Some(ema.update(current_ema, 102.0));
+    /// current_ema = Some(ema.update(current_ema, 101.0));
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn update(&self, current_ema: Option<f64>, new_price: f64) -> f64 {
+        match current_ema {
+            Some(ema) => self.alpha * new_price + (1.0 - self.alpha) * ema,
+            None => new_price, // If no previous EMA, use the price itself
+        }
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` for the first `period - 1` prices, then the SMA of the
+    /// first `period` prices, then the recursive EMA, exactly matching
+    /// [`calculate`](Self::calculate) on the same series.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::EMA;
+    ///
+    /// let mut ema = EMA::new(3)?;
+    /// assert_eq!(ema.next(10.0), None);
+    /// assert_eq!(ema.next(11.0), None);
+    /// assert_eq!(ema.next(12.0), Some(11.0));
+    /// assert_eq!(ema.next(13.0), Some(12.0));
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Some((self.count, self.sum, self.value));
+        if self.value.is_some() {
+            self.value = Some(self.update(self.value, price));
+        } else {
+            self.count += 1;
+            self.sum += price;
+            if self.count == self.period {
+                self.value = Some(self.sum / self.period as f64);
+            }
+        }
+        self.value
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some((count, sum, value)) = self.undo.take() {
+            self.count = count;
+            self.sum = sum;
+            self.value = value;
+        }
+    }
+
+    /// Clears the state so the EMA can be reused on a new series
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.sum = 0.0;
+        self.value = None;
+        self.undo = None;
+    }
+
+    /// Returns the current EMA value, or `None` during warmup
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Returns the period used for EMA calculation
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the smoothing factor (alpha) used for EMA calculation
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema_creation() {
+        let ema = EMA::new(10).unwrap();
+        assert_eq!(ema.period(), 10);
+        assert!((ema.alpha() - 2.0 / 11.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ema_invalid_period() {
+        let result = EMA::new(0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ema_invalid_period_context() {
+        let err = EMA::new(0).unwrap_err();
+        assert_eq!(
+            err,
+            IndicatorError::InvalidParameter {
+                field: "period",
+                value: 0.0,
+                constraint: "> 0",
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid parameter `period` = 0: must be > 0"
+        );
+    }
+
+    #[test]
+    fn test_ema_calculate() {
+        let ema = EMA::new(3).unwrap();
+        let prices = vec![10.0, 11.0, 12.0, 13.0, 14.0];
+        let result = ema.calculate(&prices).unwrap();
+
+        assert_eq!(result.len(), 5);
+        assert!(result[0].is_none());
+        assert!(result[1].is_none());
+        assert!(result[2].is_some());
+
+        // First EMA value should be SMA of first 3 values
+        let expected_first_ema = (10.0 + 11.0 + 12.0) / 3.0;
+        assert!((result[2].unwrap() - expected_first_ema).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ema_insufficient_data() {
+        let ema = EMA::new(10).unwrap();
+        let prices = vec![10.0, 11.0, 12.0];
+        let result = ema.calculate(&prices);
+
+        assert!(result.is_err());
+        match result {
+            Err(IndicatorError::InsufficientData { required, actual }) => {
+                assert_eq!(required, 10);
+                assert_eq!(actual, 3);
+            }
+            _ => panic!("Expected InsufficientData error"),
+        }
+    }
+
+    #[test]
+    fn test_ema_empty_data() {
+        let ema = EMA::new(5).unwrap();
+        let prices = vec![];
+        let result = ema.calculate(&prices);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ema_update_streaming() {
+        let ema = EMA::new(3).unwrap();
+
+        // Start with no previous EMA
+        let ema1 = ema.update(None, 10.0);
+        assert_eq!(ema1, 10.0);
+
+        let ema2 = ema.update(Some(ema1), 12.0);
+        // α = 2/(3+1) = 0.5
+        // EMA = 0.5 * 12.0 + 0.5 * 10.0 = 11.0
+        assert_eq!(ema2, 11.0);
+
+        let ema3 = ema.update(Some(ema2), 14.0);
+        // EMA = 0.5 * 14.0 + 0.5 * 11.0 = 12.5
+        assert_eq!(ema3, 12.5);
+    }
+
+    #[test]
+    fn test_ema_monotonic_increasing() {
+        let ema = EMA::new(5).unwrap();
+        let prices = vec![10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0];
+        let result = ema.calculate(&prices).unwrap();
+
+        // Skip None values and check that EMA values are increasing
+        let ema_values: Vec<f64> = result.iter().filter_map(|&x| x).collect();
+        for i in 1..ema_values.len() {
+            assert!(ema_values[i] > ema_values[i - 1]);
+        }
+    }
+
+    #[test]
+    fn test_ema_responds_to_changes() {
+        let ema = EMA::new(3).unwrap();
+        // Price spike in the middle
+        let prices = vec![100.0, 100.0, 100.0, 150.0, 100.0, 100.0];
+        let result = ema.calculate(&prices).unwrap();
+
+        let ema_values: Vec<f64> = result.iter().filter_map(|&x| x).collect();
+
+        // EMA should increase when price spikes
+        assert!(ema_values[1] > ema_values[0]);
+        // And should start decreasing after the spike
+        assert!(ema_values[3] < ema_values[2]);
+    }
+
+    #[test]
+    fn test_ema_next_matches_calculate() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 4.0)
+            .collect();
+        let mut ema = EMA::new(7).unwrap();
+        let batch = ema.calculate(&prices).unwrap();
+        let streamed: Vec<Option<f64>> = prices.iter().map(|&p| ema.next(p)).collect();
+
+        assert_eq!(streamed, batch);
+        assert_eq!(ema.value(), batch[29]);
+
+        ema.reset();
+        assert_eq!(ema.value(), None);
+        let again: Vec<Option<f64>> = prices.iter().map(|&p| ema.next(p)).collect();
+        assert_eq!(again, batch);
+    }
+
+    #[test]
+    fn test_ema_update_last_revises_current_bar() {
+        let prices = [10.0, 11.0, 12.0, 13.0, 14.0];
+        let mut ema = EMA::new(3).unwrap();
+        let batch = ema.calculate(&prices).unwrap();
+
+        for (&price, expected) in prices.iter().zip(&batch) {
+            ema.next(price + 5.0);
+            assert_eq!(ema.update_last(price), *expected);
+        }
+
+        ema.rollback();
+        ema.rollback();
+        assert_eq!(ema.value(), batch[3]);
+    }
+}
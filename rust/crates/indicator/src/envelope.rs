@@ -0,0 +1,206 @@
+//! Moving Average Envelopes
+
+use crate::error::check_min_length;
+use crate::moving_average::{AnyMovingAverage, MaType, MovingAverage};
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Envelope values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopeOutput {
+    /// The moving average
+    pub middle: f64,
+    /// Moving average shifted up by the band percentage
+    pub upper: f64,
+    /// Moving average shifted down by the band percentage
+    pub lower: f64,
+}
+
+/// Moving Average Envelopes indicator
+///
+/// Fixed-percentage bands around a moving average of the chosen [`MaType`]:
+///
+/// Middle = MA(period)
+/// Upper = Middle × (1 + percent / 100)
+/// Lower = Middle × (1 − percent / 100)
+///
+/// Unlike Bollinger Bands the width does not react to volatility, so the
+/// percentage is usually tuned per instrument.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Envelope, MaType};
+///
+/// let envelope = Envelope::new(MaType::Sma, 3, 10.0)?;
+/// let result = envelope.calculate(&[9.0, 10.0, 11.0, 12.0])?;
+///
+/// assert!(result[1].is_none());
+/// let band = result[2].unwrap();
+/// assert_eq!(band.middle, 10.0);
+/// assert!((band.upper - 11.0).abs() < 1e-12 && (band.lower - 9.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    ma: AnyMovingAverage,
+    percent: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Envelope {
+    /// Creates a new envelope indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `ma_type` - Moving average family for the middle line
+    /// * `period` - Moving average period (must be > 0, typically 20)
+    /// * `percent` - Band offset in percent of the average (must be >= 0 and
+    ///   < 100, typically 2.5)
+    pub fn new(ma_type: MaType, period: usize, percent: f64) -> Result<Self, IndicatorError> {
+        if percent.is_nan() || !(0.0..100.0).contains(&percent) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "percent",
+                value: percent,
+                constraint: ">= 0 and < 100",
+            });
+        }
+
+        Ok(Self {
+            ma: AnyMovingAverage::new(ma_type, period)?,
+            percent,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the envelope for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<EnvelopeOutput>>, IndicatorError> {
+        check_min_length(self.period(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until the moving average has warmed up.
+    pub fn next(&mut self, price: f64) -> Option<EnvelopeOutput> {
+        self.undo = Snapshot::of(self);
+        let middle = self.ma.next(price)?;
+        let offset = middle * self.percent / 100.0;
+        Some(EnvelopeOutput {
+            middle,
+            upper: middle + offset,
+            lower: middle - offset,
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<EnvelopeOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ma.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the moving average family
+    pub fn ma_type(&self) -> MaType {
+        self.ma.ma_type()
+    }
+
+    /// Returns the moving average period
+    pub fn period(&self) -> usize {
+        self.ma.period()
+    }
+
+    /// Returns the band offset in percent
+    pub fn percent(&self) -> f64 {
+        self.percent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EMA, WMA};
+
+    #[test]
+    fn test_envelope_follows_ma_type() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 50.0 + (i as f64 * 0.3).sin() * 4.0)
+            .collect();
+        let ema = EMA::new(8).unwrap().calculate(&prices).unwrap();
+        let wma = WMA::new(8).unwrap().calculate(&prices).unwrap();
+
+        for (ma_type, expected) in [(MaType::Ema, ema), (MaType::Wma, wma)] {
+            let result = Envelope::new(ma_type, 8, 2.5)
+                .unwrap()
+                .calculate(&prices)
+                .unwrap();
+            for (band, ma) in result.iter().zip(&expected) {
+                match (band, ma) {
+                    (Some(band), Some(ma)) => {
+                        assert!((band.middle - ma).abs() < 1e-12);
+                        assert!((band.upper - ma * 1.025).abs() < 1e-9);
+                        assert!((band.lower - ma * 0.975).abs() < 1e-9);
+                    }
+                    (band, ma) => assert!(band.is_none() && ma.is_none()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_envelope_streaming_matches_batch() {
+        let prices = [3.0, 4.0, 5.0, 4.5, 6.0, 5.5, 7.0];
+        let mut envelope = Envelope::new(MaType::Sma, 3, 5.0).unwrap();
+        let batch = envelope.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| envelope.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        envelope.reset();
+        assert_eq!(envelope.next(1.0), None);
+        assert_eq!(envelope.ma_type(), MaType::Sma);
+        assert_eq!((envelope.period(), envelope.percent()), (3, 5.0));
+    }
+
+    #[test]
+    fn test_envelope_invalid_inputs() {
+        assert!(Envelope::new(MaType::Sma, 0, 2.5).is_err());
+        assert!(Envelope::new(MaType::Ema, 20, -1.0).is_err());
+        assert!(Envelope::new(MaType::Wma, 20, 100.0).is_err());
+        assert!(Envelope::new(MaType::Wma, 20, f64::NAN).is_err());
+        assert!(matches!(
+            Envelope::new(MaType::Ema, 20, 2.5)
+                .unwrap()
+                .calculate(&[1.0; 19]),
+            Err(IndicatorError::InsufficientData {
+                required: 20,
+                actual: 19
+            })
+        ));
+    }
+}
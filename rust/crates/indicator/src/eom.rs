@@ -0,0 +1,264 @@
+//! Ease of Movement (EOM)
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Ease of Movement indicator
+///
+/// Arms' measure of how much volume it takes to move price:
+///
+/// Distance = (High + Low) / 2 − (High_prev + Low_prev) / 2
+/// Box Ratio = (Volume / scale) / (High − Low)
+/// EMV = Distance / Box Ratio
+///
+/// `scale` only keeps the values readable; 100,000,000 is common for
+/// equities. Bars with no range or no volume contribute zero. With a
+/// smoothing period the output is the simple average of the last
+/// `smoothing` EMV values.
+///
+/// # Example
+///
+/// ```
+/// use indicator::EaseOfMovement;
+///
+/// let high = [10.0, 12.0, 13.0];
+/// let low = [8.0, 10.0, 11.0];
+/// let volume = [1_000.0, 2_000.0, 4_000.0];
+///
+/// let result = EaseOfMovement::new(1_000.0, None)?.calculate(&high, &low, &volume)?;
+/// assert_eq!(result[0], None);
+/// // Midpoint up 2, box ratio (2000 / 1000) / 2 = 1
+/// assert_eq!(result[1], Some(2.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct EaseOfMovement {
+    scale: f64,
+    smoothing: Option<usize>,
+    prev_midpoint: Option<f64>,
+    /// Raw EMV values in the smoothing window, oldest first
+    window: VecDeque<f64>,
+    sum: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl EaseOfMovement {
+    /// Creates a new Ease of Movement indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - Volume divisor (must be > 0)
+    /// * `smoothing` - Optional SMA period applied to EMV (must be > 0,
+    ///   typically 14)
+    pub fn new(scale: f64, smoothing: Option<usize>) -> Result<Self, IndicatorError> {
+        if scale.is_nan() || scale <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "scale",
+                value: scale,
+                constraint: "> 0",
+            });
+        }
+        if smoothing == Some(0) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "smoothing",
+                value: 0.0,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            scale,
+            smoothing,
+            prev_midpoint: None,
+            window: VecDeque::new(),
+            sum: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates EMV for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are too few bars for a single value.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        volume: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("volume", volume.len()),
+        ])?;
+        check_min_length(self.required(), len)?;
+
+        let mut state = Self::new(self.scale, self.smoothing)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], volume[i]))
+            .collect())
+    }
+
+    /// Calculates EMV for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few candles for a single
+    /// value.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.required(), candles.len())?;
+
+        let mut state = Self::new(self.scale, self.smoothing)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.volume))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, volume: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let midpoint = (high + low) / 2.0;
+        let prev_midpoint = self.prev_midpoint.replace(midpoint)?;
+
+        let range = high - low;
+        let emv = if range > 0.0 && volume > 0.0 {
+            (midpoint - prev_midpoint) * range * self.scale / volume
+        } else {
+            0.0
+        };
+
+        let Some(period) = self.smoothing else {
+            return Some(emv);
+        };
+        if self.window.len() == period {
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        }
+        self.window.push_back(emv);
+        self.sum += emv;
+        (self.window.len() == period).then(|| self.sum / period as f64)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, volume: f64) -> Option<f64> {
+        self.rollback();
+        self.next(high, low, volume)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev_midpoint = None;
+        self.window.clear();
+        self.sum = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the volume divisor
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the smoothing period, if any
+    pub fn smoothing(&self) -> Option<usize> {
+        self.smoothing
+    }
+
+    fn required(&self) -> usize {
+        self.smoothing.unwrap_or(1) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eom_raw_values() {
+        let high = [10.0, 12.0, 11.0, 11.0];
+        let low = [8.0, 10.0, 9.0, 11.0];
+        let volume = [500.0, 400.0, 800.0, 300.0];
+        let result = EaseOfMovement::new(100.0, None)
+            .unwrap()
+            .calculate(&high, &low, &volume)
+            .unwrap();
+
+        // Bar 2: midpoint down 1, box ratio (800 / 100) / 2 = 4
+        assert_eq!(result[2], Some(-0.25));
+        // Bar 3 has no range
+        assert_eq!(result[3], Some(0.0));
+    }
+
+    #[test]
+    fn test_eom_smoothing_is_sma_of_raw() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| {
+                let base = 40.0 + (i as f64 * 0.6).sin() * 3.0;
+                let volume = 10_000.0 + (i % 5) as f64 * 3_000.0;
+                Candle::new(i, base, base + 1.0, base - 0.5, base, volume)
+            })
+            .collect();
+        let raw = EaseOfMovement::new(1e4, None)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        let smoothed = EaseOfMovement::new(1e4, Some(4))
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+
+        assert!(smoothed[3].is_none());
+        for i in 4..candles.len() {
+            let expected = raw[i - 3..=i].iter().flatten().sum::<f64>() / 4.0;
+            assert!((smoothed[i].unwrap() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_eom_streaming_and_reset() {
+        let mut eom = EaseOfMovement::new(1.0, Some(2)).unwrap();
+        assert_eq!(eom.next(2.0, 1.0, 1.0), None);
+        assert_eq!(eom.next(3.0, 2.0, 1.0), None);
+        assert_eq!(eom.next(4.0, 3.0, 1.0), Some(1.0));
+
+        eom.reset();
+        assert_eq!(eom.next(4.0, 3.0, 1.0), None);
+        assert_eq!((eom.scale(), eom.smoothing()), (1.0, Some(2)));
+    }
+
+    #[test]
+    fn test_eom_invalid_inputs() {
+        assert!(EaseOfMovement::new(0.0, None).is_err());
+        assert!(EaseOfMovement::new(f64::NAN, None).is_err());
+        assert!(EaseOfMovement::new(1.0, Some(0)).is_err());
+        let eom = EaseOfMovement::new(1.0, Some(14)).unwrap();
+        assert!(matches!(
+            eom.calculate(&[1.0; 14], &[1.0; 14], &[1.0; 14]),
+            Err(IndicatorError::InsufficientData {
+                required: 15,
+                actual: 14
+            })
+        ));
+    }
+}
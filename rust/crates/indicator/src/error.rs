@@ -0,0 +1,92 @@
+//! Error types for indicator calculations
+
+use thiserror::Error;
+
+/// Errors that can occur during indicator calculations
+///
+/// Variants carry the offending field and value so callers can react to the
+/// failure programmatically, while `Display` stays human-readable.
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum IndicatorError {
+    /// An indicator parameter violated its constraint
+    #[error("Invalid parameter `{field}` = {value}: must be {constraint}")]
+    InvalidParameter {
+        /// Name of the offending field (e.g. `"period"`)
+        field: &'static str,
+        /// Value that was supplied
+        value: f64,
+        /// Constraint the value must satisfy (e.g. `"> 0"`)
+        constraint: &'static str,
+    },
+
+    /// The input series is too short for the requested calculation
+    #[error("Insufficient data: need at least {required} data points, got {actual}")]
+    InsufficientData {
+        /// Minimum number of data points required
+        required: usize,
+        /// Number of data points supplied
+        actual: usize,
+    },
+
+    /// Input series that must be aligned bar-for-bar have different lengths
+    #[error("Length mismatch: `{field}` has {actual} values, expected {expected}")]
+    LengthMismatch {
+        /// Name of the series whose length differs
+        field: &'static str,
+        /// Length of the first input series
+        expected: usize,
+        /// Length of the offending series
+        actual: usize,
+    },
+
+    /// A numerical routine failed while computing a result
+    #[error("Calculation error in {operation}: {reason}")]
+    CalculationError {
+        /// Operation that failed
+        operation: &'static str,
+        /// Underlying reason reported by the failing routine
+        reason: String,
+    },
+
+    /// An input was missing or NaN under [`NanPolicy::Error`](crate::NanPolicy::Error)
+    #[error("Missing or NaN input at index {index}")]
+    MissingData {
+        /// Position of the offending input in the series or stream
+        index: usize,
+    },
+
+    /// The indicator does not implement an optional operation
+    #[error("Unsupported operation: {operation} is not available for this indicator")]
+    Unsupported {
+        /// Operation that was requested (e.g. `"rollback"`)
+        operation: &'static str,
+    },
+}
+
+/// Checks that aligned input series all have the length of the first one
+///
+/// Returns the common length on success.
+pub(crate) fn check_equal_lengths(
+    series: &[(&'static str, usize)],
+) -> Result<usize, IndicatorError> {
+    let expected = series.first().map_or(0, |&(_, len)| len);
+    for &(field, actual) in series {
+        if actual != expected {
+            return Err(IndicatorError::LengthMismatch {
+                field,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(expected)
+}
+
+/// Checks that an input series has at least `required` values
+pub(crate) fn check_min_length(required: usize, actual: usize) -> Result<(), IndicatorError> {
+    if actual < required {
+        return Err(IndicatorError::InsufficientData { required, actual });
+    }
+    Ok(())
+}
@@ -0,0 +1,755 @@
+//! General-purpose smoothing filters
+//!
+//! * [`SavitzkyGolay`] - local polynomial least-squares smoother that preserves
+//!   peaks and slopes better than a moving average of the same width
+//! * [`GaussianFilter`] - Gaussian-kernel weighted smoother
+//! * [`HodrickPrescott`] - trend/cycle decomposition of a whole series
+//!
+//! The first two filters offer two alignments. `calculate`/`next` are causal: each
+//! output uses only the current and past values, so they are safe for
+//! signals. `smooth` is centered on each value and uses future data, which
+//! gives lag-free curves for research but must not feed live decisions.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::filters::SavitzkyGolay;
+//!
+//! let prices = vec![10.0, 10.4, 10.1, 10.8, 11.2, 10.9, 11.5, 11.9, 11.6];
+//! let sg = SavitzkyGolay::new(5, 2)?;
+//!
+//! let causal = sg.calculate(&prices)?;
+//! let centered = sg.smooth(&prices)?;
+//!
+//! assert!(causal[3].is_none() && causal[4].is_some());
+//! assert!(centered[1].is_none() && centered[2].is_some());
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::IndicatorError;
+
+/// Savitzky-Golay smoothing filter
+///
+/// Fits a polynomial of degree `order` to each window of `window` values by
+/// least squares and takes its value at the latest point (causal) or at the
+/// window center (centered). Polynomials up to degree `order` pass through
+/// unchanged.
+///
+/// [`with_derivative`](Self::with_derivative) builds a filter that outputs a
+/// derivative of the fitted polynomial instead, e.g. a smoothed slope per bar.
+#[derive(Debug, Clone)]
+pub struct SavitzkyGolay {
+    window: usize,
+    order: usize,
+    derivative: usize,
+    /// Weights applied to the window, oldest first, for the causal fit
+    trailing: Vec<f64>,
+    /// Weights applied to the window for the centered fit
+    centered: Vec<f64>,
+    buffer: Window,
+}
+
+impl SavitzkyGolay {
+    /// Creates a new Savitzky-Golay filter
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of values per fit (must be odd and >= 3)
+    /// * `order` - Polynomial degree (must be < `window`)
+    pub fn new(window: usize, order: usize) -> Result<Self, IndicatorError> {
+        Self::with_derivative(window, order, 0)
+    }
+
+    /// Creates a Savitzky-Golay filter that outputs a derivative of the fit
+    ///
+    /// The output is the `derivative`-th derivative of the fitted polynomial
+    /// per bar, so `derivative = 1` gives the slope in price units per bar.
+    /// `derivative = 0` is the same as [`new`](Self::new).
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of values per fit (must be odd and >= 3)
+    /// * `order` - Polynomial degree (must be < `window`)
+    /// * `derivative` - Derivative order (must be <= `order`)
+    pub fn with_derivative(
+        window: usize,
+        order: usize,
+        derivative: usize,
+    ) -> Result<Self, IndicatorError> {
+        if window < 3 || window.is_multiple_of(2) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "window",
+                value: window as f64,
+                constraint: "odd and >= 3",
+            });
+        }
+        if order >= window {
+            return Err(IndicatorError::InvalidParameter {
+                field: "order",
+                value: order as f64,
+                constraint: "< window",
+            });
+        }
+        if derivative > order {
+            return Err(IndicatorError::InvalidParameter {
+                field: "derivative",
+                value: derivative as f64,
+                constraint: "<= order",
+            });
+        }
+
+        let half = (window / 2) as f64;
+        let trailing_positions: Vec<f64> = (0..window)
+            .map(|i| i as f64 - (window - 1) as f64)
+            .collect();
+        let centered_positions: Vec<f64> = (0..window).map(|i| i as f64 - half).collect();
+
+        Ok(Self {
+            window,
+            order,
+            derivative,
+            trailing: polynomial_fit_weights(&trailing_positions, order, derivative)?,
+            centered: polynomial_fit_weights(&centered_positions, order, derivative)?,
+            buffer: Window::new(window),
+        })
+    }
+
+    /// Calculates the causal filter for a series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `window` values are given.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_length(values, self.window)?;
+        let mut state = self.clone();
+        state.reset();
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value and returns the causal filter output
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.buffer
+            .push(value)
+            .then(|| dot(&self.trailing, self.buffer.values.iter()))
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        self.buffer.rollback();
+    }
+
+    /// Centered smoothing of a whole series
+    ///
+    /// Uses `window / 2` future values per output, so the first and last
+    /// `window / 2` outputs are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `window` values are given.
+    pub fn smooth(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_length(values, self.window)?;
+        Ok(convolve_centered(values, &self.centered))
+    }
+
+    /// Resets the streaming state
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns the window length
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the polynomial order
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the derivative order of the output (`0` for the smoothed value)
+    pub fn derivative(&self) -> usize {
+        self.derivative
+    }
+}
+
+/// Gaussian-kernel smoothing filter
+///
+/// Weights values by `exp(−t² / 2σ²)` at distance `t` bars, truncated at
+/// `⌈3σ⌉` bars. The causal filter uses the half kernel over past values.
+#[derive(Debug, Clone)]
+pub struct GaussianFilter {
+    sigma: f64,
+    radius: usize,
+    /// Half-kernel weights, oldest first
+    trailing: Vec<f64>,
+    /// Full kernel of `2·radius + 1` weights
+    centered: Vec<f64>,
+    buffer: Window,
+}
+
+impl GaussianFilter {
+    /// Creates a new Gaussian filter
+    ///
+    /// # Arguments
+    ///
+    /// * `sigma` - Kernel standard deviation in bars (must be > 0)
+    pub fn new(sigma: f64) -> Result<Self, IndicatorError> {
+        if sigma.is_nan() || sigma <= 0.0 || sigma.is_infinite() {
+            return Err(IndicatorError::InvalidParameter {
+                field: "sigma",
+                value: sigma,
+                constraint: "> 0 and finite",
+            });
+        }
+
+        let radius = (3.0 * sigma).ceil() as usize;
+        let kernel = |t: f64| (-t * t / (2.0 * sigma * sigma)).exp();
+        let normalize = |weights: Vec<f64>| {
+            let total: f64 = weights.iter().sum();
+            weights.into_iter().map(|w| w / total).collect::<Vec<_>>()
+        };
+        let trailing = normalize((0..=radius).rev().map(|t| kernel(t as f64)).collect());
+        let centered = normalize(
+            (0..=2 * radius)
+                .map(|i| kernel(i as f64 - radius as f64))
+                .collect(),
+        );
+
+        Ok(Self {
+            sigma,
+            radius,
+            trailing,
+            centered,
+            buffer: Window::new(radius + 1),
+        })
+    }
+
+    /// Calculates the causal filter for a series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `radius + 1` values are given.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_length(values, self.radius + 1)?;
+        let mut state = self.clone();
+        state.reset();
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value and returns the causal filter output
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.buffer
+            .push(value)
+            .then(|| dot(&self.trailing, self.buffer.values.iter()))
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        self.buffer.rollback();
+    }
+
+    /// Centered smoothing of a whole series
+    ///
+    /// The first and last `radius` outputs are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `2·radius + 1` values are given.
+    pub fn smooth(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_length(values, self.centered.len())?;
+        Ok(convolve_centered(values, &self.centered))
+    }
+
+    /// Resets the streaming state
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns the kernel standard deviation
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Returns the kernel truncation radius in bars
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+}
+
+/// Trend and cycle components from [`HodrickPrescott::decompose`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HpDecomposition {
+    /// Smooth trend component
+    pub trend: Vec<f64>,
+    /// Cyclical component, `value − trend`
+    pub cycle: Vec<f64>,
+}
+
+/// Hodrick-Prescott filter
+///
+/// Splits a series `y` into a trend `τ` and a cycle `y − τ` by minimizing
+///
+/// Σ (yₜ − τₜ)² + λ Σ (τₜ₊₁ − 2τₜ + τₜ₋₁)²
+///
+/// Larger `lambda` penalizes curvature more and gives a smoother trend;
+/// common choices are `1600` for quarterly data, `129600` for monthly and
+/// `6.25` for annual. The minimizer solves the pentadiagonal system
+/// `(I + λDᵀD)τ = y`, which takes O(n).
+///
+/// The filter is two-sided: every trend value depends on the whole series,
+/// including future values, so it suits research but not live signals.
+#[derive(Debug, Clone)]
+pub struct HodrickPrescott {
+    lambda: f64,
+}
+
+impl HodrickPrescott {
+    /// Creates a new Hodrick-Prescott filter
+    ///
+    /// # Arguments
+    ///
+    /// * `lambda` - Smoothing parameter (must be >= 0; `0` returns the series as the trend)
+    pub fn new(lambda: f64) -> Result<Self, IndicatorError> {
+        if lambda.is_nan() || lambda < 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "lambda",
+                value: lambda,
+                constraint: ">= 0",
+            });
+        }
+        Ok(Self { lambda })
+    }
+
+    /// Decomposes a series into trend and cycle
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than 3 values are given.
+    pub fn decompose(&self, values: &[f64]) -> Result<HpDecomposition, IndicatorError> {
+        check_length(values, 3)?;
+        let n = values.len();
+
+        // Diagonals of I + λDᵀD, accumulated from the rows [1, −2, 1] of D
+        let mut diag = vec![1.0; n];
+        let mut upper1 = vec![0.0; n - 1];
+        let mut upper2 = vec![0.0; n - 2];
+        const SECOND_DIFF: [f64; 3] = [1.0, -2.0, 1.0];
+        for k in 0..n - 2 {
+            for a in 0..3 {
+                diag[k + a] += self.lambda * SECOND_DIFF[a] * SECOND_DIFF[a];
+            }
+            for a in 0..2 {
+                upper1[k + a] += self.lambda * SECOND_DIFF[a] * SECOND_DIFF[a + 1];
+            }
+            upper2[k] += self.lambda * SECOND_DIFF[0] * SECOND_DIFF[2];
+        }
+
+        let trend = solve_pentadiagonal(&diag, &upper1, &upper2, values);
+        let cycle = values.iter().zip(&trend).map(|(y, t)| y - t).collect();
+        Ok(HpDecomposition { trend, cycle })
+    }
+
+    /// Returns the smoothing parameter
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+fn check_length(values: &[f64], required: usize) -> Result<(), IndicatorError> {
+    if values.len() < required {
+        return Err(IndicatorError::InsufficientData {
+            required,
+            actual: values.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Fixed-size trailing window that can undo its most recent push
+#[derive(Debug, Clone)]
+struct Window {
+    size: usize,
+    values: VecDeque<f64>,
+    /// Value evicted by the last push (inner `None` if nothing was evicted),
+    /// or `None` when there is no push to undo
+    undo: Option<Option<f64>>,
+}
+
+impl Window {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            values: VecDeque::with_capacity(size),
+            undo: None,
+        }
+    }
+
+    /// Pushes `value`, evicting the oldest value if full; returns true once full
+    fn push(&mut self, value: f64) -> bool {
+        let evicted = if self.values.len() == self.size {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        self.undo = Some(evicted);
+        self.values.len() == self.size
+    }
+
+    fn rollback(&mut self) {
+        if let Some(evicted) = self.undo.take() {
+            self.values.pop_back();
+            if let Some(oldest) = evicted {
+                self.values.push_front(oldest);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.undo = None;
+    }
+}
+
+fn dot<'a>(weights: &[f64], values: impl Iterator<Item = &'a f64>) -> f64 {
+    weights.iter().zip(values).map(|(w, v)| w * v).sum()
+}
+
+fn convolve_centered(values: &[f64], weights: &[f64]) -> Vec<Option<f64>> {
+    let half = weights.len() / 2;
+    (0..values.len())
+        .map(|i| {
+            (i >= half && i + half < values.len())
+                .then(|| dot(weights, values[i - half..=i + half].iter()))
+        })
+        .collect()
+}
+
+/// Weights `w` such that `Σ wᵢ·yᵢ` is the `derivative`-th derivative at
+/// position 0 of the degree-`order` least-squares polynomial through
+/// `(positions[i], yᵢ)`
+///
+/// With the design matrix `Aᵢⱼ = positionᵢʲ`, the fitted coefficient `k` is
+/// `eₖᵀ(AᵀA)⁻¹Aᵀy`, so `w = k!·A·z` where `(AᵀA)·z = eₖ` and `k = derivative`.
+fn polynomial_fit_weights(
+    positions: &[f64],
+    order: usize,
+    derivative: usize,
+) -> Result<Vec<f64>, IndicatorError> {
+    let terms = order + 1;
+    let design: Vec<Vec<f64>> = positions
+        .iter()
+        .map(|&t| (0..terms).map(|j| t.powi(j as i32)).collect())
+        .collect();
+
+    let mut normal = vec![vec![0.0; terms]; terms];
+    for row in &design {
+        for (j, &a) in row.iter().enumerate() {
+            for (k, &b) in row.iter().enumerate() {
+                normal[j][k] += a * b;
+            }
+        }
+    }
+    let mut rhs = vec![0.0; terms];
+    rhs[derivative] = 1.0;
+    let z = solve_linear_system(normal, rhs)?;
+
+    let factorial: f64 = (1..=derivative).map(|k| k as f64).product();
+    Ok(design
+        .iter()
+        .map(|row| factorial * dot(&z, row.iter()))
+        .collect())
+}
+
+/// Solves `matrix · x = rhs` by Gaussian elimination with partial pivoting
+pub(crate) fn solve_linear_system(
+    mut matrix: Vec<Vec<f64>>,
+    mut rhs: Vec<f64>,
+) -> Result<Vec<f64>, IndicatorError> {
+    let n = rhs.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        if matrix[pivot][col].abs() < f64::EPSILON {
+            return Err(IndicatorError::CalculationError {
+                operation: "least squares",
+                reason: "singular normal equations".to_string(),
+            });
+        }
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (cell, &pivot_cell) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *cell -= factor * pivot_cell;
+            }
+            rhs[col + 1 + offset] -= factor * rhs[col];
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| matrix[row][k] * solution[k]).sum();
+        solution[row] = (rhs[row] - tail) / matrix[row][row];
+    }
+    Ok(solution)
+}
+
+/// Solves a symmetric positive-definite pentadiagonal system by LDLᵀ factorization
+///
+/// `diag`, `upper1` and `upper2` hold the main diagonal and the first and
+/// second superdiagonals.
+fn solve_pentadiagonal(diag: &[f64], upper1: &[f64], upper2: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    // Unit lower factor L[i][i−1] and L[i][i−2], and the diagonal D
+    let mut lower1 = vec![0.0; n];
+    let mut lower2 = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    for i in 0..n {
+        d[i] = diag[i];
+        if i >= 1 {
+            d[i] -= lower1[i] * lower1[i] * d[i - 1];
+        }
+        if i >= 2 {
+            d[i] -= lower2[i] * lower2[i] * d[i - 2];
+        }
+        if i + 1 < n {
+            let coupled = if i >= 1 {
+                lower2[i + 1] * d[i - 1] * lower1[i]
+            } else {
+                0.0
+            };
+            lower1[i + 1] = (upper1[i] - coupled) / d[i];
+        }
+        if i + 2 < n {
+            lower2[i + 2] = upper2[i] / d[i];
+        }
+    }
+
+    let mut x = rhs.to_vec();
+    for i in 1..n {
+        x[i] -= lower1[i] * x[i - 1];
+        if i >= 2 {
+            x[i] -= lower2[i] * x[i - 2];
+        }
+    }
+    for (value, d) in x.iter_mut().zip(&d) {
+        *value /= d;
+    }
+    for i in (0..n).rev() {
+        if i + 1 < n {
+            x[i] -= lower1[i + 1] * x[i + 1];
+        }
+        if i + 2 < n {
+            x[i] -= lower2[i + 2] * x[i + 2];
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_savitzky_golay_known_coefficients() {
+        let sg = SavitzkyGolay::new(5, 2).unwrap();
+        let expected = [-3.0, 12.0, 17.0, 12.0, -3.0];
+        for (weight, e) in sg.centered.iter().zip(expected) {
+            assert!((weight - e / 35.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_savitzky_golay_preserves_polynomials() {
+        let quadratic: Vec<f64> = (0..20)
+            .map(|i| 0.5 * (i * i) as f64 - 3.0 * i as f64 + 7.0)
+            .collect();
+        let sg = SavitzkyGolay::new(7, 2).unwrap();
+
+        for (output, input) in sg.calculate(&quadratic).unwrap().iter().zip(&quadratic) {
+            if let Some(output) = output {
+                assert!((output - input).abs() < 1e-8);
+            }
+        }
+        for (output, input) in sg.smooth(&quadratic).unwrap().iter().zip(&quadratic) {
+            if let Some(output) = output {
+                assert!((output - input).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_savitzky_golay_streaming_matches_batch() {
+        let values = vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0];
+        let mut sg = SavitzkyGolay::new(5, 3).unwrap();
+        let batch = sg.calculate(&values).unwrap();
+        let streamed: Vec<_> = values.iter().map(|&v| sg.next(v)).collect();
+        assert_eq!(batch, streamed);
+    }
+
+    #[test]
+    fn test_revising_last_value_matches_final_series() {
+        let values = vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0];
+        let mut filter = GaussianFilter::new(1.0).unwrap();
+        let expected = filter.calculate(&values).unwrap();
+
+        let mut latest = None;
+        for &value in &values {
+            // Each bar first prints a provisional value, then its close
+            filter.next(value + 10.0);
+            filter.update_last(value - 3.0);
+            latest = filter.update_last(value);
+        }
+        assert_eq!(latest, expected[values.len() - 1]);
+
+        filter.rollback();
+        filter.rollback();
+        assert_eq!(filter.next(values[7]), expected[7]);
+    }
+
+    #[test]
+    fn test_gaussian_filter() {
+        let filter = GaussianFilter::new(1.0).unwrap();
+        assert_eq!(filter.radius(), 3);
+
+        let constant = vec![5.0; 10];
+        for value in filter.calculate(&constant).unwrap().into_iter().flatten() {
+            assert!((value - 5.0).abs() < 1e-12);
+        }
+
+        let alternating: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let smoothed = filter.smooth(&alternating).unwrap();
+        assert_eq!(smoothed[2], None);
+        assert!(smoothed[10].unwrap().abs() < 0.05);
+    }
+
+    #[test]
+    fn test_filter_invalid_parameters() {
+        assert!(SavitzkyGolay::new(4, 2).is_err());
+        assert!(SavitzkyGolay::new(5, 5).is_err());
+        assert!(GaussianFilter::new(0.0).is_err());
+
+        let sg = SavitzkyGolay::new(5, 2).unwrap();
+        assert!(matches!(
+            sg.smooth(&[1.0, 2.0]),
+            Err(IndicatorError::InsufficientData {
+                required: 5,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hodrick_prescott_solves_normal_equations() {
+        let values: Vec<f64> = (0..60)
+            .map(|i| 100.0 + 0.3 * i as f64 + (i as f64 * 0.5).sin() * 4.0)
+            .collect();
+        let lambda = 1600.0;
+        let HpDecomposition { trend, cycle } = HodrickPrescott::new(lambda)
+            .unwrap()
+            .decompose(&values)
+            .unwrap();
+
+        // The trend satisfies (I + λDᵀD)τ = y
+        let second_diff: Vec<f64> = trend.windows(3).map(|w| w[0] - 2.0 * w[1] + w[2]).collect();
+        for (t, y) in values.iter().enumerate() {
+            let penalty: f64 = (0..second_diff.len())
+                .filter(|&k| k <= t && t <= k + 2)
+                .map(|k| [1.0, -2.0, 1.0][t - k] * second_diff[k])
+                .sum();
+            assert!((trend[t] + lambda * penalty - y).abs() < 1e-8, "row {t}");
+        }
+        for ((t, c), y) in trend.iter().zip(&cycle).zip(&values) {
+            assert!((t + c - y).abs() < 1e-12);
+        }
+        // The oscillation ends up in the cycle
+        assert!(cycle.iter().map(|c| c.abs()).fold(0.0, f64::max) > 2.0);
+    }
+
+    #[test]
+    fn test_hodrick_prescott_limits() {
+        let line: Vec<f64> = (0..10).map(|i| 2.0 * i as f64 - 5.0).collect();
+        let hp = HodrickPrescott::new(1e6).unwrap();
+        for c in hp.decompose(&line).unwrap().cycle {
+            assert!(c.abs() < 1e-6);
+        }
+
+        let noisy = [1.0, 4.0, 2.0, 8.0, 3.0];
+        let identity = HodrickPrescott::new(0.0)
+            .unwrap()
+            .decompose(&noisy)
+            .unwrap();
+        assert_eq!(identity.trend, noisy.to_vec());
+
+        assert!(HodrickPrescott::new(-1.0).is_err());
+        assert!(matches!(
+            hp.decompose(&[1.0, 2.0]),
+            Err(IndicatorError::InsufficientData {
+                required: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_savitzky_golay_derivatives() {
+        // Known first-derivative weights for a 5-point quadratic: (−2, −1, 0, 1, 2) / 10
+        let slope = SavitzkyGolay::with_derivative(5, 2, 1).unwrap();
+        assert_eq!(slope.derivative(), 1);
+        for (weight, e) in slope.centered.iter().zip([-2.0, -1.0, 0.0, 1.0, 2.0]) {
+            assert!((weight - e / 10.0).abs() < 1e-12);
+        }
+
+        // y = t³ − 2t² + t: y' = 3t² − 4t + 1, y'' = 6t − 4
+        let cubic: Vec<f64> = (0..15)
+            .map(|i| {
+                let t = i as f64;
+                t.powi(3) - 2.0 * t * t + t
+            })
+            .collect();
+        let first = SavitzkyGolay::with_derivative(7, 3, 1).unwrap();
+        let second = SavitzkyGolay::with_derivative(7, 3, 2).unwrap();
+        let causal = first.calculate(&cubic).unwrap();
+        let centered = second.smooth(&cubic).unwrap();
+        for (i, slope) in causal.iter().enumerate().skip(6) {
+            let t = i as f64;
+            assert!((slope.unwrap() - (3.0 * t * t - 4.0 * t + 1.0)).abs() < 1e-6);
+        }
+        for (i, curvature) in centered.iter().enumerate().take(12).skip(3) {
+            assert!((curvature.unwrap() - (6.0 * i as f64 - 4.0)).abs() < 1e-6);
+        }
+
+        assert!(SavitzkyGolay::with_derivative(5, 2, 3).is_err());
+    }
+}
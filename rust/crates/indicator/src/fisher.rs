@@ -0,0 +1,251 @@
+//! Ehlers Fisher Transform
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, RollingMax, RollingMin};
+
+/// Largest magnitude of the normalised value, keeping the logarithm finite
+const CLAMP: f64 = 0.999;
+
+/// Fisher Transform values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherOutput {
+    /// Fisher Transform of the normalised median price
+    pub fisher: f64,
+    /// Previous bar's Fisher value; `None` on the first value
+    pub trigger: Option<f64>,
+}
+
+/// Ehlers Fisher Transform indicator
+///
+/// Normalises the median price to its position within the lookback range,
+/// then applies the Fisher transform so turning points show up as sharp,
+/// near-Gaussian extremes:
+///
+/// Median = (High + Low) / 2
+/// X = 0.66 × ((Median − LL) / (HH − LL) − 0.5) + 0.67 × X_prev, clamped to ±0.999
+/// Fisher = 0.5 × ln((1 + X) / (1 − X)) + 0.5 × Fisher_prev
+///
+/// where HH and LL are the highest and lowest medians of the last `period`
+/// bars. The trigger line is Fisher delayed by one bar; crosses between the
+/// two are the usual signals. A window with no range normalises to its
+/// midpoint.
+///
+/// # Example
+///
+/// ```
+/// use indicator::Fisher;
+///
+/// let high: Vec<f64> = (0..20).map(|i| 10.0 + i as f64).collect();
+/// let low: Vec<f64> = high.iter().map(|h| h - 1.0).collect();
+///
+/// let result = Fisher::new(5)?.calculate(&high, &low)?;
+/// assert!(result[3].is_none());
+/// assert_eq!(result[4].unwrap().trigger, None);
+/// // A steady rally keeps the median at the top of its range
+/// let last = result[19].unwrap();
+/// assert!(last.fisher > 2.0 && last.trigger.unwrap() > 2.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fisher {
+    /// Highest and lowest median prices in the window
+    highs: RollingMax,
+    lows: RollingMin,
+    value: f64,
+    fisher: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Fisher {
+    /// Creates a new Fisher Transform indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Normalisation lookback in bars (must be > 0, typically 9-10)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            value: 0.0,
+            fisher: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the Fisher Transform for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+    ) -> Result<Vec<Option<FisherOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[("high", high.len()), ("low", low.len())])?;
+        check_min_length(self.period(), len)?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(high
+            .iter()
+            .zip(low)
+            .map(|(&high, &low)| state.next(high, low))
+            .collect())
+    }
+
+    /// Calculates the Fisher Transform for a series of candles on a fresh
+    /// state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<FisherOutput>>, IndicatorError> {
+        check_min_length(self.period(), candles.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64) -> Option<FisherOutput> {
+        self.undo = Snapshot::of(self);
+        let median = (high + low) / 2.0;
+        let highest = self.highs.next(median);
+        let (highest, lowest) = highest.zip(self.lows.next(median))?;
+        let position = if highest > lowest {
+            (median - lowest) / (highest - lowest)
+        } else {
+            0.5
+        };
+        self.value = (0.66 * (position - 0.5) + 0.67 * self.value).clamp(-CLAMP, CLAMP);
+
+        let trigger = self.fisher;
+        let fisher =
+            0.5 * ((1.0 + self.value) / (1.0 - self.value)).ln() + 0.5 * trigger.unwrap_or(0.0);
+        self.fisher = Some(fisher);
+        Some(FisherOutput { fisher, trigger })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64) -> Option<FisherOutput> {
+        self.rollback();
+        self.next(high, low)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.value = 0.0;
+        self.fisher = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the normalisation lookback
+    pub fn period(&self) -> usize {
+        self.highs.period()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fisher_recursion() {
+        let high = [10.0, 12.0, 11.0, 13.0, 12.5];
+        let low = [9.0, 11.0, 10.0, 12.0, 11.5];
+        let result = Fisher::new(3).unwrap().calculate(&high, &low).unwrap();
+
+        let medians: Vec<f64> = high.iter().zip(&low).map(|(h, l)| (h + l) / 2.0).collect();
+        let (mut value, mut fisher) = (0.0_f64, 0.0_f64);
+        for i in 2..5 {
+            let window = &medians[i - 2..=i];
+            let lo = window.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            value =
+                (0.66 * ((medians[i] - lo) / (hi - lo) - 0.5) + 0.67 * value).clamp(-CLAMP, CLAMP);
+            let prev = fisher;
+            fisher = 0.5 * ((1.0 + value) / (1.0 - value)).ln() + 0.5 * fisher;
+
+            let output = result[i].unwrap();
+            assert!((output.fisher - fisher).abs() < 1e-12);
+            if i > 2 {
+                assert_eq!(output.trigger, Some(prev));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fisher_flat_and_symmetry() {
+        let flat = Fisher::new(4)
+            .unwrap()
+            .calculate(&[5.0; 6], &[4.0; 6])
+            .unwrap();
+        assert_eq!(flat[5].unwrap().fisher, 0.0);
+
+        let rising: Vec<f64> = (0..15).map(|i| i as f64).collect();
+        let falling: Vec<f64> = rising.iter().map(|p| -p).collect();
+        let fisher = Fisher::new(5).unwrap();
+        let up = fisher.calculate(&rising, &rising).unwrap()[14].unwrap();
+        let down = fisher.calculate(&falling, &falling).unwrap()[14].unwrap();
+        assert!(up.fisher > 0.0);
+        assert!((up.fisher + down.fisher).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fisher_streaming_matches_batch() {
+        let candles: Vec<Candle> = (0..40)
+            .map(|i| {
+                let base = 80.0 + (i as f64 * 0.3).sin() * 6.0;
+                Candle::new(i, base, base + 1.0, base - 1.0, base, 1.0)
+            })
+            .collect();
+        let mut fisher = Fisher::new(9).unwrap();
+        let batch = fisher.calculate_candles(&candles).unwrap();
+        let streamed: Vec<_> = candles.iter().map(|c| fisher.next(c.high, c.low)).collect();
+        assert_eq!(batch, streamed);
+
+        fisher.reset();
+        assert_eq!(fisher.next(1.0, 0.0), None);
+        assert_eq!(fisher.period(), 9);
+    }
+
+    #[test]
+    fn test_fisher_invalid_inputs() {
+        assert!(Fisher::new(0).is_err());
+        let fisher = Fisher::new(10).unwrap();
+        assert!(matches!(
+            fisher.calculate(&[1.0; 9], &[1.0; 9]),
+            Err(IndicatorError::InsufficientData {
+                required: 10,
+                actual: 9
+            })
+        ));
+        assert!(fisher.calculate(&[1.0; 10], &[1.0; 11]).is_err());
+    }
+}
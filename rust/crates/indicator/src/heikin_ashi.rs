@@ -0,0 +1,138 @@
+//! Heikin-Ashi candle transform
+
+use crate::Candle;
+
+/// Heikin-Ashi candle transform
+///
+/// Rebuilds each bar from averages so runs of same-coloured candles stand
+/// out and downstream indicators see a smoother series:
+///
+/// HA Close = (Open + High + Low + Close) / 4
+/// HA Open = (HA Open_prev + HA Close_prev) / 2, or (Open + Close) / 2 on the first bar
+/// HA High = max(High, HA Open, HA Close)
+/// HA Low = min(Low, HA Open, HA Close)
+///
+/// Timestamps and volumes are carried over unchanged. Every input bar maps
+/// to exactly one output bar, so the result lines up with the source series.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Candle, HeikinAshi};
+///
+/// let candles = [
+///     Candle::new(0, 10.0, 12.0, 9.0, 11.0, 100.0),
+///     Candle::new(1, 11.0, 13.0, 10.0, 12.0, 150.0),
+/// ];
+/// let bars = HeikinAshi::new().transform(&candles);
+///
+/// assert_eq!((bars[0].open, bars[0].close), (10.5, 10.5));
+/// assert_eq!((bars[1].open, bars[1].close), (10.5, 11.5));
+/// assert_eq!((bars[1].high, bars[1].low), (13.0, 10.0));
+/// assert_eq!(bars[1].volume, 150.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HeikinAshi {
+    /// Previous Heikin-Ashi open and close
+    prev: Option<(f64, f64)>,
+}
+
+impl HeikinAshi {
+    /// Creates a new transform with no history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transforms a series of candles on a fresh state
+    pub fn transform(&self, candles: &[Candle]) -> Vec<Candle> {
+        let mut state = Self::new();
+        candles.iter().map(|&candle| state.next(candle)).collect()
+    }
+
+    /// Transforms the next candle (streaming mode)
+    pub fn next(&mut self, candle: Candle) -> Candle {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match self.prev {
+            Some((prev_open, prev_close)) => (prev_open + prev_close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+        self.prev = Some((open, close));
+
+        Candle {
+            open,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            close,
+            ..candle
+        }
+    }
+
+    /// Resets the transform so the next candle starts a new series
+    pub fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        (0..30)
+            .map(|i| {
+                let open = 20.0 + (i as f64 * 0.5).sin() * 2.0;
+                let close = open + (i as f64 * 1.1).cos();
+                Candle::new(
+                    i,
+                    open,
+                    open.max(close) + 0.4,
+                    open.min(close) - 0.3,
+                    close,
+                    i as f64,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_heikin_ashi_recursion() {
+        let candles = candles();
+        let bars = HeikinAshi::new().transform(&candles);
+        assert_eq!(bars.len(), candles.len());
+
+        for i in 1..candles.len() {
+            let (c, bar) = (candles[i], bars[i]);
+            assert_eq!(bar.open, (bars[i - 1].open + bars[i - 1].close) / 2.0);
+            assert_eq!(bar.close, (c.open + c.high + c.low + c.close) / 4.0);
+            assert!(bar.high >= bar.open.max(bar.close) && bar.high >= c.high);
+            assert!(bar.low <= bar.open.min(bar.close) && bar.low <= c.low);
+            assert_eq!((bar.timestamp, bar.volume), (c.timestamp, c.volume));
+        }
+    }
+
+    #[test]
+    fn test_heikin_ashi_extends_range_to_synthetic_open() {
+        // A gap down leaves the synthetic open above the bar's own high
+        let candles = [
+            Candle::new(0, 100.0, 101.0, 99.0, 100.0, 0.0),
+            Candle::new(1, 90.0, 91.0, 89.0, 90.0, 0.0),
+        ];
+        let bars = HeikinAshi::new().transform(&candles);
+        assert_eq!(bars[1].open, 100.0);
+        assert_eq!(bars[1].high, 100.0);
+        assert_eq!(bars[1].low, 89.0);
+    }
+
+    #[test]
+    fn test_heikin_ashi_streaming_matches_batch() {
+        let candles = candles();
+        let mut heikin_ashi = HeikinAshi::new();
+        let batch = heikin_ashi.transform(&candles);
+        let streamed: Vec<Candle> = candles.iter().map(|&c| heikin_ashi.next(c)).collect();
+        assert_eq!(batch, streamed);
+
+        heikin_ashi.reset();
+        assert_eq!(heikin_ashi.next(candles[0]), batch[0]);
+        assert!(HeikinAshi::new().transform(&[]).is_empty());
+    }
+}
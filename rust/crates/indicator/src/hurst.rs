@@ -0,0 +1,357 @@
+//! Rolling Hurst exponent by rescaled-range or detrended fluctuation analysis
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Smallest sub-series length used in the scaling regression
+const MIN_SCALE: usize = 8;
+
+/// Estimation method for [`Hurst`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HurstMethod {
+    /// Classic rescaled-range (R/S) analysis
+    RescaledRange,
+    /// Detrended fluctuation analysis (DFA-1, linear detrending)
+    Dfa,
+}
+
+/// Rolling Hurst exponent of a price series
+///
+/// Estimates how the spread of log returns grows with the horizon over the
+/// last `window` returns. The returns are split into non-overlapping blocks
+/// of 8, 16, 32, … bars (at least two blocks per scale), a fluctuation
+/// measure is averaged per scale, and the exponent is the slope of its log
+/// against the log of the block length:
+///
+/// * [`HurstMethod::RescaledRange`]: range of the block's cumulative
+///   mean-adjusted returns divided by their standard deviation
+/// * [`HurstMethod::Dfa`]: root-mean-square residual of the integrated
+///   series around a per-block least-squares line
+///
+/// `H ≈ 0.5` indicates a random walk, `H > 0.5` a persistent (trending)
+/// series and `H < 0.5` an anti-persistent (mean-reverting) one. R/S is
+/// biased upward on short windows, so compare its readings against the
+/// same method on a benchmark rather than against 0.5 exactly.
+///
+/// Every bar re-runs the estimate over the window, which is O(window · log window).
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Hurst, HurstMethod};
+///
+/// // Zig-zagging prices: every move is reversed on the next bar
+/// let prices: Vec<f64> = (0..80)
+///     .map(|i| 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 } * (1.0 + (i % 3) as f64 * 0.1))
+///     .collect();
+///
+/// let hurst = Hurst::new(64, HurstMethod::Dfa)?;
+/// let result = hurst.calculate(&prices)?;
+///
+/// assert!(result[63].is_none());
+/// assert!(result[79].unwrap() < 0.5);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hurst {
+    window: usize,
+    method: HurstMethod,
+    prev_price: Option<f64>,
+    /// Log returns in the window, oldest first
+    returns: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Hurst {
+    /// Creates a new rolling Hurst exponent estimator
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of returns per estimate (must be >= 32, so that at
+    ///   least two scales are available)
+    /// * `method` - Rescaled-range or DFA estimation
+    pub fn new(window: usize, method: HurstMethod) -> Result<Self, IndicatorError> {
+        if window < 4 * MIN_SCALE {
+            return Err(IndicatorError::InvalidParameter {
+                field: "window",
+                value: window as f64,
+                constraint: ">= 32",
+            });
+        }
+
+        Ok(Self {
+            window,
+            method,
+            prev_price: None,
+            returns: VecDeque::with_capacity(window),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling Hurst exponent for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `window` values
+    /// are `None`, since `window` returns need `window + 1` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.window + 1, prices.len())?;
+
+        let mut state = Self::new(self.window, self.method)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until the window is full, and also for a window of
+    /// constant returns, which has no scaling behaviour to measure.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let prev = self.prev_price.replace(price)?;
+        if self.returns.len() == self.window {
+            self.returns.pop_front();
+        }
+        self.returns.push_back((price / prev).ln());
+        if self.returns.len() < self.window {
+            return None;
+        }
+
+        let returns = self.returns.make_contiguous();
+        match self.method {
+            HurstMethod::RescaledRange => rescaled_range(returns),
+            HurstMethod::Dfa => dfa(returns),
+        }
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the state so the estimator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.prev_price = None;
+        self.returns.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the number of returns per estimate
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the estimation method
+    pub fn method(&self) -> HurstMethod {
+        self.method
+    }
+}
+
+/// Block lengths 8, 16, 32, … leaving at least two blocks each
+fn scales(len: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(MIN_SCALE), |&n| Some(n * 2)).take_while(move |&n| 2 * n <= len)
+}
+
+/// Slope of the log-log fit of a fluctuation measure against block length
+fn scaling_exponent(
+    returns: &[f64],
+    fluctuation: impl Fn(&[f64], usize) -> Option<f64>,
+) -> Option<f64> {
+    let points: Vec<(f64, f64)> = scales(returns.len())
+        .filter_map(|n| {
+            let value = fluctuation(returns, n)?;
+            (value > 0.0).then(|| ((n as f64).ln(), value.ln()))
+        })
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / count;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / count;
+    let (sxy, sxx) = points.iter().fold((0.0, 0.0), |(sxy, sxx), &(x, y)| {
+        (
+            sxy + (x - mean_x) * (y - mean_y),
+            sxx + (x - mean_x).powi(2),
+        )
+    });
+    Some(sxy / sxx)
+}
+
+fn rescaled_range(returns: &[f64]) -> Option<f64> {
+    scaling_exponent(returns, |returns, n| {
+        let ratios: Vec<f64> = returns
+            .chunks_exact(n)
+            .filter_map(|block| {
+                let mean = block.iter().sum::<f64>() / n as f64;
+                let (mut cumulative, mut high, mut low) = (0.0, 0.0_f64, 0.0_f64);
+                let mut sum_sq = 0.0;
+                for x in block {
+                    cumulative += x - mean;
+                    high = high.max(cumulative);
+                    low = low.min(cumulative);
+                    sum_sq += (x - mean).powi(2);
+                }
+                let std = (sum_sq / n as f64).sqrt();
+                (std > 0.0).then(|| (high - low) / std)
+            })
+            .collect();
+        (!ratios.is_empty()).then(|| ratios.iter().sum::<f64>() / ratios.len() as f64)
+    })
+}
+
+fn dfa(returns: &[f64]) -> Option<f64> {
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let profile: Vec<f64> = returns
+        .iter()
+        .scan(0.0, |sum, x| {
+            *sum += x - mean;
+            Some(*sum)
+        })
+        .collect();
+
+    scaling_exponent(&profile, |profile, n| {
+        // Least-squares line over x = 0..n in each block
+        let nf = n as f64;
+        let mean_x = (nf - 1.0) / 2.0;
+        let sxx = nf * (nf * nf - 1.0) / 12.0;
+        let mut sum_sq = 0.0;
+        let mut count = 0;
+        for block in profile.chunks_exact(n) {
+            let mean_y = block.iter().sum::<f64>() / nf;
+            let sxy: f64 = block
+                .iter()
+                .enumerate()
+                .map(|(x, y)| (x as f64 - mean_x) * (y - mean_y))
+                .sum();
+            let slope = sxy / sxx;
+            sum_sq += block
+                .iter()
+                .enumerate()
+                .map(|(x, y)| (y - mean_y - slope * (x as f64 - mean_x)).powi(2))
+                .sum::<f64>();
+            count += n;
+        }
+        Some((sum_sq / count as f64).sqrt())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random shocks in [−0.5, 0.5)
+    fn shocks(len: usize) -> Vec<f64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect()
+    }
+
+    fn prices_from(returns: &[f64]) -> Vec<f64> {
+        std::iter::once(100.0)
+            .chain(returns.iter().scan(100.0, |price, r| {
+                *price *= (r * 0.01).exp();
+                Some(*price)
+            }))
+            .collect()
+    }
+
+    fn last_estimate(prices: &[f64], method: HurstMethod) -> f64 {
+        let hurst = Hurst::new(prices.len() - 1, method).unwrap();
+        hurst
+            .calculate(prices)
+            .unwrap()
+            .last()
+            .copied()
+            .flatten()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_random_walk_near_one_half() {
+        let prices = prices_from(&shocks(512));
+        let dfa = last_estimate(&prices, HurstMethod::Dfa);
+        let rs = last_estimate(&prices, HurstMethod::RescaledRange);
+        assert!((0.35..0.65).contains(&dfa), "DFA {dfa}");
+        assert!((0.4..0.75).contains(&rs), "R/S {rs}");
+    }
+
+    #[test]
+    fn test_persistent_and_anti_persistent_series() {
+        let noise = shocks(512);
+        // AR(1) returns with strong positive autocorrelation
+        let persistent: Vec<f64> = noise
+            .iter()
+            .scan(0.0, |prev, e| {
+                *prev = 0.9 * *prev + e;
+                Some(*prev)
+            })
+            .collect();
+        // Returns that mostly undo the previous move
+        let reverting: Vec<f64> = noise
+            .iter()
+            .scan(0.0, |prev, e| {
+                *prev = -0.8 * *prev + e;
+                Some(*prev)
+            })
+            .collect();
+
+        for method in [HurstMethod::Dfa, HurstMethod::RescaledRange] {
+            let trending = last_estimate(&prices_from(&persistent), method);
+            let mean_reverting = last_estimate(&prices_from(&reverting), method);
+            assert!(trending > 0.65, "{method:?} trending {trending}");
+            assert!(
+                mean_reverting < 0.4,
+                "{method:?} reverting {mean_reverting}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let prices = prices_from(&shocks(80));
+        let mut hurst = Hurst::new(40, HurstMethod::RescaledRange).unwrap();
+        let batch = hurst.calculate(&prices).unwrap();
+        let streamed: Vec<Option<f64>> = prices.iter().map(|&p| hurst.next(p)).collect();
+        assert_eq!(batch, streamed);
+        assert!(batch[39].is_none() && batch[40].is_some());
+
+        hurst.reset();
+        assert_eq!(hurst.next(100.0), None);
+
+        assert!(Hurst::new(31, HurstMethod::Dfa).is_err());
+        assert!(Hurst::new(32, HurstMethod::Dfa)
+            .unwrap()
+            .calculate(&prices[..32])
+            .is_err());
+        let flat = Hurst::new(32, HurstMethod::Dfa)
+            .unwrap()
+            .calculate(&[50.0; 40])
+            .unwrap();
+        assert!(flat.iter().all(Option::is_none));
+    }
+}
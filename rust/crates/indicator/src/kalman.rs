@@ -0,0 +1,644 @@
+//! Kalman filters for price smoothing and dynamic hedge ratios
+//!
+//! * [`KalmanFilter`] - 1-D local-level filter, an adaptive smoother with no lag
+//!   parameter to tune beyond the noise ratio
+//! * [`KalmanTrend`] - 2-D local-linear-trend filter that also tracks the slope,
+//!   so it follows sustained moves without the lag of a local-level model
+//! * [`KalmanHedgeRatio`] - 2-D filter tracking a time-varying intercept and
+//!   slope between two price series, the usual dynamic hedge ratio for pairs
+//!   trading
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::kalman::KalmanHedgeRatio;
+//!
+//! let x = vec![10.0, 10.5, 10.2, 11.0, 11.4, 11.1, 12.0, 12.3];
+//! let y: Vec<f64> = x.iter().map(|p| 1.5 * p + 2.0).collect();
+//!
+//! let kalman = KalmanHedgeRatio::new(1e-4, 1e-3)?;
+//! let estimates = kalman.calculate(&x, &y)?;
+//!
+//! let last = estimates.last().unwrap().unwrap();
+//! println!("hedge ratio {:.3}, spread z-score {:.2}", last.beta, last.z_score());
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::error::check_equal_lengths;
+use crate::IndicatorError;
+
+/// Covariance matrix of a 2-D state
+type Covariance = [[f64; 2]; 2];
+
+/// Diffuse prior on `[alpha, beta]` so the first observations dominate the estimate
+const INITIAL_COVARIANCE: Covariance = [[1e3, 0.0], [0.0, 1e3]];
+
+/// 1-D local-level Kalman filter
+///
+/// Models the observed series as a random-walk level plus white measurement
+/// noise. The ratio `process_variance / measurement_variance` sets the
+/// smoothing: smaller values follow the level more slowly. A NaN observation
+/// is treated as missing: the filter predicts but does not update, and
+/// returns `None`.
+#[derive(Debug, Clone)]
+pub struct KalmanFilter {
+    process_variance: f64,
+    measurement_variance: f64,
+    /// Current level estimate and its variance
+    state: Option<(f64, f64)>,
+    /// State before the last `next`, or `None` when there is nothing to roll back
+    undo: Option<Option<(f64, f64)>>,
+}
+
+impl KalmanFilter {
+    /// Creates a new 1-D Kalman filter
+    ///
+    /// # Arguments
+    ///
+    /// * `process_variance` - Variance of the level's step-to-step change (must be >= 0)
+    /// * `measurement_variance` - Variance of the observation noise (must be > 0)
+    pub fn new(process_variance: f64, measurement_variance: f64) -> Result<Self, IndicatorError> {
+        if process_variance.is_nan() || process_variance < 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "process_variance",
+                value: process_variance,
+                constraint: ">= 0",
+            });
+        }
+        if measurement_variance.is_nan() || measurement_variance <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "measurement_variance",
+                value: measurement_variance,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            process_variance,
+            measurement_variance,
+            state: None,
+            undo: None,
+        })
+    }
+
+    /// Filters a series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `values` is empty.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        if values.is_empty() {
+            return Err(IndicatorError::InsufficientData {
+                required: 1,
+                actual: 0,
+            });
+        }
+        let mut state = Self::new(self.process_variance, self.measurement_variance)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Processes the next observation, returning the filtered level
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.undo = Some(self.state);
+        let (level, variance) = match self.state {
+            // The first observation initializes the level with measurement uncertainty
+            None if value.is_nan() => return None,
+            None => (value, self.measurement_variance),
+            Some((level, variance)) => {
+                let predicted = variance + self.process_variance;
+                if value.is_nan() {
+                    self.state = Some((level, predicted));
+                    return None;
+                }
+                let gain = predicted / (predicted + self.measurement_variance);
+                (level + gain * (value - level), (1.0 - gain) * predicted)
+            }
+        };
+        self.state = Some((level, variance));
+        Some(level)
+    }
+
+    /// Current level estimate
+    pub fn level(&self) -> Option<f64> {
+        self.state.map(|(level, _)| level)
+    }
+
+    /// Variance of the current level estimate
+    pub fn variance(&self) -> Option<f64> {
+        self.state.map(|(_, variance)| variance)
+    }
+
+    /// Replaces the most recent observation, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest observation can be undone; further calls do nothing
+    /// until the next observation arrives.
+    pub fn rollback(&mut self) {
+        if let Some(state) = self.undo.take() {
+            self.state = state;
+        }
+    }
+
+    /// Resets the filter state
+    pub fn reset(&mut self) {
+        self.state = None;
+        self.undo = None;
+    }
+}
+
+/// Level and slope estimate of a [`KalmanTrend`] filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrendEstimate {
+    /// Filtered level
+    pub level: f64,
+    /// Filtered per-bar slope of the level
+    pub slope: f64,
+}
+
+/// 2-D local-linear-trend Kalman filter
+///
+/// Models the series as a level that moves by a slope each bar, where both
+/// level and slope take random-walk steps with variances `level_variance`
+/// and `slope_variance`, observed with white noise of variance
+/// `measurement_variance`. Because the slope is part of the state, a steady
+/// trend is tracked without the lag of [`KalmanFilter`]; a small
+/// `slope_variance` gives a smooth trend estimate. A NaN observation is
+/// treated as missing: the filter predicts but does not update, and returns
+/// `None`.
+#[derive(Debug, Clone)]
+pub struct KalmanTrend {
+    level_variance: f64,
+    slope_variance: f64,
+    measurement_variance: f64,
+    /// State `[level, slope]`, or `None` before the first observation
+    state: Option<[f64; 2]>,
+    /// State covariance
+    covariance: Covariance,
+    /// State and covariance before the last `next`, or `None` when there is
+    /// nothing to roll back
+    undo: Option<(Option<[f64; 2]>, Covariance)>,
+}
+
+impl KalmanTrend {
+    /// Creates a new local-linear-trend Kalman filter
+    ///
+    /// # Arguments
+    ///
+    /// * `level_variance` - Variance of the level's step-to-step noise (must be >= 0)
+    /// * `slope_variance` - Variance of the slope's step-to-step change (must be >= 0)
+    /// * `measurement_variance` - Variance of the observation noise (must be > 0)
+    pub fn new(
+        level_variance: f64,
+        slope_variance: f64,
+        measurement_variance: f64,
+    ) -> Result<Self, IndicatorError> {
+        for (field, value) in [
+            ("level_variance", level_variance),
+            ("slope_variance", slope_variance),
+        ] {
+            if value.is_nan() || value < 0.0 {
+                return Err(IndicatorError::InvalidParameter {
+                    field,
+                    value,
+                    constraint: ">= 0",
+                });
+            }
+        }
+        if measurement_variance.is_nan() || measurement_variance <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "measurement_variance",
+                value: measurement_variance,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            level_variance,
+            slope_variance,
+            measurement_variance,
+            state: None,
+            covariance: [[0.0; 2]; 2],
+            undo: None,
+        })
+    }
+
+    /// Filters a series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `values` is empty.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<TrendEstimate>>, IndicatorError> {
+        if values.is_empty() {
+            return Err(IndicatorError::InsufficientData {
+                required: 1,
+                actual: 0,
+            });
+        }
+        let mut state = Self::new(
+            self.level_variance,
+            self.slope_variance,
+            self.measurement_variance,
+        )?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Processes the next observation, returning the filtered level and slope
+    pub fn next(&mut self, value: f64) -> Option<TrendEstimate> {
+        self.undo = Some((self.state, self.covariance));
+        let Some([level, slope]) = self.state else {
+            if value.is_nan() {
+                return None;
+            }
+            // Level starts at the observation; the slope prior is diffuse
+            self.state = Some([value, 0.0]);
+            self.covariance = [
+                [self.measurement_variance, 0.0],
+                [0.0, INITIAL_COVARIANCE[1][1]],
+            ];
+            return self.estimate();
+        };
+
+        // Predict with F = [[1, 1], [0, 1]]: P ← F·P·Fᵀ + Q
+        let p = self.covariance;
+        let p00 = p[0][0] + p[0][1] + p[1][0] + p[1][1] + self.level_variance;
+        let p01 = p[0][1] + p[1][1];
+        let p11 = p[1][1] + self.slope_variance;
+        let predicted = [level + slope, slope];
+        self.covariance = [[p00, p01], [p01, p11]];
+        self.state = Some(predicted);
+        if value.is_nan() {
+            return None;
+        }
+
+        // Update with H = [1, 0]
+        let innovation = value - predicted[0];
+        let innovation_variance = p00 + self.measurement_variance;
+        let gain = [p00 / innovation_variance, p01 / innovation_variance];
+        self.state = Some([
+            predicted[0] + gain[0] * innovation,
+            predicted[1] + gain[1] * innovation,
+        ]);
+        self.covariance = [
+            [(1.0 - gain[0]) * p00, (1.0 - gain[0]) * p01],
+            [(1.0 - gain[0]) * p01, p11 - gain[1] * p01],
+        ];
+        self.estimate()
+    }
+
+    /// Current level and slope estimate
+    pub fn estimate(&self) -> Option<TrendEstimate> {
+        self.state
+            .map(|[level, slope]| TrendEstimate { level, slope })
+    }
+
+    /// Replaces the most recent observation, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<TrendEstimate> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest observation can be undone; further calls do nothing
+    /// until the next observation arrives.
+    pub fn rollback(&mut self) {
+        if let Some((state, covariance)) = self.undo.take() {
+            self.state = state;
+            self.covariance = covariance;
+        }
+    }
+
+    /// Resets the filter state
+    pub fn reset(&mut self) {
+        self.state = None;
+        self.covariance = [[0.0; 2]; 2];
+        self.undo = None;
+    }
+}
+
+/// Dynamic hedge-ratio estimate for one observation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeEstimate {
+    /// Estimated hedge ratio (slope of `y` on `x`)
+    pub beta: f64,
+    /// Estimated intercept
+    pub alpha: f64,
+    /// One-step forecast error `y − (alpha + beta·x)` using the prior estimate
+    pub spread: f64,
+    /// Standard deviation of the forecast error
+    pub spread_std: f64,
+}
+
+impl HedgeEstimate {
+    /// Forecast error in standard deviations, the usual pairs entry/exit signal
+    pub fn z_score(&self) -> f64 {
+        self.spread / self.spread_std
+    }
+}
+
+/// 2-D Kalman filter for a time-varying regression `y = alpha + beta·x`
+///
+/// The state `[alpha, beta]` follows a random walk with covariance
+/// `delta / (1 − delta) · I`, and observations carry noise with variance
+/// `measurement_variance` (the formulation popularized by E. Chan). Larger
+/// `delta` lets the hedge ratio adapt faster.
+#[derive(Debug, Clone)]
+pub struct KalmanHedgeRatio {
+    delta: f64,
+    measurement_variance: f64,
+    /// State `[alpha, beta]`
+    state: [f64; 2],
+    /// State covariance
+    covariance: Covariance,
+    /// State and covariance before the last `next`, or `None` when there is
+    /// nothing to roll back
+    undo: Option<([f64; 2], Covariance)>,
+}
+
+impl KalmanHedgeRatio {
+    /// Creates a new dynamic hedge-ratio estimator
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - State drift parameter (must be in (0, 1))
+    /// * `measurement_variance` - Variance of the regression residual (must be > 0)
+    pub fn new(delta: f64, measurement_variance: f64) -> Result<Self, IndicatorError> {
+        if delta.is_nan() || delta <= 0.0 || delta >= 1.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "delta",
+                value: delta,
+                constraint: "in (0, 1)",
+            });
+        }
+        if measurement_variance.is_nan() || measurement_variance <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "measurement_variance",
+                value: measurement_variance,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            delta,
+            measurement_variance,
+            state: [0.0; 2],
+            covariance: INITIAL_COVARIANCE,
+            undo: None,
+        })
+    }
+
+    /// Estimates hedge ratios for aligned price series on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if they are empty.
+    pub fn calculate(
+        &self,
+        x: &[f64],
+        y: &[f64],
+    ) -> Result<Vec<Option<HedgeEstimate>>, IndicatorError> {
+        let len = check_equal_lengths(&[("x", x.len()), ("y", y.len())])?;
+        if len == 0 {
+            return Err(IndicatorError::InsufficientData {
+                required: 1,
+                actual: 0,
+            });
+        }
+        let mut state = Self::new(self.delta, self.measurement_variance)?;
+        Ok(x.iter().zip(y).map(|(&x, &y)| state.next(x, y)).collect())
+    }
+
+    /// Processes the next pair of prices
+    ///
+    /// Returns `None` (and only predicts) if either price is NaN.
+    pub fn next(&mut self, x: f64, y: f64) -> Option<HedgeEstimate> {
+        self.undo = Some((self.state, self.covariance));
+        // Predict: the state is a random walk, so only the covariance grows
+        let drift = self.delta / (1.0 - self.delta);
+        let mut p = self.covariance;
+        p[0][0] += drift;
+        p[1][1] += drift;
+        self.covariance = p;
+        if x.is_nan() || y.is_nan() {
+            return None;
+        }
+
+        // Observation vector h = [1, x]
+        let forecast = self.state[0] + self.state[1] * x;
+        let spread = y - forecast;
+        let ph = [p[0][0] + p[0][1] * x, p[1][0] + p[1][1] * x];
+        let spread_variance = ph[0] + ph[1] * x + self.measurement_variance;
+        let gain = [ph[0] / spread_variance, ph[1] / spread_variance];
+
+        self.state[0] += gain[0] * spread;
+        self.state[1] += gain[1] * spread;
+        // P ← P − K·hᵀP, with hᵀP = phᵀ because P is symmetric
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell -= gain[i] * ph[j];
+            }
+        }
+
+        Some(HedgeEstimate {
+            beta: self.state[1],
+            alpha: self.state[0],
+            spread,
+            spread_std: spread_variance.sqrt(),
+        })
+    }
+
+    /// Replaces the most recent pair of prices, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, x: f64, y: f64) -> Option<HedgeEstimate> {
+        self.rollback();
+        self.next(x, y)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest pair can be undone; further calls do nothing until the
+    /// next pair arrives.
+    pub fn rollback(&mut self) {
+        if let Some((state, covariance)) = self.undo.take() {
+            self.state = state;
+            self.covariance = covariance;
+        }
+    }
+
+    /// Resets the estimator state
+    pub fn reset(&mut self) {
+        self.state = [0.0; 2];
+        self.covariance = INITIAL_COVARIANCE;
+        self.undo = None;
+    }
+
+    /// Returns the state drift parameter
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kalman_filter_smooths_noise() {
+        let filter = KalmanFilter::new(1e-4, 1.0).unwrap();
+        let noisy: Vec<f64> = (0..200)
+            .map(|i| 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let filtered = filter.calculate(&noisy).unwrap();
+
+        assert_eq!(filtered[0], Some(101.0));
+        let last = filtered.last().unwrap().unwrap();
+        assert!((last - 100.0).abs() < 0.2, "level {}", last);
+    }
+
+    #[test]
+    fn test_kalman_filter_missing_observations() {
+        let mut filter = KalmanFilter::new(0.1, 1.0).unwrap();
+        assert_eq!(filter.next(f64::NAN), None);
+        assert_eq!(filter.next(10.0), Some(10.0));
+
+        let variance = filter.variance().unwrap();
+        assert_eq!(filter.next(f64::NAN), None);
+        assert_eq!(filter.level(), Some(10.0));
+        assert!(filter.variance().unwrap() > variance);
+
+        filter.rollback();
+        assert_eq!(filter.variance(), Some(variance));
+        let expected = filter.clone().next(12.0);
+        filter.next(20.0);
+        assert_eq!(filter.update_last(12.0), expected);
+
+        filter.reset();
+        assert_eq!(filter.level(), None);
+        assert!(KalmanFilter::new(0.1, 0.0).is_err());
+        assert!(filter.calculate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_hedge_ratio_converges() {
+        let x: Vec<f64> = (0..300)
+            .map(|i| 20.0 + 10.0 * (i as f64 * 0.1).sin())
+            .collect();
+        let y: Vec<f64> = x.iter().map(|p| 0.8 * p + 3.0).collect();
+        let estimates = KalmanHedgeRatio::new(1e-5, 1e-4)
+            .unwrap()
+            .calculate(&x, &y)
+            .unwrap();
+
+        let last = estimates.last().unwrap().unwrap();
+        assert!((last.beta - 0.8).abs() < 1e-2, "beta {}", last.beta);
+        assert!((last.alpha - 3.0).abs() < 0.5, "alpha {}", last.alpha);
+        assert!(last.spread.abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_hedge_ratio_and_trend_update_last() {
+        let mut hedge = KalmanHedgeRatio::new(1e-4, 1e-3).unwrap();
+        hedge.next(20.0, 19.0);
+        let expected = hedge.clone().next(21.0, 19.8);
+        hedge.next(30.0, 5.0);
+        assert_eq!(hedge.update_last(21.0, 19.8), expected);
+
+        let mut trend = KalmanTrend::new(0.01, 0.001, 1.0).unwrap();
+        trend.next(10.0);
+        let expected = trend.clone().next(12.0);
+        trend.next(50.0);
+        assert_eq!(trend.update_last(12.0), expected);
+        trend.rollback();
+        trend.rollback();
+        assert_eq!(trend.next(12.0), expected);
+    }
+
+    #[test]
+    fn test_hedge_ratio_tracks_regime_change() {
+        let mut kalman = KalmanHedgeRatio::new(1e-3, 1e-3).unwrap();
+        let mut last = None;
+        for i in 0..400 {
+            let x = 20.0 + 10.0 * (i as f64 * 0.3).cos();
+            let beta = if i < 200 { 1.0 } else { 2.0 };
+            last = kalman.next(x, beta * x);
+        }
+        assert!((last.unwrap().beta - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_hedge_ratio_invalid_inputs() {
+        assert!(KalmanHedgeRatio::new(0.0, 1.0).is_err());
+        assert!(KalmanHedgeRatio::new(1.0, 1.0).is_err());
+        assert!(KalmanHedgeRatio::new(0.5, -1.0).is_err());
+
+        let kalman = KalmanHedgeRatio::new(1e-4, 1e-3).unwrap();
+        assert!(matches!(
+            kalman.calculate(&[1.0, 2.0], &[1.0]),
+            Err(IndicatorError::LengthMismatch { field: "y", .. })
+        ));
+    }
+
+    #[test]
+    fn test_kalman_trend_tracks_ramp_without_lag() {
+        // Noisy ramp rising 0.5 per bar
+        let ramp: Vec<f64> = (0..300)
+            .map(|i| 50.0 + 0.5 * i as f64 + if i % 2 == 0 { 0.8 } else { -0.8 })
+            .collect();
+        let trend = KalmanTrend::new(1e-4, 1e-6, 1.0).unwrap();
+        let level = KalmanFilter::new(1e-4, 1.0).unwrap();
+        let trend = trend.calculate(&ramp).unwrap();
+        let level = level.calculate(&ramp).unwrap();
+
+        let truth = 50.0 + 0.5 * 299.0;
+        let last = trend.last().unwrap().unwrap();
+        assert!((last.slope - 0.5).abs() < 0.01, "slope {}", last.slope);
+        assert!((last.level - truth).abs() < 0.5, "level {}", last.level);
+        // The local-level filter falls well behind the trend
+        assert!(truth - level.last().unwrap().unwrap() > 5.0);
+    }
+
+    #[test]
+    fn test_kalman_trend_missing_observations_and_reset() {
+        let mut trend = KalmanTrend::new(0.01, 0.001, 1.0).unwrap();
+        assert_eq!(trend.next(f64::NAN), None);
+        assert_eq!(
+            trend.next(10.0),
+            Some(TrendEstimate {
+                level: 10.0,
+                slope: 0.0
+            })
+        );
+        // The diffuse slope prior picks up the first difference almost fully
+        let second = trend.next(12.0).unwrap();
+        assert!((second.slope - 2.0).abs() < 0.1, "slope {}", second.slope);
+
+        // A missing bar extrapolates the trend
+        assert_eq!(trend.next(f64::NAN), None);
+        let predicted = trend.estimate().unwrap();
+        assert!((predicted.level - (second.level + second.slope)).abs() < 1e-12);
+
+        let mut streamed = KalmanTrend::new(0.01, 0.001, 1.0).unwrap();
+        let values = [1.0, 2.5, f64::NAN, 4.0, 5.5];
+        let batch = streamed.calculate(&values).unwrap();
+        let online: Vec<_> = values.iter().map(|&v| streamed.next(v)).collect();
+        assert_eq!(batch, online);
+
+        trend.reset();
+        assert_eq!(trend.estimate(), None);
+        assert!(KalmanTrend::new(-1.0, 0.0, 1.0).is_err());
+        assert!(KalmanTrend::new(0.0, f64::NAN, 1.0).is_err());
+        assert!(KalmanTrend::new(0.0, 0.0, 0.0).is_err());
+        assert!(trend.calculate(&[]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Kaufman Adaptive Moving Average (KAMA)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Kaufman Adaptive Moving Average indicator
+///
+/// An exponential average whose smoothing constant follows the efficiency
+/// ratio of the last `period` price changes, so it tracks closely in clean
+/// trends and flattens out in noise:
+///
+/// ER = |P(t) − P(t−period)| / Σ|P(i) − P(i−1)|
+/// SC = (ER × (fast_sc − slow_sc) + slow_sc)²
+/// KAMA(t) = KAMA(t−1) + SC × (P(t) − KAMA(t−1))
+///
+/// where `fast_sc = 2 / (fast + 1)` and `slow_sc = 2 / (slow + 1)`. The
+/// average is seeded with the price at bar `period − 1`, so the first value
+/// is available at bar `period`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::KAMA;
+///
+/// let prices = [10.0, 10.5, 11.0, 11.5, 12.0, 12.5, 13.0];
+/// let result = KAMA::new(3, 2, 30)?.calculate(&prices)?;
+///
+/// assert!(result[2].is_none());
+/// // A perfectly efficient trend uses the fast constant
+/// assert!((result[3].unwrap() - (11.0 + (2.0 / 3.0_f64).powi(2) * 0.5)).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct KAMA {
+    period: usize,
+    fast_sc: f64,
+    slow_sc: f64,
+    /// The last `period + 1` prices, oldest first
+    window: VecDeque<f64>,
+    value: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl KAMA {
+    /// Creates a new KAMA indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Efficiency-ratio lookback (must be > 0, typically 10)
+    /// * `fast` - Fastest EMA period (must be > 0, typically 2)
+    /// * `slow` - Slowest EMA period (must be > `fast`, typically 30)
+    pub fn new(period: usize, fast: usize, slow: usize) -> Result<Self, IndicatorError> {
+        for (field, value) in [("period", period), ("fast", fast)] {
+            if value == 0 {
+                return Err(IndicatorError::InvalidParameter {
+                    field,
+                    value: value as f64,
+                    constraint: "> 0",
+                });
+            }
+        }
+        if slow <= fast {
+            return Err(IndicatorError::InvalidParameter {
+                field: "slow",
+                value: slow as f64,
+                constraint: "> fast",
+            });
+        }
+
+        Ok(Self {
+            period,
+            fast_sc: 2.0 / (fast as f64 + 1.0),
+            slow_sc: 2.0 / (slow as f64 + 1.0),
+            window: VecDeque::with_capacity(period + 1),
+            value: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates KAMA for a batch of prices on a fresh state
+    ///
+    /// Returns one value per input; the first `period` entries are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period + 1, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period + 1` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period + 1 {
+            self.window.pop_front();
+        }
+        self.window.push_back(price);
+        if self.window.len() <= self.period {
+            return None;
+        }
+
+        let change = (price - self.window[0]).abs();
+        let volatility: f64 = self
+            .window
+            .iter()
+            .zip(self.window.iter().skip(1))
+            .map(|(prev, curr)| (curr - prev).abs())
+            .sum();
+        let efficiency = if volatility > 0.0 {
+            change / volatility
+        } else {
+            0.0
+        };
+        let sc = (efficiency * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+
+        let prev = self.value.unwrap_or(self.window[self.period - 1]);
+        let kama = prev + sc * (price - prev);
+        self.value = Some(kama);
+        self.value
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Current KAMA value
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.value = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the efficiency-ratio period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kama_choppy_market_uses_slow_constant() {
+        // Alternating prices: net change zero, efficiency ratio zero
+        let prices = [10.0, 11.0, 10.0, 11.0, 10.0];
+        let result = KAMA::new(4, 2, 30).unwrap().calculate(&prices).unwrap();
+
+        let slow_sc: f64 = 2.0 / 31.0;
+        let expected = 11.0 + slow_sc.powi(2) * (10.0 - 11.0);
+        assert!((result[4].unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_kama_tracks_trend_faster_than_noise() {
+        let trend: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        let noise: Vec<f64> = (0..30)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 101.0 } + i as f64 * 0.1)
+            .collect();
+        let kama = KAMA::new(10, 2, 30).unwrap();
+
+        // Share of the price move since the first value that KAMA followed
+        let followed = |prices: &[f64]| {
+            let result = kama.calculate(prices).unwrap();
+            (result[29].unwrap() - result[10].unwrap()) / (prices[29] - prices[10])
+        };
+        assert!(followed(&trend) > 0.9);
+        assert!(followed(&noise) < 0.5);
+    }
+
+    #[test]
+    fn test_kama_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..25).map(|i| 50.0 + (i as f64 * 0.4).sin()).collect();
+        let kama = KAMA::new(5, 2, 20).unwrap();
+        let batch = kama.calculate(&prices).unwrap();
+
+        let mut streaming = kama.clone();
+        let streamed: Vec<_> = prices.iter().map(|&p| streaming.next(p)).collect();
+        assert_eq!(batch, streamed);
+        assert_eq!(streaming.value(), batch[24]);
+
+        streaming.reset();
+        assert_eq!(streaming.next(1.0), None);
+    }
+
+    #[test]
+    fn test_kama_invalid_inputs() {
+        assert!(KAMA::new(0, 2, 30).is_err());
+        assert!(KAMA::new(10, 0, 30).is_err());
+        assert!(matches!(
+            KAMA::new(10, 30, 30),
+            Err(IndicatorError::InvalidParameter { field: "slow", .. })
+        ));
+        assert!(KAMA::new(10, 2, 30).unwrap().calculate(&[1.0; 10]).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+//! Klinger Volume Oscillator (KVO)
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, EMA};
+
+/// Klinger Volume Oscillator values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KlingerOutput {
+    /// Fast EMA minus slow EMA of volume force
+    pub kvo: f64,
+    /// EMA of the KVO line; `None` during its warmup
+    pub signal: Option<f64>,
+}
+
+/// Klinger Volume Oscillator indicator
+///
+/// # Formula
+///
+/// Trend = +1 if H + L + C rose from the previous bar, otherwise −1
+/// DM = High − Low
+/// CM = CM_prev + DM while the trend holds, DM_prev + DM when it flips
+/// VF = Volume × |2 × (DM / CM − 1)| × Trend × 100
+/// KVO = EMA_fast(VF) − EMA_slow(VF)
+/// Signal = EMA_signal(KVO)
+///
+/// Volume force starts on the second bar, which has a trend to compare
+/// against; a bar with no cumulative range contributes zero. The EMAs are
+/// seeded like [`EMA::calculate`], so the first KVO appears at bar
+/// `slow_period`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Candle, KlingerVolumeOscillator};
+///
+/// let candles: Vec<Candle> = (0..80)
+///     .map(|i| {
+///         let base = 100.0 + (i as f64 * 0.2).sin() * 5.0;
+///         Candle::new(i, base, base + 1.0, base - 1.0, base, 10_000.0)
+///     })
+///     .collect();
+///
+/// let result = KlingerVolumeOscillator::new(34, 55, 13)?.calculate_candles(&candles)?;
+/// assert!(result[54].is_none());
+/// assert!(result[55].unwrap().signal.is_none());
+/// assert!(result[67].unwrap().signal.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct KlingerVolumeOscillator {
+    fast: EMA,
+    slow: EMA,
+    signal: EMA,
+    /// Previous bar's H + L + C and high-low range
+    prev: Option<(f64, f64)>,
+    trend: Option<f64>,
+    cumulative: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl KlingerVolumeOscillator {
+    /// Creates a new Klinger Volume Oscillator
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - Fast volume-force EMA (must be > 0 and < `slow_period`,
+    ///   typically 34)
+    /// * `slow_period` - Slow volume-force EMA (typically 55)
+    /// * `signal_period` - Signal-line EMA of KVO (must be > 0, typically 13)
+    pub fn new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if fast_period >= slow_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "fast_period",
+                value: fast_period as f64,
+                constraint: "< slow_period",
+            });
+        }
+
+        Ok(Self {
+            fast: EMA::new(fast_period)?,
+            slow: EMA::new(slow_period)?,
+            signal: EMA::new(signal_period)?,
+            prev: None,
+            trend: None,
+            cumulative: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates KVO and its signal line for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `slow_period + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+        volume: &[f64],
+    ) -> Result<Vec<Option<KlingerOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+            ("volume", volume.len()),
+        ])?;
+        check_min_length(self.slow.period() + 1, len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i], volume[i]))
+            .collect())
+    }
+
+    /// Calculates KVO and its signal line for a series of candles on a fresh
+    /// state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `slow_period + 1`
+    /// candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<KlingerOutput>>, IndicatorError> {
+        check_min_length(self.slow.period() + 1, candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|c| state.next(c.high, c.low, c.close, c.volume))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64, volume: f64) -> Option<KlingerOutput> {
+        self.undo = Snapshot::of(self);
+        let sum = high + low + close;
+        let range = high - low;
+        let (prev_sum, prev_range) = self.prev.replace((sum, range))?;
+
+        let trend = if sum > prev_sum { 1.0 } else { -1.0 };
+        self.cumulative = if self.trend == Some(trend) {
+            self.cumulative + range
+        } else {
+            prev_range + range
+        };
+        self.trend = Some(trend);
+
+        let force = if self.cumulative > 0.0 {
+            volume * (2.0 * (range / self.cumulative - 1.0)).abs() * trend * 100.0
+        } else {
+            0.0
+        };
+        let fast = self.fast.next(force);
+        let kvo = self
+            .slow
+            .next(force)
+            .zip(fast)
+            .map(|(slow, fast)| fast - slow)?;
+        Some(KlingerOutput {
+            kvo,
+            signal: self.signal.next(kvo),
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<KlingerOutput> {
+        self.rollback();
+        self.next(high, low, close, volume)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.prev = None;
+        self.trend = None;
+        self.cumulative = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the fast, slow and signal periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (self.fast.period(), self.slow.period(), self.signal.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume_force(candles: &[Candle]) -> Vec<f64> {
+        let mut forces = Vec::new();
+        let (mut trend, mut cm) = (0.0, 0.0);
+        for pair in candles.windows(2) {
+            let (prev, bar) = (pair[0], pair[1]);
+            let sum = |c: Candle| c.high + c.low + c.close;
+            let new_trend = if sum(bar) > sum(prev) { 1.0 } else { -1.0 };
+            let dm = bar.high - bar.low;
+            cm = if new_trend == trend {
+                cm + dm
+            } else {
+                prev.high - prev.low + dm
+            };
+            trend = new_trend;
+            forces.push(bar.volume * (2.0 * (dm / cm - 1.0)).abs() * trend * 100.0);
+        }
+        forces
+    }
+
+    fn candles() -> Vec<Candle> {
+        (0..60)
+            .map(|i| {
+                let base = 20.0 + (i as f64 * 0.35).sin() * 2.0;
+                let spread = 0.5 + (i % 3) as f64 * 0.25;
+                let volume = 5_000.0 + (i % 7) as f64 * 400.0;
+                Candle::new(i, base, base + spread, base - spread, base, volume)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_klinger_matches_ema_difference() {
+        let candles = candles();
+        let forces = volume_force(&candles);
+        let fast = EMA::new(5).unwrap().calculate(&forces).unwrap();
+        let slow = EMA::new(10).unwrap().calculate(&forces).unwrap();
+
+        let result = KlingerVolumeOscillator::new(5, 10, 4)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        assert!(result[9].is_none());
+        for i in 10..candles.len() {
+            let expected = fast[i - 1].unwrap() - slow[i - 1].unwrap();
+            assert!((result[i].unwrap().kvo - expected).abs() < 1e-6);
+        }
+        assert!(result[12].unwrap().signal.is_none());
+        assert!(result[13].unwrap().signal.is_some());
+    }
+
+    #[test]
+    fn test_klinger_streaming_matches_batch() {
+        let candles = candles();
+        let mut kvo = KlingerVolumeOscillator::new(6, 12, 3).unwrap();
+        let high: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let low: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let volume: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+        let batch = kvo.calculate(&high, &low, &close, &volume).unwrap();
+        assert_eq!(batch, kvo.calculate_candles(&candles).unwrap());
+
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| kvo.next(c.high, c.low, c.close, c.volume))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        kvo.reset();
+        assert_eq!(kvo.next(1.0, 0.0, 0.5, 1.0), None);
+        assert_eq!(kvo.periods(), (6, 12, 3));
+    }
+
+    #[test]
+    fn test_klinger_invalid_inputs() {
+        assert!(KlingerVolumeOscillator::new(55, 34, 13).is_err());
+        assert!(KlingerVolumeOscillator::new(0, 55, 13).is_err());
+        assert!(KlingerVolumeOscillator::new(34, 55, 0).is_err());
+        let kvo = KlingerVolumeOscillator::new(34, 55, 13).unwrap();
+        assert!(matches!(
+            kvo.calculate_candles(&[Candle::default(); 55]),
+            Err(IndicatorError::InsufficientData {
+                required: 56,
+                actual: 55
+            })
+        ));
+        assert!(kvo
+            .calculate(&[1.0; 56], &[1.0; 56], &[1.0; 56], &[1.0; 55])
+            .is_err());
+    }
+}
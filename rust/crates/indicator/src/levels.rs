@@ -0,0 +1,320 @@
+//! Horizontal support and resistance level detection
+//!
+//! Swing points produced by [`SwingDetector`](crate::SwingDetector) or
+//! [`ZigZag`](crate::ZigZag) are clustered by price: swings whose prices lie within a
+//! percentage tolerance of each other are treated as repeated touches of the
+//! same horizontal level. Each level also spans a zone from its lowest to its
+//! highest touch and is scored by touch count, span and recency, so the
+//! strongest levels can be picked for alerts.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::levels::{LevelDetector, LevelKind};
+//! use indicator::{SwingKind, SwingPoint};
+//!
+//! let swings = vec![
+//!     SwingPoint { kind: SwingKind::High, index: 3, price: 110.0 },
+//!     SwingPoint { kind: SwingKind::Low, index: 8, price: 100.0 },
+//!     SwingPoint { kind: SwingKind::High, index: 14, price: 110.4 },
+//!     SwingPoint { kind: SwingKind::Low, index: 20, price: 100.3 },
+//! ];
+//!
+//! let detector = LevelDetector::new(0.5, 2)?;
+//! let levels = detector.detect(&swings);
+//!
+//! assert_eq!(levels.len(), 2);
+//! assert_eq!(levels[0].kind_at(105.0), LevelKind::Support);
+//! assert_eq!(levels[1].kind_at(105.0), LevelKind::Resistance);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::{IndicatorError, SwingKind, SwingPoint};
+
+/// Role a level plays relative to the current price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelKind {
+    /// Level at or below the current price
+    Support,
+    /// Level above the current price
+    Resistance,
+}
+
+/// A horizontal price level formed by clustered swing points
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    /// Average price of the swings forming the level
+    pub price: f64,
+    /// Number of swings that touched the level
+    pub touches: usize,
+    /// Number of swing highs among the touches
+    pub high_touches: usize,
+    /// Number of swing lows among the touches
+    pub low_touches: usize,
+    /// Bar index of the earliest touch
+    pub first_index: usize,
+    /// Bar index of the most recent touch
+    pub last_index: usize,
+    /// Lowest touch price, the bottom of the level's zone
+    pub zone_low: f64,
+    /// Highest touch price, the top of the level's zone
+    pub zone_high: f64,
+    /// Position of the most recent touch within the swing history, from `0.0`
+    /// (the first swing) to `1.0` (the latest swing)
+    pub recency: f64,
+    /// Strength score: the touch count scaled by up to 2× for levels that
+    /// stayed relevant across the whole swing history, and halved for levels
+    /// not touched since the start of the history
+    pub strength: f64,
+}
+
+impl Level {
+    /// Classifies the level as support or resistance relative to `price`
+    pub fn kind_at(&self, price: f64) -> LevelKind {
+        if self.price <= price {
+            LevelKind::Support
+        } else {
+            LevelKind::Resistance
+        }
+    }
+
+    /// Whether `price` lies inside the level's zone
+    pub fn contains(&self, price: f64) -> bool {
+        (self.zone_low..=self.zone_high).contains(&price)
+    }
+
+    /// Distance from `price` to the nearest edge of the zone, in percent of
+    /// `price`; `0.0` inside the zone
+    pub fn distance_percent(&self, price: f64) -> f64 {
+        let gap = if price < self.zone_low {
+            self.zone_low - price
+        } else if price > self.zone_high {
+            price - self.zone_high
+        } else {
+            0.0
+        };
+        gap / price.abs() * 100.0
+    }
+}
+
+/// Clusters swing points into support/resistance levels
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelDetector {
+    /// Maximum distance from a level, in percent of its price, for a swing to count as a touch
+    tolerance_percent: f64,
+    /// Minimum number of touches for a cluster to be reported as a level
+    min_touches: usize,
+}
+
+impl LevelDetector {
+    /// Creates a new level detector
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance_percent` - Maximum distance, in percent of the level price,
+    ///   for a swing to count as a touch (must be > 0)
+    /// * `min_touches` - Minimum number of touches to report a level (must be > 0)
+    pub fn new(tolerance_percent: f64, min_touches: usize) -> Result<Self, IndicatorError> {
+        if tolerance_percent.is_nan() || tolerance_percent <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "tolerance_percent",
+                value: tolerance_percent,
+                constraint: "> 0",
+            });
+        }
+        if min_touches == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "min_touches",
+                value: min_touches as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            tolerance_percent,
+            min_touches,
+        })
+    }
+
+    /// Detects levels from a set of swing points
+    ///
+    /// # Returns
+    ///
+    /// Levels with at least `min_touches` touches, sorted by ascending price.
+    /// Use [`strongest`] to rank them instead.
+    pub fn detect(&self, swings: &[SwingPoint]) -> Vec<Level> {
+        let mut sorted: Vec<&SwingPoint> = swings.iter().collect();
+        sorted.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        let first_bar = swings.iter().map(|s| s.index).min().unwrap_or(0);
+        let last_bar = swings.iter().map(|s| s.index).max().unwrap_or(0);
+        let horizon = (last_bar - first_bar).max(1) as f64;
+
+        let mut levels = Vec::new();
+        let mut cluster: Vec<&SwingPoint> = Vec::new();
+        let mut cluster_sum = 0.0;
+
+        for swing in sorted {
+            if !cluster.is_empty() {
+                let mean = cluster_sum / cluster.len() as f64;
+                if (swing.price - mean).abs() > mean.abs() * self.tolerance_percent / 100.0 {
+                    levels.extend(self.build_level(&cluster, first_bar, horizon));
+                    cluster.clear();
+                    cluster_sum = 0.0;
+                }
+            }
+            cluster_sum += swing.price;
+            cluster.push(swing);
+        }
+        levels.extend(self.build_level(&cluster, first_bar, horizon));
+
+        levels
+    }
+
+    /// Returns the touch tolerance in percent
+    pub fn tolerance_percent(&self) -> f64 {
+        self.tolerance_percent
+    }
+
+    /// Returns the minimum number of touches for a level
+    pub fn min_touches(&self) -> usize {
+        self.min_touches
+    }
+
+    fn build_level(
+        &self,
+        cluster: &[&SwingPoint],
+        first_bar: usize,
+        horizon: f64,
+    ) -> Option<Level> {
+        if cluster.len() < self.min_touches {
+            return None;
+        }
+
+        let touches = cluster.len();
+        let high_touches = cluster.iter().filter(|s| s.kind == SwingKind::High).count();
+        let first_index = cluster.iter().map(|s| s.index).min()?;
+        let last_index = cluster.iter().map(|s| s.index).max()?;
+        let span = (last_index - first_index) as f64 / horizon;
+        let recency = (last_index - first_bar) as f64 / horizon;
+        let prices = || cluster.iter().map(|s| s.price);
+
+        Some(Level {
+            price: prices().sum::<f64>() / touches as f64,
+            touches,
+            high_touches,
+            low_touches: touches - high_touches,
+            first_index,
+            last_index,
+            zone_low: prices().fold(f64::INFINITY, f64::min),
+            zone_high: prices().fold(f64::NEG_INFINITY, f64::max),
+            recency,
+            strength: touches as f64 * (1.0 + span) * (0.5 + 0.5 * recency),
+        })
+    }
+}
+
+/// Sorts levels by descending strength and keeps the first `count`
+pub fn strongest(mut levels: Vec<Level>, count: usize) -> Vec<Level> {
+    levels.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+    levels.truncate(count);
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(kind: SwingKind, index: usize, price: f64) -> SwingPoint {
+        SwingPoint { kind, index, price }
+    }
+
+    #[test]
+    fn test_level_detector_invalid_parameters() {
+        assert!(LevelDetector::new(0.0, 2).is_err());
+        assert!(LevelDetector::new(1.0, 0).is_err());
+        assert!(LevelDetector::new(1.0, 1).is_ok());
+    }
+
+    #[test]
+    fn test_levels_cluster_nearby_swings() {
+        let swings = vec![
+            point(SwingKind::High, 2, 50.0),
+            point(SwingKind::Low, 5, 40.0),
+            point(SwingKind::High, 9, 50.2),
+            point(SwingKind::Low, 12, 45.0),
+            point(SwingKind::High, 16, 49.9),
+            point(SwingKind::Low, 20, 40.1),
+        ];
+        let detector = LevelDetector::new(1.0, 2).unwrap();
+        let levels = detector.detect(&swings);
+
+        assert_eq!(levels.len(), 2);
+
+        let support = &levels[0];
+        assert!((support.price - 40.05).abs() < 1e-10);
+        assert_eq!(support.touches, 2);
+        assert_eq!(support.low_touches, 2);
+        assert_eq!((support.first_index, support.last_index), (5, 20));
+
+        let resistance = &levels[1];
+        assert_eq!(resistance.touches, 3);
+        assert_eq!(resistance.high_touches, 3);
+        assert!(resistance.strength > support.strength);
+    }
+
+    #[test]
+    fn test_levels_min_touches_filter() {
+        let swings = vec![
+            point(SwingKind::High, 1, 10.0),
+            point(SwingKind::Low, 4, 8.0),
+        ];
+        let detector = LevelDetector::new(1.0, 2).unwrap();
+        assert!(detector.detect(&swings).is_empty());
+        assert!(detector.detect(&[]).is_empty());
+
+        let detector = LevelDetector::new(1.0, 1).unwrap();
+        assert_eq!(detector.detect(&swings).len(), 2);
+    }
+
+    #[test]
+    fn test_level_role_reversal() {
+        // A broken resistance retested from above acts as support
+        let swings = vec![
+            point(SwingKind::High, 3, 100.0),
+            point(SwingKind::Low, 10, 100.2),
+        ];
+        let levels = LevelDetector::new(0.5, 2).unwrap().detect(&swings);
+
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].high_touches, 1);
+        assert_eq!(levels[0].low_touches, 1);
+        assert_eq!(levels[0].kind_at(98.0), LevelKind::Resistance);
+        assert_eq!(levels[0].kind_at(105.0), LevelKind::Support);
+    }
+
+    #[test]
+    fn test_level_zones_and_recency() {
+        let swings = vec![
+            point(SwingKind::Low, 0, 20.0),
+            point(SwingKind::Low, 10, 20.1),
+            point(SwingKind::High, 40, 30.0),
+            point(SwingKind::Low, 50, 30.1),
+        ];
+        let levels = LevelDetector::new(1.0, 2).unwrap().detect(&swings);
+        let (old, recent) = (&levels[0], &levels[1]);
+
+        assert_eq!((old.zone_low, old.zone_high), (20.0, 20.1));
+        assert_eq!((recent.zone_low, recent.zone_high), (30.0, 30.1));
+        assert!((old.recency - 0.2).abs() < 1e-12);
+        assert_eq!(recent.recency, 1.0);
+
+        assert!(recent.contains(30.05) && !recent.contains(30.2));
+        assert_eq!(recent.distance_percent(30.0), 0.0);
+        assert!((old.distance_percent(25.0) - 19.6).abs() < 1e-9);
+
+        // Same touch count and span weight, but the recent level ranks first
+        let ranked = strongest(levels.clone(), 1);
+        assert_eq!(ranked, vec![levels[1].clone()]);
+    }
+}
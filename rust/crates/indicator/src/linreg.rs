@@ -0,0 +1,294 @@
+//! Rolling least-squares regression: LSMA, slope, R² and regression channel
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Regression line statistics for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinRegOutput {
+    /// Fitted value at the latest bar (the least-squares moving average)
+    pub value: f64,
+    /// Slope of the fitted line in price per bar
+    pub slope: f64,
+    /// Coefficient of determination of the fit, in [0, 1]
+    pub r_squared: f64,
+    /// Standard error of the regression (residual standard deviation)
+    pub std_error: f64,
+    /// `value` plus `deviations` standard errors
+    pub upper: f64,
+    /// `value` minus `deviations` standard errors
+    pub lower: f64,
+}
+
+/// Rolling linear regression over the last `period` prices
+///
+/// Fits `price = intercept + slope × x` by ordinary least squares with
+/// `x = 0, 1, …, period − 1` across the window, and reports the line's value
+/// at the newest bar (LSMA), its slope, R² and a channel of `deviations`
+/// standard errors either side.
+///
+/// The sums of `y`, `x·y` and `y²` are updated in O(1) per bar: sliding the
+/// window shifts every `x` down by one, which subtracts the remaining `Σy`
+/// from `Σx·y`. The sums of `x` and `x²` depend only on `period`.
+///
+/// A flat window is a perfect fit and reports an R² of 1.
+///
+/// # Example
+///
+/// ```
+/// use indicator::LinearRegression;
+///
+/// let regression = LinearRegression::new(4, 2.0)?;
+/// let result = regression.calculate(&[1.0, 3.0, 5.0, 7.0, 9.0])?;
+///
+/// let last = result[4].unwrap();
+/// assert!((last.value - 9.0).abs() < 1e-12);
+/// assert!((last.slope - 2.0).abs() < 1e-12);
+/// assert!((last.r_squared - 1.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinearRegression {
+    period: usize,
+    deviations: f64,
+    window: VecDeque<f64>,
+    sum_y: f64,
+    sum_xy: f64,
+    sum_yy: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl LinearRegression {
+    /// Creates a new rolling linear regression
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of prices in the window (must be >= 2)
+    /// * `deviations` - Channel half-width in standard errors (must be >= 0)
+    pub fn new(period: usize, deviations: f64) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+        if deviations.is_nan() || deviations < 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "deviations",
+                value: deviations,
+                constraint: ">= 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            deviations,
+            window: VecDeque::with_capacity(period),
+            sum_y: 0.0,
+            sum_xy: 0.0,
+            sum_yy: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the regression for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<LinRegOutput>>, IndicatorError> {
+        check_min_length(self.period, prices.len())?;
+
+        let mut state = Self::new(self.period, self.deviations)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<LinRegOutput> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                self.sum_y -= oldest;
+                self.sum_yy -= oldest * oldest;
+                // Every remaining x moves down by one
+                self.sum_xy -= self.sum_y;
+            }
+        }
+        self.sum_xy += self.window.len() as f64 * price;
+        self.sum_y += price;
+        self.sum_yy += price * price;
+        self.window.push_back(price);
+
+        if self.window.len() < self.period {
+            return None;
+        }
+        Some(self.fit())
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<LinRegOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum_y = 0.0;
+        self.sum_xy = 0.0;
+        self.sum_yy = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the channel half-width in standard errors
+    pub fn deviations(&self) -> f64 {
+        self.deviations
+    }
+
+    fn fit(&self) -> LinRegOutput {
+        let n = self.period as f64;
+        let sum_x = n * (n - 1.0) / 2.0;
+        let sxx = n * (n * n - 1.0) / 12.0;
+        let sxy = self.sum_xy - sum_x * self.sum_y / n;
+        let syy = (self.sum_yy - self.sum_y * self.sum_y / n).max(0.0);
+
+        let slope = sxy / sxx;
+        let intercept = (self.sum_y - slope * sum_x) / n;
+        let value = intercept + slope * (n - 1.0);
+
+        let explained = sxy * sxy / sxx;
+        let r_squared = if syy > 0.0 {
+            (explained / syy).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        // Two fitted parameters; a two-point window always fits exactly
+        let residual = (syy - explained).max(0.0);
+        let std_error = (residual / (n - 2.0).max(1.0)).sqrt();
+        let width = self.deviations * std_error;
+
+        LinRegOutput {
+            value,
+            slope,
+            r_squared,
+            std_error,
+            upper: value + width,
+            lower: value - width,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Direct least-squares fit of one window
+    fn naive(window: &[f64]) -> (f64, f64, f64, f64) {
+        let n = window.len() as f64;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = window.iter().sum::<f64>() / n;
+        let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+        for (x, &y) in window.iter().enumerate() {
+            let dx = x as f64 - mean_x;
+            sxx += dx * dx;
+            sxy += dx * (y - mean_y);
+            syy += (y - mean_y).powi(2);
+        }
+        let slope = sxy / sxx;
+        let value = mean_y + slope * (n - 1.0 - mean_x);
+        let sse: f64 = window
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (y - (mean_y + slope * (x as f64 - mean_x))).powi(2))
+            .sum();
+        (value, slope, 1.0 - sse / syy, (sse / (n - 2.0)).sqrt())
+    }
+
+    #[test]
+    fn test_regression_matches_direct_fit() {
+        let prices: Vec<f64> = (0..50)
+            .map(|i| 100.0 + i as f64 * 0.3 + (i as f64 * 0.9).sin() * 2.0)
+            .collect();
+        let period = 10;
+        let result = LinearRegression::new(period, 2.0)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+
+        assert!(result[..period - 1].iter().all(Option::is_none));
+        for i in period - 1..prices.len() {
+            let output = result[i].unwrap();
+            let (value, slope, r_squared, std_error) = naive(&prices[i + 1 - period..=i]);
+            assert!((output.value - value).abs() < 1e-9, "bar {i}");
+            assert!((output.slope - slope).abs() < 1e-9);
+            assert!((output.r_squared - r_squared).abs() < 1e-9);
+            assert!((output.std_error - std_error).abs() < 1e-9);
+            assert!((output.upper - (value + 2.0 * std_error)).abs() < 1e-9);
+            assert!((output.lower - (value - 2.0 * std_error)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_regression_flat_and_two_point_windows() {
+        let flat = LinearRegression::new(3, 1.0)
+            .unwrap()
+            .calculate(&[5.0; 4])
+            .unwrap();
+        let last = flat[3].unwrap();
+        assert_eq!((last.value, last.slope, last.r_squared), (5.0, 0.0, 1.0));
+        assert_eq!(last.upper, last.lower);
+
+        let two = LinearRegression::new(2, 1.0)
+            .unwrap()
+            .calculate(&[1.0, 4.0, 2.0])
+            .unwrap();
+        assert_eq!(two[2].unwrap().slope, -2.0);
+        assert_eq!(two[2].unwrap().std_error, 0.0);
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let prices: Vec<f64> = (0..40).map(|i| (i as f64 * 0.4).cos() * 5.0).collect();
+        let mut regression = LinearRegression::new(8, 1.5).unwrap();
+        let batch = regression.calculate(&prices).unwrap();
+        let streamed: Vec<Option<LinRegOutput>> =
+            prices.iter().map(|&p| regression.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        regression.reset();
+        assert_eq!(regression.next(1.0), None);
+
+        assert!(LinearRegression::new(1, 2.0).is_err());
+        assert!(LinearRegression::new(5, -1.0).is_err());
+        assert!(LinearRegression::new(5, 2.0)
+            .unwrap()
+            .calculate(&[1.0; 4])
+            .is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Moving Average Convergence Divergence (MACD)
+
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// MACD values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdOutput {
+    /// Fast EMA minus slow EMA
+    pub macd: f64,
+    /// EMA of the MACD line; `None` until `signal_period` MACD values exist
+    pub signal: Option<f64>,
+    /// MACD line minus signal line
+    pub histogram: Option<f64>,
+}
+
+/// Moving Average Convergence Divergence indicator
+///
+/// # Formula
+///
+/// MACD = EMA(fast) − EMA(slow)
+/// Signal = EMA(signal) of MACD
+/// Histogram = MACD − Signal
+///
+/// Each EMA is seeded with the simple average of its first `period` inputs,
+/// exactly as [`EMA::calculate`] does, so the MACD line equals the
+/// difference of the two batch EMAs.
+///
+/// # Example
+///
+/// ```
+/// use indicator::MACD;
+///
+/// let macd = MACD::new(3, 6, 3)?;
+/// let prices: Vec<f64> = (0..12).map(|i| 100.0 + i as f64).collect();
+/// let result = macd.calculate(&prices)?;
+///
+/// assert!(result[4].is_none());
+/// let last = result[11].unwrap();
+/// assert!(last.macd > 0.0);
+/// assert!(last.histogram.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MACD {
+    fast: EMA,
+    slow: EMA,
+    signal: EMA,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl MACD {
+    /// Creates a new MACD indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - Period of the fast EMA (must be > 0 and < `slow_period`)
+    /// * `slow_period` - Period of the slow EMA
+    /// * `signal_period` - Period of the signal-line EMA (must be > 0)
+    ///
+    /// The classic parameters are 12, 26 and 9.
+    pub fn new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if fast_period >= slow_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "fast_period",
+                value: fast_period as f64,
+                constraint: "< slow_period",
+            });
+        }
+
+        Ok(Self {
+            fast: EMA::new(fast_period)?,
+            slow: EMA::new(slow_period)?,
+            signal: EMA::new(signal_period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates MACD for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first
+    /// `slow_period - 1` values are `None`.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<MacdOutput>>, IndicatorError> {
+        let required = self.slow.period();
+        if prices.len() < required {
+            return Err(IndicatorError::InsufficientData {
+                required,
+                actual: prices.len(),
+            });
+        }
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<MacdOutput> {
+        self.undo = Snapshot::of(self);
+        let fast = self.fast.next(price);
+        let macd = self
+            .slow
+            .next(price)
+            .zip(fast)
+            .map(|(slow, fast)| fast - slow)?;
+        let signal = self.signal.next(macd);
+
+        Some(MacdOutput {
+            macd,
+            signal,
+            histogram: signal.map(|signal| macd - signal),
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<MacdOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the fast, slow and signal periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (self.fast.period(), self.slow.period(), self.signal.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices() -> Vec<f64> {
+        (0..40)
+            .map(|i| 100.0 + (i as f64 * 0.4).sin() * 5.0 + i as f64 * 0.2)
+            .collect()
+    }
+
+    #[test]
+    fn test_macd_line_is_ema_difference() {
+        let prices = prices();
+        let result = MACD::new(12, 26, 9).unwrap().calculate(&prices).unwrap();
+        let fast = EMA::new(12).unwrap().calculate(&prices).unwrap();
+        let slow = EMA::new(26).unwrap().calculate(&prices).unwrap();
+
+        assert!(result[24].is_none());
+        for i in 25..prices.len() {
+            let expected = fast[i].unwrap() - slow[i].unwrap();
+            assert!((result[i].unwrap().macd - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_signal_and_histogram() {
+        let prices = prices();
+        let result = MACD::new(12, 26, 9).unwrap().calculate(&prices).unwrap();
+        let macd_line: Vec<f64> = result.iter().flatten().map(|o| o.macd).collect();
+        let signal = EMA::new(9).unwrap().calculate(&macd_line).unwrap();
+
+        // Signal starts after 9 MACD values: index 25 + 8
+        assert_eq!(result[32].unwrap().signal, None);
+        for (output, expected) in result[25..].iter().flatten().zip(&signal) {
+            assert_eq!(output.signal, *expected);
+            if let (Some(signal), Some(histogram)) = (output.signal, output.histogram) {
+                assert!((histogram - (output.macd - signal)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_macd_streaming_matches_batch() {
+        let prices = prices();
+        let mut macd = MACD::new(5, 10, 4).unwrap();
+        let batch = macd.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| macd.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        macd.reset();
+        assert_eq!(macd.next(1.0), None);
+        assert_eq!(macd.periods(), (5, 10, 4));
+    }
+
+    #[test]
+    fn test_macd_invalid_parameters() {
+        assert!(MACD::new(26, 12, 9).is_err());
+        assert!(MACD::new(12, 12, 9).is_err());
+        assert!(MACD::new(0, 26, 9).is_err());
+        assert!(MACD::new(12, 26, 0).is_err());
+        assert!(MACD::new(3, 6, 3).unwrap().calculate(&[1.0; 5]).is_err());
+    }
+}
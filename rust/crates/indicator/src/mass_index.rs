@@ -0,0 +1,251 @@
+//! Mass Index
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, EMA};
+
+/// Mass Index indicator
+///
+/// Dorsey's range-expansion gauge:
+///
+/// Ratio = EMA(High − Low) / EMA(EMA(High − Low))
+/// Mass Index = Σ Ratio over the last `sum_period` bars
+///
+/// With the classic 9/25 parameters a "reversal bulge" is a rise above
+/// 27 followed by a drop below 26.5. Both EMAs are seeded like
+/// [`EMA::calculate`], so the first value appears at bar
+/// `2 × (ema_period − 1) + sum_period − 1`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::MassIndex;
+///
+/// // A constant range gives a ratio of exactly one per bar
+/// let high = [11.0; 45];
+/// let low = [10.0; 45];
+///
+/// let result = MassIndex::new(9, 25)?.calculate(&high, &low)?;
+/// assert!(result[39].is_none());
+/// assert!((result[40].unwrap() - 25.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MassIndex {
+    ema: EMA,
+    ema_of_ema: EMA,
+    sum_period: usize,
+    /// EMA ratios in the summation window, oldest first
+    ratios: VecDeque<f64>,
+    sum: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl MassIndex {
+    /// Creates a new Mass Index indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `ema_period` - Period of both range EMAs (must be > 0, typically 9)
+    /// * `sum_period` - Number of ratios summed (must be > 0, typically 25)
+    pub fn new(ema_period: usize, sum_period: usize) -> Result<Self, IndicatorError> {
+        if sum_period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "sum_period",
+                value: sum_period as f64,
+                constraint: "> 0",
+            });
+        }
+        let ema = EMA::new(ema_period)?;
+
+        Ok(Self {
+            ema: ema.clone(),
+            ema_of_ema: ema,
+            sum_period,
+            ratios: VecDeque::with_capacity(sum_period),
+            sum: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the Mass Index for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are too few bars for a single value.
+    pub fn calculate(&self, high: &[f64], low: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[("high", high.len()), ("low", low.len())])?;
+        check_min_length(self.required(), len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(high
+            .iter()
+            .zip(low)
+            .map(|(&high, &low)| state.next(high, low))
+            .collect())
+    }
+
+    /// Calculates the Mass Index for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few candles for a single
+    /// value.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.required(), candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// A zero double EMA (no range at all) contributes a ratio of one.
+    pub fn next(&mut self, high: f64, low: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let single = self.ema.next(high - low)?;
+        let double = self.ema_of_ema.next(single)?;
+        let ratio = if double != 0.0 { single / double } else { 1.0 };
+
+        if self.ratios.len() == self.sum_period {
+            self.sum -= self.ratios.pop_front().unwrap_or(0.0);
+        }
+        self.ratios.push_back(ratio);
+        self.sum += ratio;
+        (self.ratios.len() == self.sum_period).then_some(self.sum)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64) -> Option<f64> {
+        self.rollback();
+        self.next(high, low)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.ema_of_ema.reset();
+        self.ratios.clear();
+        self.sum = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the EMA and summation periods
+    pub fn periods(&self) -> (usize, usize) {
+        (self.ema.period(), self.sum_period)
+    }
+
+    fn required(&self) -> usize {
+        2 * (self.ema.period() - 1) + self.sum_period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mass_index_sums_ema_ratios() {
+        let high: Vec<f64> = (0..40)
+            .map(|i| 20.0 + (i as f64 * 0.3).sin() + (i as f64 * 0.11).cos())
+            .collect();
+        let low: Vec<f64> = high
+            .iter()
+            .enumerate()
+            .map(|(i, h)| h - 1.0 - (i as f64 * 0.4).sin().abs())
+            .collect();
+        let ranges: Vec<f64> = high.iter().zip(&low).map(|(h, l)| h - l).collect();
+
+        let ema = EMA::new(3).unwrap();
+        let single: Vec<f64> = ema
+            .calculate(&ranges)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        let double: Vec<f64> = ema
+            .calculate(&single)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        let ratios: Vec<f64> = single[2..]
+            .iter()
+            .zip(&double)
+            .map(|(s, d)| s / d)
+            .collect();
+
+        let result = MassIndex::new(3, 5)
+            .unwrap()
+            .calculate(&high, &low)
+            .unwrap();
+        assert!(result[7].is_none());
+        for (value, window) in result[8..].iter().zip(ratios.windows(5)) {
+            let expected: f64 = window.iter().sum();
+            assert!((value.unwrap() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mass_index_streaming_matches_batch() {
+        let candles: Vec<Candle> = (0..30)
+            .map(|i| {
+                let base = 10.0 + (i as f64 * 0.7).sin();
+                let spread = 0.5 + (i % 3) as f64 * 0.2;
+                Candle::new(i, base, base + spread, base - spread, base, 1.0)
+            })
+            .collect();
+        let mut mass = MassIndex::new(4, 6).unwrap();
+        let batch = mass.calculate_candles(&candles).unwrap();
+        let streamed: Vec<_> = candles.iter().map(|c| mass.next(c.high, c.low)).collect();
+        assert_eq!(batch, streamed);
+
+        mass.reset();
+        assert_eq!(mass.next(1.0, 0.0), None);
+        assert_eq!(mass.periods(), (4, 6));
+    }
+
+    #[test]
+    fn test_mass_index_invalid_inputs() {
+        assert!(MassIndex::new(0, 25).is_err());
+        assert!(MassIndex::new(9, 0).is_err());
+        assert!(matches!(
+            MassIndex::new(9, 25)
+                .unwrap()
+                .calculate(&[1.0; 40], &[1.0; 40]),
+            Err(IndicatorError::InsufficientData {
+                required: 41,
+                actual: 40
+            })
+        ));
+        assert!(MassIndex::new(1, 1)
+            .unwrap()
+            .calculate(&[1.0; 2], &[1.0; 3])
+            .is_err());
+    }
+}
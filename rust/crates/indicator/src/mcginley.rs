@@ -0,0 +1,214 @@
+//! McGinley Dynamic
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// McGinley Dynamic moving average
+///
+/// A moving average whose speed adapts to the market: its denominator scales
+/// with the fourth power of price over the line, so it slows down when price
+/// runs above it and speeds up when price falls below, hugging declines more
+/// tightly than rallies.
+///
+/// # Formula
+///
+/// MD(t) = MD(t−1) + (Price − MD(t−1)) / (k × N × (Price / MD(t−1))⁴)
+///
+/// where N is the period and k a constant (0.6 by convention). The line is
+/// seeded with the first price, so every bar has a value. The denominator is
+/// floored at one so a collapse in price can never carry the line past it,
+/// and a zero line has no defined ratio and is treated as a ratio of one.
+///
+/// # Example
+///
+/// ```
+/// use indicator::McGinleyDynamic;
+///
+/// let prices = [100.0, 100.0, 110.0];
+/// let result = McGinleyDynamic::new(10, 0.6)?.calculate(&prices)?;
+///
+/// assert_eq!(result[1], 100.0);
+/// // 100 + 10 / (0.6 × 10 × 1.1⁴)
+/// assert!((result[2] - (100.0 + 10.0 / (6.0 * 1.1_f64.powi(4)))).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct McGinleyDynamic {
+    period: usize,
+    constant: f64,
+    value: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl McGinleyDynamic {
+    /// Creates a new McGinley Dynamic indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Nominal smoothing period (must be > 0, typically 10-20)
+    /// * `constant` - Denominator scale `k` (must be > 0, typically 0.6)
+    pub fn new(period: usize, constant: f64) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        if constant.is_nan() || constant <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "constant",
+                value: constant,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            constant,
+            value: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the McGinley Dynamic for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `prices` is empty.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        check_min_length(1, prices.len())?;
+
+        let mut state = Self::new(self.period, self.constant)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode) and returns the updated line
+    pub fn next(&mut self, price: f64) -> f64 {
+        self.undo = Snapshot::of(self);
+        let value = match self.value {
+            Some(prev) => {
+                let ratio = if prev != 0.0 { price / prev } else { 1.0 };
+                let denominator = self.constant * self.period as f64 * ratio.powi(4);
+                let step = (price - prev) / denominator.max(1.0);
+                prev + step
+            }
+            None => price,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> f64 {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Current value of the line, `None` before the first price
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.value = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the nominal smoothing period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the denominator scale `k`
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mcginley_recursion() {
+        let prices = [50.0, 52.0, 51.0, 55.0];
+        let result = McGinleyDynamic::new(8, 0.6)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+
+        let mut expected = 50.0;
+        for (i, &price) in prices.iter().enumerate().skip(1) {
+            let ratio: f64 = price / expected;
+            expected += (price - expected) / (0.6 * 8.0 * ratio.powi(4));
+            assert!((result[i] - expected).abs() < 1e-12);
+        }
+        assert_eq!(result[0], 50.0);
+    }
+
+    #[test]
+    fn test_mcginley_adapts_faster_on_drops() {
+        // The fourth-power ratio shrinks the denominator when price falls
+        // below the line, so it follows declines faster than rallies
+        let mut up = McGinleyDynamic::new(10, 0.6).unwrap();
+        let mut down = up.clone();
+        up.next(100.0);
+        down.next(100.0);
+        let up_step = up.next(110.0) - 100.0;
+        let down_step = 100.0 - down.next(90.0);
+        assert!(down_step > up_step);
+    }
+
+    #[test]
+    fn test_mcginley_never_overshoots() {
+        let mut md = McGinleyDynamic::new(1, 0.6).unwrap();
+        md.next(100.0);
+        let value = md.next(10.0);
+        assert!((10.0..100.0).contains(&value));
+    }
+
+    #[test]
+    fn test_mcginley_streaming_and_reset() {
+        let prices: Vec<f64> = (0..20).map(|i| 30.0 + (i as f64 * 0.5).sin()).collect();
+        let mut md = McGinleyDynamic::new(12, 0.6).unwrap();
+        let batch = md.calculate(&prices).unwrap();
+        let streamed: Vec<f64> = prices.iter().map(|&p| md.next(p)).collect();
+        assert_eq!(batch, streamed);
+        assert_eq!(md.value(), batch.last().copied());
+
+        md.reset();
+        assert_eq!(md.value(), None);
+        assert_eq!((md.period(), md.constant()), (12, 0.6));
+    }
+
+    #[test]
+    fn test_mcginley_invalid_inputs() {
+        assert!(McGinleyDynamic::new(0, 0.6).is_err());
+        assert!(McGinleyDynamic::new(10, 0.0).is_err());
+        assert!(McGinleyDynamic::new(10, f64::NAN).is_err());
+        assert!(matches!(
+            McGinleyDynamic::new(10, 0.6).unwrap().calculate(&[]),
+            Err(IndicatorError::InsufficientData {
+                required: 1,
+                actual: 0
+            })
+        ));
+    }
+}
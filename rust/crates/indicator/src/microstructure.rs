@@ -0,0 +1,598 @@
+//! Market microstructure indicators on quote and trade events
+//!
+//! Unlike the bar-based indicators, these consume top-of-book quotes and
+//! individual trades:
+//!
+//! * [`OrderFlowImbalance`] - net pressure from changes in the best bid/ask queues
+//! * [`Vpin`] - volume-synchronized probability of informed trading (flow toxicity)
+//! * [`SpreadStatistics`] - rolling bid-ask spread mean, dispersion and relative size
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::microstructure::{OrderFlowImbalance, Quote};
+//!
+//! let mut ofi = OrderFlowImbalance::new(2)?;
+//!
+//! ofi.next(Quote::new(0, 100.00, 500.0, 100.02, 400.0));
+//! ofi.next(Quote::new(1, 100.00, 800.0, 100.02, 400.0)); // bid queue grows
+//! let value = ofi.next(Quote::new(2, 100.01, 200.0, 100.02, 300.0)).unwrap();
+//!
+//! assert!(value > 0.0); // buying pressure
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::bars::Trade;
+use crate::{IndicatorError, RollingMoments};
+
+/// Top-of-book quote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    /// Quote time as a Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// Best bid price
+    pub bid_price: f64,
+    /// Quantity at the best bid
+    pub bid_size: f64,
+    /// Best ask price
+    pub ask_price: f64,
+    /// Quantity at the best ask
+    pub ask_size: f64,
+}
+
+impl Quote {
+    /// Creates a new quote
+    pub fn new(
+        timestamp: i64,
+        bid_price: f64,
+        bid_size: f64,
+        ask_price: f64,
+        ask_size: f64,
+    ) -> Self {
+        Self {
+            timestamp,
+            bid_price,
+            bid_size,
+            ask_price,
+            ask_size,
+        }
+    }
+
+    /// Midpoint of the best bid and ask
+    pub fn mid_price(&self) -> f64 {
+        0.5 * (self.bid_price + self.ask_price)
+    }
+
+    /// Quoted spread (ask − bid)
+    pub fn spread(&self) -> f64 {
+        self.ask_price - self.bid_price
+    }
+}
+
+fn require_positive_period(period: usize) -> Result<(), IndicatorError> {
+    if period == 0 {
+        return Err(IndicatorError::InvalidParameter {
+            field: "period",
+            value: period as f64,
+            constraint: "> 0",
+        });
+    }
+    Ok(())
+}
+
+/// Rolling order-flow imbalance (Cont, Kukanov & Stoikov, 2014)
+///
+/// Each quote update contributes
+///
+/// ```text
+/// e = 1{Pb ≥ Pb'}·qb − 1{Pb ≤ Pb'}·qb' − 1{Pa ≤ Pa'}·qa + 1{Pa ≥ Pa'}·qa'
+/// ```
+///
+/// where primes denote the previous quote. The indicator is the sum of the
+/// last `period` contributions; positive values indicate net buying pressure.
+#[derive(Debug, Clone)]
+pub struct OrderFlowImbalance {
+    period: usize,
+    previous: Option<Quote>,
+    events: VecDeque<f64>,
+    sum: f64,
+    /// `previous` and `sum` before the last `next` and the event it evicted
+    /// (`None` if it added no event), or `None` when there is nothing to roll
+    /// back
+    undo: Option<(Option<Quote>, f64, Option<Option<f64>>)>,
+}
+
+impl OrderFlowImbalance {
+    /// Creates a new order-flow imbalance indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of quote updates summed (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        require_positive_period(period)?;
+        Ok(Self {
+            period,
+            previous: None,
+            events: VecDeque::with_capacity(period),
+            sum: 0.0,
+            undo: None,
+        })
+    }
+
+    /// Calculates the rolling imbalance for a series of quotes on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `period + 1` quotes are given.
+    pub fn calculate(&self, quotes: &[Quote]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        if quotes.len() <= self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period + 1,
+                actual: quotes.len(),
+            });
+        }
+        let mut state = Self::new(self.period)?;
+        Ok(quotes.iter().map(|&quote| state.next(quote)).collect())
+    }
+
+    /// Processes the next quote
+    pub fn next(&mut self, quote: Quote) -> Option<f64> {
+        self.undo = Some((self.previous, self.sum, None));
+        let previous = self.previous.replace(quote)?;
+
+        let mut event = 0.0;
+        if quote.bid_price >= previous.bid_price {
+            event += quote.bid_size;
+        }
+        if quote.bid_price <= previous.bid_price {
+            event -= previous.bid_size;
+        }
+        if quote.ask_price <= previous.ask_price {
+            event -= quote.ask_size;
+        }
+        if quote.ask_price >= previous.ask_price {
+            event += previous.ask_size;
+        }
+
+        let evicted = if self.events.len() == self.period {
+            self.events.pop_front()
+        } else {
+            None
+        };
+        self.sum -= evicted.unwrap_or(0.0);
+        self.events.push_back(event);
+        self.sum += event;
+        if let Some((_, _, pushed)) = self.undo.as_mut() {
+            *pushed = Some(evicted);
+        }
+
+        (self.events.len() == self.period).then_some(self.sum)
+    }
+
+    /// Replaces the most recent quote, e.g. when a quote update is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, quote: Quote) -> Option<f64> {
+        self.rollback();
+        self.next(quote)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest quote can be undone; further calls do nothing until
+    /// the next quote arrives.
+    pub fn rollback(&mut self) {
+        let Some((previous, sum, pushed)) = self.undo.take() else {
+            return;
+        };
+        if let Some(evicted) = pushed {
+            self.events.pop_back();
+            if let Some(oldest) = evicted {
+                self.events.push_front(oldest);
+            }
+        }
+        self.previous = previous;
+        self.sum = sum;
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.events.clear();
+        self.sum = 0.0;
+        self.undo = None;
+    }
+
+    /// Returns the period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+/// Volume-synchronized probability of informed trading
+///
+/// Trades are signed with the tick rule (uptick = buy, downtick = sell,
+/// unchanged = previous side; the very first trade is split evenly) and poured
+/// into equal-volume buckets, splitting trades across bucket boundaries. VPIN
+/// is the mean absolute buy/sell imbalance of the last `window` buckets as a
+/// fraction of the bucket volume, in [0, 1].
+#[derive(Debug, Clone)]
+pub struct Vpin {
+    bucket_volume: f64,
+    window: usize,
+    last_price: Option<f64>,
+    /// +1 buy, −1 sell, 0 unknown
+    last_side: f64,
+    buy_volume: f64,
+    sell_volume: f64,
+    imbalances: VecDeque<f64>,
+    /// State needed to undo the last `next`, or `None` when there is nothing
+    /// to roll back
+    undo: Option<VpinUndo>,
+}
+
+/// [`Vpin`] state before a trade: the scalars, the number of completed
+/// buckets and the imbalances the trade evicted, oldest first
+#[derive(Debug, Clone)]
+struct VpinUndo {
+    last_price: Option<f64>,
+    last_side: f64,
+    buy_volume: f64,
+    sell_volume: f64,
+    buckets: usize,
+    evicted: Vec<f64>,
+}
+
+impl Vpin {
+    /// Creates a new VPIN estimator
+    ///
+    /// # Arguments
+    ///
+    /// * `bucket_volume` - Volume per bucket (must be > 0)
+    /// * `window` - Number of buckets averaged (must be > 0)
+    pub fn new(bucket_volume: f64, window: usize) -> Result<Self, IndicatorError> {
+        if bucket_volume.is_nan() || bucket_volume <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "bucket_volume",
+                value: bucket_volume,
+                constraint: "> 0",
+            });
+        }
+        if window == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "window",
+                value: window as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            bucket_volume,
+            window,
+            last_price: None,
+            last_side: 0.0,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            imbalances: VecDeque::with_capacity(window),
+            undo: None,
+        })
+    }
+
+    /// Processes the next trade
+    ///
+    /// Returns the VPIN of the last `window` completed buckets, or `None`
+    /// until that many buckets have filled.
+    pub fn next(&mut self, trade: Trade) -> Option<f64> {
+        let mut undo = VpinUndo {
+            last_price: self.last_price,
+            last_side: self.last_side,
+            buy_volume: self.buy_volume,
+            sell_volume: self.sell_volume,
+            buckets: self.imbalances.len(),
+            evicted: Vec::new(),
+        };
+        if let Some(last_price) = self.last_price {
+            if trade.price > last_price {
+                self.last_side = 1.0;
+            } else if trade.price < last_price {
+                self.last_side = -1.0;
+            }
+        }
+        self.last_price = Some(trade.price);
+        let buy_share = 0.5 * (1.0 + self.last_side);
+
+        let mut remaining = trade.size;
+        while remaining > 0.0 {
+            let capacity = self.bucket_volume - self.buy_volume - self.sell_volume;
+            let filled = remaining.min(capacity);
+            self.buy_volume += filled * buy_share;
+            self.sell_volume += filled * (1.0 - buy_share);
+            remaining -= filled;
+
+            if filled >= capacity {
+                if self.imbalances.len() == self.window {
+                    undo.evicted.extend(self.imbalances.pop_front());
+                }
+                self.imbalances
+                    .push_back((self.buy_volume - self.sell_volume).abs());
+                self.buy_volume = 0.0;
+                self.sell_volume = 0.0;
+            }
+        }
+
+        self.undo = Some(undo);
+        self.value()
+    }
+
+    /// Replaces the most recent trade, e.g. when a trade print is corrected
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, trade: Trade) -> Option<f64> {
+        self.rollback();
+        self.next(trade)
+    }
+
+    /// Undoes the most recent [`next`](Self::next), including any buckets it
+    /// completed
+    ///
+    /// Only the latest trade can be undone; further calls do nothing until
+    /// the next trade arrives.
+    pub fn rollback(&mut self) {
+        let Some(undo) = self.undo.take() else {
+            return;
+        };
+        // The evicted buckets followed by the current ones replay every
+        // bucket in order, so the earlier window is a prefix of them
+        for &imbalance in undo.evicted.iter().rev() {
+            self.imbalances.push_front(imbalance);
+        }
+        self.imbalances.truncate(undo.buckets);
+        self.last_price = undo.last_price;
+        self.last_side = undo.last_side;
+        self.buy_volume = undo.buy_volume;
+        self.sell_volume = undo.sell_volume;
+    }
+
+    /// VPIN of the last `window` completed buckets
+    pub fn value(&self) -> Option<f64> {
+        (self.imbalances.len() == self.window).then(|| {
+            self.imbalances.iter().sum::<f64>() / (self.window as f64 * self.bucket_volume)
+        })
+    }
+
+    /// Resets the estimator state
+    pub fn reset(&mut self) {
+        self.last_price = None;
+        self.last_side = 0.0;
+        self.buy_volume = 0.0;
+        self.sell_volume = 0.0;
+        self.imbalances.clear();
+        self.undo = None;
+    }
+
+    /// Returns the volume per bucket
+    pub fn bucket_volume(&self) -> f64 {
+        self.bucket_volume
+    }
+
+    /// Returns the number of buckets averaged
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+/// Rolling bid-ask spread statistics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadStats {
+    /// Quoted spread of the latest quote
+    pub spread: f64,
+    /// Latest spread relative to the mid price, in basis points
+    pub relative_spread_bps: f64,
+    /// Mean spread over the window
+    pub mean: f64,
+    /// Population standard deviation of the spread over the window
+    pub std_dev: f64,
+}
+
+/// Rolling bid-ask spread statistics over the last `period` quotes
+#[derive(Debug, Clone)]
+pub struct SpreadStatistics {
+    moments: RollingMoments,
+}
+
+impl SpreadStatistics {
+    /// Creates a new spread statistics tracker
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of quotes in the window (must be >= 2)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            moments: RollingMoments::new(period)?,
+        })
+    }
+
+    /// Calculates spread statistics for a series of quotes on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than `period` quotes are given.
+    pub fn calculate(&self, quotes: &[Quote]) -> Result<Vec<Option<SpreadStats>>, IndicatorError> {
+        if quotes.len() < self.period() {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period(),
+                actual: quotes.len(),
+            });
+        }
+        let mut state = Self::new(self.period())?;
+        Ok(quotes.iter().map(|&quote| state.next(quote)).collect())
+    }
+
+    /// Processes the next quote
+    pub fn next(&mut self, quote: Quote) -> Option<SpreadStats> {
+        let spread = quote.spread();
+        let moments = self.moments.next(spread)?;
+        Some(SpreadStats {
+            spread,
+            relative_spread_bps: 10_000.0 * spread / quote.mid_price(),
+            mean: moments.mean,
+            std_dev: moments.std_dev,
+        })
+    }
+
+    /// Replaces the most recent quote, e.g. when a quote update is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, quote: Quote) -> Option<SpreadStats> {
+        self.rollback();
+        self.next(quote)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest quote can be undone; further calls do nothing until
+    /// the next quote arrives.
+    pub fn rollback(&mut self) {
+        self.moments.rollback();
+    }
+
+    /// Resets the tracker state
+    pub fn reset(&mut self) {
+        self.moments.reset();
+    }
+
+    /// Returns the period
+    pub fn period(&self) -> usize {
+        self.moments.period()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_flow_imbalance_events() {
+        let mut ofi = OrderFlowImbalance::new(1).unwrap();
+        assert_eq!(ofi.next(Quote::new(0, 10.0, 100.0, 10.1, 100.0)), None);
+
+        // Bid size grows at the same price: +50
+        assert_eq!(
+            ofi.next(Quote::new(1, 10.0, 150.0, 10.1, 100.0)),
+            Some(50.0)
+        );
+        // Ask price improves to 10.05 with 40 shown: −40
+        assert_eq!(
+            ofi.next(Quote::new(2, 10.0, 150.0, 10.05, 40.0)),
+            Some(-40.0)
+        );
+        // Bid price drops: the old queue of 150 is gone
+        assert_eq!(
+            ofi.next(Quote::new(3, 9.95, 80.0, 10.05, 40.0)),
+            Some(-150.0)
+        );
+    }
+
+    #[test]
+    fn test_order_flow_imbalance_rolling_sum() {
+        let ofi = OrderFlowImbalance::new(2).unwrap();
+        let quotes = [
+            Quote::new(0, 10.0, 100.0, 10.1, 100.0),
+            Quote::new(1, 10.0, 150.0, 10.1, 100.0), // +50
+            Quote::new(2, 10.0, 170.0, 10.1, 100.0), // +20
+            Quote::new(3, 10.0, 170.0, 10.1, 130.0), // −30
+        ];
+        let values = ofi.calculate(&quotes).unwrap();
+
+        assert_eq!(values, vec![None, None, Some(70.0), Some(-10.0)]);
+        assert!(ofi.calculate(&quotes[..2]).is_err());
+    }
+
+    #[test]
+    fn test_vpin_one_sided_and_balanced_flow() {
+        // Steadily rising prices: every bucket is all buys
+        let mut vpin = Vpin::new(100.0, 2).unwrap();
+        let mut value = None;
+        for i in 0..10 {
+            value = vpin.next(Trade::new(i, 10.0 + i as f64 * 0.01, 50.0));
+        }
+        // The first trade is split evenly, so the first bucket is 75/25
+        assert!((value.unwrap() - 1.0).abs() < 1e-12);
+
+        // Alternating up/down ticks with equal size balance out
+        let mut vpin = Vpin::new(100.0, 2).unwrap();
+        for i in 0..20 {
+            let price = if i % 2 == 0 { 10.0 } else { 10.01 };
+            value = vpin.next(Trade::new(i, price, 50.0));
+        }
+        assert!(value.unwrap().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vpin_splits_trades_across_buckets() {
+        let mut vpin = Vpin::new(10.0, 3).unwrap();
+        vpin.next(Trade::new(0, 10.0, 1.0));
+        // One large uptick trade fills the rest of three buckets at once
+        let value = vpin.next(Trade::new(1, 10.1, 29.0)).unwrap();
+        // Buckets: |9.5 − 0.5| = 9, 10, 10
+        assert!((value - 29.0 / 30.0).abs() < 1e-12);
+
+        assert!(Vpin::new(0.0, 3).is_err());
+        assert!(Vpin::new(10.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_spread_statistics() {
+        let stats = SpreadStatistics::new(2).unwrap();
+        let quotes = [
+            Quote::new(0, 99.99, 1.0, 100.01, 1.0),
+            Quote::new(1, 99.98, 1.0, 100.02, 1.0),
+        ];
+        let values = stats.calculate(&quotes).unwrap();
+
+        assert_eq!(values[0], None);
+        let last = values[1].unwrap();
+        assert!((last.spread - 0.04).abs() < 1e-10);
+        assert!((last.relative_spread_bps - 4.0).abs() < 1e-8);
+        assert!((last.mean - 0.03).abs() < 1e-10);
+        assert!((last.std_dev - 0.01).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_update_last_revises_quotes_and_trades() {
+        let quotes: Vec<Quote> = (0..12)
+            .map(|i| {
+                let bid = 100.0 + (i as f64 * 0.9).sin() * 0.05;
+                Quote::new(i, bid, 100.0 + i as f64, bid + 0.02, 150.0 - i as f64)
+            })
+            .collect();
+        let provisional =
+            |q: Quote| Quote::new(q.timestamp, q.bid_price - 0.1, 1.0, q.ask_price + 0.1, 1.0);
+
+        let mut ofi = OrderFlowImbalance::new(3).unwrap();
+        let mut spreads = SpreadStatistics::new(3).unwrap();
+        let ofi_batch = ofi.calculate(&quotes).unwrap();
+        let spread_batch = spreads.calculate(&quotes).unwrap();
+        for (i, &quote) in quotes.iter().enumerate() {
+            ofi.next(provisional(quote));
+            spreads.next(provisional(quote));
+            assert_eq!(ofi.update_last(quote), ofi_batch[i]);
+            let revised = spreads.update_last(quote);
+            assert_eq!(revised.is_some(), spread_batch[i].is_some());
+            if let (Some(a), Some(b)) = (revised, spread_batch[i]) {
+                assert!((a.mean - b.mean).abs() < 1e-12);
+            }
+        }
+
+        // A corrected print that completed several buckets is fully undone
+        let mut vpin = Vpin::new(10.0, 2).unwrap();
+        vpin.next(Trade::new(0, 10.0, 15.0));
+        let expected = vpin.clone().next(Trade::new(1, 9.9, 2.0));
+        vpin.next(Trade::new(1, 10.1, 30.0));
+        assert!(vpin.value().is_some());
+        assert_eq!(vpin.update_last(Trade::new(1, 9.9, 2.0)), expected);
+        assert_eq!(vpin.value(), None);
+    }
+}
@@ -0,0 +1,410 @@
+//! Missing-data handling for streaming indicators
+
+use crate::{Candle, Indicator, IndicatorError};
+
+/// How an indicator treats missing (`None`) or NaN inputs
+///
+/// Without a policy a single NaN enters the recursion of indicators such as
+/// EMA or RSI and every later value is NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Fail with [`IndicatorError::MissingData`]
+    Error,
+    /// Ignore the input: the indicator is not updated and the bar has no value
+    Skip,
+    /// Repeat the last valid input; skip until there is one
+    ForwardFill,
+    /// Restart the indicator, which warms up again from the next valid input
+    Reset,
+}
+
+/// Indicator inputs that [`NanGuard`] can recognise as missing
+///
+/// A price is missing when it is NaN; a [`Candle`] is missing when any of its
+/// OHLCV fields is NaN, so a forward fill repeats the whole last valid bar.
+pub trait MissingInput: Copy {
+    /// Returns `true` if the input carries no usable value
+    fn is_missing(&self) -> bool;
+}
+
+impl MissingInput for f64 {
+    fn is_missing(&self) -> bool {
+        self.is_nan()
+    }
+}
+
+impl MissingInput for Candle {
+    fn is_missing(&self) -> bool {
+        [self.open, self.high, self.low, self.close, self.volume]
+            .iter()
+            .any(|v| v.is_nan())
+    }
+}
+
+/// An indicator that applies a [`NanPolicy`] to its inputs, created by
+/// [`Indicator::with_nan_policy`]
+///
+/// Works for any indicator whose input implements [`MissingInput`], i.e.
+/// single prices and candles.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Indicator, NanPolicy, SMA};
+///
+/// let prices = [1.0, 2.0, f64::NAN, 4.0, 5.0];
+///
+/// let skip = SMA::new(2)?.with_nan_policy(NanPolicy::Skip).calculate(&prices)?;
+/// assert_eq!(skip, vec![None, Some(1.5), None, Some(3.0), Some(4.5)]);
+///
+/// let fill = SMA::new(2)?.with_nan_policy(NanPolicy::ForwardFill).calculate(&prices)?;
+/// assert_eq!(fill, vec![None, Some(1.5), Some(2.0), Some(3.0), Some(4.5)]);
+///
+/// let strict = SMA::new(2)?.with_nan_policy(NanPolicy::Error);
+/// assert!(strict.calculate(&prices).is_err());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct NanGuard<I: Indicator> {
+    inner: I,
+    policy: NanPolicy,
+    /// Last valid input, for forward filling
+    last: Option<I::Input>,
+    /// Number of inputs seen, for error positions
+    index: usize,
+    /// Whether a missing input under [`NanPolicy::Reset`] is waiting to
+    /// restart the wrapped indicator before its next valid input
+    restart_pending: bool,
+    /// Guard state before the last counted input, or `None` when there is
+    /// nothing to roll back
+    undo: Option<Undo<I::Input>>,
+}
+
+/// What [`NanGuard::rollback`](Indicator::rollback) restores
+#[derive(Debug, Clone, Copy)]
+struct Undo<T> {
+    last: Option<T>,
+    restart_pending: bool,
+    /// Whether the input reached the wrapped indicator
+    fed_inner: bool,
+}
+
+impl<I: Indicator> NanGuard<I> {
+    pub(crate) fn new(inner: I, policy: NanPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            last: None,
+            index: 0,
+            restart_pending: false,
+            undo: None,
+        }
+    }
+
+    /// Returns the wrapped indicator
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the missing-data policy
+    pub fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+}
+
+impl<I: Indicator> NanGuard<I>
+where
+    I::Input: MissingInput,
+{
+    /// Adds a new, possibly missing, input (streaming mode)
+    ///
+    /// # Errors
+    ///
+    /// Returns `MissingData` for a `None` or NaN input under
+    /// [`NanPolicy::Error`]. The input is not counted, so the stream can
+    /// continue after handling the error.
+    pub fn try_next(
+        &mut self,
+        value: Option<I::Input>,
+    ) -> Result<Option<I::Output>, IndicatorError> {
+        let index = self.index;
+        let mut undo = Undo {
+            last: self.last,
+            restart_pending: self.restart_pending,
+            fed_inner: false,
+        };
+        let input = match value.filter(|v| !v.is_missing()) {
+            Some(value) => {
+                self.last = Some(value);
+                Some(value)
+            }
+            None => match self.policy {
+                NanPolicy::Error => return Err(IndicatorError::MissingData { index }),
+                NanPolicy::Skip => None,
+                NanPolicy::ForwardFill => self.last,
+                NanPolicy::Reset => {
+                    // Deferred to the next valid input so the gap can be rolled back
+                    self.restart_pending = true;
+                    self.last = None;
+                    None
+                }
+            },
+        };
+        self.index += 1;
+
+        let Some(input) = input else {
+            self.undo = Some(undo);
+            return Ok(None);
+        };
+        if self.restart_pending {
+            self.inner.reset();
+            self.restart_pending = false;
+        }
+        undo.fed_inner = true;
+        self.undo = Some(undo);
+        Ok(self.inner.next(input))
+    }
+
+    /// Replaces the most recent, possibly missing, input, e.g. when a gap in
+    /// a live feed is filled
+    ///
+    /// Equivalent to [`rollback`](Indicator::rollback) followed by
+    /// [`try_next`](Self::try_next).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unsupported` if the wrapped indicator does not support
+    /// rollback, and `MissingData` as [`try_next`](Self::try_next) does.
+    pub fn try_update_last(
+        &mut self,
+        value: Option<I::Input>,
+    ) -> Result<Option<I::Output>, IndicatorError> {
+        self.rollback()?;
+        self.try_next(value)
+    }
+
+    /// Calculates the indicator for a batch of inputs with gaps on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `MissingData` for the first `None` or NaN input under
+    /// [`NanPolicy::Error`].
+    pub fn calculate_options(
+        &self,
+        values: &[Option<I::Input>],
+    ) -> Result<Vec<Option<I::Output>>, IndicatorError>
+    where
+        I: Clone,
+    {
+        let mut state = self.clone();
+        state.reset();
+        values.iter().map(|&value| state.try_next(value)).collect()
+    }
+}
+
+impl<I: Indicator> Indicator for NanGuard<I>
+where
+    I::Input: MissingInput,
+{
+    type Input = I::Input;
+    type Output = I::Output;
+
+    /// Adds a new input, treating NaN as missing
+    ///
+    /// Under [`NanPolicy::Error`] a NaN gives `None`; use
+    /// [`try_next`](NanGuard::try_next) to observe the error.
+    fn next(&mut self, value: I::Input) -> Option<I::Output> {
+        self.try_next(Some(value)).ok().flatten()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.last = None;
+        self.index = 0;
+        self.restart_pending = false;
+        self.undo = None;
+    }
+
+    /// Rolls back the guard's forward-fill and restart state, and the
+    /// wrapped indicator if the last input reached it
+    ///
+    /// A missing input under [`NanPolicy::Error`] is not counted, so the
+    /// input before it is the one rolled back.
+    fn rollback(&mut self) -> Result<(), IndicatorError> {
+        let Some(undo) = self.undo else {
+            return Ok(());
+        };
+        if undo.fed_inner {
+            self.inner.rollback()?;
+        }
+        self.undo = None;
+        self.last = undo.last;
+        self.restart_pending = undo.restart_pending;
+        self.index -= 1;
+        Ok(())
+    }
+
+    fn calculate(&self, values: &[I::Input]) -> Result<Vec<Option<I::Output>>, IndicatorError>
+    where
+        Self: Clone,
+    {
+        let mut state = self.clone();
+        state.reset();
+        values
+            .iter()
+            .map(|&value| state.try_next(Some(value)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AtrOutput, ATR, EMA, RSI};
+
+    const PRICES: [f64; 8] = [10.0, 11.0, 12.0, f64::NAN, 13.0, 14.0, 15.0, 16.0];
+
+    #[test]
+    fn test_unguarded_nan_poisons_recursion() {
+        let raw = EMA::new(3).unwrap().calculate(&PRICES).unwrap();
+        assert!(raw[3..].iter().all(|v| v.unwrap().is_nan()));
+
+        let skip = EMA::new(3)
+            .unwrap()
+            .with_nan_policy(NanPolicy::Skip)
+            .calculate(&PRICES)
+            .unwrap();
+        let clean = EMA::new(3)
+            .unwrap()
+            .calculate(&[10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0])
+            .unwrap();
+        assert_eq!(skip[3], None);
+        assert_eq!(skip[7], clean[6]);
+    }
+
+    #[test]
+    fn test_reset_restarts_warmup() {
+        let guarded = EMA::new(3).unwrap().with_nan_policy(NanPolicy::Reset);
+        let values = [
+            Some(10.0),
+            Some(11.0),
+            Some(12.0),
+            None,
+            Some(13.0),
+            Some(14.0),
+            Some(15.0),
+        ];
+        let result = guarded.calculate_options(&values).unwrap();
+
+        assert_eq!(result[2], Some(11.0));
+        assert_eq!(&result[3..6], &[None, None, None]);
+        assert_eq!(result[6], Some(14.0));
+    }
+
+    #[test]
+    fn test_forward_fill_and_leading_gap() {
+        let guarded = RSI::new(3).unwrap().with_nan_policy(NanPolicy::ForwardFill);
+        let values = [
+            None,
+            Some(1.0),
+            Some(2.0),
+            None,
+            Some(3.0),
+            Some(2.5),
+            Some(3.5),
+        ];
+        let filled = guarded.calculate_options(&values).unwrap();
+        let manual = RSI::new(3)
+            .unwrap()
+            .calculate(&[1.0, 2.0, 2.0, 3.0, 2.5, 3.5])
+            .unwrap();
+
+        assert_eq!(filled[0], None);
+        assert_eq!(&filled[1..], &manual[..]);
+        assert_eq!(guarded.policy(), NanPolicy::ForwardFill);
+    }
+
+    #[test]
+    fn test_error_policy_reports_index_in_batch_and_stream() {
+        let mut guarded = EMA::new(3).unwrap().with_nan_policy(NanPolicy::Error);
+        assert_eq!(
+            guarded.calculate(&PRICES),
+            Err(IndicatorError::MissingData { index: 3 })
+        );
+
+        for &price in &PRICES[..3] {
+            guarded.try_next(Some(price)).unwrap();
+        }
+        assert_eq!(
+            guarded.try_next(None),
+            Err(IndicatorError::MissingData { index: 3 })
+        );
+        // The stream continues as if the missing input never arrived
+        assert_eq!(guarded.try_next(Some(13.0)), Ok(Some(12.0)));
+        assert_eq!(guarded.inner().period(), 3);
+        assert_eq!(
+            IndicatorError::MissingData { index: 3 }.to_string(),
+            "Missing or NaN input at index 3"
+        );
+    }
+
+    #[test]
+    fn test_update_last_restores_policy_state() {
+        // Each bar first arrives as the next bar's value, then is revised
+        let inputs = [10.0, f64::NAN, 11.0, 12.0, f64::NAN, f64::NAN, 13.0, 12.5];
+        for policy in [NanPolicy::Skip, NanPolicy::ForwardFill, NanPolicy::Reset] {
+            let mut guarded = EMA::new(3).unwrap().with_nan_policy(policy);
+            let expected = guarded.calculate(&inputs).unwrap();
+            let revised: Vec<_> = (0..inputs.len())
+                .map(|i| {
+                    guarded.next(inputs[(i + 1) % inputs.len()]);
+                    guarded.update_last(inputs[i]).unwrap()
+                })
+                .collect();
+            assert_eq!(revised, expected, "{policy:?}");
+        }
+
+        // Filling a gap under Reset keeps the warmed-up state
+        let mut guarded = EMA::new(3).unwrap().with_nan_policy(NanPolicy::Reset);
+        for price in [10.0, 11.0, 12.0] {
+            guarded.next(price);
+        }
+        assert_eq!(guarded.try_next(None), Ok(None));
+        assert_eq!(guarded.try_update_last(Some(13.0)), Ok(Some(12.0)));
+    }
+
+    #[test]
+    fn test_candle_with_any_nan_field_is_missing() {
+        let candle = |high: f64, low: f64, close: f64| Candle::new(0, close, high, low, close, 1.0);
+        let candles = [
+            candle(11.0, 9.0, 10.0),
+            candle(12.0, 10.0, 11.0),
+            Candle {
+                volume: f64::NAN,
+                ..candle(13.0, 11.0, 12.0)
+            },
+            candle(f64::NAN, 11.0, 12.0),
+            candle(13.0, 11.0, 12.5),
+            candle(14.0, 12.0, 13.0),
+        ];
+        let skip = ATR::new(2)
+            .unwrap()
+            .with_nan_policy(NanPolicy::Skip)
+            .calculate(&candles)
+            .unwrap();
+        let clean = ATR::new(2)
+            .unwrap()
+            .calculate_candles(&[candles[0], candles[1], candles[4], candles[5]])
+            .unwrap();
+
+        assert_eq!(&skip[2..4], &[None, None]);
+        let atr = |output: &Option<AtrOutput>| output.and_then(|o| o.atr);
+        assert_eq!(atr(&skip[5]), clean[3].atr);
+
+        let strict = ATR::new(2).unwrap().with_nan_policy(NanPolicy::Error);
+        assert_eq!(
+            strict.calculate(&candles),
+            Err(IndicatorError::MissingData { index: 2 })
+        );
+    }
+}
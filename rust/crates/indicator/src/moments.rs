@@ -0,0 +1,435 @@
+//! Rolling distribution moments (mean, standard deviation, skewness, kurtosis)
+
+use std::collections::VecDeque;
+
+use crate::IndicatorError;
+
+/// Distribution moments of a rolling window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moments {
+    /// Arithmetic mean of the window
+    pub mean: f64,
+    /// Population standard deviation of the window
+    pub std_dev: f64,
+    /// Population skewness (third standardized moment)
+    pub skewness: f64,
+    /// Population excess kurtosis (fourth standardized moment minus 3)
+    pub excess_kurtosis: f64,
+}
+
+impl Moments {
+    /// Bias-corrected sample skewness for a window of `n` values
+    ///
+    /// The adjusted Fisher-Pearson estimate `G₁ = g₁ · √(n(n − 1)) / (n − 2)`,
+    /// as reported by pandas' rolling `skew`. Returns `None` for `n < 3`.
+    pub fn sample_skewness(&self, n: usize) -> Option<f64> {
+        if n < 3 {
+            return None;
+        }
+        let n = n as f64;
+        Some(self.skewness * (n * (n - 1.0)).sqrt() / (n - 2.0))
+    }
+
+    /// Bias-corrected sample excess kurtosis for a window of `n` values
+    ///
+    /// `G₂ = ((n + 1) · g₂ + 6) · (n − 1) / ((n − 2)(n − 3))`, as reported by
+    /// pandas' rolling `kurt`. Returns `None` for `n < 4`.
+    pub fn sample_excess_kurtosis(&self, n: usize) -> Option<f64> {
+        if n < 4 {
+            return None;
+        }
+        let n = n as f64;
+        Some(((n + 1.0) * self.excess_kurtosis + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+    }
+}
+
+/// Rolling skewness and excess kurtosis indicator
+///
+/// Maintains the first four central moments of a sliding window using
+/// Welford-style incremental updates: each new value is added to the running
+/// sums and the value leaving the window is removed with the inverse update,
+/// so every step is O(1) and avoids the catastrophic cancellation of naive
+/// power-sum formulas.
+///
+/// A window with zero variance reports zero skewness and excess kurtosis.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingMoments;
+///
+/// let moments = RollingMoments::new(4)?;
+/// let returns = vec![0.01, -0.02, 0.015, 0.03, -0.01, 0.005];
+/// let result = moments.calculate(&returns)?;
+///
+/// assert_eq!(result.len(), returns.len());
+/// assert!(result[2].is_none());
+/// assert!(result[3].is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMoments {
+    /// Number of values in the rolling window
+    period: usize,
+    /// Values currently in the window, oldest first
+    window: VecDeque<f64>,
+    /// Running mean of the window
+    mean: f64,
+    /// Sum of squared deviations from the mean
+    m2: f64,
+    /// Sum of cubed deviations from the mean
+    m3: f64,
+    /// Sum of fourth-power deviations from the mean
+    m4: f64,
+    /// Value evicted by the last `next` (inner `None` if nothing was
+    /// evicted) and the moment sums before it, or `None` when there is
+    /// nothing to roll back
+    undo: Option<(Option<f64>, [f64; 4])>,
+}
+
+impl RollingMoments {
+    /// Creates a new rolling moments indicator with the specified window length
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the rolling window (must be >= 2)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::RollingMoments;
+    ///
+    /// let moments = RollingMoments::new(20)?;
+    /// assert_eq!(moments.period(), 20);
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            undo: None,
+        })
+    }
+
+    /// Calculates rolling moments for a batch of values
+    ///
+    /// The calculation starts from an empty window regardless of any streaming
+    /// state held by `self`, so the output matches feeding `values` through
+    /// [`next`](Self::next) on a freshly created indicator.
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<Moments>>, IndicatorError> {
+        if values.len() < self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period,
+                actual: values.len(),
+            });
+        }
+
+        let mut state = Self::new(self.period)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value to the window (streaming mode)
+    ///
+    /// Returns `None` until `period` values have been seen, then the moments of
+    /// the most recent `period` values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::RollingMoments;
+    ///
+    /// let mut moments = RollingMoments::new(3)?;
+    /// assert!(moments.next(1.0).is_none());
+    /// assert!(moments.next(2.0).is_none());
+    ///
+    /// let latest = moments.next(3.0).unwrap();
+    /// assert!((latest.mean - 2.0).abs() < 1e-12);
+    /// assert!(latest.skewness.abs() < 1e-12);
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn next(&mut self, value: f64) -> Option<Moments> {
+        let moments = [self.mean, self.m2, self.m3, self.m4];
+        let mut evicted = None;
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                self.remove(oldest);
+                evicted = Some(oldest);
+            }
+        }
+        self.window.push_back(value);
+        self.add(value);
+        self.undo = Some((evicted, moments));
+
+        if self.window.len() < self.period {
+            return None;
+        }
+        Some(self.moments())
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by
+    /// [`next`](Self::next), and O(1) like both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::RollingMoments;
+    ///
+    /// let mut moments = RollingMoments::new(2)?;
+    /// moments.next(1.0);
+    /// moments.next(5.0); // provisional print of the current bar
+    /// let revised = moments.update_last(3.0).unwrap();
+    /// assert!((revised.mean - 2.0).abs() < 1e-12);
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    pub fn update_last(&mut self, value: f64) -> Option<Moments> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next), restoring any value it evicted
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        let Some((evicted, [mean, m2, m3, m4])) = self.undo.take() else {
+            return;
+        };
+        self.window.pop_back();
+        if let Some(oldest) = evicted {
+            self.window.push_front(oldest);
+        }
+        self.mean = mean;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4;
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.undo = None;
+        self.window.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.m3 = 0.0;
+        self.m4 = 0.0;
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Incorporates `value` into the running central moments
+    ///
+    /// Must be called after `value` has been pushed onto the window.
+    fn add(&mut self, value: f64) {
+        let n = self.window.len() as f64;
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * (n - 1.0);
+
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+    }
+
+    /// Removes `value` from the running central moments (inverse of [`add`](Self::add))
+    ///
+    /// Must be called after `value` has been popped from the window.
+    fn remove(&mut self, value: f64) {
+        let remaining = self.window.len();
+        if remaining == 0 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            self.m3 = 0.0;
+            self.m4 = 0.0;
+            return;
+        }
+
+        let n = remaining as f64 + 1.0;
+        let mean = self.mean - (value - self.mean) / (n - 1.0);
+        let delta = value - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * (n - 1.0);
+
+        let m2 = (self.m2 - term1).max(0.0);
+        let m3 = self.m3 - term1 * delta_n * (n - 2.0) + 3.0 * delta_n * m2;
+        let m4 = self.m4 - term1 * delta_n2 * (n * n - 3.0 * n + 3.0) - 6.0 * delta_n2 * m2
+            + 4.0 * delta_n * m3;
+
+        self.mean = mean;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4.max(0.0);
+    }
+
+    fn moments(&self) -> Moments {
+        let n = self.window.len() as f64;
+        let variance = self.m2 / n;
+
+        // A (numerically) constant window has no defined shape
+        let noise_floor = (f64::EPSILON * self.mean.abs().max(1.0)).powi(2);
+        let (skewness, excess_kurtosis) = if variance <= noise_floor {
+            (0.0, 0.0)
+        } else {
+            (
+                (self.m3 / n) / variance.powf(1.5),
+                (self.m4 / n) / (variance * variance) - 3.0,
+            )
+        };
+
+        Moments {
+            mean: self.mean,
+            std_dev: variance.sqrt(),
+            skewness,
+            excess_kurtosis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_moments(window: &[f64]) -> Moments {
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        let central = |p: i32| window.iter().map(|x| (x - mean).powi(p)).sum::<f64>() / n;
+        let variance = central(2);
+        Moments {
+            mean,
+            std_dev: variance.sqrt(),
+            skewness: central(3) / variance.powf(1.5),
+            excess_kurtosis: central(4) / variance.powi(2) - 3.0,
+        }
+    }
+
+    #[test]
+    fn test_moments_invalid_period() {
+        assert!(RollingMoments::new(0).is_err());
+        assert!(RollingMoments::new(1).is_err());
+        assert!(RollingMoments::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_moments_match_naive_computation() {
+        let moments = RollingMoments::new(5).unwrap();
+        let values = vec![1.0, 3.0, 2.0, 8.0, 4.0, -1.0, 6.0, 5.5, 0.5, 9.0, 2.5];
+        let result = moments.calculate(&values).unwrap();
+
+        assert!(result[..4].iter().all(Option::is_none));
+        for (i, actual) in result.iter().enumerate().skip(4) {
+            let actual = actual.unwrap();
+            let expected = naive_moments(&values[i - 4..=i]);
+            assert!((actual.mean - expected.mean).abs() < 1e-10);
+            assert!((actual.std_dev - expected.std_dev).abs() < 1e-10);
+            assert!((actual.skewness - expected.skewness).abs() < 1e-9);
+            assert!((actual.excess_kurtosis - expected.excess_kurtosis).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_moments_stable_with_large_offset() {
+        // Values far from zero break naive power-sum formulas
+        let moments = RollingMoments::new(4).unwrap();
+        let values: Vec<f64> = [1.0, 2.0, 4.0, 8.0, 3.0, 5.0]
+            .iter()
+            .map(|x| 1e9 + x)
+            .collect();
+        let result = moments.calculate(&values).unwrap();
+
+        let actual = result[5].unwrap();
+        let expected = naive_moments(&values[2..6]);
+        assert!((actual.std_dev - expected.std_dev).abs() < 1e-6);
+        assert!((actual.skewness - expected.skewness).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_moments_bias_correction() {
+        // Population g₁ ≈ 1.138 and g₂ = −0.212 for this window
+        let values = [1.0, 2.0, 3.0, 4.0, 10.0];
+        let moments = RollingMoments::new(5).unwrap().calculate(&values).unwrap()[4].unwrap();
+
+        assert!((moments.sample_skewness(5).unwrap() - 1.2 * 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((moments.sample_excess_kurtosis(5).unwrap() - 3.152).abs() < 1e-9);
+        assert_eq!(moments.sample_skewness(2), None);
+        assert_eq!(moments.sample_excess_kurtosis(3), None);
+    }
+
+    #[test]
+    fn test_moments_constant_window() {
+        let mut moments = RollingMoments::new(3).unwrap();
+        let mut last = None;
+        for _ in 0..5 {
+            last = moments.next(7.0);
+        }
+
+        let last = last.unwrap();
+        assert_eq!(last.mean, 7.0);
+        assert_eq!(last.std_dev, 0.0);
+        assert_eq!(last.skewness, 0.0);
+        assert_eq!(last.excess_kurtosis, 0.0);
+    }
+
+    #[test]
+    fn test_moments_streaming_matches_batch() {
+        let values = vec![0.01, -0.02, 0.015, 0.03, -0.01, 0.005, 0.02, -0.03];
+        let mut streaming = RollingMoments::new(4).unwrap();
+        let batch = streaming.calculate(&values).unwrap();
+
+        for (value, expected) in values.iter().zip(batch) {
+            assert_eq!(streaming.next(*value), expected);
+        }
+
+        streaming.reset();
+        assert!(streaming.next(1.0).is_none());
+    }
+
+    #[test]
+    fn test_moments_update_last_matches_final_series() {
+        let values = vec![0.01, -0.02, 0.015, 0.03, -0.01, 0.005, 0.02, -0.03];
+        let mut streaming = RollingMoments::new(4).unwrap();
+        let batch = streaming.calculate(&values).unwrap();
+
+        for (&value, expected) in values.iter().zip(&batch) {
+            streaming.next(value * 3.0 + 0.1);
+            let revised = streaming.update_last(value);
+            match (revised, expected) {
+                (Some(a), Some(b)) => {
+                    assert!((a.mean - b.mean).abs() < 1e-12);
+                    assert!((a.std_dev - b.std_dev).abs() < 1e-12);
+                    assert!((a.skewness - b.skewness).abs() < 1e-9);
+                }
+                (a, b) => assert_eq!(a.is_none(), b.is_none()),
+            }
+        }
+
+        // Rolling back restores the value evicted by the last push
+        streaming.rollback();
+        let restored = streaming.next(values[7]).unwrap();
+        assert!((restored.mean - batch[7].unwrap().mean).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,170 @@
+//! Moving average trait and runtime selection of the average family
+
+use crate::{IndicatorError, EMA, SMA, WMA};
+
+/// A streaming moving average
+///
+/// Implemented by the moving averages that can be swapped for one another
+/// inside composite indicators such as [`Envelope`](crate::Envelope).
+pub trait MovingAverage {
+    /// Adds a new value, returning the average once `period` values have
+    /// been seen
+    fn next(&mut self, value: f64) -> Option<f64>;
+
+    /// Clears the state so the average can be reused on a new series
+    fn reset(&mut self);
+
+    /// Returns the averaging period
+    fn period(&self) -> usize;
+}
+
+/// Moving average family chosen at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaType {
+    /// Simple moving average ([`SMA`])
+    Sma,
+    /// Exponential moving average ([`EMA`]), seeded with the SMA of its first
+    /// `period` values
+    Ema,
+    /// Linearly weighted moving average ([`WMA`])
+    Wma,
+}
+
+impl MovingAverage for SMA {
+    fn next(&mut self, value: f64) -> Option<f64> {
+        SMA::next(self, value)
+    }
+
+    fn reset(&mut self) {
+        SMA::reset(self)
+    }
+
+    fn period(&self) -> usize {
+        SMA::period(self)
+    }
+}
+
+impl MovingAverage for WMA {
+    fn next(&mut self, value: f64) -> Option<f64> {
+        WMA::next(self, value)
+    }
+
+    fn reset(&mut self) {
+        WMA::reset(self)
+    }
+
+    fn period(&self) -> usize {
+        WMA::period(self)
+    }
+}
+
+impl MovingAverage for EMA {
+    fn next(&mut self, value: f64) -> Option<f64> {
+        EMA::next(self, value)
+    }
+
+    fn reset(&mut self) {
+        EMA::reset(self)
+    }
+
+    fn period(&self) -> usize {
+        EMA::period(self)
+    }
+}
+
+/// A moving average of any [`MaType`], dispatching statically so composite
+/// indicators stay `Clone`
+#[derive(Debug, Clone)]
+pub(crate) enum AnyMovingAverage {
+    Sma(SMA),
+    Ema(EMA),
+    Wma(WMA),
+}
+
+impl AnyMovingAverage {
+    pub(crate) fn new(ma_type: MaType, period: usize) -> Result<Self, IndicatorError> {
+        Ok(match ma_type {
+            MaType::Sma => Self::Sma(SMA::new(period)?),
+            MaType::Ema => Self::Ema(EMA::new(period)?),
+            MaType::Wma => Self::Wma(WMA::new(period)?),
+        })
+    }
+
+    pub(crate) fn ma_type(&self) -> MaType {
+        match self {
+            Self::Sma(_) => MaType::Sma,
+            Self::Ema(_) => MaType::Ema,
+            Self::Wma(_) => MaType::Wma,
+        }
+    }
+}
+
+impl MovingAverage for AnyMovingAverage {
+    fn next(&mut self, value: f64) -> Option<f64> {
+        match self {
+            Self::Sma(ma) => MovingAverage::next(ma, value),
+            Self::Ema(ma) => MovingAverage::next(ma, value),
+            Self::Wma(ma) => MovingAverage::next(ma, value),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Sma(ma) => MovingAverage::reset(ma),
+            Self::Ema(ma) => MovingAverage::reset(ma),
+            Self::Wma(ma) => MovingAverage::reset(ma),
+        }
+    }
+
+    fn period(&self) -> usize {
+        match self {
+            Self::Sma(ma) => MovingAverage::period(ma),
+            Self::Ema(ma) => MovingAverage::period(ma),
+            Self::Wma(ma) => MovingAverage::period(ma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_moving_average_matches_batch() {
+        let prices: Vec<f64> = (0..20).map(|i| 10.0 + (i as f64 * 0.5).sin()).collect();
+        let batches = [
+            (
+                MaType::Sma,
+                SMA::new(5).unwrap().calculate(&prices).unwrap(),
+            ),
+            (
+                MaType::Ema,
+                EMA::new(5).unwrap().calculate(&prices).unwrap(),
+            ),
+            (
+                MaType::Wma,
+                WMA::new(5).unwrap().calculate(&prices).unwrap(),
+            ),
+        ];
+        for (ma_type, batch) in batches {
+            let mut ma = AnyMovingAverage::new(ma_type, 5).unwrap();
+            assert_eq!(ma.ma_type(), ma_type);
+            assert_eq!(MovingAverage::period(&ma), 5);
+            for (price, expected) in prices.iter().zip(&batch) {
+                match (MovingAverage::next(&mut ma, *price), expected) {
+                    (Some(a), Some(b)) => assert!((a - b).abs() < 1e-12),
+                    (a, b) => assert_eq!(a, *b),
+                }
+            }
+            MovingAverage::reset(&mut ma);
+            assert_eq!(MovingAverage::next(&mut ma, 1.0), None);
+        }
+    }
+
+    #[test]
+    fn test_any_moving_average_invalid_period() {
+        for ma_type in [MaType::Sma, MaType::Ema, MaType::Wma] {
+            assert!(AnyMovingAverage::new(ma_type, 0).is_err());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! On-Balance Volume (OBV)
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// On-Balance Volume indicator
+///
+/// A running total of volume signed by the direction of the close:
+///
+/// * close above the previous close: OBV += volume
+/// * close below the previous close: OBV −= volume
+/// * unchanged close: OBV unchanged
+///
+/// The series starts at zero on the first bar; only its changes carry
+/// information.
+///
+/// # Example
+///
+/// ```
+/// use indicator::OBV;
+///
+/// let close = [10.0, 10.5, 10.2, 10.2, 10.8];
+/// let volume = [1_000.0, 1_500.0, 800.0, 900.0, 2_000.0];
+///
+/// let result = OBV::new().calculate(&close, &volume)?;
+/// assert_eq!(result, vec![0.0, 1_500.0, 700.0, 700.0, 2_700.0]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OBV {
+    prev_close: Option<f64>,
+    value: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl OBV {
+    /// Creates a new OBV indicator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calculates OBV for a batch of closes and volumes on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if they are empty.
+    pub fn calculate(&self, close: &[f64], volume: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        let len = check_equal_lengths(&[("close", close.len()), ("volume", volume.len())])?;
+        check_min_length(1, len)?;
+
+        let mut state = Self::new();
+        Ok(close
+            .iter()
+            .zip(volume)
+            .map(|(&close, &volume)| state.next(close, volume))
+            .collect())
+    }
+
+    /// Calculates OBV for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `candles` is empty.
+    pub fn calculate_candles(&self, candles: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        check_min_length(1, candles.len())?;
+
+        let mut state = Self::new();
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.close, candle.volume))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode) and returns the updated OBV
+    pub fn next(&mut self, close: f64, volume: f64) -> f64 {
+        self.undo = Snapshot::of(self);
+        if let Some(prev) = self.prev_close {
+            if close > prev {
+                self.value += volume;
+            } else if close < prev {
+                self.value -= volume;
+            }
+        }
+        self.prev_close = Some(close);
+        self.value
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, close: f64, volume: f64) -> f64 {
+        self.rollback();
+        self.next(close, volume)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Current OBV value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        *self = Self::default();
+        self.undo = Snapshot::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obv_signs_volume_by_close_direction() {
+        let close = [5.0, 4.0, 4.0, 6.0, 3.0];
+        let volume = [10.0, 20.0, 30.0, 40.0, 50.0];
+        let result = OBV::new().calculate(&close, &volume).unwrap();
+        assert_eq!(result, vec![0.0, -20.0, -20.0, 20.0, -30.0]);
+    }
+
+    #[test]
+    fn test_obv_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = [(10.0, 100.0), (11.0, 50.0), (10.5, 75.0)]
+            .iter()
+            .enumerate()
+            .map(|(i, &(close, volume))| Candle::new(i as i64, close, close, close, close, volume))
+            .collect();
+        let batch = OBV::new().calculate_candles(&candles).unwrap();
+
+        let mut obv = OBV::new();
+        let streamed: Vec<f64> = candles
+            .iter()
+            .map(|c| obv.next(c.close, c.volume))
+            .collect();
+        assert_eq!(batch, streamed);
+        assert_eq!(obv.value(), -25.0);
+
+        obv.reset();
+        assert_eq!(obv.next(1.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_obv_invalid_inputs() {
+        assert!(OBV::new().calculate(&[], &[]).is_err());
+        assert!(matches!(
+            OBV::new().calculate(&[1.0, 2.0], &[1.0]),
+            Err(IndicatorError::LengthMismatch {
+                field: "volume",
+                ..
+            })
+        ));
+    }
+}
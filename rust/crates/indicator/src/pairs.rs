@@ -0,0 +1,389 @@
+//! Pair-trading statistics: hedge ratio, cointegration and mean-reversion speed
+//!
+//! Screening a pair usually takes three steps:
+//!
+//! 1. [`hedge_ratio`] regresses one price series on the other by ordinary
+//!    least squares, and [`spread`] forms the residual `y − α − β·x`.
+//! 2. [`engle_granger`] runs an augmented Dickey-Fuller test ([`adf`]) on
+//!    that spread; a statistic below the critical value rejects a unit root,
+//!    i.e. the two series are cointegrated.
+//! 3. [`half_life`] fits an Ornstein-Uhlenbeck (discrete AR(1)) model to the
+//!    spread and reports how many bars a deviation takes to halve.
+//!
+//! Critical values are MacKinnon's (2010) asymptotic values for a regression
+//! with a constant; small samples need somewhat more negative statistics.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::pairs::engle_granger;
+//!
+//! // `y` tracks 1.5 × `x` with a deviation that keeps oscillating around zero
+//! let x: Vec<f64> = (0..200).map(|i| 50.0 + i as f64 * 0.1).collect();
+//! let y: Vec<f64> = x
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, p)| 2.0 + 1.5 * p + 0.5 * (i as f64 * 0.7).sin())
+//!     .collect();
+//!
+//! let result = engle_granger(&x, &y, 1)?;
+//! assert!((result.hedge.beta - 1.5).abs() < 0.01);
+//! assert!(result.adf.is_stationary());
+//! assert!(result.half_life.unwrap() < 5.0);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::filters::solve_linear_system;
+use crate::IndicatorError;
+
+/// MacKinnon (2010) asymptotic ADF critical values with a constant
+const ADF_CRITICAL: CriticalValues = CriticalValues {
+    one_percent: -3.43035,
+    five_percent: -2.86154,
+    ten_percent: -2.56677,
+};
+
+/// MacKinnon (2010) asymptotic Engle-Granger critical values for two series with a constant
+const ENGLE_GRANGER_CRITICAL: CriticalValues = CriticalValues {
+    one_percent: -3.89644,
+    five_percent: -3.33613,
+    ten_percent: -3.04445,
+};
+
+/// OLS fit `y = alpha + beta·x`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeRatio {
+    /// Intercept
+    pub alpha: f64,
+    /// Slope: units of `x` to hold against one unit of `y`
+    pub beta: f64,
+}
+
+/// Critical values of a unit-root test statistic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CriticalValues {
+    /// Critical value at the 1% significance level
+    pub one_percent: f64,
+    /// Critical value at the 5% significance level
+    pub five_percent: f64,
+    /// Critical value at the 10% significance level
+    pub ten_percent: f64,
+}
+
+/// Result of an augmented Dickey-Fuller test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdfResult {
+    /// t-statistic of the lagged level coefficient; more negative is more stationary
+    pub statistic: f64,
+    /// Number of lagged differences in the regression
+    pub lags: usize,
+    /// Number of observations used in the regression
+    pub observations: usize,
+    /// Critical values to compare [`statistic`](Self::statistic) against
+    pub critical_values: CriticalValues,
+}
+
+impl AdfResult {
+    /// Whether the unit root is rejected at the 5% level
+    pub fn is_stationary(&self) -> bool {
+        self.statistic < self.critical_values.five_percent
+    }
+}
+
+/// Result of an Engle-Granger cointegration test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cointegration {
+    /// Hedge ratio from regressing `y` on `x`
+    pub hedge: HedgeRatio,
+    /// ADF test on the spread, with Engle-Granger critical values
+    pub adf: AdfResult,
+    /// Mean-reversion half-life of the spread in bars, if it mean-reverts
+    pub half_life: Option<f64>,
+}
+
+/// Regresses `y` on `x` by ordinary least squares
+///
+/// # Errors
+///
+/// Returns `LengthMismatch` if the series differ in length,
+/// `InsufficientData` for fewer than 2 values and `CalculationError` if `x`
+/// is constant.
+pub fn hedge_ratio(x: &[f64], y: &[f64]) -> Result<HedgeRatio, IndicatorError> {
+    let len = check_equal_lengths(&[("x", x.len()), ("y", y.len())])?;
+    check_min_length(2, len)?;
+
+    let n = len as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (sxy, sxx) = x.iter().zip(y).fold((0.0, 0.0), |(sxy, sxx), (x, y)| {
+        (
+            sxy + (x - mean_x) * (y - mean_y),
+            sxx + (x - mean_x).powi(2),
+        )
+    });
+    if sxx == 0.0 {
+        return Err(IndicatorError::CalculationError {
+            operation: "hedge ratio",
+            reason: "x has no variance".to_string(),
+        });
+    }
+
+    let beta = sxy / sxx;
+    Ok(HedgeRatio {
+        alpha: mean_y - beta * mean_x,
+        beta,
+    })
+}
+
+/// Spread `y − alpha − beta·x` of a pair under a hedge ratio
+///
+/// # Errors
+///
+/// Returns `LengthMismatch` if the series differ in length.
+pub fn spread(x: &[f64], y: &[f64], hedge: &HedgeRatio) -> Result<Vec<f64>, IndicatorError> {
+    check_equal_lengths(&[("x", x.len()), ("y", y.len())])?;
+    Ok(x.iter()
+        .zip(y)
+        .map(|(x, y)| y - hedge.alpha - hedge.beta * x)
+        .collect())
+}
+
+/// Augmented Dickey-Fuller unit-root test with a constant
+///
+/// Fits `Δyₜ = a + γ·yₜ₋₁ + Σᵢ δᵢ·Δyₜ₋ᵢ + εₜ` with `lags` lagged differences
+/// and reports the t-statistic of `γ`.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for fewer than `2 × lags + 4` values and
+/// `CalculationError` if the regression is degenerate (e.g. a constant series).
+pub fn adf(series: &[f64], lags: usize) -> Result<AdfResult, IndicatorError> {
+    check_min_length(2 * lags + 4, series.len())?;
+
+    let diffs: Vec<f64> = series.windows(2).map(|w| w[1] - w[0]).collect();
+    // Regressors per row: constant, lagged level, lagged differences
+    let rows: Vec<Vec<f64>> = (lags..diffs.len())
+        .map(|t| {
+            let mut row = vec![1.0, series[t]];
+            row.extend((1..=lags).map(|i| diffs[t - i]));
+            row
+        })
+        .collect();
+    let targets = &diffs[lags..];
+    let (coefficients, variances) = ols(&rows, targets)?;
+
+    Ok(AdfResult {
+        statistic: coefficients[1] / variances[1].sqrt(),
+        lags,
+        observations: rows.len(),
+        critical_values: ADF_CRITICAL,
+    })
+}
+
+/// Engle-Granger two-step cointegration test of `y` on `x`
+///
+/// Estimates the hedge ratio, then runs [`adf`] with `lags` lagged
+/// differences on the spread. Because the spread comes from a fitted
+/// regression, the statistic is compared against Engle-Granger rather than
+/// plain ADF critical values.
+///
+/// # Errors
+///
+/// Same as [`hedge_ratio`] and [`adf`].
+pub fn engle_granger(x: &[f64], y: &[f64], lags: usize) -> Result<Cointegration, IndicatorError> {
+    let hedge = hedge_ratio(x, y)?;
+    let residuals = spread(x, y, &hedge)?;
+    let adf = AdfResult {
+        critical_values: ENGLE_GRANGER_CRITICAL,
+        ..adf(&residuals, lags)?
+    };
+
+    Ok(Cointegration {
+        hedge,
+        adf,
+        half_life: half_life(&residuals)?,
+    })
+}
+
+/// Mean-reversion half-life of a spread in bars
+///
+/// Fits the discrete Ornstein-Uhlenbeck model `Δsₜ = a + b·sₜ₋₁ + εₜ`; the
+/// spread reverts at rate `θ = −ln(1 + b)` and a deviation halves after
+/// `ln 2 / θ` bars.
+///
+/// # Returns
+///
+/// `None` if the spread does not mean-revert (`b >= 0`) or overshoots every
+/// bar (`b <= −1`).
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for fewer than 3 values and `CalculationError`
+/// for a constant spread.
+pub fn half_life(spread: &[f64]) -> Result<Option<f64>, IndicatorError> {
+    check_min_length(3, spread.len())?;
+
+    let lagged = &spread[..spread.len() - 1];
+    let diffs: Vec<f64> = spread.windows(2).map(|w| w[1] - w[0]).collect();
+    let b = hedge_ratio(lagged, &diffs)?.beta;
+
+    Ok((b < 0.0 && b > -1.0).then(|| 2.0_f64.ln() / -(1.0 + b).ln()))
+}
+
+/// Ordinary least squares, returning the coefficients and their variances
+fn ols(rows: &[Vec<f64>], targets: &[f64]) -> Result<(Vec<f64>, Vec<f64>), IndicatorError> {
+    let k = rows[0].len();
+    let mut normal = vec![vec![0.0; k]; k];
+    let mut rhs = vec![0.0; k];
+    for (row, &target) in rows.iter().zip(targets) {
+        for (j, &a) in row.iter().enumerate() {
+            rhs[j] += a * target;
+            for (l, &b) in row.iter().enumerate() {
+                normal[j][l] += a * b;
+            }
+        }
+    }
+    let coefficients = solve_linear_system(normal.clone(), rhs)?;
+
+    let sum_sq: f64 = rows
+        .iter()
+        .zip(targets)
+        .map(|(row, target)| {
+            let fitted: f64 = row.iter().zip(&coefficients).map(|(a, c)| a * c).sum();
+            (target - fitted).powi(2)
+        })
+        .sum();
+    let sigma2 = sum_sq / (rows.len() - k) as f64;
+
+    // Diagonal of (XᵀX)⁻¹, one unit vector at a time
+    let variances = (0..k)
+        .map(|j| {
+            let mut unit = vec![0.0; k];
+            unit[j] = 1.0;
+            solve_linear_system(normal.clone(), unit).map(|column| sigma2 * column[j])
+        })
+        .collect::<Result<Vec<f64>, IndicatorError>>()?;
+
+    Ok((coefficients, variances))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random shocks in [−0.5, 0.5)
+    fn shocks(len: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect()
+    }
+
+    fn random_walk(len: usize, seed: u64) -> Vec<f64> {
+        shocks(len, seed)
+            .iter()
+            .scan(100.0, |level, e| {
+                *level += e;
+                Some(*level)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hedge_ratio_and_spread() {
+        let x = [1.0, 2.0, 4.0, 7.0];
+        let y: Vec<f64> = x.iter().map(|p| 3.0 + 0.5 * p).collect();
+        let hedge = hedge_ratio(&x, &y).unwrap();
+
+        assert!((hedge.alpha - 3.0).abs() < 1e-12);
+        assert!((hedge.beta - 0.5).abs() < 1e-12);
+        for s in spread(&x, &y, &hedge).unwrap() {
+            assert!(s.abs() < 1e-12);
+        }
+        assert!(hedge_ratio(&[1.0, 1.0], &[1.0, 2.0]).is_err());
+        assert!(matches!(
+            spread(&x, &y[..2], &hedge),
+            Err(IndicatorError::LengthMismatch { field: "y", .. })
+        ));
+    }
+
+    #[test]
+    fn test_adf_separates_stationary_from_random_walk() {
+        let noise = shocks(500, 7);
+        let ar: Vec<f64> = noise
+            .iter()
+            .scan(0.0, |prev, e| {
+                *prev = 0.5 * *prev + e;
+                Some(*prev)
+            })
+            .collect();
+
+        let stationary = adf(&ar, 2).unwrap();
+        assert!(stationary.is_stationary(), "{}", stationary.statistic);
+        assert!(stationary.statistic < stationary.critical_values.one_percent);
+        assert_eq!((stationary.lags, stationary.observations), (2, 497));
+
+        let walk = adf(&random_walk(500, 11), 2).unwrap();
+        assert!(!walk.is_stationary(), "{}", walk.statistic);
+
+        assert!(matches!(
+            adf(&ar[..7], 2),
+            Err(IndicatorError::InsufficientData {
+                required: 8,
+                actual: 7
+            })
+        ));
+        assert!(adf(&[5.0; 20], 1).is_err());
+    }
+
+    #[test]
+    fn test_half_life_of_exact_ar1() {
+        // sₜ = 0.9·sₜ₋₁ decays by half in ln 2 / −ln 0.9 bars
+        let decay: Vec<f64> = (0..30).map(|t| 10.0 * 0.9_f64.powi(t)).collect();
+        let expected = 2.0_f64.ln() / -(0.9_f64.ln());
+        assert!((half_life(&decay).unwrap().unwrap() - expected).abs() < 1e-9);
+
+        let explosive: Vec<f64> = (0..30).map(|t| 1.1_f64.powi(t)).collect();
+        assert_eq!(half_life(&explosive).unwrap(), None);
+        assert!(half_life(&[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_engle_granger_cointegrated_and_independent_pairs() {
+        let x: Vec<f64> = random_walk(400, 3).iter().map(|p| 4.0 * p).collect();
+        // Spread follows an AR(1) with coefficient 0.6: half-life ln 2 / −ln 0.6 ≈ 1.36
+        let deviation: Vec<f64> = shocks(400, 5)
+            .iter()
+            .scan(0.0, |prev, e| {
+                *prev = 0.6 * *prev + e;
+                Some(*prev)
+            })
+            .collect();
+        let y: Vec<f64> = x
+            .iter()
+            .zip(&deviation)
+            .map(|(p, d)| 1.0 + 2.0 * p + d)
+            .collect();
+
+        let pair = engle_granger(&x, &y, 1).unwrap();
+        assert!((pair.hedge.beta - 2.0).abs() < 0.05, "{}", pair.hedge.beta);
+        assert!(pair.adf.is_stationary());
+        assert_eq!(pair.adf.critical_values, ENGLE_GRANGER_CRITICAL);
+        let half_life = pair.half_life.unwrap();
+        assert!((0.5..3.0).contains(&half_life), "{half_life}");
+
+        let unrelated = random_walk(400, 13);
+        let independent = engle_granger(&x, &unrelated, 1).unwrap();
+        assert!(
+            !independent.adf.is_stationary(),
+            "{}",
+            independent.adf.statistic
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Candlestick pattern recognition
+//!
+//! [`PatternDetector`] scans a candle series for common one-, two- and
+//! three-bar reversal and continuation patterns. Each match is reported as a
+//! [`PatternEvent`] on the **last** bar of the pattern, since that is the
+//! first bar on which it can be known.
+//!
+//! Shapes are judged from the body (`|close − open|`), the upper and lower
+//! shadows and the full high-low range. Hammer-type bars are bullish or
+//! bearish depending on the preceding trend, measured as the change in close
+//! over `trend_period` bars before the pattern.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::patterns::{Pattern, PatternDetector, PatternDirection};
+//! use indicator::Candle;
+//!
+//! let candles = [
+//!     Candle::new(0, 10.0, 10.2, 8.9, 9.0, 0.0),
+//!     Candle::new(1, 8.8, 10.6, 8.7, 10.5, 0.0),
+//! ];
+//! let events = PatternDetector::default().detect(&candles);
+//!
+//! assert_eq!(events[0].pattern, Pattern::Engulfing);
+//! assert_eq!(events[0].direction, PatternDirection::Bullish);
+//! assert_eq!(events[0].index, 1);
+//! ```
+
+use crate::{Candle, IndicatorError};
+
+/// Recognised candlestick pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Open and close nearly equal: indecision
+    Doji,
+    /// Long lower shadow, small body near the high, after a decline
+    Hammer,
+    /// Hammer shape after an advance
+    HangingMan,
+    /// Long upper shadow, small body near the low, after a decline
+    InvertedHammer,
+    /// Inverted-hammer shape after an advance
+    ShootingStar,
+    /// Body fully engulfs the previous bar's opposite-coloured body
+    Engulfing,
+    /// Body fully contained in the previous bar's opposite-coloured body
+    Harami,
+    /// Long bearish bar, small-bodied star, then a bullish bar closing past the first bar's midpoint
+    MorningStar,
+    /// Long bullish bar, small-bodied star, then a bearish bar closing past the first bar's midpoint
+    EveningStar,
+    /// Three rising bullish bars, each opening inside the previous body
+    ThreeWhiteSoldiers,
+    /// Three falling bearish bars, each opening inside the previous body
+    ThreeBlackCrows,
+}
+
+/// Price direction implied by a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternDirection {
+    /// Pattern points to higher prices
+    Bullish,
+    /// Pattern points to lower prices
+    Bearish,
+    /// Pattern signals indecision only
+    Neutral,
+}
+
+/// A pattern match in a candle series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternEvent {
+    /// Index of the last bar of the pattern
+    pub index: usize,
+    /// Pattern that was recognised
+    pub pattern: Pattern,
+    /// Direction the pattern implies
+    pub direction: PatternDirection,
+}
+
+/// Candlestick pattern detector
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternDetector {
+    /// Maximum body-to-range ratio for a doji
+    doji_ratio: f64,
+    /// Number of bars used to judge the trend before a pattern
+    trend_period: usize,
+}
+
+impl Default for PatternDetector {
+    /// Doji bodies up to 10% of the range, trend judged over 5 bars
+    fn default() -> Self {
+        Self {
+            doji_ratio: 0.1,
+            trend_period: 5,
+        }
+    }
+}
+
+impl PatternDetector {
+    /// Creates a new pattern detector
+    ///
+    /// # Arguments
+    ///
+    /// * `doji_ratio` - Maximum body-to-range ratio of a doji (must be in (0, 1))
+    /// * `trend_period` - Bars used to judge the prior trend (must be > 0)
+    pub fn new(doji_ratio: f64, trend_period: usize) -> Result<Self, IndicatorError> {
+        if doji_ratio.is_nan() || doji_ratio <= 0.0 || doji_ratio >= 1.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "doji_ratio",
+                value: doji_ratio,
+                constraint: "in (0, 1)",
+            });
+        }
+        if trend_period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "trend_period",
+                value: trend_period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            doji_ratio,
+            trend_period,
+        })
+    }
+
+    /// Detects all patterns in a candle series
+    ///
+    /// # Returns
+    ///
+    /// Pattern events ordered by bar index. A bar can complete more than one
+    /// pattern, e.g. a doji that is also the last bar of a harami.
+    pub fn detect(&self, candles: &[Candle]) -> Vec<PatternEvent> {
+        let mut events = Vec::new();
+        for index in 0..candles.len() {
+            let mut push = |pattern, direction| {
+                events.push(PatternEvent {
+                    index,
+                    pattern,
+                    direction,
+                })
+            };
+
+            let bar = Shape::of(&candles[index]);
+            if bar.range > 0.0 && bar.body <= self.doji_ratio * bar.range {
+                push(Pattern::Doji, PatternDirection::Neutral);
+            } else if bar.body > 0.0 {
+                let trend = self.trend_before(candles, index);
+                if bar.lower >= 2.0 * bar.body && bar.upper <= bar.body {
+                    match trend {
+                        Some(PatternDirection::Bearish) => {
+                            push(Pattern::Hammer, PatternDirection::Bullish)
+                        }
+                        Some(PatternDirection::Bullish) => {
+                            push(Pattern::HangingMan, PatternDirection::Bearish)
+                        }
+                        _ => {}
+                    }
+                } else if bar.upper >= 2.0 * bar.body && bar.lower <= bar.body {
+                    match trend {
+                        Some(PatternDirection::Bearish) => {
+                            push(Pattern::InvertedHammer, PatternDirection::Bullish)
+                        }
+                        Some(PatternDirection::Bullish) => {
+                            push(Pattern::ShootingStar, PatternDirection::Bearish)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if index >= 1 {
+                if let Some(direction) = engulfing(&candles[index - 1], &candles[index]) {
+                    push(Pattern::Engulfing, direction);
+                }
+                if let Some(direction) = harami(&candles[index - 1], &candles[index]) {
+                    push(Pattern::Harami, direction);
+                }
+            }
+
+            if index >= 2 {
+                let window = &candles[index - 2..=index];
+                match self.star(window) {
+                    Some(PatternDirection::Bullish) => {
+                        push(Pattern::MorningStar, PatternDirection::Bullish)
+                    }
+                    Some(PatternDirection::Bearish) => {
+                        push(Pattern::EveningStar, PatternDirection::Bearish)
+                    }
+                    _ => {}
+                }
+                match three_bar_run(window) {
+                    Some(PatternDirection::Bullish) => {
+                        push(Pattern::ThreeWhiteSoldiers, PatternDirection::Bullish)
+                    }
+                    Some(PatternDirection::Bearish) => {
+                        push(Pattern::ThreeBlackCrows, PatternDirection::Bearish)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        events
+    }
+
+    /// Returns the maximum doji body-to-range ratio
+    pub fn doji_ratio(&self) -> f64 {
+        self.doji_ratio
+    }
+
+    /// Returns the number of bars used to judge the prior trend
+    pub fn trend_period(&self) -> usize {
+        self.trend_period
+    }
+
+    /// Direction of the closes over the `trend_period` bars before `index`
+    fn trend_before(&self, candles: &[Candle], index: usize) -> Option<PatternDirection> {
+        let start = index.checked_sub(self.trend_period + 1)?;
+        let change = candles[index - 1].close - candles[start].close;
+        if change > 0.0 {
+            Some(PatternDirection::Bullish)
+        } else if change < 0.0 {
+            Some(PatternDirection::Bearish)
+        } else {
+            None
+        }
+    }
+
+    /// Morning (bullish) or evening (bearish) star over three bars
+    fn star(&self, window: &[Candle]) -> Option<PatternDirection> {
+        let (first, star, last) = (&window[0], &window[1], &window[2]);
+        let (first_shape, star_shape) = (Shape::of(first), Shape::of(star));
+        // The first bar must be a long body and the star a small one
+        if first_shape.body < 0.5 * first_shape.range || star_shape.body > 0.3 * first_shape.body {
+            return None;
+        }
+
+        let midpoint = (first.open + first.close) / 2.0;
+        let star_top = star.open.max(star.close);
+        let star_bottom = star.open.min(star.close);
+        if first.close < first.open
+            && star_top <= first.close
+            && last.close > last.open
+            && last.close > midpoint
+        {
+            Some(PatternDirection::Bullish)
+        } else if first.close > first.open
+            && star_bottom >= first.close
+            && last.close < last.open
+            && last.close < midpoint
+        {
+            Some(PatternDirection::Bearish)
+        } else {
+            None
+        }
+    }
+}
+
+/// Body and shadow lengths of a candle
+struct Shape {
+    body: f64,
+    upper: f64,
+    lower: f64,
+    range: f64,
+}
+
+impl Shape {
+    fn of(candle: &Candle) -> Self {
+        Self {
+            body: (candle.close - candle.open).abs(),
+            upper: candle.high - candle.open.max(candle.close),
+            lower: candle.open.min(candle.close) - candle.low,
+            range: candle.high - candle.low,
+        }
+    }
+}
+
+fn engulfing(prev: &Candle, bar: &Candle) -> Option<PatternDirection> {
+    if prev.close < prev.open
+        && bar.close > bar.open
+        && bar.open <= prev.close
+        && bar.close >= prev.open
+        && bar.close - bar.open > prev.open - prev.close
+    {
+        Some(PatternDirection::Bullish)
+    } else if prev.close > prev.open
+        && bar.close < bar.open
+        && bar.open >= prev.close
+        && bar.close <= prev.open
+        && bar.open - bar.close > prev.close - prev.open
+    {
+        Some(PatternDirection::Bearish)
+    } else {
+        None
+    }
+}
+
+fn harami(prev: &Candle, bar: &Candle) -> Option<PatternDirection> {
+    let inside = |low: f64, high: f64| {
+        bar.open.min(bar.close) > low && bar.open.max(bar.close) < high && bar.open != bar.close
+    };
+    if prev.close < prev.open && bar.close > bar.open && inside(prev.close, prev.open) {
+        Some(PatternDirection::Bullish)
+    } else if prev.close > prev.open && bar.close < bar.open && inside(prev.open, prev.close) {
+        Some(PatternDirection::Bearish)
+    } else {
+        None
+    }
+}
+
+/// Three white soldiers (bullish) or three black crows (bearish)
+fn three_bar_run(window: &[Candle]) -> Option<PatternDirection> {
+    let pairs = || window.windows(2).map(|pair| (&pair[0], &pair[1]));
+    if window.iter().all(|c| c.close > c.open)
+        && pairs().all(|(prev, bar)| {
+            bar.close > prev.close && bar.open >= prev.open && bar.open <= prev.close
+        })
+    {
+        Some(PatternDirection::Bullish)
+    } else if window.iter().all(|c| c.close < c.open)
+        && pairs().all(|(prev, bar)| {
+            bar.close < prev.close && bar.open <= prev.open && bar.open >= prev.close
+        })
+    {
+        Some(PatternDirection::Bearish)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle::new(0, open, high, low, close, 0.0)
+    }
+
+    fn patterns_at(events: &[PatternEvent], index: usize) -> Vec<(Pattern, PatternDirection)> {
+        events
+            .iter()
+            .filter(|e| e.index == index)
+            .map(|e| (e.pattern, e.direction))
+            .collect()
+    }
+
+    /// Five steadily falling bars to give later bars a downtrend context
+    fn downtrend() -> Vec<Candle> {
+        (0..5)
+            .map(|i| {
+                let open = 20.0 - i as f64;
+                bar(open, open + 0.1, open - 1.1, open - 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_doji_and_trend_dependent_hammers() {
+        let detector = PatternDetector::new(0.1, 3).unwrap();
+        let mut candles = downtrend();
+        candles.push(bar(14.0, 14.55, 12.0, 14.5)); // long lower shadow after a decline
+        candles.push(bar(14.0, 15.0, 13.0, 14.02)); // doji
+        let events = detector.detect(&candles);
+
+        assert_eq!(
+            patterns_at(&events, 5),
+            vec![(Pattern::Hammer, PatternDirection::Bullish)]
+        );
+        assert_eq!(
+            patterns_at(&events, 6),
+            vec![(Pattern::Doji, PatternDirection::Neutral)]
+        );
+
+        let rising: Vec<Candle> = downtrend()
+            .iter()
+            .rev()
+            .map(|c| bar(c.close, c.high, c.low, c.open))
+            .chain([bar(21.0, 22.0, 20.9, 21.3)])
+            .collect();
+        assert_eq!(
+            patterns_at(&detector.detect(&rising), 5),
+            vec![(Pattern::ShootingStar, PatternDirection::Bearish)]
+        );
+    }
+
+    #[test]
+    fn test_two_bar_patterns() {
+        let detector = PatternDetector::default();
+        let bearish_engulfing = [bar(10.0, 11.1, 9.9, 11.0), bar(11.2, 11.3, 9.5, 9.6)];
+        assert_eq!(
+            patterns_at(&detector.detect(&bearish_engulfing), 1),
+            vec![(Pattern::Engulfing, PatternDirection::Bearish)]
+        );
+
+        let bullish_harami = [bar(12.0, 12.1, 9.9, 10.0), bar(10.5, 11.6, 10.4, 11.5)];
+        assert_eq!(
+            patterns_at(&detector.detect(&bullish_harami), 1),
+            vec![(Pattern::Harami, PatternDirection::Bullish)]
+        );
+    }
+
+    #[test]
+    fn test_stars() {
+        let detector = PatternDetector::default();
+        let morning = [
+            bar(12.0, 12.1, 9.9, 10.0),
+            bar(9.8, 10.0, 9.4, 9.7),
+            bar(10.0, 11.6, 9.9, 11.5),
+        ];
+        let events = detector.detect(&morning);
+        assert!(
+            patterns_at(&events, 2).contains(&(Pattern::MorningStar, PatternDirection::Bullish))
+        );
+
+        let evening: Vec<Candle> = morning
+            .iter()
+            .map(|c| bar(-c.open, -c.low, -c.high, -c.close))
+            .collect();
+        let events = detector.detect(&evening);
+        assert!(
+            patterns_at(&events, 2).contains(&(Pattern::EveningStar, PatternDirection::Bearish))
+        );
+    }
+
+    #[test]
+    fn test_three_bar_runs() {
+        let detector = PatternDetector::default();
+        let soldiers = [
+            bar(10.0, 11.1, 9.9, 11.0),
+            bar(10.5, 12.1, 10.4, 12.0),
+            bar(11.5, 13.1, 11.4, 13.0),
+        ];
+        assert!(patterns_at(&detector.detect(&soldiers), 2)
+            .contains(&(Pattern::ThreeWhiteSoldiers, PatternDirection::Bullish)));
+
+        let crows: Vec<Candle> = soldiers
+            .iter()
+            .map(|c| bar(-c.open, -c.low, -c.high, -c.close))
+            .collect();
+        assert!(patterns_at(&detector.detect(&crows), 2)
+            .contains(&(Pattern::ThreeBlackCrows, PatternDirection::Bearish)));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(PatternDetector::new(0.0, 5).is_err());
+        assert!(PatternDetector::new(1.0, 5).is_err());
+        assert!(PatternDetector::new(0.1, 0).is_err());
+        assert!(PatternDetector::default().detect(&[]).is_empty());
+    }
+}
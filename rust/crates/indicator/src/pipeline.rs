@@ -0,0 +1,188 @@
+//! Indicator composition combinators
+
+use std::fmt;
+
+use crate::{Indicator, IndicatorError};
+
+/// Two indicators run in sequence, created by [`Indicator::then`]
+///
+/// Every output of the first indicator is fed to the second, so the chain
+/// warms up once both have. Bars on which the first indicator has no value
+/// yet are not passed on.
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    /// Whether the last `next` reached the second indicator, i.e. what
+    /// `rollback` has to undo
+    fed_second: bool,
+}
+
+impl<A, B> Chain<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            fed_second: false,
+        }
+    }
+
+    /// Returns the first indicator of the chain
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second indicator of the chain
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A, B> Indicator for Chain<A, B>
+where
+    A: Indicator,
+    B: Indicator<Input = A::Output>,
+{
+    type Input = A::Input;
+    type Output = B::Output;
+
+    fn next(&mut self, input: A::Input) -> Option<B::Output> {
+        self.fed_second = false;
+        let intermediate = self.first.next(input)?;
+        self.fed_second = true;
+        self.second.next(intermediate)
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.fed_second = false;
+    }
+
+    /// Rolls back the second indicator, if the last input reached it, and
+    /// then the first
+    ///
+    /// If the first indicator does not support rollback the error is returned
+    /// after the second one has already been rolled back.
+    fn rollback(&mut self) -> Result<(), IndicatorError> {
+        if self.fed_second {
+            self.second.rollback()?;
+            self.fed_second = false;
+        }
+        self.first.rollback()
+    }
+}
+
+/// An indicator with a function applied to its outputs, created by
+/// [`Indicator::map`]
+#[derive(Clone)]
+pub struct Map<I, F> {
+    inner: I,
+    f: F,
+}
+
+impl<I, F> Map<I, F> {
+    pub(crate) fn new(inner: I, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    /// Returns the wrapped indicator
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+}
+
+impl<I: fmt::Debug, F> fmt::Debug for Map<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("inner", &self.inner).finish()
+    }
+}
+
+impl<I, F, O> Indicator for Map<I, F>
+where
+    I: Indicator,
+    F: FnMut(I::Output) -> O,
+{
+    type Input = I::Input;
+    type Output = O;
+
+    fn next(&mut self, input: I::Input) -> Option<O> {
+        self.inner.next(input).map(&mut self.f)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn rollback(&mut self) -> Result<(), IndicatorError> {
+        self.inner.rollback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Candle, Indicator, WilliamsR, EMA, MACD, RSI, SMA};
+
+    fn prices() -> Vec<f64> {
+        (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.35).sin() * 4.0 + i as f64 * 0.1)
+            .collect()
+    }
+
+    #[test]
+    fn test_chain_matches_manual_composition() {
+        let prices = prices();
+        let ema_of_rsi = RSI::new(14).unwrap().then(EMA::new(5).unwrap());
+        let chained = ema_of_rsi.calculate(&prices).unwrap();
+
+        let rsi: Vec<f64> = RSI::new(14)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        let manual = EMA::new(5).unwrap().calculate(&rsi).unwrap();
+
+        assert!(chained[..17].iter().all(Option::is_none));
+        let warm: Vec<Option<f64>> = chained.into_iter().skip(14).collect();
+        assert_eq!(warm, manual);
+    }
+
+    #[test]
+    fn test_map_then_smooth_and_reset() {
+        let prices = prices();
+        let mut smoothed_macd = MACD::new(5, 10, 3)
+            .unwrap()
+            .map(|output| output.macd)
+            .then(SMA::new(3).unwrap());
+
+        let first: Vec<_> = prices.iter().map(|&p| smoothed_macd.next(p)).collect();
+        assert_eq!(first.iter().position(Option::is_some), Some(11));
+
+        smoothed_macd.reset();
+        let second: Vec<_> = prices.iter().map(|&p| smoothed_macd.next(p)).collect();
+        assert_eq!(first, second);
+        assert_eq!(smoothed_macd.first().inner().periods(), (5, 10, 3));
+    }
+
+    #[test]
+    fn test_candle_chain_as_trait_object() {
+        let candles: Vec<Candle> = prices()
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| Candle::new(i as i64, p, p + 1.0, p - 1.0, p, 1.0))
+            .collect();
+
+        let mut smoothed: Box<dyn Indicator<Input = Candle, Output = f64>> = Box::new(
+            WilliamsR::new(10)
+                .unwrap()
+                .map(|r| r + 100.0)
+                .then(SMA::new(3).unwrap()),
+        );
+        let values: Vec<f64> = candles.iter().filter_map(|&c| smoothed.next(c)).collect();
+
+        assert_eq!(values.len(), candles.len() - 11);
+        assert!(values.iter().all(|v| (0.0..=100.0).contains(v)));
+    }
+}
@@ -0,0 +1,292 @@
+//! Floor-trader pivot points
+//!
+//! [`PivotPoints`] turns one period's open, high, low and close into a
+//! central pivot with three resistance and three support levels for the
+//! following period. [`PivotPoints::rolling`] groups intraday candles into
+//! UTC days or Monday-start weeks and gives every candle the levels of the
+//! previous completed period.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::pivots::{PivotMethod, PivotPoints};
+//! use indicator::Candle;
+//!
+//! let yesterday = Candle::new(0, 100.0, 110.0, 90.0, 105.0, 0.0);
+//! let levels = PivotPoints::new(PivotMethod::Classic).compute(&yesterday);
+//!
+//! assert!((levels.pivot - 101.666_666_666_666_67).abs() < 1e-9);
+//! assert!((levels.r1 - 113.333_333_333_333_33).abs() < 1e-9);
+//! assert!((levels.s1 - 93.333_333_333_333_33).abs() < 1e-9);
+//! ```
+
+use crate::Candle;
+
+/// Milliseconds in a day, the unit of [`Candle::timestamp`]
+const DAY_MS: i64 = 86_400_000;
+
+/// Formula family used to derive the levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotMethod {
+    /// P = (H + L + C) / 3, R1 = 2P − L, R2 = P + (H − L), R3 = H + 2(P − L)
+    Classic,
+    /// Classic pivot with levels at 38.2%, 61.8% and 100% of the range
+    Fibonacci,
+    /// Close-weighted pivot P = (H + L + 2C) / 4 with the classic levels
+    Woodie,
+    /// Levels around the close at 1.1/12, 1.1/6 and 1.1/4 of the range
+    Camarilla,
+}
+
+/// Aggregation period for [`PivotPoints::rolling`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotPeriod {
+    /// UTC calendar days
+    Daily,
+    /// Weeks starting on Monday 00:00 UTC
+    Weekly,
+}
+
+impl PivotPeriod {
+    /// Index of the period containing a millisecond Unix timestamp
+    fn key(self, timestamp: i64) -> i64 {
+        let day = timestamp.div_euclid(DAY_MS);
+        match self {
+            PivotPeriod::Daily => day,
+            // 1970-01-01 was a Thursday, three days after a Monday
+            PivotPeriod::Weekly => (day + 3).div_euclid(7),
+        }
+    }
+}
+
+/// Pivot and its support/resistance levels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotLevels {
+    /// Central pivot
+    pub pivot: f64,
+    /// First resistance
+    pub r1: f64,
+    /// Second resistance
+    pub r2: f64,
+    /// Third resistance
+    pub r3: f64,
+    /// First support
+    pub s1: f64,
+    /// Second support
+    pub s2: f64,
+    /// Third support
+    pub s3: f64,
+}
+
+/// Computes pivot points with a chosen formula
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PivotPoints {
+    method: PivotMethod,
+}
+
+impl PivotPoints {
+    /// Creates a pivot point calculator for `method`
+    pub fn new(method: PivotMethod) -> Self {
+        Self { method }
+    }
+
+    /// Levels for the period following `prior`, a candle spanning a whole
+    /// period
+    pub fn compute(&self, prior: &Candle) -> PivotLevels {
+        let (high, low, close) = (prior.high, prior.low, prior.close);
+        let range = high - low;
+
+        match self.method {
+            PivotMethod::Classic => floor_levels((high + low + close) / 3.0, high, low),
+            PivotMethod::Woodie => floor_levels((high + low + 2.0 * close) / 4.0, high, low),
+            PivotMethod::Fibonacci => {
+                let pivot = (high + low + close) / 3.0;
+                PivotLevels {
+                    pivot,
+                    r1: pivot + 0.382 * range,
+                    r2: pivot + 0.618 * range,
+                    r3: pivot + range,
+                    s1: pivot - 0.382 * range,
+                    s2: pivot - 0.618 * range,
+                    s3: pivot - range,
+                }
+            }
+            PivotMethod::Camarilla => {
+                let step = 1.1 * range;
+                PivotLevels {
+                    pivot: (high + low + close) / 3.0,
+                    r1: close + step / 12.0,
+                    r2: close + step / 6.0,
+                    r3: close + step / 4.0,
+                    s1: close - step / 12.0,
+                    s2: close - step / 6.0,
+                    s3: close - step / 4.0,
+                }
+            }
+        }
+    }
+
+    /// Levels in force at each candle of an intraday series
+    ///
+    /// Candles are grouped into `period`s by timestamp and each group is
+    /// aggregated into one OHLC candle; every candle gets the levels of the
+    /// most recent period before its own. Candles in the first period have no
+    /// prior period and get `None`. Candles must be in timestamp order.
+    pub fn rolling(&self, candles: &[Candle], period: PivotPeriod) -> Vec<Option<PivotLevels>> {
+        let mut levels = None;
+        let mut current: Option<(i64, Candle)> = None;
+
+        candles
+            .iter()
+            .map(|candle| {
+                let key = period.key(candle.timestamp);
+                match &mut current {
+                    Some((current_key, bar)) if *current_key == key => {
+                        bar.high = bar.high.max(candle.high);
+                        bar.low = bar.low.min(candle.low);
+                        bar.close = candle.close;
+                        bar.volume += candle.volume;
+                    }
+                    _ => {
+                        if let Some((_, bar)) = current.replace((key, *candle)) {
+                            levels = Some(self.compute(&bar));
+                        }
+                    }
+                }
+                levels
+            })
+            .collect()
+    }
+
+    /// Returns the formula family
+    pub fn method(&self) -> PivotMethod {
+        self.method
+    }
+}
+
+/// Classic floor-trader levels around a pivot
+fn floor_levels(pivot: f64, high: f64, low: f64) -> PivotLevels {
+    PivotLevels {
+        pivot,
+        r1: 2.0 * pivot - low,
+        r2: pivot + (high - low),
+        r3: high + 2.0 * (pivot - low),
+        s1: 2.0 * pivot - high,
+        s2: pivot - (high - low),
+        s3: low - 2.0 * (high - pivot),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn assert_levels(actual: PivotLevels, expected: [f64; 7]) {
+        let actual = [
+            actual.pivot,
+            actual.r1,
+            actual.r2,
+            actual.r3,
+            actual.s1,
+            actual.s2,
+            actual.s3,
+        ];
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_pivot_methods() {
+        let prior = Candle::new(0, 100.0, 110.0, 90.0, 106.0, 0.0);
+        let compute = |method| PivotPoints::new(method).compute(&prior);
+
+        assert_levels(
+            compute(PivotMethod::Classic),
+            [102.0, 114.0, 122.0, 134.0, 94.0, 82.0, 74.0],
+        );
+        assert_levels(
+            compute(PivotMethod::Fibonacci),
+            [102.0, 109.64, 114.36, 122.0, 94.36, 89.64, 82.0],
+        );
+        assert_levels(
+            compute(PivotMethod::Woodie),
+            [103.0, 116.0, 123.0, 136.0, 96.0, 83.0, 76.0],
+        );
+        assert_levels(
+            compute(PivotMethod::Camarilla),
+            [
+                102.0,
+                106.0 + 22.0 / 12.0,
+                106.0 + 22.0 / 6.0,
+                106.0 + 22.0 / 4.0,
+                106.0 - 22.0 / 12.0,
+                106.0 - 22.0 / 6.0,
+                106.0 - 22.0 / 4.0,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_pivot_levels_are_ordered() {
+        let prior = Candle::new(0, 50.0, 55.0, 48.0, 49.0, 0.0);
+        for method in [
+            PivotMethod::Classic,
+            PivotMethod::Fibonacci,
+            PivotMethod::Woodie,
+            PivotMethod::Camarilla,
+        ] {
+            let l = PivotPoints::new(method).compute(&prior);
+            assert!(l.s3 < l.s2 && l.s2 < l.s1 && l.s1 < l.r1 && l.r1 < l.r2 && l.r2 < l.r3);
+        }
+    }
+
+    #[test]
+    fn test_pivot_rolling_daily() {
+        // Three days of six-hourly bars
+        let candles: Vec<Candle> = (0..12)
+            .map(|i| {
+                let base = 100.0 + i as f64;
+                Candle::new(
+                    i * 6 * HOUR_MS,
+                    base,
+                    base + 2.0,
+                    base - 1.0,
+                    base + 0.5,
+                    1.0,
+                )
+            })
+            .collect();
+        let pivots = PivotPoints::new(PivotMethod::Classic);
+        let rolling = pivots.rolling(&candles, PivotPeriod::Daily);
+
+        assert!(rolling[..4].iter().all(Option::is_none));
+        let day_one = Candle::new(0, 100.0, 105.0, 99.0, 103.5, 4.0);
+        assert!(rolling[4..8]
+            .iter()
+            .all(|l| *l == Some(pivots.compute(&day_one))));
+        let day_two = Candle::new(0, 104.0, 109.0, 103.0, 107.5, 4.0);
+        assert_eq!(rolling[8], Some(pivots.compute(&day_two)));
+        assert_eq!(pivots.method(), PivotMethod::Classic);
+    }
+
+    #[test]
+    fn test_pivot_rolling_weekly() {
+        // Day 3 (1970-01-04) was a Sunday and day 4 a Monday
+        let day = |d: i64| d * DAY_MS;
+        let candles = [
+            Candle::new(day(2), 10.0, 12.0, 9.0, 11.0, 1.0),
+            Candle::new(day(3), 11.0, 13.0, 10.0, 12.0, 1.0),
+            Candle::new(day(4), 12.0, 12.5, 11.0, 11.5, 1.0),
+            Candle::new(day(5), 11.5, 12.0, 11.0, 11.8, 1.0),
+        ];
+        let pivots = PivotPoints::new(PivotMethod::Woodie);
+        let rolling = pivots.rolling(&candles, PivotPeriod::Weekly);
+
+        let week = Some(pivots.compute(&Candle::new(0, 10.0, 13.0, 9.0, 12.0, 2.0)));
+        assert_eq!(rolling, vec![None, None, week, week]);
+        assert!(pivots.rolling(&[], PivotPeriod::Daily).is_empty());
+    }
+}
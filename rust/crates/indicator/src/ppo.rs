@@ -0,0 +1,214 @@
+//! Percentage Price Oscillator (PPO)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// PPO values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PpoOutput {
+    /// Fast EMA minus slow EMA, as a percentage of the slow EMA
+    pub ppo: f64,
+    /// EMA of the PPO line; `None` until `signal_period` PPO values exist
+    pub signal: Option<f64>,
+    /// PPO line minus signal line
+    pub histogram: Option<f64>,
+}
+
+/// Percentage Price Oscillator indicator
+///
+/// The [`MACD`](crate::MACD) line normalised by the slow EMA, so values are
+/// comparable across instruments trading at different price levels.
+///
+/// # Formula
+///
+/// PPO = 100 × (EMA(fast) − EMA(slow)) / EMA(slow)
+/// Signal = EMA(signal) of PPO
+/// Histogram = PPO − Signal
+///
+/// The EMAs are seeded exactly as in `MACD`, so the first value appears at
+/// bar `slow_period − 1`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::PPO;
+///
+/// // The same relative move at two price levels gives the same PPO
+/// let low: Vec<f64> = (0..15).map(|i| 10.0 * 1.02_f64.powi(i)).collect();
+/// let high: Vec<f64> = low.iter().map(|p| p * 100.0).collect();
+///
+/// let ppo = PPO::new(3, 6, 3)?;
+/// let a = ppo.calculate(&low)?[14].unwrap();
+/// let b = ppo.calculate(&high)?[14].unwrap();
+/// assert!((a.ppo - b.ppo).abs() < 1e-9);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PPO {
+    fast: EMA,
+    slow: EMA,
+    signal: EMA,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl PPO {
+    /// Creates a new PPO indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `fast_period` - Period of the fast EMA (must be > 0 and < `slow_period`)
+    /// * `slow_period` - Period of the slow EMA
+    /// * `signal_period` - Period of the signal-line EMA (must be > 0)
+    ///
+    /// The classic parameters are 12, 26 and 9.
+    pub fn new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if fast_period >= slow_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "fast_period",
+                value: fast_period as f64,
+                constraint: "< slow_period",
+            });
+        }
+
+        Ok(Self {
+            fast: EMA::new(fast_period)?,
+            slow: EMA::new(slow_period)?,
+            signal: EMA::new(signal_period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates PPO for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first
+    /// `slow_period - 1` values are `None`.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<PpoOutput>>, IndicatorError> {
+        check_min_length(self.slow.period(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// A slow EMA of zero yields a PPO of zero.
+    pub fn next(&mut self, price: f64) -> Option<PpoOutput> {
+        self.undo = Snapshot::of(self);
+        let fast = self.fast.next(price);
+        let ppo = self.slow.next(price).zip(fast).map(|(slow, fast)| {
+            if slow != 0.0 {
+                100.0 * (fast - slow) / slow
+            } else {
+                0.0
+            }
+        })?;
+        let signal = self.signal.next(ppo);
+
+        Some(PpoOutput {
+            ppo,
+            signal,
+            histogram: signal.map(|signal| ppo - signal),
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<PpoOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the fast, slow and signal periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (self.fast.period(), self.slow.period(), self.signal.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MACD;
+
+    fn prices() -> Vec<f64> {
+        (0..40)
+            .map(|i| 80.0 + (i as f64 * 0.3).sin() * 6.0 + i as f64 * 0.1)
+            .collect()
+    }
+
+    #[test]
+    fn test_ppo_is_macd_over_slow_ema() {
+        let prices = prices();
+        let ppo = PPO::new(12, 26, 9).unwrap().calculate(&prices).unwrap();
+        let macd = MACD::new(12, 26, 9).unwrap().calculate(&prices).unwrap();
+        let slow = EMA::new(26).unwrap().calculate(&prices).unwrap();
+
+        assert!(ppo[24].is_none());
+        for i in 25..prices.len() {
+            let expected = 100.0 * macd[i].unwrap().macd / slow[i].unwrap();
+            assert!((ppo[i].unwrap().ppo - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ppo_signal_and_histogram() {
+        let prices = prices();
+        let result = PPO::new(5, 10, 4).unwrap().calculate(&prices).unwrap();
+        let line: Vec<f64> = result.iter().flatten().map(|o| o.ppo).collect();
+        let signal = EMA::new(4).unwrap().calculate(&line).unwrap();
+
+        for (output, expected) in result[9..].iter().flatten().zip(&signal) {
+            assert_eq!(output.signal, *expected);
+            assert_eq!(output.histogram, expected.map(|s| output.ppo - s));
+        }
+    }
+
+    #[test]
+    fn test_ppo_streaming_matches_batch() {
+        let prices = prices();
+        let mut ppo = PPO::new(5, 10, 4).unwrap();
+        let batch = ppo.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| ppo.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        ppo.reset();
+        assert_eq!(ppo.next(1.0), None);
+        assert_eq!(ppo.periods(), (5, 10, 4));
+    }
+
+    #[test]
+    fn test_ppo_invalid_parameters() {
+        assert!(PPO::new(26, 12, 9).is_err());
+        assert!(PPO::new(0, 26, 9).is_err());
+        assert!(PPO::new(12, 26, 0).is_err());
+        assert!(PPO::new(3, 6, 3).unwrap().calculate(&[1.0; 5]).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Quantitative Qualitative Estimation (QQE)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA, RSI};
+
+/// QQE values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QqeOutput {
+    /// EMA-smoothed RSI
+    pub rsi_ma: f64,
+    /// Trailing line: the long band in an uptrend, the short band in a
+    /// downtrend
+    pub trailing: f64,
+    /// Ratcheting band below the smoothed RSI
+    pub long_band: f64,
+    /// Ratcheting band above the smoothed RSI
+    pub short_band: f64,
+}
+
+/// Quantitative Qualitative Estimation indicator
+///
+/// A smoothed RSI with a volatility-based trailing line built from the
+/// "ATR" of the smoothed RSI itself:
+///
+/// RsiMa = EMA_smoothing(RSI(rsi_period))
+/// AtrRsi = |RsiMa − RsiMa_prev|
+/// Dar = EMA_wilders(EMA_wilders(AtrRsi)) × factor,  wilders = 2 × rsi_period − 1
+/// Long band = RsiMa − Dar, only allowed to rise while RsiMa stays above it
+/// Short band = RsiMa + Dar, only allowed to fall while RsiMa stays below it
+///
+/// The trend turns up when RsiMa crosses above the previous short band and
+/// down when it crosses below the previous long band, starting up; the
+/// trailing line follows the band on the trend's side. The EMAs are seeded
+/// like [`EMA::calculate`], so the first value appears at bar
+/// `rsi_period + smoothing + 2 × (wilders − 1)`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::QQE;
+///
+/// let prices: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.15).sin() * 8.0).collect();
+/// let result = QQE::new(14, 5, 4.236)?.calculate(&prices)?;
+///
+/// assert!(result[70].is_none());
+/// let last = result[119].unwrap();
+/// assert!(last.long_band < last.rsi_ma && last.rsi_ma < last.short_band);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct QQE {
+    rsi: RSI,
+    smoothing: EMA,
+    atr: EMA,
+    dar: EMA,
+    factor: f64,
+    prev_rsi_ma: Option<f64>,
+    /// Previous long and short bands
+    bands: Option<(f64, f64)>,
+    uptrend: bool,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl QQE {
+    /// Creates a new QQE indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `rsi_period` - RSI period (must be > 0, typically 14)
+    /// * `smoothing` - EMA period applied to RSI (must be > 0, typically 5)
+    /// * `factor` - Multiplier of the smoothed RSI volatility (must be > 0,
+    ///   typically 4.236)
+    pub fn new(rsi_period: usize, smoothing: usize, factor: f64) -> Result<Self, IndicatorError> {
+        let rsi = RSI::new(rsi_period)?;
+        if factor.is_nan() || factor <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "factor",
+                value: factor,
+                constraint: "> 0",
+            });
+        }
+        let wilders = 2 * rsi_period - 1;
+
+        Ok(Self {
+            rsi,
+            smoothing: EMA::new(smoothing)?,
+            atr: EMA::new(wilders)?,
+            dar: EMA::new(wilders)?,
+            factor,
+            prev_rsi_ma: None,
+            bands: None,
+            uptrend: true,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates QQE for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few prices for a single
+    /// value.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<QqeOutput>>, IndicatorError> {
+        let (rsi_period, smoothing) = self.periods();
+        let wilders = self.atr.period();
+        check_min_length(rsi_period + smoothing + 2 * (wilders - 1) + 1, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<QqeOutput> {
+        self.undo = Snapshot::of(self);
+        let rsi_ma = self.smoothing.next(self.rsi.next(price)?)?;
+        let prev_rsi_ma = self.prev_rsi_ma.replace(rsi_ma)?;
+        let atr = self.atr.next((rsi_ma - prev_rsi_ma).abs())?;
+        let dar = self.dar.next(atr)? * self.factor;
+
+        let (mut long_band, mut short_band) = (rsi_ma - dar, rsi_ma + dar);
+        if let Some((prev_long, prev_short)) = self.bands {
+            if prev_rsi_ma > prev_long && rsi_ma > prev_long {
+                long_band = long_band.max(prev_long);
+            }
+            if prev_rsi_ma < prev_short && rsi_ma < prev_short {
+                short_band = short_band.min(prev_short);
+            }
+            if prev_rsi_ma <= prev_short && rsi_ma > prev_short {
+                self.uptrend = true;
+            } else if prev_rsi_ma >= prev_long && rsi_ma < prev_long {
+                self.uptrend = false;
+            }
+        }
+        self.bands = Some((long_band, short_band));
+
+        Some(QqeOutput {
+            rsi_ma,
+            trailing: if self.uptrend { long_band } else { short_band },
+            long_band,
+            short_band,
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<QqeOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.rsi.reset();
+        self.smoothing.reset();
+        self.atr.reset();
+        self.dar.reset();
+        self.prev_rsi_ma = None;
+        self.bands = None;
+        self.uptrend = true;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the RSI and RSI smoothing periods
+    pub fn periods(&self) -> (usize, usize) {
+        (self.rsi.period(), self.smoothing.period())
+    }
+
+    /// Returns the volatility multiplier
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices() -> Vec<f64> {
+        (0..150)
+            .map(|i| 60.0 + (i as f64 * 0.2).sin() * 5.0 + (i as f64 * 0.05).cos() * 3.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_qqe_composition() {
+        let prices = prices();
+        let rsi: Vec<f64> = RSI::new(6)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        let rsi_ma: Vec<f64> = EMA::new(3)
+            .unwrap()
+            .calculate(&rsi)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        let atr: Vec<f64> = rsi_ma.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+        let ema = EMA::new(11).unwrap();
+        let smoothed: Vec<f64> = ema.calculate(&atr).unwrap().into_iter().flatten().collect();
+        let dar: Vec<f64> = ema
+            .calculate(&smoothed)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let result = QQE::new(6, 3, 4.236).unwrap().calculate(&prices).unwrap();
+        let first = 6 + 3 + 2 * 10;
+        assert!(result[first - 1].is_none());
+        let values: Vec<QqeOutput> = result.into_iter().flatten().collect();
+        assert_eq!(values.len(), dar.len());
+
+        let offset = rsi_ma.len() - dar.len();
+        let (mut long, mut short, mut up) = (f64::NAN, f64::NAN, true);
+        for (i, (output, d)) in values.iter().zip(&dar).enumerate() {
+            let (ma, prev) = (rsi_ma[i + offset], rsi_ma[i + offset - 1]);
+            let (mut new_long, mut new_short) = (ma - d * 4.236, ma + d * 4.236);
+            if i > 0 {
+                if prev > long && ma > long {
+                    new_long = new_long.max(long);
+                }
+                if prev < short && ma < short {
+                    new_short = new_short.min(short);
+                }
+                if prev <= short && ma > short {
+                    up = true;
+                } else if prev >= long && ma < long {
+                    up = false;
+                }
+            }
+            (long, short) = (new_long, new_short);
+
+            assert!((output.rsi_ma - ma).abs() < 1e-9);
+            assert!((output.long_band - long).abs() < 1e-9);
+            assert!((output.short_band - short).abs() < 1e-9);
+            assert_eq!(
+                output.trailing,
+                if up {
+                    output.long_band
+                } else {
+                    output.short_band
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_qqe_trend_flips() {
+        // A rally followed by a slide flips the trailing line to the short band
+        let prices: Vec<f64> = (0..120)
+            .map(|i| {
+                if i < 70 {
+                    50.0 + i as f64 * 0.5
+                } else {
+                    85.0 - (i - 70) as f64 * 0.8
+                }
+            })
+            .map(|p: f64| p + (p * 3.0).sin() * 0.3)
+            .collect();
+        let result = QQE::new(5, 3, 4.236).unwrap().calculate(&prices).unwrap();
+        let last = result[119].unwrap();
+        assert_eq!(last.trailing, last.short_band);
+    }
+
+    #[test]
+    fn test_qqe_streaming_matches_batch() {
+        let prices = prices();
+        let mut qqe = QQE::new(14, 5, 4.236).unwrap();
+        let batch = qqe.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| qqe.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        qqe.reset();
+        assert_eq!(qqe.next(1.0), None);
+        assert_eq!((qqe.periods(), qqe.factor()), ((14, 5), 4.236));
+    }
+
+    #[test]
+    fn test_qqe_invalid_inputs() {
+        assert!(QQE::new(0, 5, 4.236).is_err());
+        assert!(QQE::new(14, 0, 4.236).is_err());
+        assert!(QQE::new(14, 5, 0.0).is_err());
+        assert!(QQE::new(14, 5, f64::NAN).is_err());
+        assert!(matches!(
+            QQE::new(14, 5, 4.236).unwrap().calculate(&[1.0; 71]),
+            Err(IndicatorError::InsufficientData {
+                required: 72,
+                actual: 71
+            })
+        ));
+    }
+}
@@ -0,0 +1,380 @@
+//! Rolling risk-adjusted return ratios (Sharpe and Sortino)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, RollingStd};
+
+/// Rolling annualized Sharpe ratio of a returns series
+///
+/// Over the last `period` per-bar returns:
+///
+/// Sharpe = mean(r − r_f) / std(r − r_f) × √annualization
+///
+/// where `r_f = risk_free_rate / annualization` is the per-bar share of the
+/// annual risk-free rate and `std` is the sample standard deviation. A
+/// window with no dispersion follows IEEE division: ±∞ for a non-zero mean
+/// excess return and NaN for a zero one.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingSharpe;
+///
+/// let sharpe = RollingSharpe::new(4, 252.0, 0.0)?;
+/// let returns = [0.01, -0.005, 0.007, 0.002, 0.004];
+/// let result = sharpe.calculate(&returns)?;
+///
+/// assert!(result[2].is_none());
+/// assert!(result[4].unwrap() > 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingSharpe {
+    std: RollingStd,
+    annualization: f64,
+    risk_free_rate: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingSharpe {
+    /// Creates a new rolling Sharpe ratio
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of returns in the window (must be >= 2)
+    /// * `annualization` - Bars per year, e.g. `252` for daily returns (must be > 0)
+    /// * `risk_free_rate` - Annual risk-free rate as a decimal (e.g. `0.04`)
+    pub fn new(
+        period: usize,
+        annualization: f64,
+        risk_free_rate: f64,
+    ) -> Result<Self, IndicatorError> {
+        validate(annualization, risk_free_rate)?;
+        Ok(Self {
+            std: RollingStd::new(period)?,
+            annualization,
+            risk_free_rate,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling Sharpe ratio for a batch of returns on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, returns: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period(), returns.len())?;
+
+        let mut state = Self::new(self.period(), self.annualization, self.risk_free_rate)?;
+        Ok(returns.iter().map(|&r| state.next(r)).collect())
+    }
+
+    /// Adds the next per-bar return (streaming mode)
+    pub fn next(&mut self, ret: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let excess = ret - self.risk_free_rate / self.annualization;
+        let std = self.std.next(excess)?;
+        Some(self.std.mean() / std * self.annualization.sqrt())
+    }
+
+    /// Replaces the most recent return, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, ret: f64) -> Option<f64> {
+        self.rollback();
+        self.next(ret)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest return can be undone; further calls do nothing until
+    /// the next return arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.std.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the number of returns in the window
+    pub fn period(&self) -> usize {
+        self.std.period()
+    }
+
+    /// Returns the annualization factor
+    pub fn annualization(&self) -> f64 {
+        self.annualization
+    }
+
+    /// Returns the annual risk-free rate
+    pub fn risk_free_rate(&self) -> f64 {
+        self.risk_free_rate
+    }
+}
+
+/// Rolling annualized Sortino ratio of a returns series
+///
+/// Like [`RollingSharpe`], but only penalises downside volatility:
+///
+/// Sortino = mean(r − r_f) / DD × √annualization, DD = √(Σ min(r − r_f, 0)² / n)
+///
+/// The downside deviation `DD` is taken against the risk-free target over
+/// all `n = period` returns in the window. A window with no downside follows
+/// IEEE division: +∞ for a positive mean excess return.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingSortino;
+///
+/// let sortino = RollingSortino::new(4, 252.0, 0.0)?;
+/// let returns = [0.01, -0.005, 0.007, 0.002, 0.004];
+/// let result = sortino.calculate(&returns)?;
+///
+/// assert!(result[4].unwrap() > 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingSortino {
+    period: usize,
+    annualization: f64,
+    risk_free_rate: f64,
+    /// Excess returns in the window, oldest first
+    window: VecDeque<f64>,
+    sum: f64,
+    /// Sum of squared negative excess returns
+    downside_sum_sq: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingSortino {
+    /// Creates a new rolling Sortino ratio
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of returns in the window (must be >= 2)
+    /// * `annualization` - Bars per year, e.g. `252` for daily returns (must be > 0)
+    /// * `risk_free_rate` - Annual risk-free rate as a decimal (e.g. `0.04`)
+    pub fn new(
+        period: usize,
+        annualization: f64,
+        risk_free_rate: f64,
+    ) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+        validate(annualization, risk_free_rate)?;
+
+        Ok(Self {
+            period,
+            annualization,
+            risk_free_rate,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+            downside_sum_sq: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling Sortino ratio for a batch of returns on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, returns: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period, returns.len())?;
+
+        let mut state = Self::new(self.period, self.annualization, self.risk_free_rate)?;
+        Ok(returns.iter().map(|&r| state.next(r)).collect())
+    }
+
+    /// Adds the next per-bar return (streaming mode)
+    pub fn next(&mut self, ret: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let excess = ret - self.risk_free_rate / self.annualization;
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                self.sum -= oldest;
+                self.downside_sum_sq = (self.downside_sum_sq - oldest.min(0.0).powi(2)).max(0.0);
+            }
+        }
+        self.window.push_back(excess);
+        self.sum += excess;
+        self.downside_sum_sq += excess.min(0.0).powi(2);
+
+        if self.window.len() < self.period {
+            return None;
+        }
+        let n = self.period as f64;
+        let downside_deviation = (self.downside_sum_sq / n).sqrt();
+        Some(self.sum / n / downside_deviation * self.annualization.sqrt())
+    }
+
+    /// Replaces the most recent return, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, ret: f64) -> Option<f64> {
+        self.rollback();
+        self.next(ret)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest return can be undone; further calls do nothing until
+    /// the next return arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+        self.downside_sum_sq = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the number of returns in the window
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the annualization factor
+    pub fn annualization(&self) -> f64 {
+        self.annualization
+    }
+
+    /// Returns the annual risk-free rate
+    pub fn risk_free_rate(&self) -> f64 {
+        self.risk_free_rate
+    }
+}
+
+fn validate(annualization: f64, risk_free_rate: f64) -> Result<(), IndicatorError> {
+    if annualization.is_nan() || annualization <= 0.0 {
+        return Err(IndicatorError::InvalidParameter {
+            field: "annualization",
+            value: annualization,
+            constraint: "> 0",
+        });
+    }
+    if !risk_free_rate.is_finite() {
+        return Err(IndicatorError::InvalidParameter {
+            field: "risk_free_rate",
+            value: risk_free_rate,
+            constraint: "finite",
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn returns() -> Vec<f64> {
+        (0..60)
+            .map(|i| 0.001 + (i as f64 * 0.8).sin() * 0.01)
+            .collect()
+    }
+
+    #[test]
+    fn test_sharpe_matches_direct_formula() {
+        let returns = returns();
+        let (period, annualization, rf) = (20, 252.0, 0.03);
+        let result = RollingSharpe::new(period, annualization, rf)
+            .unwrap()
+            .calculate(&returns)
+            .unwrap();
+
+        assert!(result[..period - 1].iter().all(Option::is_none));
+        for (i, actual) in result.iter().enumerate().skip(period - 1) {
+            let excess: Vec<f64> = returns[i + 1 - period..=i]
+                .iter()
+                .map(|r| r - rf / annualization)
+                .collect();
+            let n = excess.len() as f64;
+            let mean = excess.iter().sum::<f64>() / n;
+            let std = (excess.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+            let expected = mean / std * annualization.sqrt();
+            assert!((actual.unwrap() - expected).abs() < 1e-9, "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_sortino_matches_direct_formula() {
+        let returns = returns();
+        let (period, annualization, rf) = (15, 52.0, 0.02);
+        let result = RollingSortino::new(period, annualization, rf)
+            .unwrap()
+            .calculate(&returns)
+            .unwrap();
+
+        for (i, actual) in result.iter().enumerate().skip(period - 1) {
+            let excess: Vec<f64> = returns[i + 1 - period..=i]
+                .iter()
+                .map(|r| r - rf / annualization)
+                .collect();
+            let n = excess.len() as f64;
+            let mean = excess.iter().sum::<f64>() / n;
+            let downside = (excess.iter().map(|x| x.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+            let expected = mean / downside * annualization.sqrt();
+            assert!((actual.unwrap() - expected).abs() < 1e-9, "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_sortino_exceeds_sharpe_with_upside_skew() {
+        // Large gains and small losses: downside deviation is well below total volatility
+        let returns = [0.05, -0.005, 0.04, -0.004, 0.06, -0.006, 0.03, -0.002];
+        let sharpe = RollingSharpe::new(8, 252.0, 0.0).unwrap();
+        let sortino = RollingSortino::new(8, 252.0, 0.0).unwrap();
+        let sharpe = sharpe.calculate(&returns).unwrap()[7].unwrap();
+        let sortino = sortino.calculate(&returns).unwrap()[7].unwrap();
+        assert!(sortino > sharpe && sharpe > 0.0);
+
+        let no_downside = RollingSortino::new(2, 252.0, 0.0)
+            .unwrap()
+            .calculate(&[0.01, 0.02])
+            .unwrap();
+        assert_eq!(no_downside[1], Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let returns = returns();
+        let mut sharpe = RollingSharpe::new(10, 252.0, 0.01).unwrap();
+        let mut sortino = RollingSortino::new(10, 252.0, 0.01).unwrap();
+        let sharpe_batch = sharpe.calculate(&returns).unwrap();
+        let sortino_batch = sortino.calculate(&returns).unwrap();
+        for (i, &r) in returns.iter().enumerate() {
+            assert_eq!(sharpe.next(r), sharpe_batch[i]);
+            assert_eq!(sortino.next(r), sortino_batch[i]);
+        }
+
+        assert!(RollingSharpe::new(1, 252.0, 0.0).is_err());
+        assert!(RollingSharpe::new(10, 0.0, 0.0).is_err());
+        assert!(RollingSortino::new(1, 252.0, 0.0).is_err());
+        assert!(RollingSortino::new(10, 252.0, f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Heuristic market regime classification
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, LinearRegression, RealizedVol, RollingQuantile};
+
+/// Market regime of one bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regime {
+    /// Prices follow a rising line closely
+    TrendingUp,
+    /// Prices follow a falling line closely
+    TrendingDown,
+    /// No clear direction at normal volatility
+    Ranging,
+    /// Volatility is unusually high for the recent history, whatever the direction
+    HighVolatility,
+}
+
+impl Regime {
+    /// Whether the regime is one of the trending states
+    pub fn is_trending(self) -> bool {
+        matches!(self, Self::TrendingUp | Self::TrendingDown)
+    }
+}
+
+/// Volatility/trend heuristic regime classifier
+///
+/// Labels each bar from two measurements over the last `period` bars:
+///
+/// * **Volatility**: close-to-close realized volatility, compared against
+///   its own `vol_quantile` quantile over the last `vol_lookback` bars. A
+///   reading above that quantile is [`Regime::HighVolatility`], which takes
+///   precedence so strategies can stand aside in disorderly markets.
+/// * **Trend**: the R² of a rolling least-squares line through the prices.
+///   At or above `trend_threshold`, the sign of the slope gives
+///   [`Regime::TrendingUp`] or [`Regime::TrendingDown`]; otherwise (or for a
+///   flat window) the bar is [`Regime::Ranging`].
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Regime, RegimeClassifier};
+///
+/// // A quiet sideways market followed by a steady advance
+/// let prices: Vec<f64> = (0..120)
+///     .map(|i| {
+///         let wiggle = if i % 2 == 0 { 0.2 } else { -0.2 };
+///         if i < 80 { 100.0 + wiggle } else { 100.0 + (i - 80) as f64 + wiggle }
+///     })
+///     .collect();
+///
+/// let classifier = RegimeClassifier::new(10, 0.8, 40, 0.9)?;
+/// let regimes = classifier.calculate(&prices)?;
+///
+/// assert_eq!(regimes[75], Some(Regime::Ranging));
+/// assert_eq!(regimes[119], Some(Regime::TrendingUp));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegimeClassifier {
+    trend_threshold: f64,
+    regression: LinearRegression,
+    volatility: RealizedVol,
+    vol_cutoff: RollingQuantile,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RegimeClassifier {
+    /// Creates a new regime classifier
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Bars used for the trend fit and realized volatility (must be >= 2)
+    /// * `trend_threshold` - Minimum R² for a trending label (must be in [0, 1])
+    /// * `vol_lookback` - Number of volatility readings the cutoff is taken over (must be > 0)
+    /// * `vol_quantile` - Quantile of recent volatility above which a bar is
+    ///   high-volatility (must be in [0, 1], e.g. `0.9`)
+    pub fn new(
+        period: usize,
+        trend_threshold: f64,
+        vol_lookback: usize,
+        vol_quantile: f64,
+    ) -> Result<Self, IndicatorError> {
+        if !(0.0..=1.0).contains(&trend_threshold) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "trend_threshold",
+                value: trend_threshold,
+                constraint: "in [0, 1]",
+            });
+        }
+        if vol_lookback == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "vol_lookback",
+                value: vol_lookback as f64,
+                constraint: "> 0",
+            });
+        }
+        if !(0.0..=1.0).contains(&vol_quantile) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "vol_quantile",
+                value: vol_quantile,
+                constraint: "in [0, 1]",
+            });
+        }
+
+        Ok(Self {
+            trend_threshold,
+            regression: LinearRegression::new(period, 0.0)?,
+            volatility: RealizedVol::new(period, 1.0)?,
+            vol_cutoff: RollingQuantile::new(vol_lookback, vol_quantile)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Classifies a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first
+    /// `period + vol_lookback - 1` values are `None`, while the volatility
+    /// history fills up.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<Regime>>, IndicatorError> {
+        check_min_length(self.period() + self.vol_lookback(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<Regime> {
+        self.undo = Snapshot::of(self);
+        let fit = self.regression.next(price);
+        let volatility = self.volatility.next(price)?;
+        let cutoff = self.vol_cutoff.next(volatility)?;
+        let fit = fit?;
+
+        Some(if volatility > cutoff {
+            Regime::HighVolatility
+        } else if fit.r_squared >= self.trend_threshold && fit.slope > 0.0 {
+            Regime::TrendingUp
+        } else if fit.r_squared >= self.trend_threshold && fit.slope < 0.0 {
+            Regime::TrendingDown
+        } else {
+            Regime::Ranging
+        })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<Regime> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the state so the classifier can be reused on a new series
+    pub fn reset(&mut self) {
+        self.regression.reset();
+        self.volatility.reset();
+        self.vol_cutoff.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the trend and volatility window
+    pub fn period(&self) -> usize {
+        self.regression.period()
+    }
+
+    /// Returns the minimum R² for a trending label
+    pub fn trend_threshold(&self) -> f64 {
+        self.trend_threshold
+    }
+
+    /// Returns the number of volatility readings the cutoff is taken over
+    pub fn vol_lookback(&self) -> usize {
+        self.vol_cutoff.period()
+    }
+
+    /// Returns the volatility quantile used as the high-volatility cutoff
+    pub fn vol_quantile(&self) -> f64 {
+        self.vol_cutoff.quantile()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quiet range, uptrend, downtrend, then a burst of wide swings
+    fn prices() -> Vec<f64> {
+        let mut prices = Vec::new();
+        let mut level = 100.0;
+        for i in 0..200 {
+            let wiggle = if i % 2 == 0 { 0.3 } else { -0.3 };
+            match i {
+                0..=79 => {}
+                80..=119 => level += 1.0,
+                120..=159 => level -= 1.0,
+                _ => {}
+            }
+            let shock = if i >= 170 { wiggle * 15.0 } else { wiggle };
+            prices.push(level + shock);
+        }
+        prices
+    }
+
+    #[test]
+    fn test_regimes_follow_market_phases() {
+        let classifier = RegimeClassifier::new(10, 0.8, 60, 0.95).unwrap();
+        let regimes = classifier.calculate(&prices()).unwrap();
+
+        assert!(regimes[..69].iter().all(Option::is_none));
+        assert_eq!(regimes[75], Some(Regime::Ranging));
+        assert_eq!(regimes[115], Some(Regime::TrendingUp));
+        assert_eq!(regimes[155], Some(Regime::TrendingDown));
+        assert_eq!(regimes[175], Some(Regime::HighVolatility));
+        assert!(regimes[115].unwrap().is_trending());
+        assert!(!Regime::HighVolatility.is_trending());
+    }
+
+    #[test]
+    fn test_flat_window_is_ranging() {
+        let classifier = RegimeClassifier::new(5, 0.5, 5, 0.9).unwrap();
+        let regimes = classifier.calculate(&[50.0; 12]).unwrap();
+        assert_eq!(regimes[11], Some(Regime::Ranging));
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let prices = prices();
+        let mut classifier = RegimeClassifier::new(8, 0.7, 30, 0.9).unwrap();
+        let batch = classifier.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| classifier.next(p)).collect();
+        assert_eq!(batch, streamed);
+        assert!(batch[37].is_some() && batch[36].is_none());
+
+        classifier.reset();
+        assert_eq!(classifier.next(100.0), None);
+
+        assert!(RegimeClassifier::new(1, 0.5, 10, 0.9).is_err());
+        assert!(RegimeClassifier::new(10, 1.5, 10, 0.9).is_err());
+        assert!(RegimeClassifier::new(10, 0.5, 0, 0.9).is_err());
+        assert!(RegimeClassifier::new(10, 0.5, 10, -0.1).is_err());
+        assert!(classifier.calculate(&prices[..37]).is_err());
+    }
+}
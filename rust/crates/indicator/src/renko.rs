@@ -0,0 +1,379 @@
+//! Renko brick charts
+//!
+//! [`Renko`] turns a stream of prices (ticks or bar closes) into fixed-height
+//! bricks, ignoring time entirely. A new brick is drawn only once price moves
+//! a full brick beyond the last one; reversing direction takes a move of two
+//! bricks, so small oscillations inside the last brick are filtered out.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::renko::{BrickDirection, BrickSize, Renko};
+//!
+//! let renko = Renko::new(BrickSize::Fixed(1.0))?;
+//! let bricks = renko.calculate(&[10.0, 10.4, 11.2, 13.1, 12.5, 10.9]);
+//!
+//! let directions: Vec<BrickDirection> = bricks.iter().map(|b| b.direction).collect();
+//! assert_eq!(
+//!     directions,
+//!     [BrickDirection::Up, BrickDirection::Up, BrickDirection::Up, BrickDirection::Down]
+//! );
+//! assert_eq!((bricks[3].open, bricks[3].close), (12.0, 11.0));
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::{Candle, IndicatorError, ATR};
+
+/// Rule that sets the height of each brick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrickSize {
+    /// Constant brick height in price units
+    Fixed(f64),
+    /// Brick height tracking `multiplier` times the Wilder ATR of price changes
+    Atr {
+        /// ATR lookback period
+        period: usize,
+        /// Number of ATRs per brick
+        multiplier: f64,
+    },
+}
+
+/// Direction of a Renko brick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrickDirection {
+    /// Brick closes above its open
+    Up,
+    /// Brick closes below its open
+    Down,
+}
+
+/// A completed Renko brick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brick {
+    /// Index of the input price that completed the brick
+    pub index: usize,
+    /// Price level the brick starts from
+    pub open: f64,
+    /// Price level the brick ends at
+    pub close: f64,
+    /// Whether the brick moved up or down
+    pub direction: BrickDirection,
+}
+
+/// Price movement since the last completed brick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartialBrick {
+    /// Close of the last completed brick, or the first price before any brick
+    pub open: f64,
+    /// Highest price seen since the last brick
+    pub high: f64,
+    /// Lowest price seen since the last brick
+    pub low: f64,
+    /// Most recent price
+    pub last: f64,
+}
+
+/// Streaming Renko brick generator
+///
+/// The first price anchors the chart. With a [`BrickSize::Atr`] size the
+/// ATR is computed from absolute close-to-close moves (there is no intrabar
+/// range in a price stream) and re-read on every input, so bricks grow and
+/// shrink with volatility; no bricks are drawn until the ATR has warmed up.
+///
+/// A single large move can complete several bricks at once, which is why
+/// [`next`](Renko::next) returns a `Vec`. Movement that has not yet filled a
+/// brick is buffered and exposed through [`partial`](Renko::partial).
+#[derive(Debug, Clone)]
+pub struct Renko {
+    brick_size: BrickSize,
+    /// ATR state when using an ATR-based brick size
+    atr: Option<ATR>,
+    /// Number of prices seen so far
+    count: usize,
+    prev_price: Option<f64>,
+    /// Upper and lower edge of the last brick (equal to the anchor before the first)
+    top: f64,
+    bottom: f64,
+    partial: Option<PartialBrick>,
+}
+
+impl Renko {
+    /// Creates a new Renko generator
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the fixed size or ATR multiplier is not
+    /// positive, or if the ATR period is zero.
+    pub fn new(brick_size: BrickSize) -> Result<Self, IndicatorError> {
+        let atr = match brick_size {
+            BrickSize::Fixed(size) => {
+                if size.is_nan() || size <= 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "brick_size",
+                        value: size,
+                        constraint: "> 0",
+                    });
+                }
+                None
+            }
+            BrickSize::Atr { period, multiplier } => {
+                if multiplier.is_nan() || multiplier <= 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "multiplier",
+                        value: multiplier,
+                        constraint: "> 0",
+                    });
+                }
+                Some(ATR::new(period)?)
+            }
+        };
+
+        Ok(Self {
+            brick_size,
+            atr,
+            count: 0,
+            prev_price: None,
+            top: 0.0,
+            bottom: 0.0,
+            partial: None,
+        })
+    }
+
+    /// Builds bricks from a price series on a fresh state
+    pub fn calculate(&self, prices: &[f64]) -> Vec<Brick> {
+        let mut state = self.fresh();
+        prices.iter().flat_map(|&price| state.next(price)).collect()
+    }
+
+    /// Builds bricks from candle closes on a fresh state
+    pub fn calculate_candles(&self, candles: &[Candle]) -> Vec<Brick> {
+        let mut state = self.fresh();
+        candles
+            .iter()
+            .flat_map(|candle| state.next(candle.close))
+            .collect()
+    }
+
+    /// Adds the next price (streaming mode), returning any bricks it completed
+    pub fn next(&mut self, price: f64) -> Vec<Brick> {
+        let index = self.count;
+        self.count += 1;
+
+        let size = match self.brick_size {
+            BrickSize::Fixed(size) => Some(size),
+            BrickSize::Atr { multiplier, .. } => match (&mut self.atr, self.prev_price) {
+                // High/low spanning both prices makes the true range |price − prev|
+                (Some(atr), Some(prev)) => atr
+                    .next(price.max(prev), price.min(prev), price)
+                    .atr
+                    .map(|value| value * multiplier),
+                _ => None,
+            },
+        };
+        self.prev_price = Some(price);
+
+        let partial = match self.partial {
+            Some(partial) => PartialBrick {
+                high: partial.high.max(price),
+                low: partial.low.min(price),
+                last: price,
+                ..partial
+            },
+            None => {
+                self.top = price;
+                self.bottom = price;
+                PartialBrick {
+                    open: price,
+                    high: price,
+                    low: price,
+                    last: price,
+                }
+            }
+        };
+        self.partial = Some(partial);
+
+        let Some(size) = size.filter(|&size| size > 0.0) else {
+            return Vec::new();
+        };
+
+        let mut bricks = Vec::new();
+        while price >= self.top + size {
+            bricks.push(Brick {
+                index,
+                open: self.top,
+                close: self.top + size,
+                direction: BrickDirection::Up,
+            });
+            self.bottom = self.top;
+            self.top += size;
+        }
+        while price <= self.bottom - size {
+            bricks.push(Brick {
+                index,
+                open: self.bottom,
+                close: self.bottom - size,
+                direction: BrickDirection::Down,
+            });
+            self.top = self.bottom;
+            self.bottom -= size;
+        }
+
+        if let Some(last) = bricks.last() {
+            self.partial = Some(PartialBrick {
+                open: last.close,
+                high: price,
+                low: price,
+                last: price,
+            });
+        }
+        bricks
+    }
+
+    /// Returns the movement buffered since the last completed brick
+    pub fn partial(&self) -> Option<&PartialBrick> {
+        self.partial.as_ref()
+    }
+
+    /// Current brick height; `None` while an ATR-based size is warming up
+    pub fn current_size(&self) -> Option<f64> {
+        match self.brick_size {
+            BrickSize::Fixed(size) => Some(size),
+            BrickSize::Atr { multiplier, .. } => self
+                .atr
+                .as_ref()
+                .and_then(ATR::value)
+                .map(|value| value * multiplier),
+        }
+    }
+
+    /// Resets the generator so the next price anchors a new chart
+    pub fn reset(&mut self) {
+        if let Some(atr) = &mut self.atr {
+            atr.reset();
+        }
+        self.count = 0;
+        self.prev_price = None;
+        self.top = 0.0;
+        self.bottom = 0.0;
+        self.partial = None;
+    }
+
+    /// Returns the brick sizing rule
+    pub fn brick_size(&self) -> BrickSize {
+        self.brick_size
+    }
+
+    fn fresh(&self) -> Self {
+        let mut state = self.clone();
+        state.reset();
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_bricks_and_two_brick_reversal() {
+        let renko = Renko::new(BrickSize::Fixed(1.0)).unwrap();
+        // 10 → 12.5 draws two up bricks; 11.2 stays inside the reversal band
+        let bricks = renko.calculate(&[10.0, 12.5, 11.2, 9.9, 13.0]);
+
+        let spans: Vec<(usize, f64, f64)> =
+            bricks.iter().map(|b| (b.index, b.open, b.close)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 10.0, 11.0),
+                (1, 11.0, 12.0),
+                (3, 11.0, 10.0),
+                (4, 11.0, 12.0),
+                (4, 12.0, 13.0)
+            ]
+        );
+        assert_eq!(bricks[2].direction, BrickDirection::Down);
+        assert_eq!(bricks[3].direction, BrickDirection::Up);
+    }
+
+    #[test]
+    fn test_partial_brick_buffers_movement() {
+        let mut renko = Renko::new(BrickSize::Fixed(2.0)).unwrap();
+        assert!(renko.next(100.0).is_empty());
+        assert!(renko.next(101.5).is_empty());
+        assert!(renko.next(99.0).is_empty());
+        assert_eq!(
+            renko.partial(),
+            Some(&PartialBrick {
+                open: 100.0,
+                high: 101.5,
+                low: 99.0,
+                last: 99.0
+            })
+        );
+
+        let bricks = renko.next(102.5);
+        assert_eq!(bricks.len(), 1);
+        let partial = renko.partial().unwrap();
+        assert_eq!((partial.open, partial.last), (102.0, 102.5));
+    }
+
+    #[test]
+    fn test_atr_brick_size_warms_up() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 50.0 + i as f64 * 0.8 + (i as f64).sin())
+            .collect();
+        let mut renko = Renko::new(BrickSize::Atr {
+            period: 5,
+            multiplier: 1.5,
+        })
+        .unwrap();
+
+        for &price in &prices[..5] {
+            assert!(renko.next(price).is_empty());
+            assert_eq!(renko.current_size(), None);
+        }
+        let batch = renko.calculate(&prices);
+        assert!(!batch.is_empty());
+        assert!(batch.iter().all(|b| b.index >= 5));
+        renko.next(prices[5]);
+        assert!(renko.current_size().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_reset() {
+        let prices: Vec<f64> = (0..60)
+            .map(|i| 20.0 + (i as f64 * 0.3).sin() * 4.0)
+            .collect();
+        let mut renko = Renko::new(BrickSize::Fixed(0.5)).unwrap();
+        let batch = renko.calculate(&prices);
+        let streamed: Vec<Brick> = prices.iter().flat_map(|&p| renko.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        let candles: Vec<Candle> = prices
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| Candle::new(i as i64, p, p, p, p, 0.0))
+            .collect();
+        assert_eq!(renko.calculate_candles(&candles), batch);
+
+        renko.reset();
+        assert_eq!(renko.partial(), None);
+    }
+
+    #[test]
+    fn test_invalid_brick_sizes() {
+        assert!(Renko::new(BrickSize::Fixed(0.0)).is_err());
+        assert!(Renko::new(BrickSize::Fixed(f64::NAN)).is_err());
+        assert!(Renko::new(BrickSize::Atr {
+            period: 0,
+            multiplier: 1.0
+        })
+        .is_err());
+        assert!(Renko::new(BrickSize::Atr {
+            period: 14,
+            multiplier: -1.0
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Return series computed from prices
+//!
+//! Helpers for the return transformations most statistics start from:
+//! one-bar simple and log returns, cumulative returns against the first
+//! price, and compounding of per-bar returns into longer periods (e.g. daily
+//! into weekly). Prices must be positive.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::returns::{compound, cumulative_returns, simple_returns};
+//!
+//! let prices = [100.0, 110.0, 99.0];
+//! let returns = simple_returns(&prices)?;
+//!
+//! assert_eq!(returns.len(), 2);
+//! assert!((returns[0] - 0.10).abs() < 1e-12);
+//! assert!((returns[1] + 0.10).abs() < 1e-12);
+//! assert!((compound(&returns) + 0.01).abs() < 1e-12);
+//! assert!((cumulative_returns(&prices)?[2] + 0.01).abs() < 1e-12);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::IndicatorError;
+
+/// One-bar simple returns `Pₜ / Pₜ₋₁ − 1`
+///
+/// The result has one value fewer than `prices`.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for fewer than 2 prices and `CalculationError`
+/// if a price is not positive.
+pub fn simple_returns(prices: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+    validate(prices, 2)?;
+    Ok(prices.windows(2).map(|w| w[1] / w[0] - 1.0).collect())
+}
+
+/// One-bar log returns `ln(Pₜ / Pₜ₋₁)`
+///
+/// The result has one value fewer than `prices`. Log returns add up over
+/// time, so their sum is the log of the total growth.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for fewer than 2 prices and `CalculationError`
+/// if a price is not positive.
+pub fn log_returns(prices: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+    validate(prices, 2)?;
+    Ok(prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect())
+}
+
+/// Cumulative simple return since the first price, `Pₜ / P₀ − 1`, at every bar
+///
+/// The result has the same length as `prices` and starts at `0.0`.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` for an empty series and `CalculationError` if
+/// a price is not positive.
+pub fn cumulative_returns(prices: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+    validate(prices, 1)?;
+    let first = prices[0];
+    Ok(prices.iter().map(|price| price / first - 1.0).collect())
+}
+
+/// Total compounded return of a series of simple returns, `∏(1 + rᵢ) − 1`
+///
+/// An empty series compounds to `0.0`.
+pub fn compound(returns: &[f64]) -> f64 {
+    returns.iter().map(|r| 1.0 + r).product::<f64>() - 1.0
+}
+
+/// Compounds per-bar simple returns into consecutive non-overlapping periods
+///
+/// Each output value is the compounded return of `period` consecutive input
+/// returns, e.g. `period = 5` turns daily returns into weekly ones. A
+/// trailing block shorter than `period` is dropped.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `period` is zero.
+pub fn compound_periods(returns: &[f64], period: usize) -> Result<Vec<f64>, IndicatorError> {
+    if period == 0 {
+        return Err(IndicatorError::InvalidParameter {
+            field: "period",
+            value: period as f64,
+            constraint: "> 0",
+        });
+    }
+    Ok(returns.chunks_exact(period).map(compound).collect())
+}
+
+fn validate(prices: &[f64], required: usize) -> Result<(), IndicatorError> {
+    if prices.len() < required {
+        return Err(IndicatorError::InsufficientData {
+            required,
+            actual: prices.len(),
+        });
+    }
+    if let Some((index, price)) = prices
+        .iter()
+        .enumerate()
+        .find(|(_, p)| p.is_nan() || **p <= 0.0)
+    {
+        return Err(IndicatorError::CalculationError {
+            operation: "returns",
+            reason: format!("prices must be positive, got {price} at index {index}"),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices() -> Vec<f64> {
+        (0..21)
+            .map(|i| 50.0 * (1.0 + 0.01 * (i as f64 * 0.9).sin()).powi(i))
+            .collect()
+    }
+
+    #[test]
+    fn test_simple_and_log_returns_agree() {
+        let prices = prices();
+        let simple = simple_returns(&prices).unwrap();
+        let log = log_returns(&prices).unwrap();
+
+        assert_eq!(simple.len(), prices.len() - 1);
+        for (s, l) in simple.iter().zip(&log) {
+            assert!(((1.0 + s).ln() - l).abs() < 1e-12);
+        }
+        // Log returns sum to the log of total growth
+        let total = (prices[20] / prices[0]).ln();
+        assert!((log.iter().sum::<f64>() - total).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cumulative_and_compounded_returns() {
+        let prices = prices();
+        let simple = simple_returns(&prices).unwrap();
+        let cumulative = cumulative_returns(&prices).unwrap();
+
+        assert_eq!(cumulative[0], 0.0);
+        for (i, c) in cumulative.iter().enumerate().skip(1) {
+            assert!((c - compound(&simple[..i])).abs() < 1e-12);
+        }
+        assert_eq!(compound(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_compound_periods() {
+        let returns = [0.1, -0.1, 0.05, 0.05, 0.2];
+        let weekly = compound_periods(&returns, 2).unwrap();
+
+        assert_eq!(weekly.len(), 2);
+        assert!((weekly[0] + 0.01).abs() < 1e-12);
+        assert!((weekly[1] - 0.1025).abs() < 1e-12);
+        assert!(compound_periods(&returns, 0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_prices() {
+        assert!(matches!(
+            simple_returns(&[1.0]),
+            Err(IndicatorError::InsufficientData {
+                required: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            log_returns(&[1.0, -2.0]),
+            Err(IndicatorError::CalculationError { .. })
+        ));
+        assert!(cumulative_returns(&[]).is_err());
+        assert!(cumulative_returns(&[f64::NAN]).is_err());
+    }
+}
@@ -0,0 +1,534 @@
+//! Rolling minimum, maximum and quantile
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Rolling maximum over the last `period` values
+///
+/// Keeps a monotonic deque of candidate maxima: a value is dropped as soon as
+/// a later value at least as large arrives, since it can never be the window
+/// maximum again. Each value is pushed and popped at most once, so updates
+/// are amortised O(1) regardless of the window length.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingMax;
+///
+/// let max = RollingMax::new(3)?;
+/// let result = max.calculate(&[1.0, 3.0, 2.0, 1.0, 0.5])?;
+///
+/// assert_eq!(result, vec![None, None, Some(3.0), Some(3.0), Some(2.0)]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMax {
+    window: MonotonicDeque,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingMax {
+    /// Creates a new rolling maximum
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            window: MonotonicDeque::new(period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling maximum for a batch of values on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period(), values.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value (streaming mode)
+    ///
+    /// Returns `None` until `period` values have been seen.
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        self.window.push(value);
+        self.window.is_full().then(|| self.window.front())
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Maximum of the values seen so far in the window, even before it is full
+    pub fn value(&self) -> Option<f64> {
+        self.window.current()
+    }
+
+    /// Number of values since the current maximum arrived, `0` for the
+    /// latest value
+    ///
+    /// Ties resolve to the most recent value.
+    pub fn age(&self) -> Option<usize> {
+        self.window.age()
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.window.period
+    }
+}
+
+/// Rolling minimum over the last `period` values
+///
+/// The mirror image of [`RollingMax`], with the same amortised O(1) updates.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingMin;
+///
+/// let min = RollingMin::new(2)?;
+/// let result = min.calculate(&[4.0, 2.0, 5.0, 6.0])?;
+///
+/// assert_eq!(result, vec![None, Some(2.0), Some(2.0), Some(5.0)]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMin {
+    /// Maximum of the negated values
+    window: MonotonicDeque,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingMin {
+    /// Creates a new rolling minimum
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            window: MonotonicDeque::new(period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling minimum for a batch of values on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period(), values.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value (streaming mode)
+    ///
+    /// Returns `None` until `period` values have been seen.
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        self.window.push(-value);
+        self.window.is_full().then(|| -self.window.front())
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Minimum of the values seen so far in the window, even before it is full
+    pub fn value(&self) -> Option<f64> {
+        self.window.current().map(|value| -value)
+    }
+
+    /// Number of values since the current minimum arrived, `0` for the
+    /// latest value
+    ///
+    /// Ties resolve to the most recent value.
+    pub fn age(&self) -> Option<usize> {
+        self.window.age()
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.window.period
+    }
+}
+
+/// Sliding-window maximum backing [`RollingMax`] and [`RollingMin`]
+///
+/// Holds `(index, value)` pairs with strictly decreasing values, so the
+/// front is always the window maximum.
+#[derive(Debug, Clone)]
+struct MonotonicDeque {
+    period: usize,
+    /// Number of values pushed so far
+    count: usize,
+    entries: VecDeque<(usize, f64)>,
+}
+
+impl MonotonicDeque {
+    fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        Ok(Self {
+            period,
+            count: 0,
+            entries: VecDeque::new(),
+        })
+    }
+
+    fn push(&mut self, value: f64) {
+        let index = self.count;
+        self.count += 1;
+        while self.entries.back().is_some_and(|&(_, v)| v <= value) {
+            self.entries.pop_back();
+        }
+        self.entries.push_back((index, value));
+        while self
+            .entries
+            .front()
+            .is_some_and(|&(i, _)| i + self.period <= index)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.count >= self.period
+    }
+
+    /// Window maximum; only valid after at least one push
+    fn front(&self) -> f64 {
+        self.entries[0].1
+    }
+
+    fn current(&self) -> Option<f64> {
+        self.entries.front().map(|&(_, value)| value)
+    }
+
+    fn age(&self) -> Option<usize> {
+        self.entries
+            .front()
+            .map(|&(index, _)| self.count - 1 - index)
+    }
+
+    fn clear(&mut self) {
+        self.count = 0;
+        self.entries.clear();
+    }
+}
+
+/// Rolling quantile (e.g. rolling median) over the last `period` values
+///
+/// Keeps the window both in arrival order and as a sorted vector; each
+/// update finds the outgoing and incoming positions by binary search, so the
+/// selected quantile is read directly from the sorted order. Quantiles that fall
+/// between order statistics are linearly interpolated (the same definition
+/// as NumPy's default `linear` method).
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingQuantile;
+///
+/// let median = RollingQuantile::median(3)?;
+/// let result = median.calculate(&[5.0, 1.0, 4.0, 2.0, 3.0])?;
+///
+/// assert_eq!(result, vec![None, None, Some(4.0), Some(2.0), Some(3.0)]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingQuantile {
+    period: usize,
+    quantile: f64,
+    /// Values in arrival order, oldest first
+    window: VecDeque<f64>,
+    /// The same values in ascending order
+    sorted: Vec<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingQuantile {
+    /// Creates a new rolling quantile
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be > 0)
+    /// * `quantile` - Quantile to report (must be in [0, 1]; `0.5` is the median)
+    pub fn new(period: usize, quantile: f64) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+        if !(0.0..=1.0).contains(&quantile) {
+            return Err(IndicatorError::InvalidParameter {
+                field: "quantile",
+                value: quantile,
+                constraint: "in [0, 1]",
+            });
+        }
+
+        Ok(Self {
+            period,
+            quantile,
+            window: VecDeque::with_capacity(period),
+            sorted: Vec::with_capacity(period),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Creates a rolling median
+    pub fn median(period: usize) -> Result<Self, IndicatorError> {
+        Self::new(period, 0.5)
+    }
+
+    /// Calculates the rolling quantile for a batch of values on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period, values.len())?;
+
+        let mut state = Self::new(self.period, self.quantile)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value (streaming mode)
+    ///
+    /// Returns `None` until `period` values have been seen.
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                let position = self
+                    .sorted
+                    .partition_point(|v| v.total_cmp(&oldest).is_lt());
+                self.sorted.remove(position);
+            }
+        }
+        self.window.push_back(value);
+        let position = self.sorted.partition_point(|v| v.total_cmp(&value).is_lt());
+        self.sorted.insert(position, value);
+
+        if self.window.len() < self.period {
+            return None;
+        }
+        let rank = self.quantile * (self.period - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+        Some(self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * weight)
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sorted.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the reported quantile
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> Vec<f64> {
+        (0..80)
+            .map(|i| ((i * 37) % 23) as f64 + (i as f64 * 0.3).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_min_max_match_window_scan() {
+        let values = values();
+        let period = 7;
+        let max = RollingMax::new(period).unwrap().calculate(&values).unwrap();
+        let min = RollingMin::new(period).unwrap().calculate(&values).unwrap();
+
+        assert!(max[..period - 1].iter().all(Option::is_none));
+        for i in period - 1..values.len() {
+            let window = &values[i + 1 - period..=i];
+            assert_eq!(
+                max[i],
+                Some(window.iter().copied().fold(f64::MIN, f64::max))
+            );
+            assert_eq!(
+                min[i],
+                Some(window.iter().copied().fold(f64::MAX, f64::min))
+            );
+        }
+    }
+
+    #[test]
+    fn test_rolling_quantile_matches_sorted_window() {
+        let values = values();
+        let period = 6;
+        for quantile in [0.0, 0.25, 0.5, 0.9, 1.0] {
+            let result = RollingQuantile::new(period, quantile)
+                .unwrap()
+                .calculate(&values)
+                .unwrap();
+            for i in period - 1..values.len() {
+                let mut window = values[i + 1 - period..=i].to_vec();
+                window.sort_by(f64::total_cmp);
+                let rank = quantile * (period - 1) as f64;
+                let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+                let expected = window[lo] + (window[hi] - window[lo]) * (rank - lo as f64);
+                assert!((result[i].unwrap() - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_median_with_duplicates() {
+        let mut median = RollingQuantile::median(4).unwrap();
+        let result: Vec<Option<f64>> = [2.0, 2.0, 1.0, 2.0, 3.0, 3.0]
+            .iter()
+            .map(|&v| median.next(v))
+            .collect();
+        assert_eq!(
+            result,
+            vec![None, None, None, Some(2.0), Some(2.0), Some(2.5)]
+        );
+    }
+
+    #[test]
+    fn test_streaming_value_and_reset() {
+        let mut max = RollingMax::new(3).unwrap();
+        assert_eq!(max.value(), None);
+        assert_eq!(max.next(1.0), None);
+        assert_eq!(max.value(), Some(1.0));
+        max.reset();
+        assert_eq!(max.value(), None);
+
+        let mut min = RollingMin::new(1).unwrap();
+        assert_eq!(min.next(-3.0), Some(-3.0));
+        assert_eq!(min.next(4.0), Some(4.0));
+    }
+
+    #[test]
+    fn test_age_of_extremes() {
+        let mut max = RollingMax::new(3).unwrap();
+        let mut min = RollingMin::new(3).unwrap();
+        assert_eq!((max.age(), min.age()), (None, None));
+
+        let ages: Vec<_> = [5.0, 1.0, 5.0, 2.0, 3.0, 4.0]
+            .iter()
+            .map(|&v| {
+                max.next(v);
+                min.next(v);
+                (max.age().unwrap(), min.age().unwrap())
+            })
+            .collect();
+        // The repeated 5.0 takes over as the maximum until it leaves the window
+        assert_eq!(ages, vec![(0, 0), (1, 0), (0, 1), (1, 2), (2, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(RollingMax::new(0).is_err());
+        assert!(RollingMin::new(0).is_err());
+        assert!(RollingQuantile::new(3, 1.5).is_err());
+        assert!(RollingQuantile::new(3, f64::NAN).is_err());
+        assert!(RollingQuantile::median(0).is_err());
+        assert!(RollingMax::new(3).unwrap().calculate(&[1.0]).is_err());
+    }
+}
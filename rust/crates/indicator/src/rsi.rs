@@ -0,0 +1,664 @@
+//! Relative Strength Index (RSI), Stochastic RSI and Connors RSI
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::sma::RollingMean;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, RollingMax, RollingMin};
+
+/// Relative Strength Index indicator with Wilder smoothing
+///
+/// # Formula
+///
+/// RS = AvgGain / AvgLoss
+/// RSI = 100 − 100 / (1 + RS)
+///
+/// The first averages are the simple means of the first `period` gains and
+/// losses; later values use `Avg = (Avg_prev × (period − 1) + x) / period`,
+/// as in [`ATR`](crate::ATR). A window with no losses reports 100, and one
+/// without any price change reports 50.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RSI;
+///
+/// let prices = [10.0, 11.0, 10.5, 11.5, 12.0];
+/// let result = RSI::new(4)?.calculate(&prices)?;
+///
+/// assert!(result[3].is_none());
+/// // Average gain 2.5 / 4, average loss 0.5 / 4
+/// assert!((result[4].unwrap() - 100.0 * 5.0 / 6.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RSI {
+    period: usize,
+    prev: Option<f64>,
+    /// Gains and losses summed during warmup, Wilder averages afterwards
+    gain: f64,
+    loss: f64,
+    count: usize,
+    /// `prev`, `gain`, `loss` and `count` before the last `next`, or `None`
+    /// when there is nothing to roll back
+    undo: Option<(Option<f64>, f64, f64, usize)>,
+}
+
+impl RSI {
+    /// Creates a new RSI indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Wilder smoothing period (must be > 0, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            prev: None,
+            gain: 0.0,
+            loss: 0.0,
+            count: 0,
+            undo: None,
+        })
+    }
+
+    /// Calculates RSI for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period + 1, prices.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` price changes have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Some((self.prev, self.gain, self.loss, self.count));
+        let change = price - self.prev.replace(price)?;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+
+        let period = self.period as f64;
+        if self.count < self.period {
+            self.gain += gain;
+            self.loss += loss;
+            self.count += 1;
+            if self.count < self.period {
+                return None;
+            }
+            self.gain /= period;
+            self.loss /= period;
+        } else {
+            self.gain = (self.gain * (period - 1.0) + gain) / period;
+            self.loss = (self.loss * (period - 1.0) + loss) / period;
+        }
+
+        if self.loss > 0.0 {
+            Some(100.0 - 100.0 / (1.0 + self.gain / self.loss))
+        } else if self.gain > 0.0 {
+            Some(100.0)
+        } else {
+            Some(50.0)
+        }
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some((prev, gain, loss, count)) = self.undo.take() {
+            self.prev = prev;
+            self.gain = gain;
+            self.loss = loss;
+            self.count = count;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.gain = 0.0;
+        self.loss = 0.0;
+        self.count = 0;
+        self.undo = None;
+    }
+
+    /// Returns the smoothing period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+/// Stochastic RSI values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochRsiOutput {
+    /// %K: stochastic of RSI, optionally smoothed, 0 to 100
+    pub k: f64,
+    /// %D: simple average of %K; `None` without a %D period or during its
+    /// warmup
+    pub d: Option<f64>,
+}
+
+/// Stochastic RSI indicator
+///
+/// Applies the stochastic oscillator to RSI instead of price:
+///
+/// StochRSI = 100 × (RSI − min(RSI)) / (max(RSI) − min(RSI))
+///
+/// over the last `stoch_period` RSI values. %K is StochRSI, optionally
+/// smoothed by a simple average, and %D a simple average of %K. A window
+/// in which RSI did not move repeats the previous raw value (50 at the
+/// start).
+///
+/// # Example
+///
+/// ```
+/// use indicator::StochRSI;
+///
+/// let prices: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+/// let result = StochRSI::new(14, 14, Some(3), Some(3))?.calculate(&prices)?;
+///
+/// assert!(result[28].is_none());
+/// assert!(result[29].unwrap().d.is_none());
+/// assert!(result[31].unwrap().d.is_some());
+/// assert!(result.iter().flatten().all(|o| (0.0..=100.0).contains(&o.k)));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct StochRSI {
+    rsi: RSI,
+    stoch_period: usize,
+    /// Highest and lowest RSI in the stochastic window
+    highs: RollingMax,
+    lows: RollingMin,
+    raw: Option<f64>,
+    k_smoothing: Option<RollingMean>,
+    d: Option<RollingMean>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl StochRSI {
+    /// Creates a new Stochastic RSI indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `rsi_period` - RSI smoothing period (must be > 0, typically 14)
+    /// * `stoch_period` - Stochastic lookback over RSI (must be > 0, typically 14)
+    /// * `k_smoothing` - Optional SMA period applied to %K (must be > 0,
+    ///   typically 3)
+    /// * `d_period` - Optional SMA period of %D over %K (must be > 0,
+    ///   typically 3)
+    pub fn new(
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smoothing: Option<usize>,
+        d_period: Option<usize>,
+    ) -> Result<Self, IndicatorError> {
+        if stoch_period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "stoch_period",
+                value: stoch_period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            rsi: RSI::new(rsi_period)?,
+            stoch_period,
+            highs: RollingMax::new(stoch_period)?,
+            lows: RollingMin::new(stoch_period)?,
+            raw: None,
+            k_smoothing: k_smoothing
+                .map(|period| RollingMean::new("k_smoothing", period))
+                .transpose()?,
+            d: d_period
+                .map(|period| RollingMean::new("d_period", period))
+                .transpose()?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates %K and %D for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few prices for a single
+    /// %K value.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<StochRsiOutput>>, IndicatorError> {
+        let smoothing = self.k_smoothing.as_ref().map_or(1, |mean| mean.period);
+        check_min_length(
+            self.rsi.period() + self.stoch_period + smoothing - 1,
+            prices.len(),
+        )?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<StochRsiOutput> {
+        self.undo = Snapshot::of(self);
+        let rsi = self.rsi.next(price)?;
+        let high = self.highs.next(rsi);
+        let (high, low) = high.zip(self.lows.next(rsi))?;
+        let raw = if high > low {
+            100.0 * (rsi - low) / (high - low)
+        } else {
+            self.raw.unwrap_or(50.0)
+        };
+        self.raw = Some(raw);
+
+        let k = match &mut self.k_smoothing {
+            Some(mean) => mean.next(raw)?,
+            None => raw,
+        };
+        let d = self.d.as_mut().and_then(|mean| mean.next(k));
+        Some(StochRsiOutput { k, d })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<StochRsiOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.rsi.reset();
+        self.highs.reset();
+        self.lows.reset();
+        self.raw = None;
+        for mean in self.k_smoothing.iter_mut().chain(&mut self.d) {
+            mean.reset();
+        }
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the RSI, stochastic, %K smoothing and %D periods
+    pub fn periods(&self) -> (usize, usize, Option<usize>, Option<usize>) {
+        (
+            self.rsi.period(),
+            self.stoch_period,
+            self.k_smoothing.as_ref().map(|mean| mean.period),
+            self.d.as_ref().map(|mean| mean.period),
+        )
+    }
+}
+
+/// Connors RSI indicator
+///
+/// Averages three bounded components:
+///
+/// CRSI = (RSI(price) + RSI(streak) + PercentRank(return)) / 3
+///
+/// * RSI of price over `rsi_period` (typically 3)
+/// * RSI over `streak_period` (typically 2) of the up/down streak: the number
+///   of consecutive rising closes, negative for falling ones, zero when
+///   unchanged
+/// * the percentage of the previous `rank_period` one-bar returns (typically
+///   100) that were below the current one
+///
+/// All three use [`RSI`]'s conventions, so the first value appears once the
+/// slowest component has warmed up, at bar
+/// `max(rsi_period, streak_period + 1, rank_period + 1)`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ConnorsRSI;
+///
+/// let prices: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.7).sin()).collect();
+/// let result = ConnorsRSI::new(3, 2, 10)?.calculate(&prices)?;
+///
+/// assert!(result[10].is_none());
+/// assert!(result[11..].iter().all(|v| (0.0..=100.0).contains(&v.unwrap())));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConnorsRSI {
+    price_rsi: RSI,
+    streak_rsi: RSI,
+    rank_period: usize,
+    prev: Option<f64>,
+    streak: f64,
+    /// Previous one-bar returns, oldest first
+    returns: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ConnorsRSI {
+    /// Creates a new Connors RSI indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `rsi_period` - RSI period of price (must be > 0, typically 3)
+    /// * `streak_period` - RSI period of the streak (must be > 0, typically 2)
+    /// * `rank_period` - Number of past returns ranked against (must be > 0,
+    ///   typically 100)
+    pub fn new(
+        rsi_period: usize,
+        streak_period: usize,
+        rank_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if rank_period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "rank_period",
+                value: rank_period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            price_rsi: RSI::new(rsi_period)?,
+            streak_rsi: RSI::new(streak_period)?,
+            rank_period,
+            prev: None,
+            streak: 0.0,
+            returns: VecDeque::with_capacity(rank_period + 1),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates Connors RSI for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few prices for a single
+    /// value.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let (rsi, streak, rank) = self.periods();
+        check_min_length(rsi.max(streak + 1).max(rank + 1) + 1, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let rsi = self.price_rsi.next(price);
+        let prev = self.prev.replace(price)?;
+
+        self.streak = if price > prev {
+            self.streak.max(0.0) + 1.0
+        } else if price < prev {
+            self.streak.min(0.0) - 1.0
+        } else {
+            0.0
+        };
+        let streak_rsi = self.streak_rsi.next(self.streak);
+
+        let change = if prev != 0.0 { price / prev - 1.0 } else { 0.0 };
+        let rank = (self.returns.len() == self.rank_period).then(|| {
+            let below = self.returns.iter().filter(|&&r| r < change).count();
+            100.0 * below as f64 / self.rank_period as f64
+        });
+        if self.returns.len() == self.rank_period {
+            self.returns.pop_front();
+        }
+        self.returns.push_back(change);
+
+        Some((rsi? + streak_rsi? + rank?) / 3.0)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.price_rsi.reset();
+        self.streak_rsi.reset();
+        self.prev = None;
+        self.streak = 0.0;
+        self.returns.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the price RSI, streak RSI and percent-rank periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (
+            self.price_rsi.period(),
+            self.streak_rsi.period(),
+            self.rank_period,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_wilder_smoothing() {
+        let prices = [10.0, 11.0, 10.5, 11.5, 12.0, 11.0];
+        let result = RSI::new(4).unwrap().calculate(&prices).unwrap();
+
+        // Seed: gain 2.5 / 4, loss 0.5 / 4; then a loss of 1
+        let gain = 2.5 / 4.0 * 3.0 / 4.0;
+        let loss = (0.5 / 4.0 * 3.0 + 1.0) / 4.0;
+        let expected = 100.0 - 100.0 / (1.0 + gain / loss);
+        assert!((result[5].unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rsi_extremes() {
+        let rising: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        let rsi = RSI::new(5).unwrap();
+        assert_eq!(rsi.calculate(&rising).unwrap()[9], Some(100.0));
+        assert_eq!(rsi.calculate(&falling).unwrap()[9], Some(0.0));
+        assert_eq!(rsi.calculate(&[2.0; 7]).unwrap()[6], Some(50.0));
+    }
+
+    #[test]
+    fn test_rsi_update_last_matches_final_series() {
+        let prices = [10.0, 11.0, 10.5, 11.5, 12.0, 11.0, 11.8];
+        let mut rsi = RSI::new(4).unwrap();
+        let batch = rsi.calculate(&prices).unwrap();
+
+        for (&price, expected) in prices.iter().zip(&batch) {
+            rsi.next(price * 1.1);
+            assert_eq!(rsi.update_last(price), *expected);
+        }
+
+        rsi.rollback();
+        assert_eq!(rsi.next(prices[6]), batch[6]);
+    }
+
+    #[test]
+    fn test_stoch_rsi_matches_rsi_window() {
+        let prices: Vec<f64> = (0..50)
+            .map(|i| 30.0 + (i as f64 * 0.4).sin() * 2.0 + (i as f64 * 0.13).cos())
+            .collect();
+        let rsi = RSI::new(6).unwrap().calculate(&prices).unwrap();
+        let rsi: Vec<f64> = rsi.into_iter().flatten().collect();
+
+        let result = StochRSI::new(6, 5, None, None)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+        assert!(result[9].is_none());
+        for (output, window) in result[10..].iter().zip(rsi.windows(5)) {
+            let low = window.iter().copied().fold(f64::INFINITY, f64::min);
+            let high = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let expected = 100.0 * (window[4] - low) / (high - low);
+            let output = output.unwrap();
+            assert!((output.k - expected).abs() < 1e-9);
+            assert_eq!(output.d, None);
+        }
+    }
+
+    #[test]
+    fn test_stoch_rsi_smoothing_and_d() {
+        let prices: Vec<f64> = (0..60).map(|i| 8.0 + (i as f64 * 0.5).sin()).collect();
+        let raw = StochRSI::new(5, 5, None, None)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+        let smoothed = StochRSI::new(5, 5, Some(3), Some(2))
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+
+        assert!(smoothed[10].is_none());
+        assert_eq!(smoothed[11].unwrap().d, None);
+        let k = |j: usize| (j - 2..=j).map(|n| raw[n].unwrap().k).sum::<f64>() / 3.0;
+        for (i, output) in smoothed.iter().enumerate().skip(12) {
+            let output = output.unwrap();
+            assert!((output.k - k(i)).abs() < 1e-9);
+            assert!((output.d.unwrap() - (k(i) + k(i - 1)) / 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_rsi_and_stoch_rsi_streaming() {
+        let prices: Vec<f64> = (0..40).map(|i| 12.0 + (i as f64 * 0.7).cos()).collect();
+        let mut rsi = RSI::new(7).unwrap();
+        let batch = rsi.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| rsi.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        let mut stoch = StochRSI::new(7, 4, Some(2), Some(3)).unwrap();
+        let batch = stoch.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| stoch.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        stoch.reset();
+        assert_eq!(stoch.next(1.0), None);
+        assert_eq!(stoch.periods(), (7, 4, Some(2), Some(3)));
+    }
+
+    #[test]
+    fn test_rsi_and_stoch_rsi_invalid_inputs() {
+        assert!(RSI::new(0).is_err());
+        assert!(StochRSI::new(0, 14, None, None).is_err());
+        assert!(StochRSI::new(14, 0, None, None).is_err());
+        assert!(StochRSI::new(14, 14, Some(0), None).is_err());
+        assert!(StochRSI::new(14, 14, None, Some(0)).is_err());
+        assert!(matches!(
+            RSI::new(14).unwrap().calculate(&[1.0; 14]),
+            Err(IndicatorError::InsufficientData {
+                required: 15,
+                actual: 14
+            })
+        ));
+        assert!(matches!(
+            StochRSI::new(14, 14, Some(3), None)
+                .unwrap()
+                .calculate(&[1.0; 29]),
+            Err(IndicatorError::InsufficientData {
+                required: 30,
+                actual: 29
+            })
+        ));
+    }
+
+    #[test]
+    fn test_connors_rsi_components() {
+        let prices = [10.0, 11.0, 12.0, 11.5, 11.5, 12.5, 13.0, 12.0];
+        let result = ConnorsRSI::new(3, 2, 4)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+        assert!(result[4].is_none());
+
+        let rsi = RSI::new(3).unwrap().calculate(&prices).unwrap();
+        let streaks = [1.0, 2.0, -1.0, 0.0, 1.0, 2.0, -1.0];
+        let streak_rsi = RSI::new(2).unwrap().calculate(&streaks).unwrap();
+        let returns: Vec<f64> = prices.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        for i in 5..prices.len() {
+            let current = returns[i - 1];
+            let below = returns[i - 5..i - 1]
+                .iter()
+                .filter(|&&r| r < current)
+                .count();
+            let rank = 100.0 * below as f64 / 4.0;
+            let expected = (rsi[i].unwrap() + streak_rsi[i - 1].unwrap() + rank) / 3.0;
+            assert!((result[i].unwrap() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_connors_rsi_streaming() {
+        let prices: Vec<f64> = (0..60)
+            .map(|i| 40.0 + (i as f64 * 0.45).cos() * 2.0)
+            .collect();
+        let mut crsi = ConnorsRSI::new(3, 2, 20).unwrap();
+        let batch = crsi.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| crsi.next(p)).collect();
+        assert_eq!(batch, streamed);
+        assert!(batch[20].is_none() && batch[21].is_some());
+
+        crsi.reset();
+        assert_eq!(crsi.next(1.0), None);
+        assert_eq!(crsi.periods(), (3, 2, 20));
+    }
+
+    #[test]
+    fn test_connors_rsi_invalid_inputs() {
+        assert!(ConnorsRSI::new(0, 2, 100).is_err());
+        assert!(ConnorsRSI::new(3, 0, 100).is_err());
+        assert!(ConnorsRSI::new(3, 2, 0).is_err());
+        assert!(matches!(
+            ConnorsRSI::new(3, 2, 100).unwrap().calculate(&[1.0; 101]),
+            Err(IndicatorError::InsufficientData {
+                required: 102,
+                actual: 101
+            })
+        ));
+    }
+}
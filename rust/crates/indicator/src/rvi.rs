@@ -0,0 +1,302 @@
+//! Relative Vigor Index (RVI)
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::sma::RollingMean;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Relative Vigor Index values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RviOutput {
+    /// Smoothed close-open move relative to the bar range
+    pub rvi: f64,
+    /// Symmetrically weighted average of the last four RVI values; `None`
+    /// during its warmup
+    pub signal: Option<f64>,
+}
+
+/// Relative Vigor Index indicator
+///
+/// Compares where bars close relative to their open against their full
+/// range, on the idea that closes tend to sit above opens in uptrends:
+///
+/// ```text
+/// Swma(x) = (x + 2 × x[1] + 2 × x[2] + x[3]) / 6
+/// Numerator = Swma(Close − Open)
+/// Denominator = Swma(High − Low)
+/// RVI = SMA_period(Numerator) / SMA_period(Denominator)
+/// Signal = Swma(RVI)
+/// ```
+///
+/// The four-bar symmetric weighting needs three bars of history, so the first
+/// RVI appears at bar `period + 2` and the first signal three bars later. A
+/// window with no range gives an RVI of zero.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RVI;
+///
+/// // Every bar closes at its high
+/// let open = [10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0];
+/// let high = open.map(|o| o + 1.0);
+/// let low = open;
+/// let close = high;
+///
+/// let result = RVI::new(2)?.calculate(&open, &high, &low, &close)?;
+/// assert!(result[3].is_none());
+/// assert_eq!(result[4].unwrap().rvi, 1.0);
+/// assert_eq!(result[6].unwrap().signal, None);
+/// assert_eq!(result[7].unwrap().signal, Some(1.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RVI {
+    numerator: RollingMean,
+    denominator: RollingMean,
+    /// The last four (Close − Open, High − Low) pairs, oldest first
+    bars: VecDeque<(f64, f64)>,
+    /// The last four RVI values, oldest first
+    values: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RVI {
+    /// Creates a new Relative Vigor Index indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - SMA period of the weighted numerator and denominator
+    ///   (must be > 0, typically 10)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            numerator: RollingMean::new("period", period)?,
+            denominator: RollingMean::new("period", period)?,
+            bars: VecDeque::with_capacity(4),
+            values: VecDeque::with_capacity(4),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates RVI and its signal line for a batch of bars on a fresh
+    /// state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period + 3` bars.
+    pub fn calculate(
+        &self,
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<RviOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("open", open.len()),
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period() + 3, len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok((0..len)
+            .map(|i| state.next(open[i], high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates RVI and its signal line for a series of candles on a fresh
+    /// state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 3`
+    /// candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<RviOutput>>, IndicatorError> {
+        check_min_length(self.period() + 3, candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|c| state.next(c.open, c.high, c.low, c.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<RviOutput> {
+        self.undo = Snapshot::of(self);
+        push(&mut self.bars, (close - open, high - low));
+        if self.bars.len() < 4 {
+            return None;
+        }
+
+        let numerator = swma(self.bars.iter().map(|bar| bar.0));
+        let denominator = swma(self.bars.iter().map(|bar| bar.1));
+        let numerator = self.numerator.next(numerator);
+        let denominator = self.denominator.next(denominator)?;
+        let rvi = if denominator > 0.0 {
+            numerator? / denominator
+        } else {
+            0.0
+        };
+
+        push(&mut self.values, rvi);
+        let signal = (self.values.len() == 4).then(|| swma(self.values.iter().copied()));
+        Some(RviOutput { rvi, signal })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<RviOutput> {
+        self.rollback();
+        self.next(open, high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.numerator.reset();
+        self.denominator.reset();
+        self.bars.clear();
+        self.values.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the SMA period
+    pub fn period(&self) -> usize {
+        self.numerator.period
+    }
+}
+
+/// Keeps the last four values of a series
+fn push<T>(window: &mut VecDeque<T>, value: T) {
+    if window.len() == 4 {
+        window.pop_front();
+    }
+    window.push_back(value);
+}
+
+/// Symmetric 1-2-2-1 weighted average of four values
+fn swma(values: impl Iterator<Item = f64>) -> f64 {
+    values
+        .zip([1.0, 2.0, 2.0, 1.0])
+        .map(|(value, weight)| value * weight)
+        .sum::<f64>()
+        / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        (0..40)
+            .map(|i| {
+                let open = 30.0 + (i as f64 * 0.4).sin() * 3.0;
+                let close = open + (i as f64 * 0.9).cos() * 0.8;
+                let high = open.max(close) + 0.3;
+                let low = open.min(close) - 0.2;
+                Candle::new(i, open, high, low, close, 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rvi_values() {
+        let candles = candles();
+        let weighted = |f: &dyn Fn(&Candle) -> f64, i: usize| {
+            (f(&candles[i])
+                + 2.0 * f(&candles[i - 1])
+                + 2.0 * f(&candles[i - 2])
+                + f(&candles[i - 3]))
+                / 6.0
+        };
+        let result = RVI::new(5).unwrap().calculate_candles(&candles).unwrap();
+        assert!(result[6].is_none());
+
+        let mut rvi = vec![0.0; candles.len()];
+        for i in 7..candles.len() {
+            let (mut num, mut den) = (0.0, 0.0);
+            for j in i - 4..=i {
+                num += weighted(&|c| c.close - c.open, j);
+                den += weighted(&|c| c.high - c.low, j);
+            }
+            rvi[i] = num / den;
+            let output = result[i].unwrap();
+            assert!((output.rvi - rvi[i]).abs() < 1e-12);
+            match output.signal {
+                Some(signal) => {
+                    let expected =
+                        (rvi[i] + 2.0 * rvi[i - 1] + 2.0 * rvi[i - 2] + rvi[i - 3]) / 6.0;
+                    assert!((signal - expected).abs() < 1e-12);
+                }
+                None => assert!(i < 10),
+            }
+        }
+    }
+
+    #[test]
+    fn test_rvi_zero_range() {
+        let mut rvi = RVI::new(1).unwrap();
+        let outputs: Vec<_> = (0..4).map(|_| rvi.next(5.0, 5.0, 5.0, 5.0)).collect();
+        assert_eq!(outputs[3].unwrap().rvi, 0.0);
+    }
+
+    #[test]
+    fn test_rvi_streaming_matches_batch() {
+        let candles = candles();
+        let open: Vec<f64> = candles.iter().map(|c| c.open).collect();
+        let high: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let low: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let mut rvi = RVI::new(10).unwrap();
+        let batch = rvi.calculate(&open, &high, &low, &close).unwrap();
+        assert_eq!(batch, rvi.calculate_candles(&candles).unwrap());
+
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| rvi.next(c.open, c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        rvi.reset();
+        assert_eq!(rvi.next(1.0, 2.0, 0.0, 1.5), None);
+        assert_eq!(rvi.period(), 10);
+    }
+
+    #[test]
+    fn test_rvi_invalid_inputs() {
+        assert!(RVI::new(0).is_err());
+        let rvi = RVI::new(10).unwrap();
+        assert!(matches!(
+            rvi.calculate_candles(&[Candle::default(); 12]),
+            Err(IndicatorError::InsufficientData {
+                required: 13,
+                actual: 12
+            })
+        ));
+        assert!(rvi
+            .calculate(&[1.0; 13], &[1.0; 13], &[1.0; 13], &[1.0; 12])
+            .is_err());
+    }
+}
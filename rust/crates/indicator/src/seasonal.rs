@@ -0,0 +1,392 @@
+//! STL seasonal-trend decomposition
+//!
+//! [`Stl`] splits a series into trend, seasonal and residual components with
+//! the LOESS-based procedure of Cleveland et al. (1990): the seasonal
+//! component is smoothed separately for every position in the cycle (e.g.
+//! every minute of the trading day), and the trend is smoothed from the
+//! deseasonalized series, alternating a few times until both settle. The
+//! robust variant down-weights outliers such as news spikes so they end up
+//! in the residual instead of distorting the seasonal profile.
+//!
+//! The decomposition is two-sided: every component uses future values, so
+//! it suits research, such as estimating an intraday volume curve, rather
+//! than live signals.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::seasonal::Stl;
+//!
+//! // Five days of 8 half-hour volume bars: busy open and close, quiet midday
+//! let profile = [9.0, 6.0, 4.0, 3.0, 3.0, 4.0, 6.0, 10.0];
+//! let volume: Vec<f64> = (0..40).map(|i| 100.0 + i as f64 + profile[i % 8]).collect();
+//!
+//! let stl = Stl::new(8)?;
+//! let parts = stl.decompose(&volume)?;
+//!
+//! // The seasonal component recovers the U-shaped daily curve
+//! assert!(parts.seasonal[16] > parts.seasonal[19]);
+//! assert!(parts.seasonal[23] > parts.seasonal[20]);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::error::check_min_length;
+use crate::IndicatorError;
+
+/// Alternations of seasonal and trend smoothing per pass
+const INNER_ITERATIONS: usize = 2;
+/// Robustness re-weighting passes for [`Stl::robust`]
+const ROBUST_ITERATIONS: usize = 15;
+
+/// Trend, seasonal and residual components of a series
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decomposition {
+    /// Slowly varying level
+    pub trend: Vec<f64>,
+    /// Repeating pattern with the configured period
+    pub seasonal: Vec<f64>,
+    /// Remainder, `value − trend − seasonal`
+    pub residual: Vec<f64>,
+}
+
+/// STL (seasonal-trend decomposition by LOESS)
+///
+/// Defaults follow the STL paper: a 7-point seasonal smoother, a trend
+/// window of the smallest odd number of at least `1.5 · period / (1 − 1.5 /
+/// seasonal_window)` bars and no robustness iterations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stl {
+    period: usize,
+    seasonal_window: usize,
+    trend_window: usize,
+    robust: bool,
+}
+
+impl Stl {
+    /// Creates an STL decomposition with default smoothing windows
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of bars per seasonal cycle (must be >= 2)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+        let seasonal_window = 7;
+        Ok(Self {
+            period,
+            seasonal_window,
+            trend_window: default_trend_window(period, seasonal_window),
+            robust: false,
+        })
+    }
+
+    /// Sets the seasonal smoothing window, in cycles
+    ///
+    /// Larger windows give a seasonal pattern that changes more slowly from
+    /// cycle to cycle. Also resets the trend window to its default for the
+    /// new seasonal window.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of cycles per seasonal fit (must be odd and >= 3)
+    pub fn with_seasonal_window(mut self, window: usize) -> Result<Self, IndicatorError> {
+        check_window("seasonal_window", window)?;
+        self.seasonal_window = window;
+        self.trend_window = default_trend_window(self.period, window);
+        Ok(self)
+    }
+
+    /// Sets the trend smoothing window, in bars
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Number of bars per trend fit (must be odd and >= 3)
+    pub fn with_trend_window(mut self, window: usize) -> Result<Self, IndicatorError> {
+        check_window("trend_window", window)?;
+        self.trend_window = window;
+        Ok(self)
+    }
+
+    /// Enables or disables robustness weighting against outliers
+    pub fn robust(mut self, robust: bool) -> Self {
+        self.robust = robust;
+        self
+    }
+
+    /// Decomposes a series into trend, seasonal and residual components
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if fewer than two full cycles are given.
+    pub fn decompose(&self, values: &[f64]) -> Result<Decomposition, IndicatorError> {
+        check_min_length(2 * self.period, values.len())?;
+
+        let n = values.len();
+        let mut weights = vec![1.0; n];
+        let mut trend = vec![0.0; n];
+        let mut seasonal = vec![0.0; n];
+        let passes = if self.robust {
+            ROBUST_ITERATIONS + 1
+        } else {
+            1
+        };
+
+        for pass in 0..passes {
+            for _ in 0..INNER_ITERATIONS {
+                seasonal = self.seasonal_step(values, &trend, &weights);
+                let deseasonalized: Vec<f64> =
+                    values.iter().zip(&seasonal).map(|(v, s)| v - s).collect();
+                trend = (0..n)
+                    .map(|i| loess(&deseasonalized, &weights, self.trend_window, i as f64))
+                    .collect();
+            }
+            if pass + 1 < passes {
+                weights = robustness_weights(values, &trend, &seasonal);
+            }
+        }
+
+        let residual = values
+            .iter()
+            .zip(&trend)
+            .zip(&seasonal)
+            .map(|((v, t), s)| v - t - s)
+            .collect();
+        Ok(Decomposition {
+            trend,
+            seasonal,
+            residual,
+        })
+    }
+
+    /// Returns the seasonal period in bars
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the seasonal smoothing window in cycles
+    pub fn seasonal_window(&self) -> usize {
+        self.seasonal_window
+    }
+
+    /// Returns the trend smoothing window in bars
+    pub fn trend_window(&self) -> usize {
+        self.trend_window
+    }
+
+    /// Whether robustness weighting is enabled
+    pub fn is_robust(&self) -> bool {
+        self.robust
+    }
+
+    /// Smooths the cycle-subseries of the detrended values and removes
+    /// their low-frequency part, returning the seasonal component
+    fn seasonal_step(&self, values: &[f64], trend: &[f64], weights: &[f64]) -> Vec<f64> {
+        let n = values.len();
+        let p = self.period;
+
+        // Each subseries is smoothed and extended by one cycle at both ends
+        let mut cycle = vec![0.0; n + 2 * p];
+        for phase in 0..p {
+            let indices: Vec<usize> = (phase..n).step_by(p).collect();
+            let detrended: Vec<f64> = indices.iter().map(|&i| values[i] - trend[i]).collect();
+            let sub_weights: Vec<f64> = indices.iter().map(|&i| weights[i]).collect();
+            for k in 0..indices.len() + 2 {
+                let position = k as f64 - 1.0;
+                cycle[phase + k * p] =
+                    loess(&detrended, &sub_weights, self.seasonal_window, position);
+            }
+        }
+
+        // Low-pass filter: moving averages of p, p and 3, then LOESS
+        let low = moving_average(&moving_average(&moving_average(&cycle, p), p), 3);
+        let ones = vec![1.0; n];
+        let low_pass_window = p + 1 - p % 2;
+        (0..n)
+            .map(|i| cycle[i + p] - loess(&low, &ones, low_pass_window, i as f64))
+            .collect()
+    }
+}
+
+/// Smallest odd integer at or above `1.5 · period / (1 − 1.5 / seasonal_window)`
+fn default_trend_window(period: usize, seasonal_window: usize) -> usize {
+    let raw = (1.5 * period as f64 / (1.0 - 1.5 / seasonal_window as f64)).ceil() as usize;
+    (raw + 1 - raw % 2).max(3)
+}
+
+fn check_window(field: &'static str, window: usize) -> Result<(), IndicatorError> {
+    if window < 3 || window.is_multiple_of(2) {
+        return Err(IndicatorError::InvalidParameter {
+            field,
+            value: window as f64,
+            constraint: "odd and >= 3",
+        });
+    }
+    Ok(())
+}
+
+fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    values
+        .windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+/// Bisquare weights from residuals scaled by six median absolute residuals
+fn robustness_weights(values: &[f64], trend: &[f64], seasonal: &[f64]) -> Vec<f64> {
+    let residuals: Vec<f64> = values
+        .iter()
+        .zip(trend)
+        .zip(seasonal)
+        .map(|((v, t), s)| (v - t - s).abs())
+        .collect();
+    let mut sorted = residuals.clone();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    };
+    let scale = 6.0 * median;
+
+    residuals
+        .iter()
+        .map(|r| {
+            if scale == 0.0 {
+                return 1.0;
+            }
+            let u = r / scale;
+            if u < 1.0 {
+                (1.0 - u * u).powi(2)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Locally weighted linear fit of `y` (at positions `0..y.len()`) evaluated at `x`
+///
+/// Uses the `window` nearest points with tricube distance weights times the
+/// robustness `weights`. Windows wider than the data stretch the distance
+/// scale, as in the STL paper.
+fn loess(y: &[f64], weights: &[f64], window: usize, x: f64) -> f64 {
+    let n = y.len();
+    let q = window.min(n);
+    // Contiguous block of the q points nearest to x
+    let centre = x.round().clamp(0.0, (n - 1) as f64) as usize;
+    let start = centre.saturating_sub(q / 2).min(n - q);
+    let end = start + q;
+
+    let mut max_distance = (x - start as f64).abs().max((x - (end - 1) as f64).abs());
+    if window > n {
+        max_distance += (window - n) as f64 / 2.0;
+    }
+    let max_distance = max_distance.max(1.0);
+
+    let (mut sw, mut swx, mut swy, mut swxx, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for j in start..end {
+        let u = (j as f64 - x).abs() / max_distance;
+        let w = if u < 1.0 {
+            (1.0 - u.powi(3)).powi(3)
+        } else {
+            0.0
+        } * weights[j];
+        let xj = j as f64;
+        sw += w;
+        swx += w * xj;
+        swy += w * y[j];
+        swxx += w * xj * xj;
+        swxy += w * xj * y[j];
+    }
+    if sw <= 0.0 {
+        return y[centre];
+    }
+
+    let mean_x = swx / sw;
+    let mean_y = swy / sw;
+    let spread = swxx / sw - mean_x * mean_x;
+    if spread.abs() < 1e-12 * (1.0 + mean_x * mean_x) {
+        return mean_y;
+    }
+    let slope = (swxy / sw - mean_x * mean_y) / spread;
+    mean_y + slope * (x - mean_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERN: [f64; 6] = [3.0, 1.0, -2.0, -3.0, -1.0, 2.0];
+
+    fn series(len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| 50.0 + 0.2 * i as f64 + PATTERN[i % 6])
+            .collect()
+    }
+
+    #[test]
+    fn test_recovers_trend_and_seasonal_pattern() {
+        let values = series(72);
+        let parts = Stl::new(6).unwrap().decompose(&values).unwrap();
+
+        for i in 12..60 {
+            assert!((parts.seasonal[i] - PATTERN[i % 6]).abs() < 0.1, "bar {i}");
+            assert!(
+                (parts.trend[i] - (50.0 + 0.2 * i as f64)).abs() < 0.1,
+                "bar {i}"
+            );
+        }
+        for (i, v) in values.iter().enumerate() {
+            let total = parts.trend[i] + parts.seasonal[i] + parts.residual[i];
+            assert!((total - v).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_robust_moves_outlier_into_residual() {
+        let mut values = series(72);
+        values[33] += 40.0;
+
+        let plain = Stl::new(6).unwrap().decompose(&values).unwrap();
+        let robust = Stl::new(6)
+            .unwrap()
+            .robust(true)
+            .decompose(&values)
+            .unwrap();
+
+        let seasonal_error = |parts: &Decomposition| {
+            (12..60)
+                .map(|i| (parts.seasonal[i] - PATTERN[i % 6]).abs())
+                .fold(0.0, f64::max)
+        };
+        assert!(seasonal_error(&robust) < 0.5 * seasonal_error(&plain));
+        assert!(robust.residual[33] > 35.0, "{}", robust.residual[33]);
+    }
+
+    #[test]
+    fn test_windows_and_invalid_inputs() {
+        let stl = Stl::new(12).unwrap();
+        assert_eq!((stl.seasonal_window(), stl.trend_window()), (7, 23));
+        let stl = stl.with_seasonal_window(13).unwrap();
+        assert_eq!(stl.trend_window(), 21);
+        assert_eq!(stl.clone().with_trend_window(9).unwrap().trend_window(), 9);
+        assert!(!stl.is_robust());
+
+        assert!(Stl::new(1).is_err());
+        assert!(stl.clone().with_seasonal_window(4).is_err());
+        assert!(stl.clone().with_trend_window(1).is_err());
+        assert!(matches!(
+            stl.decompose(&[1.0; 23]),
+            Err(IndicatorError::InsufficientData {
+                required: 24,
+                actual: 23
+            })
+        ));
+    }
+}
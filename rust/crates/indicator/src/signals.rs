@@ -0,0 +1,224 @@
+//! Crossover detection and trade signals
+//!
+//! [`crossovers`] compares two aligned series, such as a fast and a slow
+//! moving average, and [`level_crossovers`] compares one series with a
+//! constant, such as an RSI threshold. Both return one [`Signal`] per bar:
+//! [`Signal::Buy`] where the first series crosses above the second,
+//! [`Signal::Sell`] where it crosses below and [`Signal::Neutral`]
+//! elsewhere. [`signal_events`] keeps only the bars with a signal.
+//!
+//! A cross needs the series to actually change sides: touching and then
+//! moving back is not a cross, and a cross through a bar of equality is
+//! reported on the bar that leaves it. Bars where either value is missing
+//! (`None`, e.g. indicator warmup) or NaN are neutral and do not affect the
+//! comparison.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::signals::{crossovers, signal_events, Signal};
+//! use indicator::EMA;
+//!
+//! // A decline followed by a recovery
+//! let prices: Vec<f64> = (0..40)
+//!     .map(|i| if i < 20 { 120.0 - i as f64 } else { 80.0 + 2.0 * i as f64 })
+//!     .collect();
+//!
+//! let fast = EMA::new(5)?.calculate(&prices)?;
+//! let slow = EMA::new(15)?.calculate(&prices)?;
+//! let events = signal_events(&crossovers(&fast, &slow)?);
+//!
+//! // Golden cross: the fast EMA moves above the slow one during the recovery
+//! assert_eq!(events.len(), 1);
+//! assert_eq!(events[0].signal, Signal::Buy);
+//! assert!(events[0].index > 20);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::error::check_equal_lengths;
+use crate::IndicatorError;
+
+/// Trade signal for one bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Signal {
+    /// The series crossed above its reference
+    Buy,
+    /// The series crossed below its reference
+    Sell,
+    /// No cross on this bar
+    #[default]
+    Neutral,
+}
+
+/// A non-neutral signal and the bar it occurred on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalEvent {
+    /// Index of the bar
+    pub index: usize,
+    /// Signal on that bar, never [`Signal::Neutral`]
+    pub signal: Signal,
+}
+
+/// Streaming crossover detector
+///
+/// Remembers on which side of the reference the series last was, so a
+/// [`Signal`] is produced only when it moves to the other side.
+#[derive(Debug, Clone, Default)]
+pub struct CrossDetector {
+    /// Side of the last bar where the values differed
+    side: Option<Ordering>,
+}
+
+impl CrossDetector {
+    /// Creates a new detector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares the next pair of values (streaming mode)
+    pub fn next(&mut self, value: f64, reference: f64) -> Signal {
+        let side = match value.partial_cmp(&reference) {
+            Some(Ordering::Equal) | None => return Signal::Neutral,
+            Some(side) => side,
+        };
+        match self.side.replace(side) {
+            Some(Ordering::Less) if side == Ordering::Greater => Signal::Buy,
+            Some(Ordering::Greater) if side == Ordering::Less => Signal::Sell,
+            _ => Signal::Neutral,
+        }
+    }
+
+    /// Forgets the last side so the detector can be reused on a new series
+    pub fn reset(&mut self) {
+        self.side = None;
+    }
+}
+
+/// Signals where `fast` crosses `slow`
+///
+/// # Errors
+///
+/// Returns `LengthMismatch` if the series differ in length.
+pub fn crossovers(
+    fast: &[Option<f64>],
+    slow: &[Option<f64>],
+) -> Result<Vec<Signal>, IndicatorError> {
+    check_equal_lengths(&[("fast", fast.len()), ("slow", slow.len())])?;
+
+    let mut detector = CrossDetector::new();
+    Ok(fast
+        .iter()
+        .zip(slow)
+        .map(|pair| match pair {
+            (Some(fast), Some(slow)) => detector.next(*fast, *slow),
+            _ => Signal::Neutral,
+        })
+        .collect())
+}
+
+/// Signals where `series` crosses the constant `level`
+pub fn level_crossovers(series: &[Option<f64>], level: f64) -> Vec<Signal> {
+    let mut detector = CrossDetector::new();
+    series
+        .iter()
+        .map(|value| value.map_or(Signal::Neutral, |value| detector.next(value, level)))
+        .collect()
+}
+
+/// Bars with a buy or sell signal, in order
+pub fn signal_events(signals: &[Signal]) -> Vec<SignalEvent> {
+    signals
+        .iter()
+        .enumerate()
+        .filter(|(_, signal)| **signal != Signal::Neutral)
+        .map(|(index, &signal)| SignalEvent { index, signal })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RSI;
+
+    #[test]
+    fn test_crossovers_of_two_series() {
+        let fast = [1.0, 2.0, 4.0, 3.0, 1.0, 2.0].map(Some);
+        let slow = [2.0, 3.0, 3.0, 3.0, 2.0, 2.5].map(Some);
+        let signals = crossovers(&fast, &slow).unwrap();
+
+        assert_eq!(
+            signals,
+            vec![
+                Signal::Neutral,
+                Signal::Neutral,
+                Signal::Buy,
+                Signal::Neutral,
+                Signal::Sell,
+                Signal::Neutral
+            ]
+        );
+        assert_eq!(
+            signal_events(&signals),
+            vec![
+                SignalEvent {
+                    index: 2,
+                    signal: Signal::Buy
+                },
+                SignalEvent {
+                    index: 4,
+                    signal: Signal::Sell
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_touches_gaps_and_nan() {
+        // Touch from above and back up: no cross; through equality: one cross
+        let touch = level_crossovers(
+            &[Some(5.0), Some(3.0), Some(4.0), Some(3.0), Some(2.0)],
+            3.0,
+        );
+        assert_eq!(signal_events(&touch).len(), 1);
+        assert_eq!(touch[4], Signal::Sell);
+
+        // Missing and NaN values are skipped without losing the last side
+        let gaps = [Some(1.0), None, Some(f64::NAN), Some(5.0)];
+        assert_eq!(level_crossovers(&gaps, 3.0)[3], Signal::Buy);
+    }
+
+    #[test]
+    fn test_rsi_level_crossovers_and_streaming() {
+        let prices: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.2).sin() * 10.0)
+            .collect();
+        let rsi = RSI::new(14).unwrap().calculate(&prices).unwrap();
+        let batch = level_crossovers(&rsi, 50.0);
+
+        let mut detector = CrossDetector::new();
+        let streamed: Vec<Signal> = rsi
+            .iter()
+            .map(|value| value.map_or(Signal::Neutral, |v| detector.next(v, 50.0)))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        let events = signal_events(&batch);
+        assert!(events.len() >= 2);
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].signal != pair[1].signal));
+
+        detector.reset();
+        assert_eq!(detector.next(60.0, 50.0), Signal::Neutral);
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        assert!(matches!(
+            crossovers(&[Some(1.0)], &[Some(1.0), Some(2.0)]),
+            Err(IndicatorError::LengthMismatch { field: "slow", .. })
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+//! Simple Moving Average (SMA) and the rolling mean shared by the indicators
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::IndicatorError;
+
+/// Simple Moving Average (SMA) indicator
+///
+/// The unweighted mean of the last `period` prices.
+///
+/// # Example
+///
+/// ```
+/// use indicator::SMA;
+///
+/// let sma = SMA::new(3)?;
+/// let result = sma.calculate(&[1.0, 2.0, 3.0, 4.0])?;
+///
+/// assert_eq!(result, vec![None, None, Some(2.0), Some(3.0)]);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SMA {
+    mean: RollingMean,
+}
+
+impl SMA {
+    /// Creates a new SMA indicator with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            mean: RollingMean::new("period", period)?,
+        })
+    }
+
+    /// Calculates SMA for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as there isn't enough data.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period(), prices.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.mean.next(price)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next), restoring any price it evicted
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        self.mean.rollback();
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.mean.reset();
+    }
+
+    /// Returns the period used for SMA calculation
+    pub fn period(&self) -> usize {
+        self.mean.period
+    }
+}
+
+/// Simple moving average over the last `period` values
+///
+/// The window is re-summed on every update rather than kept as a running
+/// total, so a window of identical values averages to exactly that value
+/// (a %K pinned at 0 or 100 stays at 0 or 100).
+#[derive(Debug, Clone)]
+pub(crate) struct RollingMean {
+    pub(crate) period: usize,
+    window: VecDeque<f64>,
+    /// Value evicted by the last `next` (inner `None` if nothing was
+    /// evicted), or `None` when there is nothing to roll back
+    undo: Option<Option<f64>>,
+}
+
+impl RollingMean {
+    /// Creates the average, reporting a zero `period` against `field`
+    pub(crate) fn new(field: &'static str, period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field,
+                value: 0.0,
+                constraint: "> 0",
+            });
+        }
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            undo: None,
+        })
+    }
+
+    pub(crate) fn next(&mut self, value: f64) -> Option<f64> {
+        let evicted = if self.window.len() == self.period {
+            self.window.pop_front()
+        } else {
+            None
+        };
+        self.window.push_back(value);
+        self.undo = Some(evicted);
+        (self.window.len() == self.period)
+            .then(|| self.window.iter().sum::<f64>() / self.period as f64)
+    }
+
+    pub(crate) fn rollback(&mut self) {
+        if let Some(evicted) = self.undo.take() {
+            self.window.pop_back();
+            if let Some(oldest) = evicted {
+                self.window.push_front(oldest);
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.window.clear();
+        self.undo = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sma_streaming_matches_batch() {
+        let prices = vec![4.0, 8.0, 6.0, 2.0, 10.0];
+        let mut sma = SMA::new(2).unwrap();
+        let batch = sma.calculate(&prices).unwrap();
+        assert_eq!(
+            batch,
+            vec![None, Some(6.0), Some(7.0), Some(4.0), Some(6.0)]
+        );
+        let streamed: Vec<_> = prices.iter().map(|&p| sma.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        sma.reset();
+        assert_eq!(sma.next(1.0), None);
+        assert_eq!(sma.period(), 2);
+    }
+
+    #[test]
+    fn test_sma_update_last_restores_evicted_price() {
+        let mut sma = SMA::new(2).unwrap();
+        sma.next(4.0);
+        sma.next(8.0);
+        assert_eq!(sma.next(20.0), Some(14.0));
+        assert_eq!(sma.update_last(6.0), Some(7.0));
+
+        // Rolling back brings the evicted 4.0 back into the window
+        sma.rollback();
+        sma.rollback();
+        assert_eq!(sma.next(2.0), Some(5.0));
+    }
+
+    #[test]
+    fn test_sma_invalid_inputs() {
+        assert!(SMA::new(0).is_err());
+        assert!(matches!(
+            SMA::new(5).unwrap().calculate(&[1.0; 4]),
+            Err(IndicatorError::InsufficientData {
+                required: 5,
+                actual: 4
+            })
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+//! Whole-state undo for indicators without targeted rollback state
+
+use std::fmt;
+
+/// Copy of an indicator's state taken before its last `next`
+///
+/// Indicators whose state is a mix of windows and recursions keep a full
+/// copy of themselves instead of recording what each update changed. Cloning
+/// a snapshot yields an empty one, so a saved state never carries its own
+/// predecessor and the copy stays one level deep.
+pub(crate) struct Snapshot<T>(Option<Box<T>>);
+
+impl<T: Clone> Snapshot<T> {
+    /// Saves a copy of `state`
+    pub(crate) fn of(state: &T) -> Self {
+        Self(Some(Box::new(state.clone())))
+    }
+
+    /// Removes and returns the saved state, if any
+    pub(crate) fn take(&mut self) -> Option<T> {
+        self.0.take().map(|state| *state)
+    }
+}
+
+impl<T> Default for Snapshot<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Clone for Snapshot<T> {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl<T> fmt::Debug for Snapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Snapshot").field(&self.0.is_some()).finish()
+    }
+}
@@ -0,0 +1,560 @@
+//! Spectral analysis for cycle and seasonality detection
+//!
+//! The series is detrended, tapered with a Hann window, zero-padded to a
+//! power of two and transformed with a radix-2 FFT. Peaks of the resulting
+//! periodogram are the dominant cycles, reported as period (in bars) and
+//! power pairs. Typical uses are finding intraday seasonality in volume or
+//! checking whether a cycle-based indicator's period is actually present.
+//!
+//! [`AutocorrelationPeriodogram`] is a streaming alternative after John
+//! Ehlers: it measures the dominant cycle bar by bar from the autocorrelation
+//! of roofing-filtered prices, so adaptive indicators can retune their
+//! lookback as the market rhythm changes.
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::spectral::{dominant_cycles, Detrend};
+//!
+//! // A 20-bar cycle on top of a linear trend
+//! let values: Vec<f64> = (0..200)
+//!     .map(|i| 0.5 * i as f64 + (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+//!     .collect();
+//!
+//! let cycles = dominant_cycles(&values, Detrend::Linear, 1)?;
+//! assert!((cycles[0].period - 20.0).abs() < 1.0);
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::f64::consts::{PI, SQRT_2};
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Minimum number of values for a meaningful spectrum
+const MIN_LENGTH: usize = 4;
+/// Number of value pairs in each autocorrelation estimate
+const AVG_LENGTH: usize = 3;
+/// Per-bar decay of the running power maximum used for normalisation
+const POWER_DECAY: f64 = 0.995;
+
+/// Trend removal applied before the transform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detrend {
+    /// Use the series as is
+    None,
+    /// Subtract the mean
+    Mean,
+    /// Subtract the least-squares line
+    Linear,
+}
+
+/// One frequency of a periodogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralPeak {
+    /// Cycle length in bars
+    pub period: f64,
+    /// Cycles per bar
+    pub frequency: f64,
+    /// Spectral power (squared magnitude divided by the series length)
+    pub power: f64,
+}
+
+/// Periodogram of a series
+///
+/// Returns one entry per positive frequency bin of the zero-padded transform,
+/// from the lowest frequency (longest period) to the Nyquist frequency.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` if fewer than 4 values are given.
+pub fn periodogram(values: &[f64], detrend: Detrend) -> Result<Vec<SpectralPeak>, IndicatorError> {
+    if values.len() < MIN_LENGTH {
+        return Err(IndicatorError::InsufficientData {
+            required: MIN_LENGTH,
+            actual: values.len(),
+        });
+    }
+
+    let n = values.len();
+    let size = n.next_power_of_two();
+    let mut re = detrended(values, detrend);
+    let window = |i: usize| 0.5 - 0.5 * (2.0 * PI * i as f64 / (n - 1) as f64).cos();
+    for (i, value) in re.iter_mut().enumerate() {
+        *value *= window(i);
+    }
+    re.resize(size, 0.0);
+    let mut im = vec![0.0; size];
+    fft(&mut re, &mut im);
+
+    Ok((1..=size / 2)
+        .map(|k| {
+            let frequency = k as f64 / size as f64;
+            SpectralPeak {
+                period: 1.0 / frequency,
+                frequency,
+                power: (re[k] * re[k] + im[k] * im[k]) / n as f64,
+            }
+        })
+        .collect())
+}
+
+/// Strongest cycles of a series
+///
+/// Local maxima of the periodogram, strongest first, limited to `max_peaks`.
+/// Periods longer than half the series are skipped, since fewer than two
+/// full cycles cannot establish periodicity.
+///
+/// # Errors
+///
+/// Returns `InsufficientData` if fewer than 4 values are given.
+pub fn dominant_cycles(
+    values: &[f64],
+    detrend: Detrend,
+    max_peaks: usize,
+) -> Result<Vec<SpectralPeak>, IndicatorError> {
+    let spectrum = periodogram(values, detrend)?;
+    let max_period = values.len() as f64 / 2.0;
+
+    let mut peaks: Vec<SpectralPeak> = (0..spectrum.len())
+        .filter(|&k| {
+            let power = spectrum[k].power;
+            let left = k.checked_sub(1).map_or(0.0, |j| spectrum[j].power);
+            let right = spectrum.get(k + 1).map_or(0.0, |p| p.power);
+            power > left && power >= right && spectrum[k].period <= max_period
+        })
+        .map(|k| spectrum[k])
+        .collect();
+    peaks.sort_by(|a, b| b.power.total_cmp(&a.power));
+    peaks.truncate(max_peaks);
+    Ok(peaks)
+}
+
+/// Ehlers' autocorrelation periodogram dominant cycle estimator
+///
+/// Each bar the price passes through a roofing filter (a two-pole high-pass
+/// at `max_period` followed by a super smoother at `min_period`), which
+/// removes trend and noise outside the band of interest. The
+/// autocorrelations of the filtered series at lags up to `max_period` are
+/// then projected onto every candidate period, the resulting power is
+/// smoothed over time and normalised to the recent maximum, and the dominant
+/// cycle is the power-weighted average of the periods whose normalised power
+/// is at least one half.
+///
+/// The estimate is always within `[min_period, max_period]`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::spectral::AutocorrelationPeriodogram;
+///
+/// let prices: Vec<f64> = (0..300)
+///     .map(|i| 100.0 + (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+///     .collect();
+///
+/// let periodogram = AutocorrelationPeriodogram::new(10, 48)?;
+/// let cycle = periodogram.calculate(&prices)?[299].unwrap();
+/// assert!((cycle - 20.0).abs() < 2.0);
+///
+/// // e.g. tune an RSI to half the measured cycle
+/// let rsi_period = (cycle / 2.0).round() as usize;
+/// # assert_eq!(rsi_period, 10);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AutocorrelationPeriodogram {
+    min_period: usize,
+    max_period: usize,
+    prices: [f64; 3],
+    high_pass: [f64; 2],
+    count: usize,
+    /// Roofing filter output, newest first
+    filtered: VecDeque<f64>,
+    /// Smoothed power per period, from `min_period` upwards
+    power: Vec<f64>,
+    max_power: f64,
+    dominant: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl AutocorrelationPeriodogram {
+    /// Creates a new autocorrelation periodogram
+    ///
+    /// Ehlers uses a 10 to 48 bar range for daily data.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_period` - Shortest cycle considered (must be >= 3)
+    /// * `max_period` - Longest cycle considered (must be > `min_period`)
+    pub fn new(min_period: usize, max_period: usize) -> Result<Self, IndicatorError> {
+        if min_period < 3 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "min_period",
+                value: min_period as f64,
+                constraint: ">= 3",
+            });
+        }
+        if max_period <= min_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "max_period",
+                value: max_period as f64,
+                constraint: "> min_period",
+            });
+        }
+
+        Ok(Self {
+            min_period,
+            max_period,
+            prices: [0.0; 3],
+            high_pass: [0.0; 2],
+            count: 0,
+            filtered: VecDeque::with_capacity(max_period + AVG_LENGTH),
+            power: vec![0.0; max_period - min_period + 1],
+            max_power: 0.0,
+            dominant: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Estimates the dominant cycle for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. Values are `None` until
+    /// `max_period + 4` prices have been seen and while the filtered series
+    /// has shown no cyclic power at all (e.g. constant prices).
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.warmup(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        self.prices = [price, self.prices[0], self.prices[1]];
+        self.count += 1;
+
+        let filtered = self.roofing_filter();
+        if self.filtered.len() == self.max_period + AVG_LENGTH {
+            self.filtered.pop_back();
+        }
+        self.filtered.push_front(filtered);
+        if self.count < self.warmup() {
+            return None;
+        }
+
+        let correlations: Vec<f64> = (0..=self.max_period)
+            .map(|lag| self.correlation(lag))
+            .collect();
+
+        self.max_power *= POWER_DECAY;
+        for (offset, smoothed) in self.power.iter_mut().enumerate() {
+            let period = (self.min_period + offset) as f64;
+            let (mut cosine, mut sine) = (0.0, 0.0);
+            for (lag, corr) in correlations.iter().enumerate().skip(3) {
+                let (s, c) = (2.0 * PI * lag as f64 / period).sin_cos();
+                cosine += corr * c;
+                sine += corr * s;
+            }
+            let squared = cosine * cosine + sine * sine;
+            *smoothed = 0.2 * squared * squared + 0.8 * *smoothed;
+            self.max_power = self.max_power.max(*smoothed);
+        }
+
+        if self.max_power > 0.0 {
+            let (mut weighted, mut total) = (0.0, 0.0);
+            for (offset, smoothed) in self.power.iter().enumerate() {
+                let normalised = smoothed / self.max_power;
+                if normalised >= 0.5 {
+                    weighted += (self.min_period + offset) as f64 * normalised;
+                    total += normalised;
+                }
+            }
+            if total > 0.0 {
+                self.dominant = Some(weighted / total);
+            }
+        }
+        self.dominant
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the state so the estimator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.prices = [0.0; 3];
+        self.high_pass = [0.0; 2];
+        self.count = 0;
+        self.filtered.clear();
+        self.power.iter_mut().for_each(|p| *p = 0.0);
+        self.max_power = 0.0;
+        self.dominant = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the shortest cycle considered
+    pub fn min_period(&self) -> usize {
+        self.min_period
+    }
+
+    /// Returns the longest cycle considered
+    pub fn max_period(&self) -> usize {
+        self.max_period
+    }
+
+    /// Number of prices before the first estimate
+    fn warmup(&self) -> usize {
+        self.max_period + AVG_LENGTH + 1
+    }
+
+    /// High-pass at `max_period` then super smoother at `min_period`
+    fn roofing_filter(&mut self) -> f64 {
+        let high_pass = if self.count < 3 {
+            0.0
+        } else {
+            let angle = SQRT_2 * PI / self.max_period as f64;
+            let alpha = (angle.cos() + angle.sin() - 1.0) / angle.cos();
+            let [p0, p1, p2] = self.prices;
+            (1.0 - alpha / 2.0).powi(2) * (p0 - 2.0 * p1 + p2)
+                + 2.0 * (1.0 - alpha) * self.high_pass[0]
+                - (1.0 - alpha).powi(2) * self.high_pass[1]
+        };
+        let previous_high_pass = self.high_pass[0];
+        self.high_pass = [high_pass, previous_high_pass];
+
+        let a = (-SQRT_2 * PI / self.min_period as f64).exp();
+        let c2 = 2.0 * a * (SQRT_2 * PI / self.min_period as f64).cos();
+        let c3 = -a * a;
+        let c1 = 1.0 - c2 - c3;
+        let f1 = self.filtered.front().copied().unwrap_or(0.0);
+        let f2 = self.filtered.get(1).copied().unwrap_or(0.0);
+        c1 * (high_pass + previous_high_pass) / 2.0 + c2 * f1 + c3 * f2
+    }
+
+    /// Pearson correlation of the latest filtered values with those `lag` bars earlier
+    fn correlation(&self, lag: usize) -> f64 {
+        let (mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for i in 0..AVG_LENGTH {
+            let x = self.filtered[i];
+            let y = self.filtered[i + lag];
+            sx += x;
+            sy += y;
+            sxx += x * x;
+            syy += y * y;
+            sxy += x * y;
+        }
+        let n = AVG_LENGTH as f64;
+        let denominator = (n * sxx - sx * sx) * (n * syy - sy * sy);
+        if denominator > 0.0 {
+            (n * sxy - sx * sy) / denominator.sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+fn detrended(values: &[f64], detrend: Detrend) -> Vec<f64> {
+    let n = values.len() as f64;
+    match detrend {
+        Detrend::None => values.to_vec(),
+        Detrend::Mean => {
+            let mean = values.iter().sum::<f64>() / n;
+            values.iter().map(|v| v - mean).collect()
+        }
+        Detrend::Linear => {
+            let mean_x = (n - 1.0) / 2.0;
+            let mean_y = values.iter().sum::<f64>() / n;
+            let (mut sxy, mut sxx) = (0.0, 0.0);
+            for (i, y) in values.iter().enumerate() {
+                let dx = i as f64 - mean_x;
+                sxy += dx * (y - mean_y);
+                sxx += dx * dx;
+            }
+            let slope = sxy / sxx;
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, y)| y - mean_y - slope * (i as f64 - mean_x))
+                .collect()
+        }
+    }
+}
+
+/// In-place iterative radix-2 FFT; the length must be a power of two
+pub(crate) fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(period: f64, amplitude: f64, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| amplitude * (2.0 * PI * i as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_matches_naive_dft() {
+        let signal = [1.0, 2.0, -1.0, 0.5, 3.0, -2.0, 0.0, 1.5];
+        let (mut re, mut im) = (signal.to_vec(), vec![0.0; 8]);
+        fft(&mut re, &mut im);
+
+        for k in 0..8 {
+            let (mut dft_re, mut dft_im) = (0.0, 0.0);
+            for (t, x) in signal.iter().enumerate() {
+                let angle = -2.0 * PI * (k * t) as f64 / 8.0;
+                dft_re += x * angle.cos();
+                dft_im += x * angle.sin();
+            }
+            assert!((re[k] - dft_re).abs() < 1e-10);
+            assert!((im[k] - dft_im).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_detects_single_cycle() {
+        let cycles = dominant_cycles(&sine(16.0, 1.0, 256), Detrend::Mean, 3).unwrap();
+        assert_eq!(cycles[0].period, 16.0);
+        let total: f64 = periodogram(&sine(16.0, 1.0, 256), Detrend::Mean)
+            .unwrap()
+            .iter()
+            .map(|p| p.power)
+            .sum();
+        assert!(cycles[0].power > 0.4 * total);
+    }
+
+    #[test]
+    fn test_ranks_multiple_cycles_by_power() {
+        let values: Vec<f64> = sine(8.0, 1.0, 512)
+            .iter()
+            .zip(sine(32.0, 3.0, 512))
+            .map(|(a, b)| a + b)
+            .collect();
+        let cycles = dominant_cycles(&values, Detrend::Mean, 2).unwrap();
+
+        assert_eq!(cycles[0].period, 32.0);
+        assert_eq!(cycles[1].period, 8.0);
+    }
+
+    #[test]
+    fn test_linear_detrend_removes_trend_power() {
+        let values: Vec<f64> = sine(10.0, 1.0, 300)
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v + 2.0 * i as f64)
+            .collect();
+
+        let cycles = dominant_cycles(&values, Detrend::Linear, 1).unwrap();
+        assert!((cycles[0].period - 10.0).abs() < 0.5);
+        assert!(periodogram(&values[..3], Detrend::None).is_err());
+    }
+
+    #[test]
+    fn test_autocorrelation_periodogram_tracks_cycle_change() {
+        let mut prices: Vec<f64> = sine(30.0, 1.0, 300).iter().map(|v| 100.0 + v).collect();
+        let phase = 300.0 / 30.0 * 2.0 * PI;
+        prices.extend((0..300).map(|i| 100.0 + (phase + 2.0 * PI * i as f64 / 14.0).sin()));
+
+        let estimates = AutocorrelationPeriodogram::new(10, 48)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+        assert!(estimates[50].is_none() && estimates[51].is_some());
+        assert!(
+            (estimates[299].unwrap() - 30.0).abs() < 3.0,
+            "{:?}",
+            estimates[299]
+        );
+        assert!(
+            (estimates[599].unwrap() - 14.0).abs() < 2.0,
+            "{:?}",
+            estimates[599]
+        );
+        assert!(estimates
+            .iter()
+            .flatten()
+            .all(|p| (10.0..=48.0).contains(p)));
+    }
+
+    #[test]
+    fn test_autocorrelation_periodogram_streaming_and_invalid_inputs() {
+        let prices = sine(20.0, 2.0, 120);
+        let mut periodogram = AutocorrelationPeriodogram::new(8, 30).unwrap();
+        let batch = periodogram.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| periodogram.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        periodogram.reset();
+        assert_eq!(periodogram.next(1.0), None);
+        assert_eq!(
+            (periodogram.min_period(), periodogram.max_period()),
+            (8, 30)
+        );
+
+        // Constant prices carry no cycle
+        let flat = periodogram.calculate(&[5.0; 60]).unwrap();
+        assert!(flat.iter().all(Option::is_none));
+
+        assert!(AutocorrelationPeriodogram::new(2, 30).is_err());
+        assert!(AutocorrelationPeriodogram::new(10, 10).is_err());
+        assert!(periodogram.calculate(&prices[..33]).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Schaff Trend Cycle (STC)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, RollingMax, RollingMin, EMA};
+
+/// Weight of each new value in the half-life smoothing between stages
+const SMOOTHING: f64 = 0.5;
+
+/// Schaff Trend Cycle indicator
+///
+/// Runs the MACD line through two smoothed stochastic stages so it cycles
+/// between 0 and 100 faster than MACD itself:
+///
+/// 1. MACD = EMA(fast) − EMA(slow)
+/// 2. %K₁ = stochastic of MACD over `cycle` bars, smoothed by half
+/// 3. STC = stochastic of %K₁ over `cycle` bars, smoothed by half
+///
+/// A stage whose window has no range repeats its previous raw value. The
+/// first value appears at bar `slow − 1 + 2 × (cycle − 1)`. Readings above
+/// 75 are conventionally overbought and below 25 oversold.
+///
+/// # Example
+///
+/// ```
+/// use indicator::STC;
+///
+/// let prices: Vec<f64> = (0..80).map(|i| 50.0 + (i as f64 * 0.2).sin() * 5.0).collect();
+/// let result = STC::new(5, 8, 16)?.calculate(&prices)?;
+///
+/// assert!(result[22].is_none());
+/// assert!(result[23..].iter().all(|v| (0.0..=100.0).contains(&v.unwrap())));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct STC {
+    fast: EMA,
+    slow: EMA,
+    macd_stage: StochasticStage,
+    signal_stage: StochasticStage,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl STC {
+    /// Creates a new STC indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `cycle` - Stochastic lookback of both stages (must be > 0, typically 10)
+    /// * `fast_period` - Fast MACD EMA period (must be > 0 and < `slow_period`,
+    ///   typically 23)
+    /// * `slow_period` - Slow MACD EMA period (typically 50)
+    pub fn new(
+        cycle: usize,
+        fast_period: usize,
+        slow_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        if cycle == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "cycle",
+                value: cycle as f64,
+                constraint: "> 0",
+            });
+        }
+        if fast_period >= slow_period {
+            return Err(IndicatorError::InvalidParameter {
+                field: "fast_period",
+                value: fast_period as f64,
+                constraint: "< slow_period",
+            });
+        }
+
+        Ok(Self {
+            fast: EMA::new(fast_period)?,
+            slow: EMA::new(slow_period)?,
+            macd_stage: StochasticStage::new(cycle)?,
+            signal_stage: StochasticStage::new(cycle)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates STC for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than
+    /// `slow_period + 2 × (cycle − 1)` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let cycle = self.macd_stage.period();
+        check_min_length(self.slow.period() + 2 * (cycle - 1), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let fast = self.fast.next(price);
+        let macd = self
+            .slow
+            .next(price)
+            .zip(fast)
+            .map(|(slow, fast)| fast - slow)?;
+        let k = self.macd_stage.next(macd)?;
+        self.signal_stage.next(k)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.macd_stage.reset();
+        self.signal_stage.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the cycle, fast and slow periods
+    pub fn periods(&self) -> (usize, usize, usize) {
+        (
+            self.macd_stage.period(),
+            self.fast.period(),
+            self.slow.period(),
+        )
+    }
+}
+
+/// One stochastic-then-smooth stage of the STC
+#[derive(Debug, Clone)]
+struct StochasticStage {
+    highs: RollingMax,
+    lows: RollingMin,
+    raw: Option<f64>,
+    smoothed: Option<f64>,
+}
+
+impl StochasticStage {
+    fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            raw: None,
+            smoothed: None,
+        })
+    }
+
+    fn period(&self) -> usize {
+        self.highs.period()
+    }
+
+    fn next(&mut self, value: f64) -> Option<f64> {
+        let high = self.highs.next(value);
+        let (high, low) = high.zip(self.lows.next(value))?;
+        let raw = if high > low {
+            100.0 * (value - low) / (high - low)
+        } else {
+            self.raw.unwrap_or(50.0)
+        };
+        self.raw = Some(raw);
+
+        let smoothed = match self.smoothed {
+            Some(prev) => prev + SMOOTHING * (raw - prev),
+            None => raw,
+        };
+        self.smoothed = Some(smoothed);
+        self.smoothed
+    }
+
+    fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.raw = None;
+        self.smoothed = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stc_bounded_and_warmup() {
+        let prices: Vec<f64> = (0..120)
+            .map(|i| 50.0 + (i as f64 * 0.15).sin() * 8.0)
+            .collect();
+        let result = STC::new(10, 23, 50).unwrap().calculate(&prices).unwrap();
+
+        assert!(result[66].is_none());
+        assert!(result[67].is_some());
+        assert!(result.iter().flatten().all(|v| (0.0..=100.0).contains(v)));
+    }
+
+    #[test]
+    fn test_stc_follows_cycle_direction() {
+        let prices: Vec<f64> = (0..120)
+            .map(|i| 50.0 + (i as f64 * 0.15).sin() * 8.0)
+            .collect();
+        let result = STC::new(10, 23, 50).unwrap().calculate(&prices).unwrap();
+
+        // Price bottoms near bar 73 and tops near bar 94; STC swings fully
+        // to each extreme shortly after
+        assert_eq!(result[75], Some(0.0));
+        assert!(result[90].unwrap() > 99.0);
+        assert!(result[110].unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_stc_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..50).map(|i| 10.0 + (i as f64 * 0.4).cos()).collect();
+        let mut stc = STC::new(5, 6, 12).unwrap();
+        let batch = stc.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| stc.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        stc.reset();
+        assert_eq!(stc.next(1.0), None);
+        assert_eq!(stc.periods(), (5, 6, 12));
+    }
+
+    #[test]
+    fn test_stc_invalid_inputs() {
+        assert!(STC::new(0, 23, 50).is_err());
+        assert!(STC::new(10, 50, 23).is_err());
+        assert!(STC::new(10, 0, 50).is_err());
+        assert!(matches!(
+            STC::new(10, 23, 50).unwrap().calculate(&[1.0; 67]),
+            Err(IndicatorError::InsufficientData {
+                required: 68,
+                actual: 67
+            })
+        ));
+    }
+}
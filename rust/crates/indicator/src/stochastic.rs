@@ -0,0 +1,308 @@
+//! Stochastic oscillator
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::sma::RollingMean;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, RollingMax, RollingMin};
+
+/// Stochastic oscillator values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticOutput {
+    /// %K: close within the recent high-low range, optionally smoothed, 0 to 100
+    pub k: f64,
+    /// %D: simple average of %K; `None` without a %D period or during its
+    /// warmup
+    pub d: Option<f64>,
+}
+
+/// Stochastic oscillator (%K/%D)
+///
+/// Locates the close within the high-low range of the last `k_period` bars:
+///
+/// raw %K = 100 × (Close − LowestLow) / (HighestHigh − LowestLow)
+///
+/// %K is the raw value, or its simple average over `k_smoothing` bars for
+/// the "slow" stochastic, and %D a simple average of %K. A window with no
+/// range reports the midpoint, 50.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Candle, Stochastic};
+///
+/// let candles: Vec<Candle> = (0..30)
+///     .map(|i| {
+///         let close = 100.0 + (i as f64 * 0.4).sin() * 5.0;
+///         Candle::new(i, close, close + 1.0, close - 1.0, close, 1_000.0)
+///     })
+///     .collect();
+///
+/// // Slow stochastic 14-3-3
+/// let result = Stochastic::new(14, Some(3), Some(3))?.calculate_candles(&candles)?;
+///
+/// assert!(result[14].is_none());
+/// assert!(result[15].unwrap().d.is_none());
+/// assert!(result[17].unwrap().d.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Stochastic {
+    highs: RollingMax,
+    lows: RollingMin,
+    k_smoothing: Option<RollingMean>,
+    d: Option<RollingMean>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Stochastic {
+    /// Creates a new stochastic oscillator
+    ///
+    /// # Arguments
+    ///
+    /// * `k_period` - High-low lookback in bars (must be > 0, typically 14)
+    /// * `k_smoothing` - Optional SMA period applied to %K (must be > 0,
+    ///   typically 3 for the slow stochastic)
+    /// * `d_period` - Optional SMA period of %D over %K (must be > 0,
+    ///   typically 3)
+    pub fn new(
+        k_period: usize,
+        k_smoothing: Option<usize>,
+        d_period: Option<usize>,
+    ) -> Result<Self, IndicatorError> {
+        if k_period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "k_period",
+                value: k_period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            highs: RollingMax::new(k_period)?,
+            lows: RollingMin::new(k_period)?,
+            k_smoothing: k_smoothing
+                .map(|period| RollingMean::new("k_smoothing", period))
+                .transpose()?,
+            d: d_period
+                .map(|period| RollingMean::new("d_period", period))
+                .transpose()?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates %K and %D for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are too few bars for a single %K value.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<StochasticOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.warmup(), len)?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates %K and %D for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few candles for a single
+    /// %K value.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<StochasticOutput>>, IndicatorError> {
+        check_min_length(self.warmup(), candles.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<StochasticOutput> {
+        self.undo = Snapshot::of(self);
+        let highest = self.highs.next(high);
+        let (highest, lowest) = highest.zip(self.lows.next(low))?;
+        let range = highest - lowest;
+        let raw = if range == 0.0 {
+            50.0
+        } else {
+            100.0 * (close - lowest) / range
+        };
+
+        let k = match &mut self.k_smoothing {
+            Some(mean) => mean.next(raw)?,
+            None => raw,
+        };
+        let d = self.d.as_mut().and_then(|mean| mean.next(k));
+        Some(StochasticOutput { k, d })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<StochasticOutput> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        for mean in self.k_smoothing.iter_mut().chain(&mut self.d) {
+            mean.reset();
+        }
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the %K lookback, %K smoothing and %D periods
+    pub fn periods(&self) -> (usize, Option<usize>, Option<usize>) {
+        (
+            self.highs.period(),
+            self.k_smoothing.as_ref().map(|mean| mean.period),
+            self.d.as_ref().map(|mean| mean.period),
+        )
+    }
+
+    /// Number of bars before the first %K value
+    fn warmup(&self) -> usize {
+        let smoothing = self.k_smoothing.as_ref().map_or(1, |mean| mean.period);
+        self.highs.period() + smoothing - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WilliamsR;
+
+    fn candles() -> Vec<Candle> {
+        (0..40)
+            .map(|i| {
+                let base = 50.0 + (i as f64 * 0.5).sin() * 4.0 + i as f64 * 0.05;
+                Candle::new(i, base, base + 0.7, base - 0.9, base + 0.3, 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fast_k_mirrors_williams_r() {
+        let candles = candles();
+        let fast = Stochastic::new(10, None, None)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+        let williams = WilliamsR::new(10)
+            .unwrap()
+            .calculate_candles(&candles)
+            .unwrap();
+
+        for (stoch, williams) in fast.iter().zip(&williams) {
+            assert_eq!(stoch.is_some(), williams.is_some());
+            if let (Some(stoch), Some(williams)) = (stoch, williams) {
+                assert!((stoch.k - (williams + 100.0)).abs() < 1e-9);
+                assert!(stoch.d.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_slow_stochastic_smoothing() {
+        let high = [10.0, 12.0, 11.0, 13.0, 12.0, 14.0];
+        let low = [8.0, 9.0, 9.0, 10.0, 10.0, 11.0];
+        let close = [9.0, 11.0, 10.0, 12.0, 11.0, 13.5];
+        let stochastic = Stochastic::new(3, Some(2), Some(2)).unwrap();
+        let result = stochastic.calculate(&high, &low, &close).unwrap();
+
+        // Raw %K: bar 2 = 50, bar 3 = 75, bar 4 = 50, bar 5 = 87.5
+        assert!(result[2].is_none());
+        assert_eq!(result[3].unwrap().k, 62.5);
+        assert_eq!(result[3].unwrap().d, None);
+        assert_eq!(result[4].unwrap().k, 62.5);
+        assert_eq!(result[4].unwrap().d, Some(62.5));
+        assert_eq!(
+            result[5],
+            Some(StochasticOutput {
+                k: 68.75,
+                d: Some(65.625)
+            })
+        );
+        assert_eq!(stochastic.periods(), (3, Some(2), Some(2)));
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_flat_window() {
+        let candles = candles();
+        let mut stochastic = Stochastic::new(14, Some(3), Some(3)).unwrap();
+        let batch = stochastic.calculate_candles(&candles).unwrap();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| stochastic.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+        assert!(batch.iter().flatten().all(|o| (0.0..=100.0).contains(&o.k)));
+
+        let flat = [5.0; 4];
+        let result = Stochastic::new(2, None, None)
+            .unwrap()
+            .calculate(&flat, &flat, &flat)
+            .unwrap();
+        assert_eq!(result[3].unwrap().k, 50.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(Stochastic::new(0, None, None).is_err());
+        assert!(matches!(
+            Stochastic::new(14, Some(0), None),
+            Err(IndicatorError::InvalidParameter {
+                field: "k_smoothing",
+                ..
+            })
+        ));
+        assert!(Stochastic::new(14, None, Some(0)).is_err());
+
+        let stochastic = Stochastic::new(5, Some(3), None).unwrap();
+        assert!(matches!(
+            stochastic.calculate(&[1.0; 6], &[1.0; 6], &[1.0; 6]),
+            Err(IndicatorError::InsufficientData {
+                required: 7,
+                actual: 6
+            })
+        ));
+        assert!(stochastic
+            .calculate(&[1.0; 9], &[1.0; 8], &[1.0; 9])
+            .is_err());
+    }
+}
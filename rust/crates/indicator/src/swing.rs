@@ -0,0 +1,897 @@
+//! Swing high/low (pivot) detection, Williams Fractals and the ZigZag indicator
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::{Candle, IndicatorError, ATR};
+
+/// Direction of a swing point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingKind {
+    /// Local price peak
+    High,
+    /// Local price trough
+    Low,
+}
+
+/// A swing high or low at a specific bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwingPoint {
+    /// Whether the swing is a peak or a trough
+    pub kind: SwingKind,
+    /// Index of the bar where the extreme price occurred
+    pub index: usize,
+    /// Extreme price (the bar's high for swing highs, low for swing lows)
+    pub price: f64,
+}
+
+/// Reversal threshold used by [`ZigZag`] to confirm a swing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZigZagThreshold {
+    /// Price must reverse by this percentage of the swing price (e.g. `5.0` = 5%)
+    Percent(f64),
+    /// Price must reverse by `multiplier` times the Wilder ATR over `period` bars
+    Atr {
+        /// ATR lookback period
+        period: usize,
+        /// Number of ATRs the price must retrace
+        multiplier: f64,
+    },
+}
+
+/// ZigZag swing indicator
+///
+/// Tracks the running extreme of the current leg and confirms it as a swing
+/// point once price reverses by the configured threshold. A swing can only be
+/// known after that reversal, so the output is aligned to the **confirmation
+/// bar**: `output[t]` holds the swing confirmed at bar `t`, whose
+/// [`index`](SwingPoint::index) points back to the earlier extreme bar. The gap
+/// `t - index` is the confirmation delay; a backtest reading `output[..=t]`
+/// therefore never sees a swing before it could have been known.
+///
+/// The still-forming extreme of the current leg (the part of a ZigZag plot
+/// that "repaints") is available separately through
+/// [`pending`](ZigZag::pending).
+///
+/// # Example
+///
+/// ```
+/// use indicator::{SwingKind, ZigZag, ZigZagThreshold};
+///
+/// let zigzag = ZigZag::new(ZigZagThreshold::Percent(5.0))?;
+/// let high = vec![100.0, 104.0, 108.0, 103.0, 101.0, 99.0, 104.0, 107.0];
+/// let low = vec![98.0, 102.0, 106.0, 101.0, 99.0, 97.0, 102.0, 105.0];
+/// let close = vec![99.0, 103.0, 107.0, 102.0, 100.0, 98.0, 103.0, 106.0];
+///
+/// let swings = zigzag.calculate(&high, &low, &close)?;
+///
+/// // The peak at bar 2 is confirmed at bar 3, once the low drops 5% below it
+/// let peak = swings[3].unwrap();
+/// assert_eq!(peak.kind, SwingKind::High);
+/// assert_eq!(peak.index, 2);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZigZag {
+    threshold: ZigZagThreshold,
+    /// ATR state when using an ATR threshold
+    atr: Option<ATR>,
+    /// Number of bars seen so far
+    count: usize,
+    /// Direction of the leg currently being extended (`None` before the first swing)
+    direction: Option<SwingKind>,
+    /// Candidate swing high and the lowest low seen after it
+    up: Leg,
+    /// Candidate swing low and the highest high seen after it
+    down: Leg,
+    /// `count`, `direction`, `up` and `down` before the last `next`, or
+    /// `None` when there is nothing to roll back
+    undo: Option<(usize, Option<SwingKind>, Leg, Leg)>,
+}
+
+/// A candidate swing extreme, the opposite extreme reached since, and the
+/// extreme in the leg's own direction reached after that retracement
+#[derive(Debug, Clone, Copy, Default)]
+struct Leg {
+    extreme: Option<(usize, f64)>,
+    retrace: Option<(usize, f64)>,
+    rebound: Option<(usize, f64)>,
+}
+
+impl ZigZag {
+    /// Creates a new ZigZag indicator with the given reversal threshold
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the percentage or ATR multiplier is not
+    /// positive, or if the ATR period is zero.
+    pub fn new(threshold: ZigZagThreshold) -> Result<Self, IndicatorError> {
+        let atr = match threshold {
+            ZigZagThreshold::Percent(percent) => {
+                if percent.is_nan() || percent <= 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "percent",
+                        value: percent,
+                        constraint: "> 0",
+                    });
+                }
+                None
+            }
+            ZigZagThreshold::Atr { period, multiplier } => {
+                if multiplier.is_nan() || multiplier <= 0.0 {
+                    return Err(IndicatorError::InvalidParameter {
+                        field: "multiplier",
+                        value: multiplier,
+                        constraint: "> 0",
+                    });
+                }
+                Some(ATR::new(period)?)
+            }
+        };
+
+        Ok(Self {
+            threshold,
+            atr,
+            count: 0,
+            direction: None,
+            up: Leg::default(),
+            down: Leg::default(),
+            undo: None,
+        })
+    }
+
+    /// Calculates confirmed swings for a batch of bars
+    ///
+    /// The calculation starts from a fresh state regardless of any streaming
+    /// state held by `self`.
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the inputs, holding `Some(swing)` at
+    /// each bar where a swing was confirmed.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<SwingPoint>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.warmup(), len)?;
+
+        let mut state = Self::new(self.threshold)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates confirmed swings for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are too few candles to confirm a
+    /// swing.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<SwingPoint>>, IndicatorError> {
+        check_min_length(self.warmup(), candles.len())?;
+
+        let mut state = Self::new(self.threshold)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns the swing confirmed by this bar, if any. Bar indices count from
+    /// the first bar passed to the indicator (or since the last
+    /// [`reset`](Self::reset)).
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<SwingPoint> {
+        self.undo = Some((self.count, self.direction, self.up, self.down));
+        let index = self.count;
+        self.count += 1;
+        let atr = self
+            .atr
+            .as_mut()
+            .and_then(|atr| atr.next(high, low, close).atr);
+
+        // Before the first swing both directions are tracked; afterwards only
+        // the leg currently being extended
+        let confirmed = match self.direction {
+            None => {
+                self.up.extend(SwingKind::High, index, high, low);
+                self.down.extend(SwingKind::Low, index, high, low);
+                self.confirm(SwingKind::High, atr)
+                    .or_else(|| self.confirm(SwingKind::Low, atr))
+            }
+            Some(SwingKind::High) => {
+                self.up.extend(SwingKind::High, index, high, low);
+                self.confirm(SwingKind::High, atr)
+            }
+            Some(SwingKind::Low) => {
+                self.down.extend(SwingKind::Low, index, high, low);
+                self.confirm(SwingKind::Low, atr)
+            }
+        }?;
+
+        // The retracement extreme becomes the candidate of the opposite leg and
+        // the rebound after it that leg's retracement. With an ATR threshold
+        // confirmation can lag the retracement by several bars, so both may
+        // predate the current bar, which seeds the new rebound if it came later.
+        let (next_kind, leg, price) = match confirmed.kind {
+            SwingKind::High => (SwingKind::Low, self.up, low),
+            SwingKind::Low => (SwingKind::High, self.down, high),
+        };
+        let next_leg = Leg {
+            extreme: leg.retrace,
+            retrace: leg.rebound,
+            rebound: leg
+                .rebound
+                .filter(|&(rebound_index, _)| rebound_index < index)
+                .map(|_| (index, price)),
+        };
+        match next_kind {
+            SwingKind::High => self.up = next_leg,
+            SwingKind::Low => self.down = next_leg,
+        }
+        self.direction = Some(next_kind);
+
+        Some(confirmed)
+    }
+
+    /// Returns the unconfirmed extreme of the current leg
+    ///
+    /// This point may still move as new bars arrive (the "repainting" end of
+    /// a ZigZag line) and must not be treated as a known swing.
+    pub fn pending(&self) -> Option<SwingPoint> {
+        match self.direction {
+            Some(SwingKind::High) => self.up.extreme.map(|leg| swing(SwingKind::High, leg)),
+            Some(SwingKind::Low) => self.down.extreme.map(|leg| swing(SwingKind::Low, leg)),
+            None => None,
+        }
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next),
+    /// so a swing confirmed by the provisional bar is withdrawn if the revised
+    /// bar no longer confirms it.
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<SwingPoint> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until the
+    /// next bar arrives.
+    pub fn rollback(&mut self) {
+        let Some((count, direction, up, down)) = self.undo.take() else {
+            return;
+        };
+        if let Some(atr) = self.atr.as_mut() {
+            atr.rollback();
+        }
+        self.count = count;
+        self.direction = direction;
+        self.up = up;
+        self.down = down;
+    }
+
+    /// Clears all state so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.reset();
+        }
+        self.count = 0;
+        self.direction = None;
+        self.up = Leg::default();
+        self.down = Leg::default();
+        self.undo = None;
+    }
+
+    /// Returns the configured reversal threshold
+    pub fn threshold(&self) -> ZigZagThreshold {
+        self.threshold
+    }
+
+    /// Number of bars needed before a swing can be confirmed
+    fn warmup(&self) -> usize {
+        match self.threshold {
+            ZigZagThreshold::Percent(_) => 2,
+            ZigZagThreshold::Atr { period, .. } => period + 1,
+        }
+    }
+
+    /// Returns the candidate swing of `kind` if price has retraced far enough
+    /// from it to confirm it
+    fn confirm(&self, kind: SwingKind, atr: Option<f64>) -> Option<SwingPoint> {
+        let leg = match kind {
+            SwingKind::High => self.up,
+            SwingKind::Low => self.down,
+        };
+        let (extreme, (_, retrace)) = (leg.extreme?, leg.retrace?);
+        let reversal = self.reversal(extreme.1, atr)?;
+
+        let reached = match kind {
+            SwingKind::High => retrace <= extreme.1 - reversal,
+            SwingKind::Low => retrace >= extreme.1 + reversal,
+        };
+        reached.then(|| swing(kind, extreme))
+    }
+
+    /// Price distance required to confirm a reversal from `price`, or `None`
+    /// while the ATR is still warming up
+    fn reversal(&self, price: f64, atr: Option<f64>) -> Option<f64> {
+        match self.threshold {
+            ZigZagThreshold::Percent(percent) => Some(price.abs() * percent / 100.0),
+            ZigZagThreshold::Atr { multiplier, .. } => atr.map(|atr| atr * multiplier),
+        }
+    }
+}
+
+impl Leg {
+    /// Updates the leg with a new bar
+    ///
+    /// A new extreme restarts the retracement tracking, so the retracement is
+    /// always measured on bars strictly after the extreme; likewise a new
+    /// retracement restarts the rebound tracking.
+    fn extend(&mut self, kind: SwingKind, index: usize, high: f64, low: f64) {
+        let (price, opposite) = match kind {
+            SwingKind::High => (high, low),
+            SwingKind::Low => (low, high),
+        };
+        let is_new_extreme = match (kind, self.extreme) {
+            (_, None) => true,
+            (SwingKind::High, Some((_, extreme))) => price > extreme,
+            (SwingKind::Low, Some((_, extreme))) => price < extreme,
+        };
+
+        if is_new_extreme {
+            self.extreme = Some((index, price));
+            self.retrace = None;
+            self.rebound = None;
+            return;
+        }
+
+        let is_new_retrace = match (kind, self.retrace) {
+            (_, None) => true,
+            (SwingKind::High, Some((_, retrace))) => opposite < retrace,
+            (SwingKind::Low, Some((_, retrace))) => opposite > retrace,
+        };
+        if is_new_retrace {
+            self.retrace = Some((index, opposite));
+            self.rebound = None;
+            return;
+        }
+
+        let is_new_rebound = match (kind, self.rebound) {
+            (_, None) => true,
+            (SwingKind::High, Some((_, rebound))) => price > rebound,
+            (SwingKind::Low, Some((_, rebound))) => price < rebound,
+        };
+        if is_new_rebound {
+            self.rebound = Some((index, price));
+        }
+    }
+}
+
+/// Swing points confirmed on one bar by [`SwingDetector`] or [`Fractals`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FractalOutput {
+    /// Swing high confirmed on this bar, if any
+    pub high: Option<SwingPoint>,
+    /// Swing low confirmed on this bar, if any
+    pub low: Option<SwingPoint>,
+}
+
+/// Pivot (swing high/low) detector with separate left and right confirmation
+///
+/// A swing high is a bar whose high is strictly above the highs of the
+/// `left` bars before it and the `right` bars after it; a swing low mirrors
+/// it with lows. A single bar can be both. Fewer `right` bars confirm pivots
+/// sooner at the cost of more noise.
+///
+/// The streaming output is aligned to the **confirmation bar**: a pivot at
+/// bar `i` is only known once bar `i + right` has closed, so `output[i + right]`
+/// holds it and its [`index`](SwingPoint::index) points back to bar `i`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{SwingDetector, SwingKind};
+///
+/// let high = [10.0, 11.0, 13.0, 12.0, 11.5];
+/// let low = [9.0, 9.5, 12.0, 10.0, 10.5];
+///
+/// // Two bars of history on the left, one confirming bar on the right
+/// let swings = SwingDetector::new(2, 1)?.points(&high, &low)?;
+/// assert_eq!(swings.len(), 1);
+/// assert_eq!((swings[0].kind, swings[0].index, swings[0].price), (SwingKind::High, 2, 13.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SwingDetector {
+    left: usize,
+    right: usize,
+    /// Number of bars seen so far
+    count: usize,
+    /// Highs and lows of the last `left + right + 1` bars, oldest first
+    window: VecDeque<(f64, f64)>,
+}
+
+impl SwingDetector {
+    /// Creates a new swing detector
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Bars required before a pivot (must be > 0)
+    /// * `right` - Bars required after a pivot to confirm it (must be > 0)
+    pub fn new(left: usize, right: usize) -> Result<Self, IndicatorError> {
+        for (field, value) in [("left", left), ("right", right)] {
+            if value == 0 {
+                return Err(IndicatorError::InvalidParameter {
+                    field,
+                    value: value as f64,
+                    constraint: "> 0",
+                });
+            }
+        }
+
+        Ok(Self {
+            left,
+            right,
+            count: 0,
+            window: VecDeque::with_capacity(left + right + 1),
+        })
+    }
+
+    /// Detects swings for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `left + right + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+    ) -> Result<Vec<FractalOutput>, IndicatorError> {
+        let len = check_equal_lengths(&[("high", high.len()), ("low", low.len())])?;
+        check_min_length(self.span(), len)?;
+
+        let mut state = Self::new(self.left, self.right)?;
+        Ok(high
+            .iter()
+            .zip(low)
+            .map(|(&high, &low)| state.next(high, low))
+            .collect())
+    }
+
+    /// Detects swings for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `left + right + 1`
+    /// candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<FractalOutput>, IndicatorError> {
+        check_min_length(self.span(), candles.len())?;
+
+        let mut state = Self::new(self.left, self.right)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low))
+            .collect())
+    }
+
+    /// All confirmed swing points of a batch of bars, ordered by bar index
+    ///
+    /// For a single series such as closes or an oscillator, pass it as both
+    /// `high` and `low`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`calculate`](Self::calculate).
+    pub fn points(&self, high: &[f64], low: &[f64]) -> Result<Vec<SwingPoint>, IndicatorError> {
+        Ok(self
+            .calculate(high, low)?
+            .into_iter()
+            .flat_map(|output| output.high.into_iter().chain(output.low))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns the swings `right` bars ago that this bar confirms.
+    pub fn next(&mut self, high: f64, low: f64) -> FractalOutput {
+        self.count += 1;
+        if self.window.len() == self.span() {
+            self.window.pop_front();
+        }
+        self.window.push_back((high, low));
+        if self.window.len() < self.span() {
+            return FractalOutput::default();
+        }
+
+        let index = self.count - 1 - self.right;
+        let (centre_high, centre_low) = self.window[self.left];
+        let others = || {
+            self.window
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != self.left)
+                .map(|(_, bar)| bar)
+        };
+        let is_high = others().all(|&(high, _)| high < centre_high);
+        let is_low = others().all(|&(_, low)| low > centre_low);
+
+        FractalOutput {
+            high: is_high.then(|| swing(SwingKind::High, (index, centre_high))),
+            low: is_low.then(|| swing(SwingKind::Low, (index, centre_low))),
+        }
+    }
+
+    /// Clears all state so the detector can be reused on a new series
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.window.clear();
+    }
+
+    /// Returns the number of bars required before a pivot
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// Returns the number of bars required after a pivot
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    /// Bars in the detection window
+    fn span(&self) -> usize {
+        self.left + self.right + 1
+    }
+}
+
+/// Williams Fractals
+///
+/// A fractal high is a bar whose high is strictly above the highs of the
+/// `wings` bars on each side; a fractal low mirrors it with lows. The
+/// classic pattern uses two wings (five bars). A single bar can be both.
+/// This is a [`SwingDetector`] with `left = right = wings`.
+///
+/// Like [`ZigZag`], the output is aligned to the **confirmation bar**: a
+/// fractal at bar `i` is only known once bar `i + wings` has closed, so
+/// `output[i + wings]` holds it and its [`index`](SwingPoint::index)
+/// points back to bar `i`. Breakout rules reading `output[..=t]` never act
+/// on a fractal before it exists.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Fractals, SwingKind};
+///
+/// let high = [10.0, 11.0, 13.0, 12.0, 11.5, 12.5];
+/// let low = [9.0, 9.5, 12.0, 10.0, 10.5, 11.0];
+///
+/// let result = Fractals::new(2)?.calculate(&high, &low)?;
+/// let peak = result[4].high.unwrap();
+/// assert_eq!((peak.kind, peak.index, peak.price), (SwingKind::High, 2, 13.0));
+/// assert_eq!(result[5], Default::default());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fractals {
+    detector: SwingDetector,
+}
+
+impl Fractals {
+    /// Creates a new fractal detector
+    ///
+    /// # Arguments
+    ///
+    /// * `wings` - Bars required on each side of the centre (must be > 0,
+    ///   typically 2)
+    pub fn new(wings: usize) -> Result<Self, IndicatorError> {
+        if wings == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "wings",
+                value: wings as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            detector: SwingDetector::new(wings, wings)?,
+        })
+    }
+
+    /// Detects fractals for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `2 × wings + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+    ) -> Result<Vec<FractalOutput>, IndicatorError> {
+        self.detector.calculate(high, low)
+    }
+
+    /// Detects fractals for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `2 × wings + 1`
+    /// candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<FractalOutput>, IndicatorError> {
+        self.detector.calculate_candles(candles)
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns the fractals centred `wings` bars ago that this bar confirms.
+    pub fn next(&mut self, high: f64, low: f64) -> FractalOutput {
+        self.detector.next(high, low)
+    }
+
+    /// Clears all state so the detector can be reused on a new series
+    pub fn reset(&mut self) {
+        self.detector.reset();
+    }
+
+    /// Returns the number of bars on each side of a fractal
+    pub fn wings(&self) -> usize {
+        self.detector.left()
+    }
+}
+
+fn swing(kind: SwingKind, (index, price): (usize, f64)) -> SwingPoint {
+    SwingPoint { kind, index, price }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swing_detector_asymmetric_confirmation() {
+        let high = [5.0, 6.0, 9.0, 8.0, 7.0, 8.5, 6.0];
+        let low = [4.0, 5.0, 8.0, 3.0, 6.0, 7.5, 5.0];
+        let detector = SwingDetector::new(2, 1).unwrap();
+        let result = detector.calculate(&high, &low).unwrap();
+
+        assert_eq!(result[2], FractalOutput::default());
+        assert_eq!(result[3].high, Some(swing(SwingKind::High, (2, 9.0))));
+        assert_eq!(result[4].low, Some(swing(SwingKind::Low, (3, 3.0))));
+        // The higher peak at bar 2 is outside bar 5's two-bar left window
+        assert_eq!(result[6].high, Some(swing(SwingKind::High, (5, 8.5))));
+
+        let points = detector.points(&high, &low).unwrap();
+        let indices: Vec<usize> = points.iter().map(|p| p.index).collect();
+        assert_eq!(indices, vec![2, 3, 5]);
+
+        // Requiring three bars on the left rejects the peak at bar 2
+        let strict = SwingDetector::new(3, 1)
+            .unwrap()
+            .points(&high, &low)
+            .unwrap();
+        assert_eq!(strict.first().map(|p| p.index), Some(3));
+
+        let mut streaming = detector.clone();
+        let streamed: Vec<_> = high
+            .iter()
+            .zip(&low)
+            .map(|(&h, &l)| streaming.next(h, l))
+            .collect();
+        assert_eq!(streamed, result);
+        assert!(SwingDetector::new(0, 1).is_err());
+        assert!(SwingDetector::new(1, 0).is_err());
+        assert!(detector.points(&high[..3], &low[..3]).is_err());
+    }
+
+    #[test]
+    fn test_fractals_confirmed_after_wings() {
+        let high = [5.0, 7.0, 6.0, 6.0, 8.0, 6.0, 7.0];
+        let low = [4.0, 5.0, 3.0, 5.0, 6.0, 5.0, 6.0];
+        let result = Fractals::new(1).unwrap().calculate(&high, &low).unwrap();
+
+        assert_eq!(result[0], FractalOutput::default());
+        assert_eq!(result[2].high, Some(swing(SwingKind::High, (1, 7.0))));
+        assert_eq!(result[3].low, Some(swing(SwingKind::Low, (2, 3.0))));
+        // Equal neighbouring highs at bars 2 and 3 do not form a fractal
+        assert_eq!(result[3].high, None);
+        assert_eq!(result[4].high, None);
+        assert_eq!(result[5].high, Some(swing(SwingKind::High, (4, 8.0))));
+        assert_eq!(result[6].low, Some(swing(SwingKind::Low, (5, 5.0))));
+    }
+
+    #[test]
+    fn test_fractals_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = (0..25)
+            .map(|i| {
+                let base = 50.0 + (i as f64 * 0.8).sin() * 4.0;
+                Candle::new(i, base, base + 0.6, base - 0.6, base, 1.0)
+            })
+            .collect();
+        let fractals = Fractals::new(2).unwrap();
+        let batch = fractals.calculate_candles(&candles).unwrap();
+
+        let mut streaming = fractals.clone();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| streaming.next(c.high, c.low))
+            .collect();
+        assert_eq!(batch, streamed);
+        assert!(batch.iter().any(|f| f.high.is_some()));
+        assert!(batch.iter().any(|f| f.low.is_some()));
+
+        streaming.reset();
+        assert_eq!(streaming.next(1.0, 0.0), FractalOutput::default());
+        assert!(Fractals::new(0).is_err());
+        assert!(fractals.calculate(&[1.0; 4], &[1.0; 4]).is_err());
+    }
+
+    #[test]
+    fn test_zigzag_invalid_parameters() {
+        assert!(ZigZag::new(ZigZagThreshold::Percent(0.0)).is_err());
+        assert!(ZigZag::new(ZigZagThreshold::Percent(f64::NAN)).is_err());
+        assert!(ZigZag::new(ZigZagThreshold::Atr {
+            period: 0,
+            multiplier: 2.0
+        })
+        .is_err());
+        assert!(ZigZag::new(ZigZagThreshold::Atr {
+            period: 14,
+            multiplier: -1.0
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_zigzag_length_mismatch() {
+        let zigzag = ZigZag::new(ZigZagThreshold::Percent(5.0)).unwrap();
+        let result = zigzag.calculate(&[1.0, 2.0], &[1.0], &[1.0, 2.0]);
+        assert_eq!(
+            result,
+            Err(IndicatorError::LengthMismatch {
+                field: "low",
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_zigzag_percent_swings_alternate() {
+        let zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+        let closes = vec![
+            100.0, 105.0, 112.0, 108.0, 99.0, 95.0, 90.0, 96.0, 101.0, 104.0, 92.0,
+        ];
+        let result = zigzag.calculate(&closes, &closes, &closes).unwrap();
+
+        let swings: Vec<(usize, SwingPoint)> = result
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|s| (i, s)))
+            .collect();
+
+        // Trough at bar 0 (100) confirmed when price reaches 112 (>= 110)
+        assert_eq!(swings[0].0, 2);
+        assert_eq!(swings[0].1, swing(SwingKind::Low, (0, 100.0)));
+        // Peak at bar 2 (112) confirmed when price reaches 99 (<= 100.8)
+        assert_eq!(swings[1].0, 4);
+        assert_eq!(swings[1].1, swing(SwingKind::High, (2, 112.0)));
+        // Trough at bar 6 (90) confirmed when price reaches 101 (>= 99)
+        assert_eq!(swings[2].0, 8);
+        assert_eq!(swings[2].1, swing(SwingKind::Low, (6, 90.0)));
+        // Peak at bar 9 (104) confirmed at bar 10 (92 <= 93.6)
+        assert_eq!(swings[3].0, 10);
+        assert_eq!(swings[3].1, swing(SwingKind::High, (9, 104.0)));
+
+        for (confirmed_at, swing) in &swings {
+            assert!(swing.index < *confirmed_at);
+        }
+    }
+
+    #[test]
+    fn test_zigzag_pending_tracks_current_leg() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+        for price in [100.0, 120.0, 105.0] {
+            zigzag.next(price, price, price);
+        }
+        // 120 -> 105 is a 12.5% drop, confirming the peak; the trough is pending
+        assert_eq!(zigzag.pending(), Some(swing(SwingKind::Low, (2, 105.0))));
+
+        zigzag.next(101.0, 101.0, 101.0);
+        assert_eq!(zigzag.pending(), Some(swing(SwingKind::Low, (3, 101.0))));
+
+        zigzag.reset();
+        assert_eq!(zigzag.pending(), None);
+    }
+
+    #[test]
+    fn test_zigzag_update_last_withdraws_provisional_swing() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Atr {
+            period: 2,
+            multiplier: 1.0,
+        })
+        .unwrap();
+        for price in [100.0, 120.0] {
+            zigzag.next(price, price, price);
+        }
+
+        // A provisional drop confirms the peak; the revised bar does not
+        assert!(zigzag.next(90.0, 90.0, 90.0).is_some());
+        assert_eq!(zigzag.update_last(118.0, 118.0, 118.0), None);
+        assert_eq!(zigzag.pending(), Some(swing(SwingKind::High, (1, 120.0))));
+
+        zigzag.rollback();
+        assert_eq!(
+            zigzag.next(90.0, 90.0, 90.0),
+            Some(swing(SwingKind::High, (1, 120.0)))
+        );
+    }
+
+    #[test]
+    fn test_zigzag_atr_seeds_next_leg_from_extremes_before_confirmation() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Atr {
+            period: 3,
+            multiplier: 1.0,
+        })
+        .unwrap();
+        let high = [
+            100.0, 104.0, 108.0, 130.0, 121.0, 118.0, 112.0, 111.0, 110.0, 109.0, 110.0, 109.5,
+        ];
+        let low = [
+            99.0, 102.0, 106.0, 120.0, 100.0, 104.0, 106.0, 107.0, 106.5, 108.0, 108.5, 108.5,
+        ];
+        let close: Vec<f64> = high.iter().zip(&low).map(|(h, l)| (h + l) / 2.0).collect();
+        let result: Vec<_> = (0..high.len())
+            .map(|i| zigzag.next(high[i], low[i], close[i]))
+            .collect();
+
+        // The trough at bar 6 is only confirmed at bar 10 as the ATR decays;
+        // the peak at bar 7 and the pullback to 106.5 at bar 8 both happened
+        // before that, so the peak is confirmed on the very next bar
+        assert_eq!(result[10], Some(swing(SwingKind::Low, (6, 106.0))));
+        assert_eq!(result[11], Some(swing(SwingKind::High, (7, 111.0))));
+        assert_eq!(zigzag.pending(), Some(swing(SwingKind::Low, (8, 106.5))));
+    }
+
+    #[test]
+    fn test_zigzag_atr_threshold_waits_for_warmup() {
+        let zigzag = ZigZag::new(ZigZagThreshold::Atr {
+            period: 3,
+            multiplier: 2.0,
+        })
+        .unwrap();
+        let high = vec![11.0, 12.0, 13.0, 30.0, 14.0, 13.0, 12.0];
+        let low = vec![9.0, 10.0, 11.0, 28.0, 12.0, 11.0, 10.0];
+        let close = vec![10.0, 11.0, 12.0, 29.0, 13.0, 12.0, 11.0];
+        let result = zigzag.calculate(&high, &low, &close).unwrap();
+
+        // No swing can be confirmed before the ATR has `period` true ranges
+        assert!(result[..2].iter().all(Option::is_none));
+        // ATR = 2 at bar 2, so the rise from 9 to 13 confirms the first trough
+        assert_eq!(result[2], Some(swing(SwingKind::Low, (0, 9.0))));
+        // The spike to 30 widens the ATR; the peak is confirmed once the low
+        // retraces 2 ATRs (ATR ~ 7.7 at bar 5)
+        assert_eq!(result[4], None);
+        assert_eq!(result[5], Some(swing(SwingKind::High, (3, 30.0))));
+    }
+}
@@ -0,0 +1,822 @@
+//! Common streaming indicator trait
+
+use crate::autocorrelation::RollingAutocorrelation;
+use crate::bars::Trade;
+use crate::changepoint::{ChangePoint, Cusum};
+use crate::cycles::{HilbertCycle, HilbertTransform};
+use crate::filters::{GaussianFilter, SavitzkyGolay};
+use crate::kalman::{HedgeEstimate, KalmanFilter, KalmanHedgeRatio, KalmanTrend, TrendEstimate};
+use crate::microstructure::{OrderFlowImbalance, Quote, SpreadStatistics, SpreadStats, Vpin};
+use crate::missing::{NanGuard, NanPolicy};
+use crate::pipeline::{Chain, Map};
+use crate::spectral::AutocorrelationPeriodogram;
+use crate::{
+    AdxOutput, AnchoredVwap, Aroon, AroonOutput, AtrOutput, AwesomeOscillator, AwesomeOutput,
+    BalanceOfPower, BollingerBands, BollingerOutput, Candle, ChandelierExit, ChandelierOutput,
+    Choppiness, ConnorsRSI, Coppock, CorrelationOutput, EaseOfMovement, ElderRay, ElderRayOutput,
+    Envelope, EnvelopeOutput, Fisher, FisherOutput, ForceIndex, Hurst, IndicatorError,
+    KlingerOutput, KlingerVolumeOscillator, LinRegOutput, LinearRegression, MacdOutput, MassIndex,
+    McGinleyDynamic, Moments, PpoOutput, QqeOutput, RangeVol, RealizedVol, Regime,
+    RegimeClassifier, RollingCorrelation, RollingMax, RollingMin, RollingMoments, RollingQuantile,
+    RollingSharpe, RollingSortino, RollingStd, RviOutput, SessionVwap, StochRSI, StochRsiOutput,
+    Stochastic, StochasticOutput, SwingPoint, TrixOutput, TsiOutput, Vortex, VortexOutput,
+    WilliamsR, ZigZag, ADX, ALMA, ATR, CCI, CMO, DEMA, DPO, EMA, KAMA, MACD, OBV, PPO, QQE, RSI,
+    RVI, SMA, STC, TEMA, TRIX, TSI, WMA, ZLEMA,
+};
+
+/// A streaming indicator
+///
+/// Implemented by the indicators that consume one input per bar: a price
+/// (`f64`) for single-series indicators, a [`Candle`] for those that need
+/// several OHLCV fields, an `(f64, f64)` pair for those that compare two
+/// aligned series, or a quote or trade for the
+/// [`microstructure`](crate::microstructure) estimators. Indicators can
+/// therefore be stored as trait objects, driven by generic code and swapped
+/// without changing the caller.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{Indicator, RSI, SMA, WMA};
+///
+/// let mut indicators: Vec<Box<dyn Indicator<Input = f64, Output = f64>>> = vec![
+///     Box::new(SMA::new(3)?),
+///     Box::new(WMA::new(3)?),
+///     Box::new(RSI::new(3)?),
+/// ];
+///
+/// for price in [10.0, 11.0, 10.5, 11.5, 12.0] {
+///     let readings: Vec<Option<f64>> = indicators.iter_mut().map(|i| i.next(price)).collect();
+///     println!("{readings:?}");
+/// }
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+pub trait Indicator {
+    /// Value consumed per bar
+    type Input;
+    /// Value produced per bar once warmed up
+    type Output;
+
+    /// Adds a new input, returning `None` while the indicator warms up
+    fn next(&mut self, input: Self::Input) -> Option<Self::Output>;
+
+    /// Clears the state so the indicator can be reused on a new series
+    fn reset(&mut self);
+
+    /// Calculates the indicator for a batch of inputs on a fresh state
+    ///
+    /// The default feeds a reset copy through [`next`](Self::next);
+    /// indicators override it with their own `calculate`, which also
+    /// rejects series too short to produce a value.
+    fn calculate(&self, inputs: &[Self::Input]) -> Result<Vec<Option<Self::Output>>, IndicatorError>
+    where
+        Self: Sized + Clone,
+        Self::Input: Clone,
+    {
+        let mut state = self.clone();
+        state.reset();
+        Ok(inputs
+            .iter()
+            .cloned()
+            .map(|input| state.next(input))
+            .collect())
+    }
+
+    /// Replaces the most recent input, e.g. when the in-progress bar of a
+    /// live feed is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by
+    /// [`next`](Self::next).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unsupported`, without consuming the input, for indicators
+    /// that do not support `rollback`.
+    fn update_last(&mut self, input: Self::Input) -> Result<Option<Self::Output>, IndicatorError> {
+        self.rollback()?;
+        Ok(self.next(input))
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Supported by every indicator in this crate and by the
+    /// [`then`](Self::then), [`map`](Self::map),
+    /// [`with_nan_policy`](Self::with_nan_policy) and `Box` wrappers around
+    /// them. Only the latest input can be undone; further calls do nothing
+    /// until the next input arrives.
+    ///
+    /// # Errors
+    ///
+    /// The default returns `Unsupported` and leaves the state unchanged.
+    fn rollback(&mut self) -> Result<(), IndicatorError> {
+        Err(IndicatorError::Unsupported {
+            operation: "rollback",
+        })
+    }
+
+    /// Feeds this indicator's outputs into `next`, e.g. an EMA of RSI
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indicator::{Indicator, EMA, RSI};
+    ///
+    /// let prices: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+    /// let smoothed_rsi = RSI::new(14)?.then(EMA::new(5)?).calculate(&prices)?;
+    ///
+    /// assert!(smoothed_rsi[17].is_none() && smoothed_rsi[18].is_some());
+    /// # Ok::<(), indicator::IndicatorError>(())
+    /// ```
+    fn then<B>(self, next: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+        B: Indicator<Input = Self::Output>,
+    {
+        Chain::new(self, next)
+    }
+
+    /// Applies `f` to every output, e.g. to pick one line of a multi-line
+    /// indicator before chaining it
+    fn map<F, O>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Output) -> O,
+    {
+        Map::new(self, f)
+    }
+
+    /// Applies a [`NanPolicy`] to missing or NaN inputs instead of letting
+    /// them propagate through the indicator's state
+    fn with_nan_policy(self, policy: NanPolicy) -> NanGuard<Self>
+    where
+        Self: Sized,
+    {
+        NanGuard::new(self, policy)
+    }
+}
+
+impl<I: Indicator + ?Sized> Indicator for Box<I> {
+    type Input = I::Input;
+    type Output = I::Output;
+
+    fn next(&mut self, input: Self::Input) -> Option<Self::Output> {
+        (**self).next(input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn rollback(&mut self) -> Result<(), IndicatorError> {
+        (**self).rollback()
+    }
+}
+
+/// Implements [`Indicator::rollback`] by delegating to the inherent
+/// `rollback` every indicator in this crate provides
+macro_rules! rollback {
+    ($indicator:ty) => {
+        fn rollback(&mut self) -> Result<(), IndicatorError> {
+            <$indicator>::rollback(self);
+            Ok(())
+        }
+    };
+}
+
+/// Implements [`Indicator`] for price-input indicators by delegating to
+/// their inherent `next`, `reset`, `calculate` and `rollback`
+macro_rules! price_indicators {
+    ($($indicator:ty => $output:ty),* $(,)?) => {$(
+        impl Indicator for $indicator {
+            type Input = f64;
+            type Output = $output;
+
+            fn next(&mut self, price: f64) -> Option<$output> {
+                <$indicator>::next(self, price)
+            }
+
+            fn reset(&mut self) {
+                <$indicator>::reset(self)
+            }
+
+            fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<$output>>, IndicatorError> {
+                <$indicator>::calculate(self, prices)
+            }
+
+            rollback!($indicator);
+        }
+    )*};
+}
+
+/// Implements [`Indicator`] for candle-input indicators, passing the listed
+/// candle fields to their inherent `next` and delegating batches to
+/// `calculate_candles`
+macro_rules! candle_indicators {
+    ($($indicator:ty => $output:ty: ($($field:ident),+)),* $(,)?) => {$(
+        impl Indicator for $indicator {
+            type Input = Candle;
+            type Output = $output;
+
+            fn next(&mut self, candle: Candle) -> Option<$output> {
+                <$indicator>::next(self, $(candle.$field),+)
+            }
+
+            fn reset(&mut self) {
+                <$indicator>::reset(self)
+            }
+
+            fn calculate(
+                &self,
+                candles: &[Candle],
+            ) -> Result<Vec<Option<$output>>, IndicatorError> {
+                <$indicator>::calculate_candles(self, candles)
+            }
+
+            rollback!($indicator);
+        }
+    )*};
+}
+
+/// Implements [`Indicator`] for indicators of two aligned series, taking
+/// each pair as a tuple and unzipping batches for their inherent `calculate`
+macro_rules! pair_indicators {
+    ($($indicator:ty => $output:ty),* $(,)?) => {$(
+        impl Indicator for $indicator {
+            type Input = (f64, f64);
+            type Output = $output;
+
+            fn next(&mut self, (first, second): (f64, f64)) -> Option<$output> {
+                <$indicator>::next(self, first, second)
+            }
+
+            fn reset(&mut self) {
+                <$indicator>::reset(self)
+            }
+
+            fn calculate(
+                &self,
+                pairs: &[(f64, f64)],
+            ) -> Result<Vec<Option<$output>>, IndicatorError> {
+                let (first, second): (Vec<f64>, Vec<f64>) = pairs.iter().copied().unzip();
+                <$indicator>::calculate(self, &first, &second)
+            }
+
+            rollback!($indicator);
+        }
+    )*};
+}
+
+/// Implements [`Indicator`] for indicators whose inherent `next` already
+/// takes a single input of the given type
+macro_rules! event_indicators {
+    ($($indicator:ty: $input:ty => $output:ty),* $(,)?) => {$(
+        impl Indicator for $indicator {
+            type Input = $input;
+            type Output = $output;
+
+            fn next(&mut self, input: $input) -> Option<$output> {
+                <$indicator>::next(self, input)
+            }
+
+            fn reset(&mut self) {
+                <$indicator>::reset(self)
+            }
+
+            fn calculate(&self, inputs: &[$input]) -> Result<Vec<Option<$output>>, IndicatorError> {
+                <$indicator>::calculate(self, inputs)
+            }
+
+            rollback!($indicator);
+        }
+    )*};
+}
+
+price_indicators! {
+    ALMA => f64,
+    AutocorrelationPeriodogram => f64,
+    BollingerBands => BollingerOutput,
+    CMO => f64,
+    ConnorsRSI => f64,
+    Coppock => f64,
+    Cusum => ChangePoint,
+    DEMA => f64,
+    DPO => f64,
+    EMA => f64,
+    Envelope => EnvelopeOutput,
+    GaussianFilter => f64,
+    HilbertTransform => HilbertCycle,
+    Hurst => f64,
+    KalmanFilter => f64,
+    KalmanTrend => TrendEstimate,
+    KAMA => f64,
+    LinearRegression => LinRegOutput,
+    MACD => MacdOutput,
+    PPO => PpoOutput,
+    QQE => QqeOutput,
+    RealizedVol => f64,
+    RegimeClassifier => Regime,
+    RollingAutocorrelation => Vec<f64>,
+    RollingMax => f64,
+    RollingMin => f64,
+    RollingMoments => Moments,
+    RollingQuantile => f64,
+    RollingSharpe => f64,
+    RollingSortino => f64,
+    RollingStd => f64,
+    RSI => f64,
+    SavitzkyGolay => f64,
+    SMA => f64,
+    STC => f64,
+    StochRSI => StochRsiOutput,
+    TEMA => f64,
+    TRIX => TrixOutput,
+    TSI => TsiOutput,
+    WMA => f64,
+    ZLEMA => f64,
+}
+
+candle_indicators! {
+    ADX => AdxOutput: (high, low, close),
+    Aroon => AroonOutput: (high, low),
+    AwesomeOscillator => AwesomeOutput: (high, low),
+    BalanceOfPower => f64: (open, high, low, close),
+    CCI => f64: (high, low, close),
+    ChandelierExit => ChandelierOutput: (high, low, close),
+    Choppiness => f64: (high, low, close),
+    EaseOfMovement => f64: (high, low, volume),
+    ElderRay => ElderRayOutput: (high, low, close),
+    Fisher => FisherOutput: (high, low),
+    ForceIndex => f64: (close, volume),
+    KlingerVolumeOscillator => KlingerOutput: (high, low, close, volume),
+    MassIndex => f64: (high, low),
+    RangeVol => f64: (open, high, low, close),
+    Stochastic => StochasticOutput: (high, low, close),
+    RVI => RviOutput: (open, high, low, close),
+    Vortex => VortexOutput: (high, low, close),
+    WilliamsR => f64: (high, low, close),
+    ZigZag => SwingPoint: (high, low, close),
+}
+
+pair_indicators! {
+    KalmanHedgeRatio => HedgeEstimate,
+    RollingCorrelation => CorrelationOutput,
+}
+
+event_indicators! {
+    AnchoredVwap: Candle => f64,
+    OrderFlowImbalance: Quote => f64,
+    SessionVwap: Candle => f64,
+    SpreadStatistics: Quote => SpreadStats,
+}
+
+impl Indicator for Vpin {
+    type Input = Trade;
+    type Output = f64;
+
+    fn next(&mut self, trade: Trade) -> Option<f64> {
+        Vpin::next(self, trade)
+    }
+
+    fn reset(&mut self) {
+        Vpin::reset(self)
+    }
+
+    rollback!(Vpin);
+}
+
+/// ATR reports the true range from the first bar, so every bar has an output
+impl Indicator for ATR {
+    type Input = Candle;
+    type Output = AtrOutput;
+
+    fn next(&mut self, candle: Candle) -> Option<AtrOutput> {
+        Some(ATR::next(self, candle.high, candle.low, candle.close))
+    }
+
+    fn reset(&mut self) {
+        ATR::reset(self)
+    }
+
+    fn calculate(&self, candles: &[Candle]) -> Result<Vec<Option<AtrOutput>>, IndicatorError> {
+        Ok(self
+            .calculate_candles(candles)?
+            .into_iter()
+            .map(Some)
+            .collect())
+    }
+
+    rollback!(ATR);
+}
+
+/// OBV has a value from the first bar
+impl Indicator for OBV {
+    type Input = Candle;
+    type Output = f64;
+
+    fn next(&mut self, candle: Candle) -> Option<f64> {
+        Some(OBV::next(self, candle.close, candle.volume))
+    }
+
+    fn reset(&mut self) {
+        OBV::reset(self)
+    }
+
+    fn calculate(&self, candles: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        Ok(self
+            .calculate_candles(candles)?
+            .into_iter()
+            .map(Some)
+            .collect())
+    }
+
+    rollback!(OBV);
+}
+
+/// The McGinley Dynamic starts at the first price, so every bar has an output
+impl Indicator for McGinleyDynamic {
+    type Input = f64;
+    type Output = f64;
+
+    fn next(&mut self, price: f64) -> Option<f64> {
+        Some(McGinleyDynamic::next(self, price))
+    }
+
+    fn reset(&mut self) {
+        McGinleyDynamic::reset(self)
+    }
+
+    fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        Ok(McGinleyDynamic::calculate(self, prices)?
+            .into_iter()
+            .map(Some)
+            .collect())
+    }
+
+    rollback!(McGinleyDynamic);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    use crate::calendar::TradingCalendar;
+    use crate::{HurstMethod, MaType, RangeEstimator, VwapAnchor, ZigZagThreshold};
+
+    fn candles() -> Vec<Candle> {
+        (0..100)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * 3.0 + i as f64 * 0.1;
+                Candle::new(i, close - 0.3, close + 1.0, close - 1.0, close, 1_000.0)
+            })
+            .collect()
+    }
+
+    /// Streams a batch through the trait object interface
+    fn stream<I: Indicator + ?Sized>(
+        indicator: &mut I,
+        inputs: &[I::Input],
+    ) -> Vec<Option<I::Output>>
+    where
+        I::Input: Clone,
+    {
+        indicator.reset();
+        inputs
+            .iter()
+            .cloned()
+            .map(|input| indicator.next(input))
+            .collect()
+    }
+
+    #[test]
+    fn test_trait_objects_match_inherent_batch() {
+        let prices: Vec<f64> = candles().iter().map(|c| c.close).collect();
+        let mut indicators: Vec<Box<dyn Indicator<Input = f64, Output = f64>>> = vec![
+            Box::new(SMA::new(5).unwrap()),
+            Box::new(RSI::new(5).unwrap()),
+            Box::new(KAMA::new(5, 2, 30).unwrap()),
+        ];
+        let expected = [
+            SMA::new(5).unwrap().calculate(&prices).unwrap(),
+            RSI::new(5).unwrap().calculate(&prices).unwrap(),
+            KAMA::new(5, 2, 30).unwrap().calculate(&prices).unwrap(),
+        ];
+
+        for (indicator, expected) in indicators.iter_mut().zip(expected) {
+            assert_eq!(stream(indicator, &prices), expected);
+            // Streaming again after a reset gives the same series
+            assert_eq!(stream(indicator, &prices), expected);
+        }
+    }
+
+    #[test]
+    fn test_candle_indicators_match_calculate_candles() {
+        let candles = candles();
+        let mut williams = WilliamsR::new(5).unwrap();
+        let batch = Indicator::calculate(&williams, &candles).unwrap();
+        assert_eq!(batch, williams.calculate_candles(&candles).unwrap());
+        assert_eq!(stream(&mut williams, &candles), batch);
+
+        let mut adx = ADX::new(5).unwrap();
+        let batch = adx.calculate_candles(&candles).unwrap();
+        assert_eq!(stream(&mut adx, &candles), batch);
+    }
+
+    /// Checks that streaming through the trait reproduces its batch result
+    fn assert_streams<I>(mut indicator: I, inputs: &[I::Input])
+    where
+        I: Indicator + Clone,
+        I::Input: Clone,
+        I::Output: PartialEq + std::fmt::Debug,
+    {
+        let batch = Indicator::calculate(&indicator, inputs).unwrap();
+        assert!(batch.iter().any(Option::is_some));
+        assert_eq!(stream(&mut indicator, inputs), batch);
+    }
+
+    #[test]
+    fn test_every_input_kind_streams_through_trait() {
+        let candles = candles();
+        let prices: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let returns: Vec<f64> = prices.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        let shifted: Vec<f64> = prices
+            .iter()
+            .enumerate()
+            .map(|(i, p)| if i < 50 { *p } else { p + 20.0 })
+            .collect();
+        let pairs: Vec<(f64, f64)> = prices.iter().map(|&p| (p, 2.0 * p + 1.0)).collect();
+        let quotes: Vec<Quote> = candles
+            .iter()
+            .map(|c| Quote::new(c.timestamp, c.low, 100.0 + c.open, c.high, 100.0 + c.close))
+            .collect();
+        let trades: Vec<Trade> = candles
+            .iter()
+            .map(|c| Trade::new(c.timestamp, c.close, 10.0))
+            .collect();
+
+        assert_streams(AutocorrelationPeriodogram::new(6, 12).unwrap(), &prices);
+        assert_streams(Cusum::new(10, 0.5, 5.0).unwrap(), &shifted);
+        assert_streams(GaussianFilter::new(2.0).unwrap(), &prices);
+        assert_streams(HilbertTransform::new(), &prices);
+        assert_streams(KalmanFilter::new(0.01, 1.0).unwrap(), &prices);
+        assert_streams(KalmanTrend::new(0.01, 0.001, 1.0).unwrap(), &prices);
+        assert_streams(McGinleyDynamic::new(10, 0.6).unwrap(), &prices);
+        assert_streams(RegimeClassifier::new(10, 0.5, 20, 0.8).unwrap(), &prices);
+        assert_streams(RollingAutocorrelation::new(20, &[1, 2]).unwrap(), &prices);
+        assert_streams(RollingSharpe::new(10, 252.0, 0.0).unwrap(), &returns);
+        assert_streams(RollingSortino::new(10, 252.0, 0.0).unwrap(), &returns);
+        assert_streams(SavitzkyGolay::new(5, 2).unwrap(), &prices);
+
+        assert_streams(ATR::new(5).unwrap(), &candles);
+        assert_streams(OBV::new(), &candles);
+        assert_streams(
+            ZigZag::new(ZigZagThreshold::Percent(2.0)).unwrap(),
+            &candles,
+        );
+
+        assert_streams(KalmanHedgeRatio::new(1e-4, 1.0).unwrap(), &pairs);
+        assert_streams(RollingCorrelation::new(10).unwrap(), &pairs);
+
+        assert_streams(OrderFlowImbalance::new(5).unwrap(), &quotes);
+        assert_streams(SpreadStatistics::new(5).unwrap(), &quotes);
+        assert_streams(Vpin::new(50.0, 3).unwrap(), &trades);
+    }
+
+    /// Checks that revising every input through the trait reproduces the
+    /// unrevised stream
+    fn assert_revises<I>(mut indicator: I, inputs: &[I::Input])
+    where
+        I: Indicator,
+        I::Input: Clone,
+        I::Output: PartialEq + std::fmt::Debug,
+    {
+        let expected = stream(&mut indicator, inputs);
+        indicator.reset();
+        let revised: Vec<Option<I::Output>> = (0..inputs.len())
+            .map(|i| {
+                // A provisional input that differs from the final one
+                indicator.next(inputs[(i + 1) % inputs.len()].clone());
+                indicator.update_last(inputs[i].clone()).unwrap()
+            })
+            .collect();
+        assert_eq!(revised, expected);
+    }
+
+    #[test]
+    fn test_every_indicator_revises_through_trait() {
+        let candles = candles();
+        let prices: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let returns: Vec<f64> = prices.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        let pairs: Vec<(f64, f64)> = prices.iter().map(|&p| (p, 2.0 * p + 1.0)).collect();
+        let quotes: Vec<Quote> = candles
+            .iter()
+            .map(|c| Quote::new(c.timestamp, c.low, 100.0 + c.open, c.high, 100.0 + c.close))
+            .collect();
+        let trades: Vec<Trade> = candles
+            .iter()
+            .map(|c| Trade::new(c.timestamp, c.close, 10.0))
+            .collect();
+
+        assert_revises(ALMA::new(9, 0.85, 6.0).unwrap(), &prices);
+        assert_revises(AutocorrelationPeriodogram::new(6, 12).unwrap(), &prices);
+        assert_revises(BollingerBands::new(10, 2.0).unwrap(), &prices);
+        assert_revises(CMO::new(9).unwrap(), &prices);
+        assert_revises(ConnorsRSI::new(3, 2, 20).unwrap(), &prices);
+        assert_revises(Coppock::new(14, 11, 10).unwrap(), &prices);
+        assert_revises(Cusum::new(10, 0.5, 5.0).unwrap(), &prices);
+        assert_revises(DEMA::new(5).unwrap(), &prices);
+        assert_revises(DPO::new(10).unwrap(), &prices);
+        assert_revises(EMA::new(5).unwrap(), &prices);
+        assert_revises(Envelope::new(MaType::Wma, 10, 2.5).unwrap(), &prices);
+        assert_revises(GaussianFilter::new(2.0).unwrap(), &prices);
+        assert_revises(HilbertTransform::new(), &prices);
+        assert_revises(Hurst::new(32, HurstMethod::Dfa).unwrap(), &prices);
+        assert_revises(KalmanFilter::new(0.01, 1.0).unwrap(), &prices);
+        assert_revises(KalmanTrend::new(0.01, 0.001, 1.0).unwrap(), &prices);
+        assert_revises(KAMA::new(10, 2, 30).unwrap(), &prices);
+        assert_revises(LinearRegression::new(10, 2.0).unwrap(), &prices);
+        assert_revises(MACD::new(5, 10, 3).unwrap(), &prices);
+        assert_revises(McGinleyDynamic::new(10, 0.6).unwrap(), &prices);
+        assert_revises(PPO::new(5, 10, 3).unwrap(), &prices);
+        assert_revises(QQE::new(14, 5, 4.236).unwrap(), &prices);
+        assert_revises(RealizedVol::new(10, 252.0).unwrap(), &prices);
+        assert_revises(RegimeClassifier::new(10, 0.5, 20, 0.8).unwrap(), &prices);
+        assert_revises(RollingAutocorrelation::new(20, &[1, 2]).unwrap(), &prices);
+        assert_revises(RollingMax::new(5).unwrap(), &prices);
+        assert_revises(RollingMin::new(5).unwrap(), &prices);
+        assert_revises(RollingMoments::new(10).unwrap(), &prices);
+        assert_revises(RollingQuantile::new(9, 0.5).unwrap(), &prices);
+        assert_revises(RollingSharpe::new(10, 252.0, 0.0).unwrap(), &returns);
+        assert_revises(RollingSortino::new(10, 252.0, 0.0).unwrap(), &returns);
+        assert_revises(RollingStd::new(10).unwrap(), &prices);
+        assert_revises(RSI::new(5).unwrap(), &prices);
+        assert_revises(SavitzkyGolay::new(5, 2).unwrap(), &prices);
+        assert_revises(SMA::new(5).unwrap(), &prices);
+        assert_revises(STC::new(10, 5, 15).unwrap(), &prices);
+        assert_revises(StochRSI::new(5, 5, Some(3), Some(3)).unwrap(), &prices);
+        assert_revises(TEMA::new(5).unwrap(), &prices);
+        assert_revises(TRIX::new(5, Some(3)).unwrap(), &prices);
+        assert_revises(TSI::new(10, 5, Some(3)).unwrap(), &prices);
+        assert_revises(WMA::new(5).unwrap(), &prices);
+        assert_revises(ZLEMA::new(5).unwrap(), &prices);
+
+        assert_revises(ADX::new(5).unwrap(), &candles);
+        assert_revises(Aroon::new(10).unwrap(), &candles);
+        assert_revises(ATR::new(5).unwrap(), &candles);
+        assert_revises(AwesomeOscillator::new(5, 10, 3).unwrap(), &candles);
+        assert_revises(BalanceOfPower::new(Some(5)).unwrap(), &candles);
+        assert_revises(CCI::new(10, 0.015).unwrap(), &candles);
+        assert_revises(ChandelierExit::new(10, 3.0).unwrap(), &candles);
+        assert_revises(Choppiness::new(10).unwrap(), &candles);
+        assert_revises(EaseOfMovement::new(1e4, Some(5)).unwrap(), &candles);
+        assert_revises(ElderRay::new(10).unwrap(), &candles);
+        assert_revises(Fisher::new(10).unwrap(), &candles);
+        assert_revises(ForceIndex::new(5).unwrap(), &candles);
+        assert_revises(KlingerVolumeOscillator::new(5, 10, 3).unwrap(), &candles);
+        assert_revises(MassIndex::new(5, 10).unwrap(), &candles);
+        assert_revises(OBV::new(), &candles);
+        assert_revises(
+            RangeVol::new(RangeEstimator::GarmanKlass, 10, 252.0).unwrap(),
+            &candles,
+        );
+        assert_revises(RVI::new(10).unwrap(), &candles);
+        assert_revises(Stochastic::new(10, Some(3), Some(3)).unwrap(), &candles);
+        assert_revises(Vortex::new(10).unwrap(), &candles);
+        assert_revises(WilliamsR::new(10).unwrap(), &candles);
+        assert_revises(
+            ZigZag::new(ZigZagThreshold::Percent(2.0)).unwrap(),
+            &candles,
+        );
+        assert_revises(AnchoredVwap::new(vec![VwapAnchor::Index(20)]), &candles);
+        // The test candles all fall on 1970-01-01, a Thursday, just after midnight
+        let around_the_clock = TradingCalendar::builder("24h")
+            .regular_session(NaiveTime::MIN, NaiveTime::from_hms_opt(23, 59, 0).unwrap())
+            .unwrap()
+            .build();
+        assert_revises(SessionVwap::new(around_the_clock), &candles);
+
+        assert_revises(KalmanHedgeRatio::new(1e-4, 1.0).unwrap(), &pairs);
+        assert_revises(RollingCorrelation::new(10).unwrap(), &pairs);
+
+        assert_revises(OrderFlowImbalance::new(5).unwrap(), &quotes);
+        assert_revises(SpreadStatistics::new(5).unwrap(), &quotes);
+        assert_revises(Vpin::new(50.0, 3).unwrap(), &trades);
+    }
+
+    #[test]
+    fn test_update_last_through_combinators_and_trait_objects() {
+        let candles = candles();
+        let prices: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        assert_revises(SMA::new(5).unwrap().then(EMA::new(3).unwrap()), &prices);
+        assert_revises(
+            MACD::new(5, 10, 3)
+                .unwrap()
+                .map(|output| output.macd)
+                .then(WMA::new(3).unwrap()),
+            &prices,
+        );
+        assert_revises(
+            Stochastic::new(10, Some(3), None)
+                .unwrap()
+                .with_nan_policy(NanPolicy::ForwardFill),
+            &candles,
+        );
+
+        let boxed: Vec<Box<dyn Indicator<Input = Candle, Output = AtrOutput>>> =
+            vec![Box::new(ATR::new(5).unwrap())];
+        for indicator in boxed {
+            assert_revises(indicator, &candles);
+        }
+        let boxed: Vec<Box<dyn Indicator<Input = f64, Output = f64>>> = vec![
+            Box::new(WMA::new(5).unwrap()),
+            Box::new(
+                KalmanFilter::new(0.01, 1.0)
+                    .unwrap()
+                    .map(|level| level * 2.0),
+            ),
+            Box::new(RSI::new(5).unwrap().then(SMA::new(3).unwrap())),
+            Box::new(
+                EMA::new(5)
+                    .unwrap()
+                    .with_nan_policy(NanPolicy::Reset)
+                    .then(SMA::new(3).unwrap()),
+            ),
+        ];
+        for indicator in boxed {
+            assert_revises(indicator, &prices);
+        }
+    }
+
+    #[test]
+    fn test_rollback_unsupported_by_default() {
+        #[derive(Clone)]
+        struct Sum(f64);
+
+        impl Indicator for Sum {
+            type Input = f64;
+            type Output = f64;
+
+            fn next(&mut self, input: f64) -> Option<f64> {
+                self.0 += input;
+                Some(self.0)
+            }
+
+            fn reset(&mut self) {
+                self.0 = 0.0;
+            }
+        }
+
+        // Indicators without undo state reject the call and keep their state
+        let mut sum = Sum(0.0);
+        sum.next(1.0);
+        assert_eq!(
+            sum.update_last(5.0),
+            Err(IndicatorError::Unsupported {
+                operation: "rollback"
+            })
+        );
+        assert_eq!(sum.next(2.0), Some(3.0));
+
+        let mut chain = SMA::new(2).unwrap().then(Sum(0.0));
+        chain.next(1.0);
+        assert_eq!(chain.rollback(), Ok(()));
+        chain.next(1.0);
+        chain.next(3.0);
+        assert!(chain.rollback().is_err());
+
+        let mut guarded = Sum(0.0).with_nan_policy(NanPolicy::Skip);
+        guarded.next(1.0);
+        assert!(guarded.rollback().is_err());
+        // A skipped input never reached `Sum`, so it can be undone
+        guarded.next(f64::NAN);
+        assert_eq!(guarded.rollback(), Ok(()));
+    }
+
+    #[test]
+    fn test_default_calculate_uses_fresh_state() {
+        #[derive(Clone)]
+        struct Counter(usize);
+
+        impl Indicator for Counter {
+            type Input = f64;
+            type Output = usize;
+
+            fn next(&mut self, _input: f64) -> Option<usize> {
+                self.0 += 1;
+                (self.0 > 1).then_some(self.0)
+            }
+
+            fn reset(&mut self) {
+                self.0 = 0;
+            }
+        }
+
+        let counter = Counter(10);
+        assert_eq!(
+            counter.calculate(&[1.0, 2.0, 3.0]).unwrap(),
+            vec![None, Some(2), Some(3)]
+        );
+        assert_eq!(counter.0, 10);
+    }
+}
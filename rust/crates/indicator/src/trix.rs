@@ -0,0 +1,217 @@
+//! TRIX (rate of change of a triple-smoothed EMA)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// TRIX values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrixOutput {
+    /// One-bar percentage change of the triple-smoothed EMA
+    pub trix: f64,
+    /// EMA of the TRIX line; `None` without a signal period or during its
+    /// warmup
+    pub signal: Option<f64>,
+}
+
+/// TRIX indicator
+///
+/// # Formula
+///
+/// EMA3 = EMA(EMA(EMA(price)))
+/// TRIX = 100 × (EMA3(t) − EMA3(t−1)) / EMA3(t−1)
+///
+/// Each EMA is seeded with the simple average of its first `period` inputs,
+/// as [`EMA::calculate`] does, so EMA3 first appears at bar
+/// `3 × (period − 1)` and TRIX one bar later. The optional signal line is
+/// a seeded EMA of TRIX.
+///
+/// # Example
+///
+/// ```
+/// use indicator::TRIX;
+///
+/// let prices: Vec<f64> = (0..20).map(|i| 100.0 * 1.01_f64.powi(i)).collect();
+/// let result = TRIX::new(3, Some(3))?.calculate(&prices)?;
+///
+/// assert!(result[6].is_none());
+/// let first = result[7].unwrap();
+/// assert!(first.trix > 0.0);
+/// assert!(first.signal.is_none());
+/// assert!(result[9].unwrap().signal.is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TRIX {
+    emas: [EMA; 3],
+    signal: Option<EMA>,
+    prev: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl TRIX {
+    /// Creates a new TRIX indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Period of the three EMAs (must be > 0, typically 15)
+    /// * `signal_period` - Period of the signal-line EMA, if wanted (must be > 0)
+    pub fn new(period: usize, signal_period: Option<usize>) -> Result<Self, IndicatorError> {
+        let ema = EMA::new(period)?;
+        let signal = signal_period.map(EMA::new).transpose()?;
+
+        Ok(Self {
+            emas: [ema.clone(), ema.clone(), ema],
+            signal,
+            prev: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates TRIX for a batch of prices on a fresh state
+    ///
+    /// Returns one value per input; the first `3 × (period − 1) + 1` are
+    /// `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `3 × period − 1`
+    /// prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<TrixOutput>>, IndicatorError> {
+        check_min_length(3 * self.period() - 1, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<TrixOutput> {
+        self.undo = Snapshot::of(self);
+        let [first, second, third] = &mut self.emas;
+        let smoothed = third.next(second.next(first.next(price)?)?)?;
+        let prev = self.prev.replace(smoothed)?;
+
+        let trix = if prev != 0.0 {
+            100.0 * (smoothed - prev) / prev
+        } else {
+            0.0
+        };
+        let signal = self.signal.as_mut().and_then(|signal| signal.next(trix));
+        Some(TrixOutput { trix, signal })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<TrixOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        for ema in &mut self.emas {
+            ema.reset();
+        }
+        if let Some(signal) = &mut self.signal {
+            signal.reset();
+        }
+        self.prev = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the EMA period
+    pub fn period(&self) -> usize {
+        self.emas[0].period()
+    }
+
+    /// Returns the signal-line period, if any
+    pub fn signal_period(&self) -> Option<usize> {
+        self.signal.as_ref().map(|signal| signal.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trix_matches_triple_batch_ema() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 40.0 + (i as f64 * 0.5).sin() * 2.0 + i as f64 * 0.05)
+            .collect();
+        let ema = EMA::new(4).unwrap();
+        let mut smoothed: Vec<f64> = prices.clone();
+        for _ in 0..3 {
+            smoothed = ema
+                .calculate(&smoothed)
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
+        let result = TRIX::new(4, None).unwrap().calculate(&prices).unwrap();
+        assert!(result[9].is_none());
+        for (output, pair) in result[10..].iter().zip(smoothed.windows(2)) {
+            let output = output.unwrap();
+            let expected = 100.0 * (pair[1] - pair[0]) / pair[0];
+            assert!((output.trix - expected).abs() < 1e-12);
+            assert_eq!(output.signal, None);
+        }
+    }
+
+    #[test]
+    fn test_trix_flat_prices() {
+        let result = TRIX::new(2, Some(2))
+            .unwrap()
+            .calculate(&[10.0; 8])
+            .unwrap();
+        assert_eq!(
+            result[7],
+            Some(TrixOutput {
+                trix: 0.0,
+                signal: Some(0.0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_trix_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..25).map(|i| 10.0 + (i as f64).cos()).collect();
+        let mut trix = TRIX::new(3, Some(4)).unwrap();
+        let batch = trix.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| trix.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        trix.reset();
+        assert_eq!(trix.next(1.0), None);
+        assert_eq!((trix.period(), trix.signal_period()), (3, Some(4)));
+    }
+
+    #[test]
+    fn test_trix_invalid_inputs() {
+        assert!(TRIX::new(0, None).is_err());
+        assert!(TRIX::new(5, Some(0)).is_err());
+        assert!(matches!(
+            TRIX::new(5, None).unwrap().calculate(&[1.0; 13]),
+            Err(IndicatorError::InsufficientData {
+                required: 14,
+                actual: 13
+            })
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+//! True Strength Index (TSI)
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// TSI values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TsiOutput {
+    /// True Strength Index, −100 to 100
+    pub tsi: f64,
+    /// EMA of the TSI line; `None` without a signal period or during its
+    /// warmup
+    pub signal: Option<f64>,
+}
+
+/// True Strength Index indicator
+///
+/// # Formula
+///
+/// m = P(t) − P(t−1)
+/// TSI = 100 × EMA_short(EMA_long(m)) / EMA_short(EMA_long(|m|))
+///
+/// Every EMA is seeded with the simple average of its first inputs, as
+/// [`EMA::calculate`] does, so the first value appears at bar
+/// `long + short − 1`. A window without any price change reports zero.
+///
+/// # Example
+///
+/// ```
+/// use indicator::TSI;
+///
+/// let prices: Vec<f64> = (0..20).map(|i| 100.0 + i as f64).collect();
+/// let result = TSI::new(5, 3, Some(3))?.calculate(&prices)?;
+///
+/// assert!(result[6].is_none());
+/// // Only up moves: maximum strength
+/// assert_eq!(result[7].unwrap().tsi, 100.0);
+/// assert_eq!(result[9].unwrap().signal, Some(100.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TSI {
+    momentum: [EMA; 2],
+    absolute: [EMA; 2],
+    signal: Option<EMA>,
+    prev: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl TSI {
+    /// Creates a new TSI indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `long_period` - First smoothing EMA period (must be > 0, typically 25)
+    /// * `short_period` - Second smoothing EMA period (must be > 0, typically 13)
+    /// * `signal_period` - Period of the signal-line EMA, if wanted (must be > 0,
+    ///   typically 7)
+    pub fn new(
+        long_period: usize,
+        short_period: usize,
+        signal_period: Option<usize>,
+    ) -> Result<Self, IndicatorError> {
+        let long = EMA::new(long_period)?;
+        let short = EMA::new(short_period)?;
+        let signal = signal_period.map(EMA::new).transpose()?;
+
+        Ok(Self {
+            momentum: [long.clone(), short.clone()],
+            absolute: [long, short],
+            signal,
+            prev: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates TSI for a batch of prices on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `long + short`
+    /// prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<TsiOutput>>, IndicatorError> {
+        let (long, short, _) = self.periods();
+        check_min_length(long + short, prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<TsiOutput> {
+        self.undo = Snapshot::of(self);
+        let change = price - self.prev.replace(price)?;
+        let smooth = |[long, short]: &mut [EMA; 2], value: f64| short.next(long.next(value)?);
+        let momentum = smooth(&mut self.momentum, change);
+        let absolute = smooth(&mut self.absolute, change.abs());
+        let (momentum, absolute) = momentum.zip(absolute)?;
+
+        let tsi = if absolute > 0.0 {
+            100.0 * momentum / absolute
+        } else {
+            0.0
+        };
+        let signal = self.signal.as_mut().and_then(|signal| signal.next(tsi));
+        Some(TsiOutput { tsi, signal })
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<TsiOutput> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        for ema in self.momentum.iter_mut().chain(&mut self.absolute) {
+            ema.reset();
+        }
+        if let Some(signal) = &mut self.signal {
+            signal.reset();
+        }
+        self.prev = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the long, short and signal periods
+    pub fn periods(&self) -> (usize, usize, Option<usize>) {
+        (
+            self.momentum[0].period(),
+            self.momentum[1].period(),
+            self.signal.as_ref().map(|signal| signal.period()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn double_smoothed(values: &[f64], long: usize, short: usize) -> Vec<f64> {
+        let first: Vec<f64> = EMA::new(long)
+            .unwrap()
+            .calculate(values)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        EMA::new(short)
+            .unwrap()
+            .calculate(&first)
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_tsi_matches_batch_emas() {
+        let prices: Vec<f64> = (0..50)
+            .map(|i| 60.0 + (i as f64 * 0.35).sin() * 5.0 + i as f64 * 0.05)
+            .collect();
+        let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+        let absolute: Vec<f64> = changes.iter().map(|c| c.abs()).collect();
+        let numerator = double_smoothed(&changes, 10, 4);
+        let denominator = double_smoothed(&absolute, 10, 4);
+
+        let result = TSI::new(10, 4, None).unwrap().calculate(&prices).unwrap();
+        assert!(result[12].is_none());
+        for ((output, n), d) in result[13..].iter().zip(&numerator).zip(&denominator) {
+            let output = output.unwrap();
+            assert!((output.tsi - 100.0 * n / d).abs() < 1e-9);
+            assert_eq!(output.signal, None);
+        }
+    }
+
+    #[test]
+    fn test_tsi_bounded_and_flat() {
+        let prices: Vec<f64> = (0..40).map(|i| 10.0 + (i as f64).sin()).collect();
+        let result = TSI::new(6, 3, Some(3)).unwrap().calculate(&prices).unwrap();
+        assert!(result
+            .iter()
+            .flatten()
+            .all(|o| (-100.0..=100.0).contains(&o.tsi)));
+
+        let flat = TSI::new(2, 2, None).unwrap().calculate(&[5.0; 6]).unwrap();
+        assert_eq!(flat[5].unwrap().tsi, 0.0);
+    }
+
+    #[test]
+    fn test_tsi_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..30).map(|i| 20.0 + (i as f64 * 0.6).cos()).collect();
+        let mut tsi = TSI::new(7, 3, Some(4)).unwrap();
+        let batch = tsi.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| tsi.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        tsi.reset();
+        assert_eq!(tsi.next(1.0), None);
+        assert_eq!(tsi.periods(), (7, 3, Some(4)));
+    }
+
+    #[test]
+    fn test_tsi_invalid_inputs() {
+        assert!(TSI::new(0, 13, None).is_err());
+        assert!(TSI::new(25, 0, None).is_err());
+        assert!(TSI::new(25, 13, Some(0)).is_err());
+        assert!(matches!(
+            TSI::new(25, 13, None).unwrap().calculate(&[1.0; 37]),
+            Err(IndicatorError::InsufficientData {
+                required: 38,
+                actual: 37
+            })
+        ));
+    }
+}
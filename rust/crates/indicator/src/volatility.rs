@@ -0,0 +1,698 @@
+//! Rolling standard deviation, close-to-close realized volatility and
+//! OHLC range-based volatility estimators
+
+use std::collections::VecDeque;
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Rolling sample standard deviation
+///
+/// Keeps the running mean and sum of squared deviations of a sliding window
+/// with Welford's algorithm: each new value is added incrementally and the
+/// value leaving the window is removed with the inverse update, so every step
+/// is O(1) and stays accurate for values far from zero.
+///
+/// Uses the sample (`n − 1`) denominator.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RollingStd;
+///
+/// let std = RollingStd::new(3)?;
+/// let result = std.calculate(&[1.0, 2.0, 3.0, 5.0])?;
+///
+/// assert_eq!(result[1], None);
+/// assert!((result[2].unwrap() - 1.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingStd {
+    period: usize,
+    /// Values currently in the window, oldest first
+    window: VecDeque<f64>,
+    /// Running mean of the window
+    mean: f64,
+    /// Sum of squared deviations from the mean
+    m2: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RollingStd {
+    /// Creates a new rolling standard deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be >= 2)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: ">= 2",
+            });
+        }
+
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            mean: 0.0,
+            m2: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates the rolling standard deviation for a batch of values on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as the window is not yet full.
+    pub fn calculate(&self, values: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period, values.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(values.iter().map(|&value| state.next(value)).collect())
+    }
+
+    /// Adds a new value (streaming mode)
+    ///
+    /// Returns `None` until `period` values have been seen.
+    pub fn next(&mut self, value: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                self.remove(oldest);
+            }
+        }
+        self.window.push_back(value);
+        self.add(value);
+
+        (self.window.len() == self.period).then(|| self.std_dev())
+    }
+
+    /// Replaces the most recent value, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, value: f64) -> Option<f64> {
+        self.rollback();
+        self.next(value)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest value can be undone; further calls do nothing until
+    /// the next value arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the rolling window length
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Running mean of the values in the window
+    pub(crate) fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Incorporates `value`, which has just been pushed onto the window
+    fn add(&mut self, value: f64) {
+        let n = self.window.len() as f64;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Removes `value`, which has just been popped from the window
+    fn remove(&mut self, value: f64) {
+        let remaining = self.window.len();
+        if remaining == 0 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            return;
+        }
+
+        let mean = self.mean - (value - self.mean) / remaining as f64;
+        self.m2 = (self.m2 - (value - self.mean) * (value - mean)).max(0.0);
+        self.mean = mean;
+    }
+
+    fn std_dev(&self) -> f64 {
+        (self.m2 / (self.window.len() - 1) as f64).sqrt()
+    }
+}
+
+/// Annualized close-to-close realized volatility
+///
+/// The rolling sample standard deviation of log returns `ln(Pₜ / Pₜ₋₁)`
+/// over `period` returns, scaled by `√annualization` (e.g. `252` for daily
+/// bars, `52` for weekly). An annualization of `1.0` gives per-bar volatility.
+///
+/// # Example
+///
+/// ```
+/// use indicator::RealizedVol;
+///
+/// let vol = RealizedVol::new(3, 252.0)?;
+/// let prices = [100.0, 101.0, 99.5, 100.5, 102.0];
+/// let result = vol.calculate(&prices)?;
+///
+/// // Three returns need four prices
+/// assert!(result[2].is_none());
+/// assert!(result[3].is_some());
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RealizedVol {
+    std: RollingStd,
+    annualization: f64,
+    prev_price: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RealizedVol {
+    /// Creates a new realized volatility indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of returns in the window (must be >= 2)
+    /// * `annualization` - Bars per year used to annualize (must be > 0)
+    pub fn new(period: usize, annualization: f64) -> Result<Self, IndicatorError> {
+        if annualization.is_nan() || annualization <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "annualization",
+                value: annualization,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            std: RollingStd::new(period)?,
+            annualization,
+            prev_price: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates realized volatility for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period` values
+    /// are `None`, since `period` returns need `period + 1` prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period() + 1, prices.len())?;
+
+        let mut state = Self::new(self.period(), self.annualization)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let prev = self.prev_price.replace(price)?;
+        self.std
+            .next((price / prev).ln())
+            .map(|std| std * self.annualization.sqrt())
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the state so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.std.reset();
+        self.prev_price = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the number of returns in the window
+    pub fn period(&self) -> usize {
+        self.std.period()
+    }
+
+    /// Returns the annualization factor
+    pub fn annualization(&self) -> f64 {
+        self.annualization
+    }
+}
+
+/// Variance estimator used by [`RangeVol`]
+///
+/// With `o`, `h`, `l`, `c` the logs of a bar's prices and `c₋₁` the log of
+/// the previous close:
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeEstimator {
+    /// Parkinson: `(h − l)² / (4 ln 2)`; ignores drift and opening gaps
+    Parkinson,
+    /// Garman-Klass: `½(h − l)² − (2 ln 2 − 1)(c − o)²`; ignores drift and gaps
+    GarmanKlass,
+    /// Rogers-Satchell: `(h − c)(h − o) + (l − c)(l − o)`; unbiased under drift
+    RogersSatchell,
+    /// Yang-Zhang: `σ²(o − c₋₁) + k·σ²(c − o) + (1 − k)·RS` with
+    /// `k = 0.34 / (1.34 + (n + 1) / (n − 1))`; handles both drift and gaps
+    YangZhang,
+}
+
+/// Annualized range-based volatility from OHLC bars
+///
+/// Close-to-close volatility uses one price per bar; these estimators also
+/// use the open, high and low, which gives a much more efficient estimate
+/// from the same number of bars. The per-bar variance terms of the chosen
+/// [`RangeEstimator`] are averaged over the last `period` bars (Yang-Zhang
+/// uses the sample variances of the overnight and open-to-close returns in
+/// the window), and the square root is scaled by `√annualization`.
+///
+/// Yang-Zhang needs the previous close for the overnight return, so it
+/// starts one bar later than the other estimators.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{RangeEstimator, RangeVol};
+///
+/// let vol = RangeVol::new(RangeEstimator::Parkinson, 2, 252.0)?;
+/// let open = [100.0, 101.0, 100.5];
+/// let high = [102.0, 102.5, 101.5];
+/// let low = [99.0, 100.0, 99.5];
+/// let close = [101.0, 100.5, 101.0];
+/// let result = vol.calculate(&open, &high, &low, &close)?;
+///
+/// assert!(result[0].is_none());
+/// assert!(result[1].unwrap() > 0.0);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeVol {
+    estimator: RangeEstimator,
+    annualization: f64,
+    /// Per-bar variance terms; only the running mean is used
+    terms: RollingStd,
+    /// Overnight returns `ln(O / C₋₁)` (Yang-Zhang only)
+    overnight: RollingStd,
+    /// Open-to-close returns `ln(C / O)` (Yang-Zhang only)
+    open_close: RollingStd,
+    prev_close: Option<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl RangeVol {
+    /// Creates a new range-based volatility indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `estimator` - Variance estimator to use
+    /// * `period` - Number of bars in the window (must be >= 2)
+    /// * `annualization` - Bars per year used to annualize (must be > 0)
+    pub fn new(
+        estimator: RangeEstimator,
+        period: usize,
+        annualization: f64,
+    ) -> Result<Self, IndicatorError> {
+        if annualization.is_nan() || annualization <= 0.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "annualization",
+                value: annualization,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            estimator,
+            annualization,
+            terms: RollingStd::new(period)?,
+            overnight: RollingStd::new(period)?,
+            open_close: RollingStd::new(period)?,
+            prev_close: None,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates range-based volatility for a batch of bars on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` (`period` for Yang-Zhang, which needs a previous close).
+    pub fn calculate(
+        &self,
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("open", open.len()),
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.warmup(), len)?;
+
+        let mut state = Self::new(self.estimator, self.period(), self.annualization)?;
+        Ok((0..len)
+            .map(|i| state.next(open[i], high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates range-based volatility for a batch of candles on a fresh state
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.warmup(), candles.len())?;
+
+        let mut state = Self::new(self.estimator, self.period(), self.annualization)?;
+        Ok(candles
+            .iter()
+            .map(|c| state.next(c.open, c.high, c.low, c.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Prices must be positive.
+    pub fn next(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let (o, h, l, c) = (open.ln(), high.ln(), low.ln(), close.ln());
+        let rogers_satchell = (h - c) * (h - o) + (l - c) * (l - o);
+
+        let variance = match self.estimator {
+            RangeEstimator::Parkinson => {
+                self.terms.next((h - l).powi(2) / (4.0 * 2.0_f64.ln()))?;
+                self.terms.mean()
+            }
+            RangeEstimator::GarmanKlass => {
+                let term = 0.5 * (h - l).powi(2) - (2.0 * 2.0_f64.ln() - 1.0) * (c - o).powi(2);
+                self.terms.next(term)?;
+                self.terms.mean()
+            }
+            RangeEstimator::RogersSatchell => {
+                self.terms.next(rogers_satchell)?;
+                self.terms.mean()
+            }
+            RangeEstimator::YangZhang => {
+                let prev_close = self.prev_close.replace(close)?;
+                let overnight = self.overnight.next(o - prev_close.ln());
+                let open_close = self.open_close.next(c - o);
+                self.terms.next(rogers_satchell)?;
+                let (overnight, open_close) = (overnight?, open_close?);
+
+                let n = self.period() as f64;
+                let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+                overnight.powi(2) + k * open_close.powi(2) + (1.0 - k) * self.terms.mean()
+            }
+        };
+        // Garman-Klass terms can be slightly negative on bars with no range
+        Some((variance.max(0.0) * self.annualization).sqrt())
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, open: f64, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.rollback();
+        self.next(open, high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the state so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.terms.reset();
+        self.overnight.reset();
+        self.open_close.reset();
+        self.prev_close = None;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the variance estimator
+    pub fn estimator(&self) -> RangeEstimator {
+        self.estimator
+    }
+
+    /// Returns the number of bars in the window
+    pub fn period(&self) -> usize {
+        self.terms.period()
+    }
+
+    /// Returns the annualization factor
+    pub fn annualization(&self) -> f64 {
+        self.annualization
+    }
+
+    /// Bars needed for the first output
+    fn warmup(&self) -> usize {
+        match self.estimator {
+            RangeEstimator::YangZhang => self.period() + 1,
+            _ => self.period(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_std(window: &[f64]) -> f64 {
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        (window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    }
+
+    #[test]
+    fn test_rolling_std_matches_naive() {
+        let values = [1.0, 3.0, 2.0, 8.0, 4.0, -1.0, 6.0, 5.5, 0.5, 9.0];
+        let result = RollingStd::new(4).unwrap().calculate(&values).unwrap();
+
+        assert!(result[..3].iter().all(Option::is_none));
+        for (i, actual) in result.iter().enumerate().skip(3) {
+            assert!((actual.unwrap() - sample_std(&values[i - 3..=i])).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rolling_std_stable_with_large_offset() {
+        let values: Vec<f64> = [1.0, 2.0, 4.0, 8.0, 3.0, 5.0, 7.0]
+            .iter()
+            .map(|x| 1e9 + x)
+            .collect();
+        let result = RollingStd::new(3).unwrap().calculate(&values).unwrap();
+        assert!((result[6].unwrap() - sample_std(&values[4..7])).abs() < 1e-6);
+
+        let mut constant = RollingStd::new(3).unwrap();
+        let last = (0..6).map(|_| constant.next(42.0)).last().flatten();
+        assert_eq!(last, Some(0.0));
+    }
+
+    #[test]
+    fn test_realized_vol_annualizes_log_returns() {
+        let prices = [100.0, 102.0, 101.0, 103.0, 104.5, 103.5];
+        let result = RealizedVol::new(3, 252.0)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+
+        let returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        assert!(result[..3].iter().all(Option::is_none));
+        for i in 3..prices.len() {
+            let expected = sample_std(&returns[i - 3..i]) * 252.0_f64.sqrt();
+            assert!((result[i].unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_and_invalid_inputs() {
+        let prices: Vec<f64> = (0..30).map(|i| 50.0 + (i as f64 * 0.7).sin()).collect();
+        let mut vol = RealizedVol::new(10, 52.0).unwrap();
+        let batch = vol.calculate(&prices).unwrap();
+        let streamed: Vec<Option<f64>> = prices.iter().map(|&p| vol.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        vol.reset();
+        assert_eq!(vol.next(prices[0]), None);
+
+        assert!(RollingStd::new(1).is_err());
+        assert!(RealizedVol::new(5, 0.0).is_err());
+        assert!(RealizedVol::new(5, 252.0)
+            .unwrap()
+            .calculate(&prices[..5])
+            .is_err());
+    }
+
+    fn bars() -> Vec<Candle> {
+        (0..40)
+            .map(|i| {
+                let x = i as f64;
+                let open = 100.0 + (x * 0.3).sin() * 3.0;
+                let close = open * (1.0 + (x * 0.7).cos() * 0.01);
+                let high = open.max(close) * (1.0 + 0.005 + (x * 1.3).sin().abs() * 0.01);
+                let low = open.min(close) * (1.0 - 0.004 - (x * 0.9).cos().abs() * 0.01);
+                Candle::new(x as i64, open, high, low, close, 1000.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_range_estimators_match_direct_formulas() {
+        let bars = bars();
+        let period = 10;
+        let ln2 = 2.0_f64.ln();
+        let rs = |b: &Candle| {
+            let (o, h, l, c) = (b.open.ln(), b.high.ln(), b.low.ln(), b.close.ln());
+            (h - c) * (h - o) + (l - c) * (l - o)
+        };
+        let mean = |xs: &[f64]| xs.iter().sum::<f64>() / xs.len() as f64;
+
+        let parkinson = RangeVol::new(RangeEstimator::Parkinson, period, 1.0).unwrap();
+        let garman_klass = RangeVol::new(RangeEstimator::GarmanKlass, period, 1.0).unwrap();
+        let rogers_satchell = RangeVol::new(RangeEstimator::RogersSatchell, period, 1.0).unwrap();
+        let yang_zhang = RangeVol::new(RangeEstimator::YangZhang, period, 1.0).unwrap();
+        let results = [
+            parkinson.calculate_candles(&bars).unwrap(),
+            garman_klass.calculate_candles(&bars).unwrap(),
+            rogers_satchell.calculate_candles(&bars).unwrap(),
+        ];
+        let yz = yang_zhang.calculate_candles(&bars).unwrap();
+
+        assert!(results
+            .iter()
+            .all(|r| r[period - 2].is_none() && r[period - 1].is_some()));
+        assert!(yz[period - 1].is_none() && yz[period].is_some());
+
+        for i in period..bars.len() {
+            let window = &bars[i + 1 - period..=i];
+            let pk: Vec<f64> = window
+                .iter()
+                .map(|b| (b.high / b.low).ln().powi(2) / (4.0 * ln2))
+                .collect();
+            let gk: Vec<f64> = window
+                .iter()
+                .map(|b| {
+                    0.5 * (b.high / b.low).ln().powi(2)
+                        - (2.0 * ln2 - 1.0) * (b.close / b.open).ln().powi(2)
+                })
+                .collect();
+            let rs_terms: Vec<f64> = window.iter().map(rs).collect();
+            for (result, terms) in results.iter().zip([&pk, &gk, &rs_terms]) {
+                assert!(
+                    (result[i].unwrap() - mean(terms).sqrt()).abs() < 1e-12,
+                    "bar {i}"
+                );
+            }
+
+            let overnight: Vec<f64> = (i + 1 - period..=i)
+                .map(|j| (bars[j].open / bars[j - 1].close).ln())
+                .collect();
+            let open_close: Vec<f64> = window.iter().map(|b| (b.close / b.open).ln()).collect();
+            let n = period as f64;
+            let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+            let expected = sample_std(&overnight).powi(2)
+                + k * sample_std(&open_close).powi(2)
+                + (1.0 - k) * mean(&rs_terms);
+            assert!((yz[i].unwrap() - expected.sqrt()).abs() < 1e-12, "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_range_vol_annualizes_and_handles_flat_bars() {
+        let bars = bars();
+        let daily = RangeVol::new(RangeEstimator::GarmanKlass, 5, 1.0).unwrap();
+        let annual = RangeVol::new(RangeEstimator::GarmanKlass, 5, 252.0).unwrap();
+        let daily = daily.calculate_candles(&bars).unwrap();
+        let annual = annual.calculate_candles(&bars).unwrap();
+        for (d, a) in daily.iter().zip(&annual).skip(4) {
+            assert!((d.unwrap() * 252.0_f64.sqrt() - a.unwrap()).abs() < 1e-12);
+        }
+
+        let flat = [10.0; 4];
+        for estimator in [
+            RangeEstimator::Parkinson,
+            RangeEstimator::GarmanKlass,
+            RangeEstimator::RogersSatchell,
+            RangeEstimator::YangZhang,
+        ] {
+            let vol = RangeVol::new(estimator, 3, 252.0).unwrap();
+            let result = vol.calculate(&flat, &flat, &flat, &flat).unwrap();
+            assert_eq!(result[3], Some(0.0), "{estimator:?}");
+        }
+    }
+
+    #[test]
+    fn test_range_vol_streaming_and_invalid_inputs() {
+        let bars = bars();
+        let open: Vec<f64> = bars.iter().map(|b| b.open).collect();
+        let high: Vec<f64> = bars.iter().map(|b| b.high).collect();
+        let low: Vec<f64> = bars.iter().map(|b| b.low).collect();
+        let close: Vec<f64> = bars.iter().map(|b| b.close).collect();
+
+        let mut vol = RangeVol::new(RangeEstimator::YangZhang, 8, 252.0).unwrap();
+        let batch = vol.calculate(&open, &high, &low, &close).unwrap();
+        assert_eq!(batch, vol.calculate_candles(&bars).unwrap());
+        let streamed: Vec<Option<f64>> = bars
+            .iter()
+            .map(|b| vol.next(b.open, b.high, b.low, b.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        vol.reset();
+        assert_eq!(vol.next(10.0, 11.0, 9.0, 10.5), None);
+
+        assert!(RangeVol::new(RangeEstimator::Parkinson, 1, 252.0).is_err());
+        assert!(RangeVol::new(RangeEstimator::Parkinson, 5, -1.0).is_err());
+        assert!(matches!(
+            vol.calculate(&open, &high[..39], &low, &close),
+            Err(IndicatorError::LengthMismatch { field: "high", .. })
+        ));
+        assert!(matches!(
+            vol.calculate_candles(&bars[..8]),
+            Err(IndicatorError::InsufficientData {
+                required: 9,
+                actual: 8
+            })
+        ));
+    }
+}
@@ -0,0 +1,324 @@
+//! Volume-by-price profile with point of control and value area
+//!
+//! [`VolumeProfile`] splits the price span of a set of candles into equal
+//! bins and spreads each candle's volume over the bins its high-low range
+//! overlaps, in proportion to the overlap. The bin holding the most volume
+//! is the point of control (POC); the value area grows outwards from it,
+//! adding whichever neighbouring bin traded more, until it holds the
+//! requested share of total volume (70% by convention).
+//!
+//! # Example
+//!
+//! ```
+//! use indicator::volume_profile::VolumeProfile;
+//! use indicator::Candle;
+//!
+//! let candles = vec![
+//!     Candle::new(0, 10.0, 11.0, 10.0, 10.5, 100.0),
+//!     Candle::new(1, 10.5, 11.0, 10.0, 10.8, 300.0),
+//!     Candle::new(2, 11.0, 12.0, 11.0, 11.5, 50.0),
+//! ];
+//!
+//! let profile = VolumeProfile::new(2, 70.0)?.compute(&candles)?;
+//! assert_eq!(profile.bins[0].volume, 400.0);
+//! assert_eq!(profile.poc, 10.5);
+//! assert_eq!((profile.value_area_low, profile.value_area_high), (10.0, 11.0));
+//! # Ok::<(), indicator::IndicatorError>(())
+//! ```
+
+use crate::error::check_min_length;
+use crate::{Candle, IndicatorError};
+
+/// One price bin of a volume profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileBin {
+    /// Lower price edge of the bin
+    pub low: f64,
+    /// Upper price edge of the bin
+    pub high: f64,
+    /// Volume attributed to the bin
+    pub volume: f64,
+}
+
+/// Volume histogram and the levels derived from it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// Bins in ascending price order
+    pub bins: Vec<ProfileBin>,
+    /// Midpoint of the bin with the most volume (the lower bin on ties)
+    pub poc: f64,
+    /// Upper edge of the value area
+    pub value_area_high: f64,
+    /// Lower edge of the value area
+    pub value_area_low: f64,
+    /// Volume across all bins
+    pub total_volume: f64,
+}
+
+/// Builds volume-by-price profiles from candles
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeProfile {
+    /// Number of equal-width price bins
+    bins: usize,
+    /// Share of total volume, in percent, covered by the value area
+    value_area_percent: f64,
+}
+
+impl VolumeProfile {
+    /// Creates a new volume profile builder
+    ///
+    /// # Arguments
+    ///
+    /// * `bins` - Number of equal-width price bins (must be > 0)
+    /// * `value_area_percent` - Share of volume in the value area, in percent
+    ///   (must be > 0 and <= 100, typically 70)
+    pub fn new(bins: usize, value_area_percent: f64) -> Result<Self, IndicatorError> {
+        if bins == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "bins",
+                value: bins as f64,
+                constraint: "> 0",
+            });
+        }
+        if value_area_percent.is_nan() || value_area_percent <= 0.0 || value_area_percent > 100.0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "value_area_percent",
+                value: value_area_percent,
+                constraint: "> 0 and <= 100",
+            });
+        }
+
+        Ok(Self {
+            bins,
+            value_area_percent,
+        })
+    }
+
+    /// Builds the profile of all `candles`
+    ///
+    /// Candles without a range put all their volume in the bin holding their
+    /// close. If every candle traded at a single price, the bins collapse
+    /// onto it and all volume lands in the first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `candles` is empty.
+    pub fn compute(&self, candles: &[Candle]) -> Result<Profile, IndicatorError> {
+        check_min_length(1, candles.len())?;
+
+        let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+        let high = candles
+            .iter()
+            .map(|c| c.high)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let width = (high - low) / self.bins as f64;
+
+        let mut bins: Vec<ProfileBin> = (0..self.bins)
+            .map(|i| ProfileBin {
+                low: low + width * i as f64,
+                high: low + width * (i + 1) as f64,
+                volume: 0.0,
+            })
+            .collect();
+        let last = self.bins - 1;
+        let bin_of = |price: f64| {
+            if width > 0.0 {
+                (((price - low) / width) as usize).min(last)
+            } else {
+                0
+            }
+        };
+
+        for candle in candles {
+            let range = candle.high - candle.low;
+            if range <= 0.0 || width <= 0.0 {
+                bins[bin_of(candle.close)].volume += candle.volume;
+                continue;
+            }
+            for bin in &mut bins[bin_of(candle.low)..=bin_of(candle.high)] {
+                let overlap = candle.high.min(bin.high) - candle.low.max(bin.low);
+                if overlap > 0.0 {
+                    bin.volume += candle.volume * overlap / range;
+                }
+            }
+        }
+
+        Ok(self.summarize(bins))
+    }
+
+    /// Builds the profile of each trailing `window` candles
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `window - 1`
+    /// values are `None` as the window is not yet full.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `window` is zero and `InsufficientData`
+    /// if there are fewer than `window` candles.
+    pub fn rolling(
+        &self,
+        candles: &[Candle],
+        window: usize,
+    ) -> Result<Vec<Option<Profile>>, IndicatorError> {
+        if window == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "window",
+                value: window as f64,
+                constraint: "> 0",
+            });
+        }
+        check_min_length(window, candles.len())?;
+
+        let mut result = vec![None; window - 1];
+        for slice in candles.windows(window) {
+            result.push(Some(self.compute(slice)?));
+        }
+        Ok(result)
+    }
+
+    /// Returns the number of price bins
+    pub fn bins(&self) -> usize {
+        self.bins
+    }
+
+    /// Returns the value area share in percent
+    pub fn value_area_percent(&self) -> f64 {
+        self.value_area_percent
+    }
+
+    fn summarize(&self, bins: Vec<ProfileBin>) -> Profile {
+        let total_volume: f64 = bins.iter().map(|bin| bin.volume).sum();
+        let poc_index = bins.iter().enumerate().fold(0, |best, (i, bin)| {
+            if bin.volume > bins[best].volume {
+                i
+            } else {
+                best
+            }
+        });
+
+        let target = total_volume * self.value_area_percent / 100.0;
+        let (mut lower, mut upper) = (poc_index, poc_index);
+        let mut covered = bins[poc_index].volume;
+        while covered < target && (lower > 0 || upper + 1 < bins.len()) {
+            let below = lower.checked_sub(1).map(|i| bins[i].volume);
+            let above = bins.get(upper + 1).map(|bin| bin.volume);
+            match (below, above) {
+                (Some(b), Some(a)) if a >= b => {
+                    upper += 1;
+                    covered += a;
+                }
+                (Some(b), _) => {
+                    lower -= 1;
+                    covered += b;
+                }
+                (None, Some(a)) => {
+                    upper += 1;
+                    covered += a;
+                }
+                (None, None) => break,
+            }
+        }
+
+        let poc = (bins[poc_index].low + bins[poc_index].high) / 2.0;
+        Profile {
+            poc,
+            value_area_high: bins[upper].high,
+            value_area_low: bins[lower].low,
+            total_volume,
+            bins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_spreads_volume_by_overlap() {
+        let candles = [
+            Candle::new(0, 0.0, 4.0, 0.0, 2.0, 400.0),
+            Candle::new(1, 3.0, 4.0, 3.0, 3.5, 100.0),
+            Candle::new(2, 1.5, 1.5, 1.5, 1.5, 50.0),
+        ];
+        let profile = VolumeProfile::new(4, 70.0)
+            .unwrap()
+            .compute(&candles)
+            .unwrap();
+
+        let volumes: Vec<f64> = profile.bins.iter().map(|bin| bin.volume).collect();
+        assert_eq!(volumes, vec![100.0, 150.0, 100.0, 200.0]);
+        assert_eq!(profile.total_volume, 550.0);
+        assert_eq!(profile.poc, 3.5);
+        // 200 + 100 + 150 = 450 >= 385
+        assert_eq!(
+            (profile.value_area_low, profile.value_area_high),
+            (1.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_profile_value_area_covers_target_share() {
+        let candles: Vec<Candle> = (0..60)
+            .map(|i| {
+                let base = 100.0 + (i as f64 * 0.2).sin() * 5.0;
+                Candle::new(i, base, base + 0.8, base - 0.8, base, 1_000.0 + i as f64)
+            })
+            .collect();
+        let profile = VolumeProfile::new(24, 70.0)
+            .unwrap()
+            .compute(&candles)
+            .unwrap();
+
+        let inside: f64 = profile
+            .bins
+            .iter()
+            .filter(|bin| bin.low >= profile.value_area_low && bin.high <= profile.value_area_high)
+            .map(|bin| bin.volume)
+            .sum();
+        let total: f64 = candles.iter().map(|c| c.volume).sum();
+        assert!((profile.total_volume - total).abs() < 1e-6);
+        assert!(inside >= 0.7 * total);
+        assert!(profile.value_area_low <= profile.poc && profile.poc <= profile.value_area_high);
+    }
+
+    #[test]
+    fn test_profile_single_price_and_rolling() {
+        let flat = [Candle::new(0, 5.0, 5.0, 5.0, 5.0, 10.0); 3];
+        let profile = VolumeProfile::new(3, 70.0).unwrap().compute(&flat).unwrap();
+        assert_eq!(profile.bins[0].volume, 30.0);
+        assert_eq!(profile.poc, 5.0);
+
+        let candles: Vec<Candle> = (0..6)
+            .map(|i| Candle::new(i, 1.0, 2.0 + i as f64, 1.0, 2.0, 10.0))
+            .collect();
+        let profiler = VolumeProfile::new(5, 70.0).unwrap();
+        let rolling = profiler.rolling(&candles, 4).unwrap();
+        assert!(rolling[2].is_none());
+        assert_eq!(
+            rolling[5].as_ref(),
+            Some(&profiler.compute(&candles[2..]).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_profile_invalid_inputs() {
+        assert!(VolumeProfile::new(0, 70.0).is_err());
+        assert!(VolumeProfile::new(10, 0.0).is_err());
+        assert!(VolumeProfile::new(10, 100.5).is_err());
+        assert!(VolumeProfile::new(10, f64::NAN).is_err());
+
+        let profiler = VolumeProfile::new(10, 70.0).unwrap();
+        assert!(profiler.compute(&[]).is_err());
+        assert!(profiler.rolling(&[Candle::default(); 3], 0).is_err());
+        assert!(matches!(
+            profiler.rolling(&[Candle::default(); 3], 4),
+            Err(IndicatorError::InsufficientData {
+                required: 4,
+                actual: 3
+            })
+        ));
+    }
+}
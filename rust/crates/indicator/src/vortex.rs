@@ -0,0 +1,251 @@
+//! Vortex Indicator (VI+ / VI−)
+
+use std::collections::VecDeque;
+
+use crate::atr::true_range;
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Vortex values for one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VortexOutput {
+    /// Positive vortex indicator (VI+)
+    pub plus: f64,
+    /// Negative vortex indicator (VI−)
+    pub minus: f64,
+}
+
+/// Vortex Indicator
+///
+/// Compares upward and downward bar-to-bar movement with the true range:
+///
+/// VM+ = |High − Low_prev|, VM− = |Low − High_prev|
+/// VI+ = Σ VM+ / Σ TR, VI− = Σ VM− / Σ TR over the last `period` bars
+///
+/// Movement needs a previous bar, so the first value is available at bar
+/// `period`. VI+ crossing above VI− is read as the start of an uptrend.
+///
+/// # Example
+///
+/// ```
+/// use indicator::Vortex;
+///
+/// let high: Vec<f64> = (0..10).map(|i| 11.0 + i as f64).collect();
+/// let low: Vec<f64> = (0..10).map(|i| 9.0 + i as f64).collect();
+/// let close: Vec<f64> = (0..10).map(|i| 10.0 + i as f64).collect();
+///
+/// let result = Vortex::new(5)?.calculate(&high, &low, &close)?;
+/// assert!(result[4].is_none());
+/// let last = result[9].unwrap();
+/// assert!(last.plus > last.minus);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vortex {
+    period: usize,
+    /// Previous bar's high, low and close
+    prev: Option<(f64, f64, f64)>,
+    /// VM+, VM− and TR per bar in the window, oldest first
+    window: VecDeque<[f64; 3]>,
+    sums: [f64; 3],
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl Vortex {
+    /// Creates a new Vortex indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of bars summed (must be > 0, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            prev: None,
+            window: VecDeque::with_capacity(period),
+            sums: [0.0; 3],
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates VI+ and VI− for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period + 1` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<VortexOutput>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period + 1, len)?;
+
+        let mut state = Self::new(self.period)?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates VI+ and VI− for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period + 1` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<VortexOutput>>, IndicatorError> {
+        check_min_length(self.period + 1, candles.len())?;
+
+        let mut state = Self::new(self.period)?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<VortexOutput> {
+        self.undo = Snapshot::of(self);
+        let (prev_high, prev_low, prev_close) = self.prev.replace((high, low, close))?;
+        let values = [
+            (high - prev_low).abs(),
+            (low - prev_high).abs(),
+            true_range(high, low, Some(prev_close)),
+        ];
+
+        if self.window.len() == self.period {
+            if let Some(oldest) = self.window.pop_front() {
+                for (sum, value) in self.sums.iter_mut().zip(oldest) {
+                    *sum -= value;
+                }
+            }
+        }
+        for (sum, value) in self.sums.iter_mut().zip(values) {
+            *sum += value;
+        }
+        self.window.push_back(values);
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let [plus, minus, range] = self.sums;
+        if range <= 0.0 {
+            return Some(VortexOutput {
+                plus: 0.0,
+                minus: 0.0,
+            });
+        }
+        Some(VortexOutput {
+            plus: plus / range,
+            minus: minus / range,
+        })
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<VortexOutput> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.prev = None;
+        self.window.clear();
+        self.sums = [0.0; 3];
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the summation period
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vortex_known_values() {
+        // Bar 1: VM+ |12 − 8| = 4, VM− |9 − 10| = 1, TR max(3, 3, 0) = 3
+        // Bar 2: VM+ |11 − 9| = 2, VM− |8 − 12| = 4, TR max(3, 0, 3) = 3
+        let high = [10.0, 12.0, 11.0];
+        let low = [8.0, 9.0, 8.0];
+        let close = [9.0, 11.0, 10.0];
+        let result = Vortex::new(2)
+            .unwrap()
+            .calculate(&high, &low, &close)
+            .unwrap();
+
+        assert!(result[1].is_none());
+        let output = result[2].unwrap();
+        assert!((output.plus - 1.0).abs() < 1e-12);
+        assert!((output.minus - 5.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vortex_streaming_and_candles_match_batch() {
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| {
+                let base = 75.0 + (i as f64 * 0.6).sin() * 5.0;
+                Candle::new(i, base, base + 1.1, base - 0.9, base + 0.4, 1.0)
+            })
+            .collect();
+        let vortex = Vortex::new(6).unwrap();
+        let batch = vortex.calculate_candles(&candles).unwrap();
+
+        let mut streaming = vortex.clone();
+        let streamed: Vec<_> = candles
+            .iter()
+            .map(|c| streaming.next(c.high, c.low, c.close))
+            .collect();
+        assert_eq!(batch, streamed);
+
+        streaming.reset();
+        assert_eq!(streaming.next(1.0, 0.0, 0.5), None);
+    }
+
+    #[test]
+    fn test_vortex_invalid_inputs() {
+        assert!(Vortex::new(0).is_err());
+        let vortex = Vortex::new(3).unwrap();
+        assert!(matches!(
+            vortex.calculate(&[1.0; 3], &[1.0; 3], &[1.0; 3]),
+            Err(IndicatorError::InsufficientData {
+                required: 4,
+                actual: 3
+            })
+        ));
+        assert!(vortex.calculate(&[1.0; 4], &[1.0; 4], &[1.0; 5]).is_err());
+    }
+}
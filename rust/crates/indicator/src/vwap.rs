@@ -0,0 +1,475 @@
+//! Anchored and session Volume-Weighted Average Price (VWAP)
+
+use chrono::{DateTime, NaiveDate};
+
+use crate::calendar::TradingCalendar;
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError};
+
+/// Bar from which an anchored VWAP starts accumulating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VwapAnchor {
+    /// Bar at this position in the series
+    Index(usize),
+    /// First bar whose timestamp is at or after this Unix time in
+    /// milliseconds
+    Timestamp(i64),
+}
+
+/// Anchored VWAP indicator
+///
+/// Accumulates typical price × volume from each anchor bar onwards:
+///
+/// Typical Price = (High + Low + Close) / 3
+/// VWAP = Σ(Typical Price × Volume) / ΣVolume
+///
+/// [`calculate`](Self::calculate) returns a single series that restarts at
+/// every anchor, and [`calculate_each`](Self::calculate_each) one series per
+/// anchor, so several anchors (a swing low, an earnings date) can be tracked
+/// side by side. Bars before the first anchor, and anchored bars until some
+/// volume has traded, are `None`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::{AnchoredVwap, Candle, VwapAnchor};
+///
+/// let candles: Vec<Candle> = [(10.0, 100.0), (12.0, 300.0), (20.0, 100.0), (22.0, 300.0)]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, &(price, volume))| Candle::new(i as i64, price, price, price, price, volume))
+///     .collect();
+///
+/// let vwap = AnchoredVwap::new(vec![VwapAnchor::Index(0), VwapAnchor::Index(2)]);
+/// assert_eq!(
+///     vwap.calculate(&candles)?,
+///     vec![Some(10.0), Some(11.5), Some(20.0), Some(21.5)]
+/// );
+/// assert_eq!(vwap.calculate_each(&candles)?[0][3], Some(16.5));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnchoredVwap {
+    anchors: Vec<VwapAnchor>,
+    /// Number of bars seen
+    index: usize,
+    prev_timestamp: Option<i64>,
+    /// Whether an anchor has been reached
+    active: bool,
+    price_volume: f64,
+    volume: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl AnchoredVwap {
+    /// Creates a new anchored VWAP restarting at each of `anchors`
+    pub fn new(anchors: Vec<VwapAnchor>) -> Self {
+        Self {
+            anchors,
+            index: 0,
+            prev_timestamp: None,
+            active: false,
+            price_volume: 0.0,
+            volume: 0.0,
+            undo: Snapshot::default(),
+        }
+    }
+
+    /// Calculates a single VWAP series that restarts at every anchor, on a
+    /// fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `candles` is empty and
+    /// `InvalidParameter` if an anchor falls after the last candle.
+    pub fn calculate(&self, candles: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        self.check_anchors(candles)?;
+
+        let mut state = Self::new(self.anchors.clone());
+        Ok(candles.iter().map(|&candle| state.next(candle)).collect())
+    }
+
+    /// Calculates one VWAP series per anchor, in the order the anchors were
+    /// given
+    ///
+    /// Each series is `None` before its own anchor and never restarts.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `candles` is empty and
+    /// `InvalidParameter` if an anchor falls after the last candle.
+    pub fn calculate_each(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Vec<Option<f64>>>, IndicatorError> {
+        self.check_anchors(candles)?;
+
+        Ok(self
+            .anchors
+            .iter()
+            .map(|&anchor| {
+                let mut state = Self::new(vec![anchor]);
+                candles.iter().map(|&candle| state.next(candle)).collect()
+            })
+            .collect())
+    }
+
+    /// Adds a new candle (streaming mode)
+    ///
+    /// Candles must arrive in timestamp order for timestamp anchors to
+    /// trigger.
+    pub fn next(&mut self, candle: Candle) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.is_anchor(candle.timestamp) {
+            self.active = true;
+            self.price_volume = 0.0;
+            self.volume = 0.0;
+        }
+        self.index += 1;
+        self.prev_timestamp = Some(candle.timestamp);
+        if !self.active {
+            return None;
+        }
+
+        let typical = (candle.high + candle.low + candle.close) / 3.0;
+        self.price_volume += typical * candle.volume;
+        self.volume += candle.volume;
+        (self.volume > 0.0).then(|| self.price_volume / self.volume)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, candle: Candle) -> Option<f64> {
+        self.rollback();
+        self.next(candle)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state, keeping the anchors
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.prev_timestamp = None;
+        self.active = false;
+        self.price_volume = 0.0;
+        self.volume = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the anchors
+    pub fn anchors(&self) -> &[VwapAnchor] {
+        &self.anchors
+    }
+
+    fn is_anchor(&self, timestamp: i64) -> bool {
+        self.anchors.iter().any(|&anchor| match anchor {
+            VwapAnchor::Index(index) => index == self.index,
+            VwapAnchor::Timestamp(start) => {
+                timestamp >= start && self.prev_timestamp.is_none_or(|prev| prev < start)
+            }
+        })
+    }
+
+    fn check_anchors(&self, candles: &[Candle]) -> Result<(), IndicatorError> {
+        check_min_length(1, candles.len())?;
+
+        for &anchor in &self.anchors {
+            let (reached, value) = match anchor {
+                VwapAnchor::Index(index) => (index < candles.len(), index as f64),
+                VwapAnchor::Timestamp(start) => (
+                    candles.iter().any(|candle| candle.timestamp >= start),
+                    start as f64,
+                ),
+            };
+            if !reached {
+                return Err(IndicatorError::InvalidParameter {
+                    field: "anchor",
+                    value,
+                    constraint: "within the candle series",
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Session VWAP indicator
+///
+/// Typical-price VWAP that restarts at the first bar of every trading session
+/// of a [`TradingCalendar`]. Bar timestamps are read as Unix milliseconds in
+/// the exchange's local time, matching the calendar. Bars outside the
+/// session, including whole non-trading days, are `None` and do not
+/// accumulate.
+///
+/// # Example
+///
+/// ```
+/// use indicator::calendar::TradingCalendar;
+/// use indicator::{Candle, SessionVwap};
+///
+/// // 2024-07-03 and 2024-07-05; NYSE is closed on the 4th
+/// let at = |day: i64, hour: i64| ((19_907 + day) * 24 + hour) * 3_600_000;
+/// let candles = [
+///     Candle::new(at(0, 8), 9.0, 9.0, 9.0, 9.0, 100.0), // pre-market
+///     Candle::new(at(0, 10), 10.0, 10.0, 10.0, 10.0, 100.0),
+///     Candle::new(at(0, 11), 13.0, 13.0, 13.0, 13.0, 200.0),
+///     Candle::new(at(1, 10), 50.0, 50.0, 50.0, 50.0, 100.0), // holiday
+///     Candle::new(at(2, 10), 20.0, 20.0, 20.0, 20.0, 100.0),
+/// ];
+///
+/// let vwap = SessionVwap::new(TradingCalendar::nyse(2024..=2024));
+/// assert_eq!(
+///     vwap.calculate(&candles)?,
+///     vec![None, Some(10.0), Some(12.0), None, Some(20.0)]
+/// );
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionVwap {
+    calendar: TradingCalendar,
+    /// Date of the session being accumulated
+    session: Option<NaiveDate>,
+    price_volume: f64,
+    volume: f64,
+    /// Session and sums before the last `next`, or `None` when there is
+    /// nothing to roll back
+    undo: Option<(Option<NaiveDate>, f64, f64)>,
+}
+
+impl SessionVwap {
+    /// Creates a new session VWAP restarting at each session of `calendar`
+    pub fn new(calendar: TradingCalendar) -> Self {
+        Self {
+            calendar,
+            session: None,
+            price_volume: 0.0,
+            volume: 0.0,
+            undo: None,
+        }
+    }
+
+    /// Calculates session VWAP for a batch of candles, on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if `candles` is empty.
+    pub fn calculate(&self, candles: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(1, candles.len())?;
+
+        let mut state = Self::new(self.calendar.clone());
+        Ok(candles.iter().map(|&candle| state.next(candle)).collect())
+    }
+
+    /// Adds a new candle (streaming mode)
+    ///
+    /// Candles must arrive in timestamp order.
+    pub fn next(&mut self, candle: Candle) -> Option<f64> {
+        self.undo = Some((self.session, self.price_volume, self.volume));
+
+        let datetime = DateTime::from_timestamp_millis(candle.timestamp)?.naive_utc();
+        if !self.calendar.is_open(datetime) {
+            return None;
+        }
+        if self.session != Some(datetime.date()) {
+            self.session = Some(datetime.date());
+            self.price_volume = 0.0;
+            self.volume = 0.0;
+        }
+
+        let typical = (candle.high + candle.low + candle.close) / 3.0;
+        self.price_volume += typical * candle.volume;
+        self.volume += candle.volume;
+        (self.volume > 0.0).then(|| self.price_volume / self.volume)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, candle: Candle) -> Option<f64> {
+        self.rollback();
+        self.next(candle)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some((session, price_volume, volume)) = self.undo.take() {
+            self.session = session;
+            self.price_volume = price_volume;
+            self.volume = volume;
+        }
+    }
+
+    /// Resets the indicator state, keeping the calendar
+    pub fn reset(&mut self) {
+        self.session = None;
+        self.price_volume = 0.0;
+        self.volume = 0.0;
+        self.undo = None;
+    }
+
+    /// Returns the trading calendar
+    pub fn calendar(&self) -> &TradingCalendar {
+        &self.calendar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        (0..12)
+            .map(|i| {
+                let base = 50.0 + (i as f64 * 0.7).sin() * 3.0;
+                let volume = 1_000.0 + (i % 4) as f64 * 250.0;
+                Candle::new(1_000 * i, base, base + 1.0, base - 0.5, base + 0.2, volume)
+            })
+            .collect()
+    }
+
+    fn reference(candles: &[Candle], start: usize) -> Vec<Option<f64>> {
+        let (mut pv, mut volume) = (0.0, 0.0);
+        candles
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if i < start {
+                    return None;
+                }
+                pv += (c.high + c.low + c.close) / 3.0 * c.volume;
+                volume += c.volume;
+                Some(pv / volume)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vwap_per_anchor_series() {
+        let candles = candles();
+        let vwap = AnchoredVwap::new(vec![VwapAnchor::Index(2), VwapAnchor::Timestamp(6_500)]);
+        let each = vwap.calculate_each(&candles).unwrap();
+
+        assert_eq!(each.len(), 2);
+        for (series, expected) in each
+            .iter()
+            .zip([reference(&candles, 2), reference(&candles, 7)])
+        {
+            for (a, b) in series.iter().zip(&expected) {
+                match (a, b) {
+                    (Some(a), Some(b)) => assert!((a - b).abs() < 1e-9),
+                    _ => assert_eq!(a, b),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_vwap_resets_at_each_anchor() {
+        let candles = candles();
+        let vwap = AnchoredVwap::new(vec![VwapAnchor::Timestamp(7_000), VwapAnchor::Index(3)]);
+        let single = vwap.calculate(&candles).unwrap();
+        let each = vwap.calculate_each(&candles).unwrap();
+
+        assert!(single[2].is_none());
+        assert_eq!(single[3..7], each[1][3..7]);
+        assert_eq!(single[7..], each[0][7..]);
+    }
+
+    #[test]
+    fn test_vwap_waits_for_volume() {
+        let candles = [
+            Candle::new(0, 5.0, 5.0, 5.0, 5.0, 0.0),
+            Candle::new(1, 6.0, 6.0, 6.0, 6.0, 10.0),
+        ];
+        let result = AnchoredVwap::new(vec![VwapAnchor::Index(0)])
+            .calculate(&candles)
+            .unwrap();
+        assert_eq!(result, vec![None, Some(6.0)]);
+    }
+
+    #[test]
+    fn test_vwap_streaming_matches_batch() {
+        let candles = candles();
+        let mut vwap = AnchoredVwap::new(vec![VwapAnchor::Index(1), VwapAnchor::Timestamp(5_000)]);
+        let batch = vwap.calculate(&candles).unwrap();
+        let streamed: Vec<_> = candles.iter().map(|&c| vwap.next(c)).collect();
+        assert_eq!(batch, streamed);
+
+        vwap.reset();
+        assert_eq!(vwap.next(candles[0]), None);
+        assert_eq!(vwap.anchors().len(), 2);
+    }
+
+    #[test]
+    fn test_session_vwap_restarts_each_session() {
+        let nyse = TradingCalendar::nyse(2024..=2024);
+        // Hourly bars from Thursday 2024-03-28 00:00 through Monday 2024-04-01;
+        // Good Friday and the weekend are closed
+        let start = 19_810 * 86_400_000;
+        let candles: Vec<Candle> = (0..24 * 5)
+            .map(|i| {
+                let price = 100.0 + i as f64 * 0.1;
+                Candle::new(start + i * 3_600_000, price, price, price, price, 10.0)
+            })
+            .collect();
+
+        let mut vwap = SessionVwap::new(nyse);
+        let result = vwap.calculate(&candles).unwrap();
+
+        // Thursday 10:00-15:00 and Monday 10:00-15:00 are in session
+        let in_session: Vec<usize> = (0..candles.len())
+            .filter(|&i| result[i].is_some())
+            .collect();
+        let expected: Vec<usize> = (10..16).chain(4 * 24 + 10..4 * 24 + 16).collect();
+        assert_eq!(in_session, expected);
+        // The Monday session starts again from its first bar
+        let close_to = |value: Option<f64>, expected: f64| (value.unwrap() - expected).abs() < 1e-9;
+        assert!(close_to(result[4 * 24 + 10], candles[4 * 24 + 10].close));
+        assert!(close_to(
+            result[11],
+            (candles[10].close + candles[11].close) / 2.0
+        ));
+
+        let streamed: Vec<_> = candles.iter().map(|&c| vwap.next(c)).collect();
+        assert_eq!(streamed, result);
+        vwap.reset();
+        assert!(close_to(vwap.next(candles[10]), candles[10].close));
+        assert_eq!(vwap.calendar().name(), "NYSE");
+        assert!(SessionVwap::new(TradingCalendar::nyse(2024..=2024))
+            .calculate(&[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_vwap_invalid_anchors() {
+        let candles = candles();
+        assert!(matches!(
+            AnchoredVwap::new(vec![]).calculate(&[]),
+            Err(IndicatorError::InsufficientData {
+                required: 1,
+                actual: 0
+            })
+        ));
+        assert!(AnchoredVwap::new(vec![VwapAnchor::Index(12)])
+            .calculate(&candles)
+            .is_err());
+        assert!(AnchoredVwap::new(vec![VwapAnchor::Timestamp(11_001)])
+            .calculate_each(&candles)
+            .is_err());
+    }
+}
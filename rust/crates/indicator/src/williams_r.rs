@@ -0,0 +1,214 @@
+//! Williams %R oscillator
+
+use crate::error::{check_equal_lengths, check_min_length};
+use crate::snapshot::Snapshot;
+use crate::{Candle, IndicatorError, RollingMax, RollingMin};
+
+/// Williams %R momentum oscillator
+///
+/// Locates the close within the high-low range of the last `period` bars:
+///
+/// %R = −100 × (HighestHigh − Close) / (HighestHigh − LowestLow)
+///
+/// Values run from −100 (close at the lowest low) to 0 (close at the
+/// highest high); readings above −20 are conventionally overbought and
+/// below −80 oversold. A window with no range reports the midpoint, −50.
+///
+/// # Example
+///
+/// ```
+/// use indicator::WilliamsR;
+///
+/// let high = [10.0, 11.0, 12.0, 11.5];
+/// let low = [9.0, 9.5, 10.5, 10.0];
+/// let close = [9.5, 10.8, 12.0, 10.5];
+///
+/// let result = WilliamsR::new(3)?.calculate(&high, &low, &close)?;
+/// assert!(result[1].is_none());
+/// assert_eq!(result[2], Some(0.0)); // closed at the highest high
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct WilliamsR {
+    highs: RollingMax,
+    lows: RollingMin,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl WilliamsR {
+    /// Creates a new Williams %R indicator
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Lookback in bars (must be > 0, typically 14)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates %R for a batch of bars on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `LengthMismatch` if the series differ in length and
+    /// `InsufficientData` if there are fewer than `period` bars.
+    pub fn calculate(
+        &self,
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        let len = check_equal_lengths(&[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ])?;
+        check_min_length(self.period(), len)?;
+
+        let mut state = Self::new(self.period())?;
+        Ok((0..len)
+            .map(|i| state.next(high[i], low[i], close[i]))
+            .collect())
+    }
+
+    /// Calculates %R for a series of candles on a fresh state
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `period` candles.
+    pub fn calculate_candles(
+        &self,
+        candles: &[Candle],
+    ) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.period(), candles.len())?;
+
+        let mut state = Self::new(self.period())?;
+        Ok(candles
+            .iter()
+            .map(|candle| state.next(candle.high, candle.low, candle.close))
+            .collect())
+    }
+
+    /// Adds a new bar (streaming mode)
+    ///
+    /// Returns `None` until `period` bars have been seen.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        let highest = self.highs.next(high);
+        let (highest, lowest) = highest.zip(self.lows.next(low))?;
+        let range = highest - lowest;
+        if range == 0.0 {
+            return Some(-50.0);
+        }
+        Some(-100.0 * (highest - close) / range)
+    }
+
+    /// Replaces the most recent bar, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.rollback();
+        self.next(high, low, close)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest bar can be undone; further calls do nothing until
+    /// the next bar arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the lookback period
+    pub fn period(&self) -> usize {
+        self.highs.period()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_williams_r_range_position() {
+        let high = [12.0, 14.0, 13.0];
+        let low = [10.0, 11.0, 10.5];
+        let result = WilliamsR::new(3)
+            .unwrap()
+            .calculate(&high, &low, &[11.0, 13.0, 10.0])
+            .unwrap();
+        assert_eq!(result[2], Some(-100.0));
+
+        let mut williams = WilliamsR::new(3).unwrap();
+        for i in 0..3 {
+            williams.next(high[i], low[i], 11.0);
+        }
+        // Window drops the first bar: range 10.5..14, close 13
+        let value = williams.next(14.0, 11.0, 13.0).unwrap();
+        assert!((value - (-100.0 / 3.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_williams_r_flat_window() {
+        let prices = [7.0; 4];
+        let result = WilliamsR::new(2)
+            .unwrap()
+            .calculate(&prices, &prices, &prices)
+            .unwrap();
+        assert_eq!(result, vec![None, Some(-50.0), Some(-50.0), Some(-50.0)]);
+    }
+
+    #[test]
+    fn test_williams_r_candles_match_slices() {
+        let candles: Vec<Candle> = (0..12)
+            .map(|i| {
+                let base = 30.0 + (i as f64 * 0.9).sin() * 3.0;
+                Candle::new(i, base, base + 0.8, base - 0.6, base + 0.2, 1.0)
+            })
+            .collect();
+        let high: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let low: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let close: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        let williams = WilliamsR::new(5).unwrap();
+        let batch = williams.calculate_candles(&candles).unwrap();
+        assert_eq!(batch, williams.calculate(&high, &low, &close).unwrap());
+        assert!(batch.iter().flatten().all(|v| (-100.0..=0.0).contains(v)));
+    }
+
+    #[test]
+    fn test_williams_r_invalid_inputs() {
+        assert!(matches!(
+            WilliamsR::new(0),
+            Err(IndicatorError::InvalidParameter {
+                field: "period",
+                ..
+            })
+        ));
+        let williams = WilliamsR::new(14).unwrap();
+        assert!(matches!(
+            williams.calculate(&[1.0; 5], &[1.0; 5], &[1.0; 5]),
+            Err(IndicatorError::InsufficientData {
+                required: 14,
+                actual: 5
+            })
+        ));
+        assert!(williams
+            .calculate(&[1.0; 20], &[1.0; 19], &[1.0; 20])
+            .is_err());
+    }
+}
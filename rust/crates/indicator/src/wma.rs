@@ -0,0 +1,192 @@
+//! Weighted Moving Average (WMA)
+
+use std::collections::VecDeque;
+
+use crate::snapshot::Snapshot;
+use crate::IndicatorError;
+
+/// Linearly Weighted Moving Average (WMA) indicator
+///
+/// Weights the most recent value by `period`, the one before by
+/// `period − 1`, down to 1 for the oldest value in the window.
+///
+/// # Formula
+///
+/// WMA(t) = Σ (i × Price(t − period + i)) / (period × (period + 1) / 2),  i = 1..period
+///
+/// The weighted sum is updated in O(1) per value: when the window slides,
+/// every weight drops by one (subtracting the plain window sum) and the new
+/// value enters with weight `period`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::WMA;
+///
+/// let wma = WMA::new(3)?;
+/// let result = wma.calculate(&[1.0, 2.0, 3.0, 4.0])?;
+///
+/// // (1×1 + 2×2 + 3×3) / 6
+/// assert_eq!(result[2], Some(14.0 / 6.0));
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct WMA {
+    period: usize,
+    /// Sum of the weights, `period × (period + 1) / 2`
+    denominator: f64,
+    window: VecDeque<f64>,
+    sum: f64,
+    weighted_sum: f64,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl WMA {
+    /// Creates a new WMA indicator with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of values in the window (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameter {
+                field: "period",
+                value: period as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            period,
+            denominator: (period * (period + 1)) as f64 / 2.0,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+            weighted_sum: 0.0,
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates WMA for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first `period - 1`
+    /// values are `None` as there isn't enough data.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        if prices.len() < self.period {
+            return Err(IndicatorError::InsufficientData {
+                required: self.period,
+                actual: prices.len(),
+            });
+        }
+
+        let mut state = Self::new(self.period)?;
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    ///
+    /// Returns `None` until `period` prices have been seen.
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.window.len() == self.period {
+            self.weighted_sum += self.period as f64 * price - self.sum;
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        } else {
+            self.weighted_sum += (self.window.len() + 1) as f64 * price;
+        }
+        self.sum += price;
+        self.window.push_back(price);
+
+        (self.window.len() == self.period).then(|| self.weighted_sum / self.denominator)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Clears the window so the indicator can be reused on a new series
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+        self.weighted_sum = 0.0;
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the period used for WMA calculation
+    pub fn period(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_wma(window: &[f64]) -> f64 {
+        let weighted: f64 = window
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i + 1) as f64 * v)
+            .sum();
+        weighted / (window.len() * (window.len() + 1) / 2) as f64
+    }
+
+    #[test]
+    fn test_wma_matches_naive_computation() {
+        let prices = vec![10.0, 11.5, 10.8, 12.2, 13.0, 12.4, 14.1, 13.7];
+        let result = WMA::new(4).unwrap().calculate(&prices).unwrap();
+
+        assert!(result[..3].iter().all(Option::is_none));
+        for i in 3..prices.len() {
+            let expected = naive_wma(&prices[i - 3..=i]);
+            assert!((result[i].unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_wma_streaming_matches_batch() {
+        let prices = vec![5.0, 4.0, 6.0, 7.0, 3.0, 8.0];
+        let mut wma = WMA::new(3).unwrap();
+        let batch = wma.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| wma.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        wma.reset();
+        assert_eq!(wma.next(1.0), None);
+    }
+
+    #[test]
+    fn test_wma_period_one_is_identity() {
+        let result = WMA::new(1).unwrap().calculate(&[3.0, 7.0]).unwrap();
+        assert_eq!(result, vec![Some(3.0), Some(7.0)]);
+    }
+
+    #[test]
+    fn test_wma_invalid_inputs() {
+        assert!(WMA::new(0).is_err());
+        assert!(matches!(
+            WMA::new(5).unwrap().calculate(&[1.0, 2.0]),
+            Err(IndicatorError::InsufficientData {
+                required: 5,
+                actual: 2
+            })
+        ));
+    }
+}
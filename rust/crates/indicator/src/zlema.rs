@@ -0,0 +1,205 @@
+//! Zero-Lag Exponential Moving Average (ZLEMA)
+
+use std::collections::VecDeque;
+
+use crate::error::check_min_length;
+use crate::snapshot::Snapshot;
+use crate::{IndicatorError, EMA};
+
+/// Zero-Lag Exponential Moving Average indicator
+///
+/// Removes most of an EMA's lag by smoothing a de-lagged series instead of
+/// price itself:
+///
+/// lag = (period − 1) / 2, rounded down
+/// De-lagged(t) = 2 × Price(t) − Price(t − lag)
+/// ZLEMA = EMA(De-lagged)
+///
+/// The smoothing factor is the [`EMA`]'s `2 / (period + 1)`, and the EMA is
+/// seeded with the simple average of its first `period` inputs as in
+/// [`EMA::calculate`], so the first value appears at bar `lag + period − 1`.
+///
+/// # Example
+///
+/// ```
+/// use indicator::ZLEMA;
+///
+/// let zlema = ZLEMA::new(5)?;
+/// let prices: Vec<f64> = (0..10).map(|i| i as f64).collect();
+/// let result = zlema.calculate(&prices)?;
+///
+/// assert!(result[5].is_none());
+/// // On a straight line the de-lagging cancels the EMA's lag
+/// assert_eq!(result[6], Some(6.0));
+/// assert!((result[9].unwrap() - 9.0).abs() < 1e-12);
+/// # Ok::<(), indicator::IndicatorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZLEMA {
+    ema: EMA,
+    lag: usize,
+    /// The last `lag + 1` prices, oldest first
+    history: VecDeque<f64>,
+    /// State before the last `next`, or empty when there is nothing to
+    /// roll back
+    undo: Snapshot<Self>,
+}
+
+impl ZLEMA {
+    /// Creates a new ZLEMA indicator with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Number of periods for the EMA calculation (must be > 0)
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        let ema = EMA::new(period)?;
+        let lag = (period - 1) / 2;
+
+        Ok(Self {
+            ema,
+            lag,
+            history: VecDeque::with_capacity(lag + 1),
+            undo: Snapshot::default(),
+        })
+    }
+
+    /// Calculates ZLEMA for a batch of prices on a fresh state
+    ///
+    /// # Returns
+    ///
+    /// A vector with the same length as the input. The first
+    /// `lag + period − 1` values are `None` as there isn't enough data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InsufficientData` if there are fewer than `lag + period`
+    /// prices.
+    pub fn calculate(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        check_min_length(self.lag + self.period(), prices.len())?;
+
+        let mut state = self.clone();
+        state.reset();
+        Ok(prices.iter().map(|&price| state.next(price)).collect())
+    }
+
+    /// Adds a new price (streaming mode)
+    pub fn next(&mut self, price: f64) -> Option<f64> {
+        self.undo = Snapshot::of(self);
+        if self.history.len() == self.lag + 1 {
+            self.history.pop_front();
+        }
+        self.history.push_back(price);
+        if self.history.len() <= self.lag {
+            return None;
+        }
+
+        let lagged = self.history.front().copied().unwrap_or(price);
+        self.ema.next(2.0 * price - lagged)
+    }
+
+    /// Replaces the most recent price, e.g. when the in-progress bar is revised
+    ///
+    /// Equivalent to [`rollback`](Self::rollback) followed by [`next`](Self::next).
+    pub fn update_last(&mut self, price: f64) -> Option<f64> {
+        self.rollback();
+        self.next(price)
+    }
+
+    /// Undoes the most recent [`next`](Self::next)
+    ///
+    /// Only the latest price can be undone; further calls do nothing until
+    /// the next price arrives.
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.undo.take() {
+            *self = previous;
+        }
+    }
+
+    /// Resets the indicator state
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.history.clear();
+        self.undo = Snapshot::default();
+    }
+
+    /// Returns the period used for the EMA
+    pub fn period(&self) -> usize {
+        self.ema.period()
+    }
+
+    /// Returns the smoothing factor (alpha) of the EMA
+    pub fn alpha(&self) -> f64 {
+        self.ema.alpha()
+    }
+
+    /// Returns the de-lagging offset in bars
+    pub fn lag(&self) -> usize {
+        self.lag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zlema_is_ema_of_delagged_series() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 70.0 + (i as f64 * 0.4).sin() * 6.0)
+            .collect();
+        let delagged: Vec<f64> = (3..prices.len())
+            .map(|i| 2.0 * prices[i] - prices[i - 3])
+            .collect();
+        let ema = EMA::new(7).unwrap().calculate(&delagged).unwrap();
+
+        let zlema = ZLEMA::new(7).unwrap();
+        assert_eq!(zlema.lag(), 3);
+        let result = zlema.calculate(&prices).unwrap();
+        assert!(result[..3].iter().all(Option::is_none));
+        for (actual, expected) in result[3..].iter().zip(&ema) {
+            match (actual, expected) {
+                (Some(a), Some(b)) => assert!((a - b).abs() < 1e-12),
+                (a, b) => assert_eq!(a, b),
+            }
+        }
+    }
+
+    #[test]
+    fn test_zlema_lags_less_than_ema() {
+        let prices: Vec<f64> = (0..40).map(|i| 2.0 * i as f64).collect();
+        let ema = EMA::new(10).unwrap().calculate(&prices).unwrap();
+        let zlema = ZLEMA::new(10).unwrap().calculate(&prices).unwrap();
+
+        let price = prices[39];
+        assert!((price - zlema[39].unwrap()).abs() < (price - ema[39].unwrap()).abs());
+    }
+
+    #[test]
+    fn test_zlema_streaming_matches_batch() {
+        let prices: Vec<f64> = (0..25).map(|i| 3.0 + (i as f64 * 0.9).cos()).collect();
+        let mut zlema = ZLEMA::new(6).unwrap();
+        let batch = zlema.calculate(&prices).unwrap();
+        let streamed: Vec<_> = prices.iter().map(|&p| zlema.next(p)).collect();
+        assert_eq!(batch, streamed);
+
+        zlema.reset();
+        assert_eq!(zlema.next(1.0), None);
+        assert_eq!(zlema.period(), 6);
+        assert!((zlema.alpha() - 2.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zlema_invalid_inputs() {
+        assert!(ZLEMA::new(0).is_err());
+        // Period 1 has no lag and reduces to the price itself
+        let identity = ZLEMA::new(1).unwrap().calculate(&[4.0, 5.0]).unwrap();
+        assert_eq!(identity, vec![Some(4.0), Some(5.0)]);
+        assert!(matches!(
+            ZLEMA::new(20).unwrap().calculate(&[1.0; 28]),
+            Err(IndicatorError::InsufficientData {
+                required: 29,
+                actual: 28
+            })
+        ));
+    }
+}
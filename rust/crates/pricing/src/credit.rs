@@ -0,0 +1,421 @@
+//! Credit default swaps and hazard-rate curves
+//!
+//! A simplified version of the ISDA standard model: default is driven by a
+//! piecewise-constant hazard rate, interest rates come from a
+//! [`YieldCurve`], the premium leg pays on a regular schedule with accrual
+//! on default, and the protection leg is integrated on a weekly grid.
+//!
+//! The [`HazardCurve`] also supplies risky discount factors for pricing
+//! other instruments with counterparty default risk.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::credit::{bootstrap_hazard_curve, Cds, CdsQuote};
+//! use pricing::curve::{Interpolation, YieldCurve};
+//!
+//! let discount = YieldCurve::new(vec![1.0, 10.0], vec![0.97, 0.74], Interpolation::LinearZero)?;
+//! let quotes = [
+//!     CdsQuote { maturity: 1.0, spread: 0.0060 },
+//!     CdsQuote { maturity: 3.0, spread: 0.0085 },
+//!     CdsQuote { maturity: 5.0, spread: 0.0110 },
+//! ];
+//!
+//! let hazard = bootstrap_hazard_curve(&quotes, 0.4, 4, &discount)?;
+//! println!("5y survival: {:.4}", hazard.survival_probability(5.0));
+//!
+//! // A 5y contract struck at 100bp is worth something to the protection buyer
+//! let cds = Cds::new(5.0, 0.0100, 0.4, 4)?;
+//! assert!(cds.npv(&discount, &hazard) > 0.0);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use crate::curve::YieldCurve;
+use crate::PricingError;
+
+/// Maximum step of the protection-leg integration grid (one week)
+const INTEGRATION_STEP: f64 = 1.0 / 52.0;
+/// Upper bound of the hazard-rate search during bootstrapping
+const MAX_HAZARD_RATE: f64 = 10.0;
+/// Tolerance on bootstrapped hazard rates
+const HAZARD_TOLERANCE: f64 = 1e-12;
+/// Maximum bisection iterations per pillar
+const MAX_ITERATIONS: usize = 200;
+
+/// Piecewise-constant hazard-rate curve
+///
+/// `hazard_rates[i]` applies on `(times[i-1], times[i]]`; the last rate is
+/// held flat beyond the last pillar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HazardCurve {
+    times: Vec<f64>,
+    hazard_rates: Vec<f64>,
+}
+
+impl HazardCurve {
+    /// Creates a curve from pillar times and hazard rates
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the inputs are empty or of different
+    /// lengths, if times are not positive and strictly increasing, or if a
+    /// hazard rate is negative.
+    pub fn new(times: Vec<f64>, hazard_rates: Vec<f64>) -> Result<Self, PricingError> {
+        if times.is_empty() || times.len() != hazard_rates.len() {
+            return Err(PricingError::InvalidParameter {
+                field: "hazard_rates",
+                value: hazard_rates.len() as f64,
+                constraint: "non-empty and one per pillar time",
+            });
+        }
+        let mut previous = 0.0;
+        for &time in &times {
+            if time.is_nan() || time <= previous {
+                return Err(PricingError::InvalidParameter {
+                    field: "time",
+                    value: time,
+                    constraint: "> 0 and strictly increasing",
+                });
+            }
+            previous = time;
+        }
+        if let Some(&rate) = hazard_rates.iter().find(|h| h.is_nan() || **h < 0.0) {
+            return Err(PricingError::InvalidParameter {
+                field: "hazard_rate",
+                value: rate,
+                constraint: ">= 0",
+            });
+        }
+
+        Ok(Self {
+            times,
+            hazard_rates,
+        })
+    }
+
+    /// Creates a curve with a single constant hazard rate
+    pub fn flat(hazard_rate: f64) -> Result<Self, PricingError> {
+        Self::new(vec![1.0], vec![hazard_rate])
+    }
+
+    /// Instantaneous hazard rate at time `t`
+    pub fn hazard_rate(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&time| time < t);
+        self.hazard_rates[i.min(self.hazard_rates.len() - 1)]
+    }
+
+    /// Probability of surviving to time `t`
+    pub fn survival_probability(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 1.0;
+        }
+        let mut integral = 0.0;
+        let mut start = 0.0;
+        for (&end, &rate) in self.times.iter().zip(&self.hazard_rates) {
+            if t <= end {
+                return (-(integral + rate * (t - start))).exp();
+            }
+            integral += rate * (end - start);
+            start = end;
+        }
+        let last = self.hazard_rates[self.hazard_rates.len() - 1];
+        (-(integral + last * (t - start))).exp()
+    }
+
+    /// Probability of default between `t1` and `t2`
+    pub fn default_probability(&self, t1: f64, t2: f64) -> f64 {
+        self.survival_probability(t1) - self.survival_probability(t2)
+    }
+
+    /// Discount factor for a cash flow at `t` that is lost on default
+    ///
+    /// Multiply by the cash flow and add `recovery × (DF − risky DF)` for a
+    /// claim with partial recovery.
+    pub fn risky_discount_factor(&self, discount: &YieldCurve, t: f64) -> f64 {
+        discount.discount_factor(t) * self.survival_probability(t)
+    }
+
+    /// Pillar times and their hazard rates
+    pub fn pillars(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.times
+            .iter()
+            .copied()
+            .zip(self.hazard_rates.iter().copied())
+    }
+}
+
+/// Credit default swap from the protection buyer's point of view, per unit notional
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cds {
+    maturity: f64,
+    spread: f64,
+    recovery_rate: f64,
+    frequency: u32,
+}
+
+impl Cds {
+    /// Creates a CDS contract
+    ///
+    /// # Arguments
+    ///
+    /// * `maturity` - Maturity in years (must be > 0)
+    /// * `spread` - Running premium as a decimal (must be >= 0)
+    /// * `recovery_rate` - Assumed recovery on default (must be in [0, 1))
+    /// * `frequency` - Premium payments per year (must be > 0)
+    pub fn new(
+        maturity: f64,
+        spread: f64,
+        recovery_rate: f64,
+        frequency: u32,
+    ) -> Result<Self, PricingError> {
+        if maturity.is_nan() || maturity <= 0.0 {
+            return Err(PricingError::InvalidParameter {
+                field: "maturity",
+                value: maturity,
+                constraint: "> 0",
+            });
+        }
+        if spread.is_nan() || spread < 0.0 {
+            return Err(PricingError::InvalidParameter {
+                field: "spread",
+                value: spread,
+                constraint: ">= 0",
+            });
+        }
+        if recovery_rate.is_nan() || !(0.0..1.0).contains(&recovery_rate) {
+            return Err(PricingError::InvalidParameter {
+                field: "recovery_rate",
+                value: recovery_rate,
+                constraint: "in [0, 1)",
+            });
+        }
+        if frequency == 0 {
+            return Err(PricingError::InvalidParameter {
+                field: "frequency",
+                value: 0.0,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            maturity,
+            spread,
+            recovery_rate,
+            frequency,
+        })
+    }
+
+    /// Risky annuity: present value of 1 per year of premium, including
+    /// accrued premium paid on default (half a period on average)
+    pub fn risky_pv01(&self, discount: &YieldCurve, hazard: &HazardCurve) -> f64 {
+        let period = 1.0 / self.frequency as f64;
+        let mut annuity = 0.0;
+        let mut end = self.maturity;
+        while end > 1e-9 {
+            let start = (end - period).max(0.0);
+            let accrual = end - start;
+            let survival = hazard.survival_probability(end);
+            let defaulted = hazard.default_probability(start, end);
+            annuity += accrual * discount.discount_factor(end) * (survival + 0.5 * defaulted);
+            end -= period;
+        }
+        annuity
+    }
+
+    /// Present value of the protection leg
+    pub fn protection_leg(&self, discount: &YieldCurve, hazard: &HazardCurve) -> f64 {
+        let steps = (self.maturity / INTEGRATION_STEP).ceil().max(1.0) as usize;
+        let dt = self.maturity / steps as f64;
+        let loss = 1.0 - self.recovery_rate;
+        (0..steps)
+            .map(|j| {
+                let (t0, t1) = (j as f64 * dt, (j + 1) as f64 * dt);
+                loss * discount.discount_factor(0.5 * (t0 + t1))
+                    * hazard.default_probability(t0, t1)
+            })
+            .sum()
+    }
+
+    /// Value to the protection buyer: protection leg minus premium leg
+    pub fn npv(&self, discount: &YieldCurve, hazard: &HazardCurve) -> f64 {
+        self.protection_leg(discount, hazard) - self.spread * self.risky_pv01(discount, hazard)
+    }
+
+    /// Spread at which the contract has zero value
+    pub fn par_spread(&self, discount: &YieldCurve, hazard: &HazardCurve) -> f64 {
+        self.protection_leg(discount, hazard) / self.risky_pv01(discount, hazard)
+    }
+
+    /// Returns the maturity
+    pub fn maturity(&self) -> f64 {
+        self.maturity
+    }
+
+    /// Returns the running spread
+    pub fn spread(&self) -> f64 {
+        self.spread
+    }
+}
+
+/// Par spread quote for one CDS maturity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CdsQuote {
+    /// Maturity in years
+    pub maturity: f64,
+    /// Par spread as a decimal
+    pub spread: f64,
+}
+
+/// Bootstraps a piecewise-constant hazard curve from CDS par spreads
+///
+/// Quotes are sorted by maturity; each adds one pillar whose hazard rate is
+/// solved so the quoted contract prices at zero.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for an empty quote set, invalid contract
+/// terms, or duplicate maturities, and `CalculationError` if a hazard rate
+/// cannot be solved (e.g. spreads inverted beyond what non-negative hazard
+/// rates can fit).
+pub fn bootstrap_hazard_curve(
+    quotes: &[CdsQuote],
+    recovery_rate: f64,
+    frequency: u32,
+    discount: &YieldCurve,
+) -> Result<HazardCurve, PricingError> {
+    if quotes.is_empty() {
+        return Err(PricingError::InvalidParameter {
+            field: "quotes",
+            value: 0.0,
+            constraint: "non-empty",
+        });
+    }
+    let mut sorted = quotes.to_vec();
+    sorted.sort_by(|a, b| a.maturity.total_cmp(&b.maturity));
+
+    let mut times: Vec<f64> = Vec::with_capacity(sorted.len());
+    let mut hazard_rates: Vec<f64> = Vec::with_capacity(sorted.len());
+    for quote in &sorted {
+        let cds = Cds::new(quote.maturity, quote.spread, recovery_rate, frequency)?;
+        if times.last().is_some_and(|&last| quote.maturity <= last) {
+            return Err(PricingError::InvalidParameter {
+                field: "maturity",
+                value: quote.maturity,
+                constraint: "unique across quotes",
+            });
+        }
+        times.push(quote.maturity);
+        hazard_rates.push(0.0);
+
+        let npv_at = |rate: f64, hazard_rates: &mut Vec<f64>| {
+            *hazard_rates.last_mut().expect("pillar was pushed") = rate;
+            let curve = HazardCurve {
+                times: times.clone(),
+                hazard_rates: hazard_rates.clone(),
+            };
+            cds.npv(discount, &curve)
+        };
+
+        // The protection buyer's value increases with the hazard rate
+        let (mut low, mut high) = (0.0, MAX_HAZARD_RATE);
+        if npv_at(low, &mut hazard_rates) > 0.0 || npv_at(high, &mut hazard_rates) < 0.0 {
+            return Err(PricingError::CalculationError {
+                operation: "hazard curve bootstrap",
+                reason: format!(
+                    "no non-negative hazard rate reprices the {}y quote",
+                    quote.maturity
+                ),
+            });
+        }
+        for _ in 0..MAX_ITERATIONS {
+            let mid = 0.5 * (low + high);
+            if npv_at(mid, &mut hazard_rates) > 0.0 {
+                high = mid;
+            } else {
+                low = mid;
+            }
+            if high - low < HAZARD_TOLERANCE {
+                break;
+            }
+        }
+        npv_at(0.5 * (low + high), &mut hazard_rates);
+    }
+
+    HazardCurve::new(times, hazard_rates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::Interpolation;
+
+    fn flat_discount(rate: f64) -> YieldCurve {
+        YieldCurve::new(vec![1.0], vec![(-rate).exp()], Interpolation::LinearZero).unwrap()
+    }
+
+    #[test]
+    fn test_survival_probability() {
+        let curve = HazardCurve::new(vec![1.0, 3.0], vec![0.01, 0.03]).unwrap();
+
+        assert!((curve.survival_probability(1.0) - (-0.01_f64).exp()).abs() < 1e-15);
+        assert!((curve.survival_probability(2.0) - (-0.04_f64).exp()).abs() < 1e-15);
+        // Last hazard rate held flat beyond the last pillar
+        assert!((curve.survival_probability(4.0) - (-0.10_f64).exp()).abs() < 1e-15);
+        assert_eq!(curve.hazard_rate(5.0), 0.03);
+        assert_eq!(curve.survival_probability(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_credit_triangle() {
+        // Par spread ≈ hazard × (1 − recovery) for a flat curve
+        let hazard = HazardCurve::flat(0.02).unwrap();
+        let cds = Cds::new(5.0, 0.0, 0.4, 4).unwrap();
+        let spread = cds.par_spread(&flat_discount(0.03), &hazard);
+
+        assert!((spread - 0.012).abs() < 5e-5, "par spread {}", spread);
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_quotes() {
+        let discount = flat_discount(0.02);
+        let quotes = [
+            CdsQuote {
+                maturity: 5.0,
+                spread: 0.0150,
+            },
+            CdsQuote {
+                maturity: 1.0,
+                spread: 0.0080,
+            },
+            CdsQuote {
+                maturity: 3.0,
+                spread: 0.0120,
+            },
+        ];
+        let hazard = bootstrap_hazard_curve(&quotes, 0.4, 4, &discount).unwrap();
+
+        for quote in quotes {
+            let cds = Cds::new(quote.maturity, quote.spread, 0.4, 4).unwrap();
+            assert!(cds.npv(&discount, &hazard).abs() < 1e-10);
+        }
+        // Upward-sloping spreads imply rising hazard rates
+        let rates: Vec<f64> = hazard.pillars().map(|(_, h)| h).collect();
+        assert!(rates.windows(2).all(|w| w[1] > w[0]));
+    }
+
+    #[test]
+    fn test_risky_discount_factor() {
+        let discount = flat_discount(0.05);
+        let hazard = HazardCurve::flat(0.03).unwrap();
+        assert!((hazard.risky_discount_factor(&discount, 2.0) - (-0.16_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(HazardCurve::new(vec![1.0], vec![-0.01]).is_err());
+        assert!(HazardCurve::new(vec![2.0, 1.0], vec![0.01, 0.01]).is_err());
+        assert!(Cds::new(5.0, 0.01, 1.0, 4).is_err());
+        assert!(Cds::new(0.0, 0.01, 0.4, 4).is_err());
+        assert!(bootstrap_hazard_curve(&[], 0.4, 4, &flat_discount(0.02)).is_err());
+    }
+}
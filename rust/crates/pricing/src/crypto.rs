@@ -0,0 +1,282 @@
+//! Crypto derivatives conventions
+//!
+//! Perpetual swaps exchange a periodic funding payment between longs and
+//! shorts instead of expiring; futures trade at a basis to spot that is
+//! usually quoted annualized; and options on the major venues are often
+//! inverse (coin-margined), settling in the underlying coin rather than the
+//! quote currency. These differ from the equity conventions of
+//! [`OptionParams`]: there is no separate dividend yield, pricing is off the
+//! futures price, and premiums and Greeks are expressed in coin.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::crypto::{accrue_funding, InverseOption};
+//! use pricing::OptionType;
+//!
+//! // Three 8-hour funding periods on a 50,000 USD long
+//! let accrual = accrue_funding(50_000.0, &[0.0001, 0.0001, 0.00005], 8.0)?;
+//! assert!((accrual.payment - -12.5).abs() < 1e-9);
+//!
+//! let option = InverseOption {
+//!     underlying_price: 60_000.0,
+//!     strike_price: 65_000.0,
+//!     time_to_expiry: 30.0 / 365.0,
+//!     volatility: 0.55,
+//! };
+//! let result = option.price(OptionType::Call)?;
+//! println!("Premium: {:.4} BTC ({:.0} USD)", result.price_coin, result.price_quote);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+//!
+//! [`OptionParams`]: crate::OptionParams
+
+use crate::{BlackScholes, OptionParams, OptionType, PricingError};
+
+/// Hours in a 365-day year, the usual crypto annualization basis
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+
+/// Funding accrued on a perpetual position over several funding periods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingAccrual {
+    /// Sum of the per-period funding rates
+    pub total_rate: f64,
+    /// Cash flow to the position in quote currency; positive rates make
+    /// longs pay and shorts receive
+    pub payment: f64,
+    /// Average per-period rate annualized with simple compounding
+    pub annualized_rate: f64,
+}
+
+/// Accrues funding on a perpetual position
+///
+/// # Arguments
+///
+/// * `notional` - Signed position value in quote currency (positive long, negative short)
+/// * `rates` - Funding rate of each period as a decimal
+/// * `interval_hours` - Length of a funding period in hours (must be > 0)
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for a non-positive interval or an empty rate
+/// history.
+pub fn accrue_funding(
+    notional: f64,
+    rates: &[f64],
+    interval_hours: f64,
+) -> Result<FundingAccrual, PricingError> {
+    if interval_hours.is_nan() || interval_hours <= 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "interval_hours",
+            value: interval_hours,
+            constraint: "> 0",
+        });
+    }
+    if rates.is_empty() {
+        return Err(PricingError::InvalidParameter {
+            field: "rates",
+            value: 0.0,
+            constraint: "non-empty",
+        });
+    }
+
+    let total_rate: f64 = rates.iter().sum();
+    Ok(FundingAccrual {
+        total_rate,
+        payment: -notional * total_rate,
+        annualized_rate: annualize_funding(total_rate / rates.len() as f64, interval_hours),
+    })
+}
+
+/// Annualizes a per-period funding rate with simple compounding
+pub fn annualize_funding(rate: f64, interval_hours: f64) -> f64 {
+    rate * HOURS_PER_YEAR / interval_hours
+}
+
+/// Premium of a perpetual over spot as a fraction of spot
+pub fn perpetual_premium(spot_price: f64, perpetual_price: f64) -> f64 {
+    perpetual_price / spot_price - 1.0
+}
+
+/// Basis of a dated future over spot (or over a perpetual)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Basis {
+    /// Futures price minus spot price
+    pub absolute: f64,
+    /// Basis as a fraction of spot
+    pub relative: f64,
+    /// Relative basis divided by time to expiry, the usual quoting convention
+    pub annualized: f64,
+    /// Continuously compounded annualized basis, `ln(F / S) / T`
+    pub annualized_continuous: f64,
+}
+
+/// Annualized basis of a future against a reference price
+///
+/// The reference is normally spot, but a perpetual price gives the
+/// futures-perp basis used in calendar trades.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if either price or the time to expiry is not positive.
+pub fn annualized_basis(
+    spot_price: f64,
+    futures_price: f64,
+    time_to_expiry: f64,
+) -> Result<Basis, PricingError> {
+    for (field, value) in [
+        ("spot_price", spot_price),
+        ("futures_price", futures_price),
+        ("time_to_expiry", time_to_expiry),
+    ] {
+        if value.is_nan() || value <= 0.0 {
+            return Err(PricingError::InvalidParameter {
+                field,
+                value,
+                constraint: "> 0",
+            });
+        }
+    }
+
+    let relative = futures_price / spot_price - 1.0;
+    Ok(Basis {
+        absolute: futures_price - spot_price,
+        relative,
+        annualized: relative / time_to_expiry,
+        annualized_continuous: (futures_price / spot_price).ln() / time_to_expiry,
+    })
+}
+
+/// Inverse (coin-margined) European option
+///
+/// The payoff of a call is `max(S − K, 0) / S` coins, worth exactly the
+/// linear payoff in quote currency at expiry. Priced with Black-76 on the
+/// futures price and zero rates, as the venues quoting these contracts do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InverseOption {
+    /// Futures (or index) price of the underlying in quote currency
+    pub underlying_price: f64,
+    /// Strike in quote currency
+    pub strike_price: f64,
+    /// Time to expiry in years
+    pub time_to_expiry: f64,
+    /// Annualized volatility
+    pub volatility: f64,
+}
+
+/// Inverse option premium and coin-denominated Greeks
+#[derive(Debug, Clone, PartialEq)]
+pub struct InverseOptionResult {
+    /// Premium in coin
+    pub price_coin: f64,
+    /// Premium in quote currency
+    pub price_quote: f64,
+    /// Premium-adjusted delta in coin per coin of notional, `N(d1) − price_coin` for a call
+    pub delta: f64,
+    /// Gamma of the quote-currency value, per unit of underlying price
+    pub gamma: f64,
+    /// Vega in coin per 1% volatility change
+    pub vega: f64,
+    /// Theta in coin per year
+    pub theta: f64,
+}
+
+impl InverseOption {
+    /// Prices the option and its coin Greeks
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` under the same rules as [`OptionParams::validate`].
+    pub fn price(&self, option_type: OptionType) -> Result<InverseOptionResult, PricingError> {
+        let linear = BlackScholes::price(
+            &OptionParams {
+                spot_price: self.underlying_price,
+                strike_price: self.strike_price,
+                time_to_expiry: self.time_to_expiry,
+                risk_free_rate: 0.0,
+                volatility: self.volatility,
+                dividend_yield: 0.0,
+            },
+            option_type,
+        )?;
+
+        let price_coin = linear.price / self.underlying_price;
+        Ok(InverseOptionResult {
+            price_coin,
+            price_quote: linear.price,
+            delta: linear.delta - price_coin,
+            gamma: linear.gamma,
+            vega: linear.vega / self.underlying_price,
+            theta: linear.theta / self.underlying_price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_funding_accrual() {
+        let long = accrue_funding(10_000.0, &[0.0001, -0.0002, 0.0003], 8.0).unwrap();
+        assert!((long.total_rate - 0.0002).abs() < 1e-15);
+        assert!((long.payment - -2.0).abs() < 1e-9);
+        // Average of 0.0002 / 3 per 8 hours, 1095 periods a year
+        assert!((long.annualized_rate - 0.0002 / 3.0 * 1095.0).abs() < 1e-12);
+
+        let short = accrue_funding(-10_000.0, &[0.0001, -0.0002, 0.0003], 8.0).unwrap();
+        assert!((short.payment - 2.0).abs() < 1e-9);
+
+        assert!(accrue_funding(1.0, &[], 8.0).is_err());
+        assert!(accrue_funding(1.0, &[0.0001], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_annualized_basis() {
+        let basis = annualized_basis(60_000.0, 61_500.0, 0.25).unwrap();
+
+        assert_eq!(basis.absolute, 1_500.0);
+        assert!((basis.relative - 0.025).abs() < 1e-12);
+        assert!((basis.annualized - 0.1).abs() < 1e-12);
+        assert!((basis.annualized_continuous - 4.0 * 1.025_f64.ln()).abs() < 1e-12);
+        assert!((perpetual_premium(60_000.0, 60_030.0) - 0.0005).abs() < 1e-12);
+        assert!(annualized_basis(60_000.0, 61_500.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_inverse_option_matches_linear_value() {
+        let option = InverseOption {
+            underlying_price: 40_000.0,
+            strike_price: 40_000.0,
+            time_to_expiry: 0.25,
+            volatility: 0.6,
+        };
+        let call = option.price(OptionType::Call).unwrap();
+        let put = option.price(OptionType::Put).unwrap();
+
+        assert!((call.price_coin * 40_000.0 - call.price_quote).abs() < 1e-9);
+        // ATM put-call parity with zero rates: equal premiums
+        assert!((call.price_coin - put.price_coin).abs() < 1e-12);
+        // Premium adjustment lowers call delta and makes put delta more negative
+        assert!(call.delta < 0.6 && call.delta > 0.4);
+        assert!(put.delta < -0.5);
+    }
+
+    #[test]
+    fn test_inverse_delta_is_coin_value_derivative() {
+        // The premium-adjusted delta is S · d(price_coin)/dS
+        let option = |s: f64| InverseOption {
+            underlying_price: s,
+            strike_price: 30_000.0,
+            time_to_expiry: 0.5,
+            volatility: 0.7,
+        };
+        let h = 1.0;
+        let up = option(32_000.0 + h).price(OptionType::Call).unwrap();
+        let down = option(32_000.0 - h).price(OptionType::Call).unwrap();
+        let mid = option(32_000.0).price(OptionType::Call).unwrap();
+
+        let numerical = 32_000.0 * (up.price_coin - down.price_coin) / (2.0 * h);
+        assert!((mid.delta - numerical).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,537 @@
+//! Discount curves and bootstrapping from market instruments
+//!
+//! A [`YieldCurve`] stores discount factors at pillar times and interpolates
+//! between them. [`bootstrap`] builds one from deposit, FRA, futures and par
+//! swap quotes, solving for each pillar so the curve reprices every input
+//! instrument exactly (single-curve framework).
+//!
+//! Times are year fractions from the curve date; rates are decimals.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::curve::{bootstrap, Instrument, Interpolation};
+//!
+//! let instruments = [
+//!     Instrument::Deposit { maturity: 0.25, rate: 0.030 },
+//!     Instrument::Deposit { maturity: 0.5, rate: 0.032 },
+//!     Instrument::Fra { start: 0.5, end: 1.0, rate: 0.035 },
+//!     Instrument::Swap { maturity: 2.0, rate: 0.036, frequency: 2 },
+//!     Instrument::Swap { maturity: 5.0, rate: 0.038, frequency: 2 },
+//! ];
+//!
+//! let curve = bootstrap(&instruments, Interpolation::LogLinearDiscount)?;
+//! println!("5y zero rate: {:.4}", curve.zero_rate(5.0));
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use crate::PricingError;
+
+/// Bracket for the pillar discount factor during bootstrapping
+const MIN_DISCOUNT_FACTOR: f64 = 1e-8;
+const MAX_DISCOUNT_FACTOR: f64 = 2.0;
+/// Tolerance on the pillar discount factor
+const DISCOUNT_FACTOR_TOLERANCE: f64 = 1e-14;
+/// Maximum bisection iterations per pillar
+const MAX_ITERATIONS: usize = 200;
+
+/// Interpolation scheme between curve pillars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear in continuously compounded zero rates
+    LinearZero,
+    /// Linear in log discount factors (piecewise-constant forward rates)
+    LogLinearDiscount,
+}
+
+/// Discount curve defined by discount factors at pillar times
+///
+/// Before the first pillar the first zero rate is held flat; beyond the last
+/// pillar the last zero rate is held flat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct YieldCurve {
+    times: Vec<f64>,
+    discount_factors: Vec<f64>,
+    interpolation: Interpolation,
+}
+
+impl YieldCurve {
+    /// Creates a curve from pillar times and discount factors
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the inputs are empty or of different
+    /// lengths, if times are not positive and strictly increasing, or if a
+    /// discount factor is not positive.
+    pub fn new(
+        times: Vec<f64>,
+        discount_factors: Vec<f64>,
+        interpolation: Interpolation,
+    ) -> Result<Self, PricingError> {
+        if times.is_empty() || times.len() != discount_factors.len() {
+            return Err(PricingError::InvalidParameter {
+                field: "discount_factors",
+                value: discount_factors.len() as f64,
+                constraint: "non-empty and one per pillar time",
+            });
+        }
+        let mut previous = 0.0;
+        for &time in &times {
+            if time.is_nan() || time <= previous {
+                return Err(PricingError::InvalidParameter {
+                    field: "time",
+                    value: time,
+                    constraint: "> 0 and strictly increasing",
+                });
+            }
+            previous = time;
+        }
+        if let Some(&df) = discount_factors
+            .iter()
+            .find(|df| df.is_nan() || **df <= 0.0)
+        {
+            return Err(PricingError::InvalidParameter {
+                field: "discount_factor",
+                value: df,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            times,
+            discount_factors,
+            interpolation,
+        })
+    }
+
+    /// Discount factor for a cash flow at time `t` (1 for `t <= 0`)
+    pub fn discount_factor(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 1.0;
+        }
+        let last = self.times.len() - 1;
+        if t <= self.times[0] || t >= self.times[last] {
+            let pillar = if t <= self.times[0] { 0 } else { last };
+            let zero = -self.discount_factors[pillar].ln() / self.times[pillar];
+            return (-zero * t).exp();
+        }
+
+        let i = self.times.partition_point(|&time| time < t);
+        let (t0, t1) = (self.times[i - 1], self.times[i]);
+        let (df0, df1) = (self.discount_factors[i - 1], self.discount_factors[i]);
+        let w = (t - t0) / (t1 - t0);
+        match self.interpolation {
+            Interpolation::LinearZero => {
+                let (z0, z1) = (-df0.ln() / t0, -df1.ln() / t1);
+                (-(z0 + w * (z1 - z0)) * t).exp()
+            }
+            Interpolation::LogLinearDiscount => (df0.ln() + w * (df1.ln() - df0.ln())).exp(),
+        }
+    }
+
+    /// Continuously compounded zero rate to time `t`
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return -self.discount_factors[0].ln() / self.times[0];
+        }
+        -self.discount_factor(t).ln() / t
+    }
+
+    /// Continuously compounded forward rate between `t1` and `t2`
+    pub fn forward_rate(&self, t1: f64, t2: f64) -> f64 {
+        (self.discount_factor(t1) / self.discount_factor(t2)).ln() / (t2 - t1)
+    }
+
+    /// Pillar times and their discount factors
+    pub fn pillars(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.times
+            .iter()
+            .copied()
+            .zip(self.discount_factors.iter().copied())
+    }
+
+    /// Interpolation scheme
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+}
+
+/// Market instrument used to bootstrap a curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instrument {
+    /// Money-market deposit with simple interest from today to `maturity`
+    Deposit {
+        /// Maturity in years
+        maturity: f64,
+        /// Simple interest rate
+        rate: f64,
+    },
+    /// Forward rate agreement on the period `start` to `end`
+    Fra {
+        /// Start of the accrual period in years
+        start: f64,
+        /// End of the accrual period in years
+        end: f64,
+        /// Simple forward rate
+        rate: f64,
+    },
+    /// Interest-rate future on the period `start` to `end`
+    Future {
+        /// Start of the underlying deposit in years
+        start: f64,
+        /// End of the underlying deposit in years
+        end: f64,
+        /// Quoted price (100 − rate in percent)
+        price: f64,
+        /// Amount subtracted from the futures rate to obtain the forward rate
+        convexity_adjustment: f64,
+    },
+    /// Par interest-rate swap paying a fixed rate `frequency` times a year
+    ///
+    /// Payment dates run backwards from maturity, so a short stub falls at
+    /// the start.
+    Swap {
+        /// Maturity in years
+        maturity: f64,
+        /// Par fixed rate
+        rate: f64,
+        /// Fixed payments per year
+        frequency: u32,
+    },
+}
+
+impl Instrument {
+    /// Time of the instrument's last cash flow, where its pillar is placed
+    pub fn maturity(&self) -> f64 {
+        match *self {
+            Instrument::Deposit { maturity, .. } | Instrument::Swap { maturity, .. } => maturity,
+            Instrument::Fra { end, .. } | Instrument::Future { end, .. } => end,
+        }
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        let (start, end) = match *self {
+            Instrument::Deposit { maturity, .. } | Instrument::Swap { maturity, .. } => {
+                (0.0, maturity)
+            }
+            Instrument::Fra { start, end, .. } | Instrument::Future { start, end, .. } => {
+                (start, end)
+            }
+        };
+        if start.is_nan() || start < 0.0 {
+            return Err(PricingError::InvalidParameter {
+                field: "start",
+                value: start,
+                constraint: ">= 0",
+            });
+        }
+        if end.is_nan() || end <= start {
+            return Err(PricingError::InvalidParameter {
+                field: "maturity",
+                value: end,
+                constraint: "> start",
+            });
+        }
+        if let Instrument::Swap { frequency: 0, .. } = self {
+            return Err(PricingError::InvalidParameter {
+                field: "frequency",
+                value: 0.0,
+                constraint: "> 0",
+            });
+        }
+
+        // A NaN quote makes every residual comparison false, so catch it here
+        let quotes: &[(&'static str, f64)] = match *self {
+            Instrument::Deposit { rate, .. }
+            | Instrument::Fra { rate, .. }
+            | Instrument::Swap { rate, .. } => &[("rate", rate)],
+            Instrument::Future {
+                price,
+                convexity_adjustment,
+                ..
+            } => &[
+                ("price", price),
+                ("convexity_adjustment", convexity_adjustment),
+            ],
+        };
+        for &(field, value) in quotes {
+            if !value.is_finite() {
+                return Err(PricingError::InvalidParameter {
+                    field,
+                    value,
+                    constraint: "finite",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Present value of the instrument per unit notional on `curve`; zero at par
+    fn residual(&self, curve: &YieldCurve) -> f64 {
+        let forward_residual = |start: f64, end: f64, rate: f64| {
+            curve.discount_factor(end) * (1.0 + rate * (end - start)) - curve.discount_factor(start)
+        };
+        match *self {
+            Instrument::Deposit { maturity, rate } => forward_residual(0.0, maturity, rate),
+            Instrument::Fra { start, end, rate } => forward_residual(start, end, rate),
+            Instrument::Future {
+                start,
+                end,
+                price,
+                convexity_adjustment,
+            } => {
+                let rate = (100.0 - price) / 100.0 - convexity_adjustment;
+                forward_residual(start, end, rate)
+            }
+            Instrument::Swap {
+                maturity,
+                rate,
+                frequency,
+            } => {
+                let period = 1.0 / frequency as f64;
+                let mut fixed_leg = 0.0;
+                let mut end = maturity;
+                while end > 1e-9 {
+                    let start = (end - period).max(0.0);
+                    fixed_leg += rate * (end - start) * curve.discount_factor(end);
+                    end -= period;
+                }
+                // Floating leg of a par swap is worth 1 − DF(T) in a single-curve setup
+                fixed_leg + curve.discount_factor(maturity) - 1.0
+            }
+        }
+    }
+}
+
+/// Bootstraps a discount curve from market instruments
+///
+/// Instruments are sorted by maturity and each adds one pillar, solved by
+/// bisection so that the instrument prices at par given all earlier pillars.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for an empty instrument set, invalid
+/// instrument dates, or two instruments with the same maturity, and
+/// `CalculationError` if a pillar cannot be solved.
+pub fn bootstrap(
+    instruments: &[Instrument],
+    interpolation: Interpolation,
+) -> Result<YieldCurve, PricingError> {
+    if instruments.is_empty() {
+        return Err(PricingError::InvalidParameter {
+            field: "instruments",
+            value: 0.0,
+            constraint: "non-empty",
+        });
+    }
+    for instrument in instruments {
+        instrument.validate()?;
+    }
+    let mut sorted = instruments.to_vec();
+    sorted.sort_by(|a, b| a.maturity().total_cmp(&b.maturity()));
+
+    // Each instrument appends its pillar to this partial curve, and the
+    // bisection overwrites the trial discount factor in place
+    let mut curve = YieldCurve {
+        times: Vec::with_capacity(sorted.len()),
+        discount_factors: Vec::with_capacity(sorted.len()),
+        interpolation,
+    };
+    for instrument in &sorted {
+        let maturity = instrument.maturity();
+        if curve.times.last().is_some_and(|&last| maturity <= last) {
+            return Err(PricingError::InvalidParameter {
+                field: "maturity",
+                value: maturity,
+                constraint: "unique across instruments",
+            });
+        }
+        curve.times.push(maturity);
+        curve.discount_factors.push(1.0);
+
+        let residual_at = |df: f64, curve: &mut YieldCurve| {
+            *curve
+                .discount_factors
+                .last_mut()
+                .expect("pillar was pushed") = df;
+            instrument.residual(curve)
+        };
+
+        // Every instrument's value increases with its own pillar discount factor
+        let (mut low, mut high) = (MIN_DISCOUNT_FACTOR, MAX_DISCOUNT_FACTOR);
+        // Written so that a NaN residual also fails the bracket
+        let brackets_root =
+            residual_at(low, &mut curve) <= 0.0 && residual_at(high, &mut curve) >= 0.0;
+        if !brackets_root {
+            return Err(PricingError::CalculationError {
+                operation: "curve bootstrap",
+                reason: format!(
+                    "no discount factor reprices the instrument maturing at {maturity}"
+                ),
+            });
+        }
+        for _ in 0..MAX_ITERATIONS {
+            let mid = 0.5 * (low + high);
+            if residual_at(mid, &mut curve) > 0.0 {
+                high = mid;
+            } else {
+                low = mid;
+            }
+            if high - low < DISCOUNT_FACTOR_TOLERANCE {
+                break;
+            }
+        }
+        residual_at(0.5 * (low + high), &mut curve);
+    }
+
+    YieldCurve::new(curve.times, curve.discount_factors, interpolation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> Vec<Instrument> {
+        vec![
+            Instrument::Deposit {
+                maturity: 0.25,
+                rate: 0.030,
+            },
+            Instrument::Future {
+                start: 0.25,
+                end: 0.5,
+                price: 96.80,
+                convexity_adjustment: 0.0001,
+            },
+            Instrument::Fra {
+                start: 0.5,
+                end: 1.0,
+                rate: 0.034,
+            },
+            Instrument::Swap {
+                maturity: 2.0,
+                rate: 0.035,
+                frequency: 1,
+            },
+            Instrument::Swap {
+                maturity: 3.5,
+                rate: 0.037,
+                frequency: 2,
+            },
+            Instrument::Swap {
+                maturity: 10.0,
+                rate: 0.040,
+                frequency: 2,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_inputs() {
+        for interpolation in [Interpolation::LinearZero, Interpolation::LogLinearDiscount] {
+            let curve = bootstrap(&market(), interpolation).unwrap();
+            assert_eq!(curve.pillars().count(), 6);
+            for instrument in market() {
+                let residual = instrument.residual(&curve);
+                assert!(residual.abs() < 1e-10, "{:?}: {}", instrument, residual);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deposit_discount_factor() {
+        let curve = bootstrap(
+            &[Instrument::Deposit {
+                maturity: 0.5,
+                rate: 0.04,
+            }],
+            Interpolation::LinearZero,
+        )
+        .unwrap();
+
+        assert!((curve.discount_factor(0.5) - 1.0 / 1.02).abs() < 1e-12);
+        // Flat zero extrapolation on both sides
+        assert!((curve.zero_rate(0.1) - curve.zero_rate(5.0)).abs() < 1e-12);
+        assert_eq!(curve.discount_factor(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_log_linear_gives_flat_forwards() {
+        let curve = YieldCurve::new(
+            vec![1.0, 2.0],
+            vec![(-0.03_f64).exp(), (-0.08_f64).exp()],
+            Interpolation::LogLinearDiscount,
+        )
+        .unwrap();
+
+        assert!((curve.forward_rate(1.0, 1.5) - 0.05).abs() < 1e-12);
+        assert!((curve.forward_rate(1.5, 2.0) - 0.05).abs() < 1e-12);
+        assert!((curve.zero_rate(1.0) - 0.03).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_curves_and_instruments() {
+        assert!(
+            YieldCurve::new(vec![1.0, 1.0], vec![0.9, 0.8], Interpolation::LinearZero).is_err()
+        );
+        assert!(YieldCurve::new(vec![1.0], vec![0.0], Interpolation::LinearZero).is_err());
+        assert!(bootstrap(&[], Interpolation::LinearZero).is_err());
+
+        let duplicate = [
+            Instrument::Deposit {
+                maturity: 1.0,
+                rate: 0.03,
+            },
+            Instrument::Swap {
+                maturity: 1.0,
+                rate: 0.03,
+                frequency: 1,
+            },
+        ];
+        assert!(bootstrap(&duplicate, Interpolation::LinearZero).is_err());
+
+        let zero_frequency = [Instrument::Swap {
+            maturity: 2.0,
+            rate: 0.03,
+            frequency: 0,
+        }];
+        assert!(bootstrap(&zero_frequency, Interpolation::LinearZero).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_quotes_are_rejected() {
+        let nan_rate = [Instrument::Deposit {
+            maturity: 1.0,
+            rate: f64::NAN,
+        }];
+        assert!(matches!(
+            bootstrap(&nan_rate, Interpolation::LinearZero),
+            Err(PricingError::InvalidParameter { field: "rate", .. })
+        ));
+
+        let infinite_price = [Instrument::Future {
+            start: 0.25,
+            end: 0.5,
+            price: f64::INFINITY,
+            convexity_adjustment: 0.0,
+        }];
+        assert!(matches!(
+            bootstrap(&infinite_price, Interpolation::LinearZero),
+            Err(PricingError::InvalidParameter { field: "price", .. })
+        ));
+    }
+
+    #[test]
+    fn test_unbracketed_pillar_is_an_error() {
+        // A deposit rate this negative needs a discount factor above the
+        // search range
+        let deep_negative = [Instrument::Deposit {
+            maturity: 1.0,
+            rate: -0.9,
+        }];
+        assert!(matches!(
+            bootstrap(&deep_negative, Interpolation::LinearZero),
+            Err(PricingError::CalculationError { .. })
+        ));
+    }
+}
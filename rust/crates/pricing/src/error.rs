@@ -0,0 +1,40 @@
+//! Error types for option pricing calculations
+
+use thiserror::Error;
+
+/// Errors that can occur during option pricing calculations
+///
+/// Variants carry the offending field and value so callers can react to the
+/// failure programmatically, while `Display` stays human-readable.
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PricingError {
+    /// A pricing input violated its constraint
+    #[error("Invalid parameter `{field}` = {value}: must be {constraint}")]
+    InvalidParameter {
+        /// Name of the offending field (e.g. `"volatility"`)
+        field: &'static str,
+        /// Value that was supplied
+        value: f64,
+        /// Constraint the value must satisfy (e.g. `">= 0"`)
+        constraint: &'static str,
+    },
+
+    /// A required input was not supplied for the given key
+    #[error("Missing {field} for `{key}`")]
+    MissingInput {
+        /// Name of the missing input (e.g. `"beta"`)
+        field: &'static str,
+        /// Key the input was looked up by (e.g. an underlying symbol)
+        key: String,
+    },
+
+    /// A numerical routine failed while computing a result
+    #[error("Calculation error in {operation}: {reason}")]
+    CalculationError {
+        /// Operation that failed
+        operation: &'static str,
+        /// Underlying reason reported by the failing routine
+        reason: String,
+    },
+}
@@ -0,0 +1,410 @@
+//! Commodity futures curves
+//!
+//! A [`FuturesCurve`] holds the settlement prices of one commodity's listed
+//! contracts and reports the shape of the term structure (contango or
+//! backwardation), the roll yield earned by rolling from one contract to the
+//! next, calendar-spread analytics, and a seasonal decomposition of the
+//! curve into a log-linear trend plus one factor per delivery month.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::futures::{CurveShape, FuturesCurve, FuturesQuote};
+//!
+//! let curve = FuturesCurve::new(vec![
+//!     FuturesQuote { expiry: 0.10, delivery_month: 12, price: 78.4 },
+//!     FuturesQuote { expiry: 0.18, delivery_month: 1, price: 78.1 },
+//!     FuturesQuote { expiry: 0.27, delivery_month: 2, price: 77.6 },
+//!     FuturesQuote { expiry: 0.35, delivery_month: 3, price: 77.2 },
+//! ])?;
+//!
+//! assert_eq!(curve.shape(), CurveShape::Backwardation);
+//! // Long positions earn a positive roll yield in backwardation
+//! assert!(curve.roll_yields()[0] > 0.0);
+//!
+//! let spread = curve.calendar_spread(0, 3)?;
+//! assert!((spread.spread - -1.2).abs() < 1e-9);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use crate::PricingError;
+
+/// Settlement price of one futures contract
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuturesQuote {
+    /// Time to expiry in years
+    pub expiry: f64,
+    /// Delivery month, 1 (January) to 12 (December)
+    pub delivery_month: u32,
+    /// Settlement price
+    pub price: f64,
+}
+
+/// Overall shape of a futures curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveShape {
+    /// Every contract is priced above the previous one
+    Contango,
+    /// Every contract is priced below the previous one
+    Backwardation,
+    /// Prices both rise and fall along the curve
+    Mixed,
+    /// All contracts have the same price
+    Flat,
+}
+
+/// Analytics for a spread between two contracts of the same curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarSpread {
+    /// Expiry of the near leg
+    pub near_expiry: f64,
+    /// Expiry of the far leg
+    pub far_expiry: f64,
+    /// Far price minus near price
+    pub spread: f64,
+    /// Spread as a fraction of the near price
+    pub spread_ratio: f64,
+    /// Continuously compounded annualized carry, `ln(far / near) / Δt`
+    pub annualized_carry: f64,
+}
+
+/// Trend and monthly seasonal factors of a futures curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeasonalDecomposition {
+    /// Log price of the trend at expiry zero
+    pub intercept: f64,
+    /// Trend slope in log price per year
+    pub slope: f64,
+    /// Log-price factor per delivery month (index 0 is January); months
+    /// without contracts are zero and the others average to zero
+    pub factors: [f64; 12],
+}
+
+impl SeasonalDecomposition {
+    /// Trend price at `expiry`, excluding seasonality
+    pub fn trend(&self, expiry: f64) -> f64 {
+        (self.intercept + self.slope * expiry).exp()
+    }
+
+    /// Multiplicative seasonal factor for a delivery month (1-12)
+    pub fn seasonal_factor(&self, delivery_month: u32) -> f64 {
+        self.factors[(delivery_month as usize - 1) % 12].exp()
+    }
+}
+
+/// Term structure of futures prices for one commodity
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuturesCurve {
+    quotes: Vec<FuturesQuote>,
+}
+
+impl FuturesCurve {
+    /// Creates a curve from contract quotes, sorted by expiry
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if there are no quotes, or if an expiry is
+    /// negative or duplicated, a price is not positive, or a delivery month
+    /// is outside 1-12.
+    pub fn new(mut quotes: Vec<FuturesQuote>) -> Result<Self, PricingError> {
+        if quotes.is_empty() {
+            return Err(PricingError::InvalidParameter {
+                field: "quotes",
+                value: 0.0,
+                constraint: "non-empty",
+            });
+        }
+        for quote in &quotes {
+            if quote.expiry.is_nan() || quote.expiry < 0.0 {
+                return Err(PricingError::InvalidParameter {
+                    field: "expiry",
+                    value: quote.expiry,
+                    constraint: ">= 0",
+                });
+            }
+            if quote.price.is_nan() || quote.price <= 0.0 {
+                return Err(PricingError::InvalidParameter {
+                    field: "price",
+                    value: quote.price,
+                    constraint: "> 0",
+                });
+            }
+            if !(1..=12).contains(&quote.delivery_month) {
+                return Err(PricingError::InvalidParameter {
+                    field: "delivery_month",
+                    value: quote.delivery_month as f64,
+                    constraint: "in 1..=12",
+                });
+            }
+        }
+        quotes.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        if let Some(pair) = quotes.windows(2).find(|w| w[0].expiry == w[1].expiry) {
+            return Err(PricingError::InvalidParameter {
+                field: "expiry",
+                value: pair[1].expiry,
+                constraint: "unique across quotes",
+            });
+        }
+
+        Ok(Self { quotes })
+    }
+
+    /// Contract quotes ordered by expiry
+    pub fn quotes(&self) -> &[FuturesQuote] {
+        &self.quotes
+    }
+
+    /// Price at an arbitrary expiry
+    ///
+    /// Log-linear interpolation between contracts, flat beyond the first
+    /// and last contract.
+    pub fn price(&self, expiry: f64) -> f64 {
+        let i = self.quotes.partition_point(|q| q.expiry < expiry);
+        if i == 0 {
+            return self.quotes[0].price;
+        }
+        if i == self.quotes.len() {
+            return self.quotes[i - 1].price;
+        }
+        let (a, b) = (&self.quotes[i - 1], &self.quotes[i]);
+        let w = (expiry - a.expiry) / (b.expiry - a.expiry);
+        (a.price.ln() * (1.0 - w) + b.price.ln() * w).exp()
+    }
+
+    /// Shape of the curve from consecutive contract prices
+    pub fn shape(&self) -> CurveShape {
+        let (mut rising, mut falling) = (false, false);
+        for pair in self.quotes.windows(2) {
+            rising |= pair[1].price > pair[0].price;
+            falling |= pair[1].price < pair[0].price;
+        }
+        match (rising, falling) {
+            (true, false) => CurveShape::Contango,
+            (false, true) => CurveShape::Backwardation,
+            (true, true) => CurveShape::Mixed,
+            (false, false) => CurveShape::Flat,
+        }
+    }
+
+    /// Annualized slope between the first and last contract
+    ///
+    /// Positive in contango, negative in backwardation; zero for a single
+    /// contract.
+    pub fn slope(&self) -> f64 {
+        if self.quotes.len() < 2 {
+            return 0.0;
+        }
+        let (first, last) = (&self.quotes[0], &self.quotes[self.quotes.len() - 1]);
+        (last.price / first.price).ln() / (last.expiry - first.expiry)
+    }
+
+    /// Annualized roll yield between each pair of consecutive contracts
+    ///
+    /// `ln(F_i / F_{i+1}) / (T_{i+1} − T_i)`: the return a long position
+    /// earns from the curve shape alone as the far contract rolls down to
+    /// the near price.
+    pub fn roll_yields(&self) -> Vec<f64> {
+        self.quotes
+            .windows(2)
+            .map(|w| (w[0].price / w[1].price).ln() / (w[1].expiry - w[0].expiry))
+            .collect()
+    }
+
+    /// Spread between the contracts at positions `near` and `far`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if either index is out of range or `near`
+    /// is not before `far`.
+    pub fn calendar_spread(&self, near: usize, far: usize) -> Result<CalendarSpread, PricingError> {
+        if far >= self.quotes.len() {
+            return Err(PricingError::InvalidParameter {
+                field: "far",
+                value: far as f64,
+                constraint: "< number of contracts",
+            });
+        }
+        if near >= far {
+            return Err(PricingError::InvalidParameter {
+                field: "near",
+                value: near as f64,
+                constraint: "< far",
+            });
+        }
+        let (a, b) = (&self.quotes[near], &self.quotes[far]);
+        Ok(CalendarSpread {
+            near_expiry: a.expiry,
+            far_expiry: b.expiry,
+            spread: b.price - a.price,
+            spread_ratio: (b.price - a.price) / a.price,
+            annualized_carry: (b.price / a.price).ln() / (b.expiry - a.expiry),
+        })
+    }
+
+    /// Splits the curve into a log-linear trend and monthly seasonal factors
+    ///
+    /// Fits `ln F = a + b·T + s(month)` by least squares, with the seasonal
+    /// factors constrained to average zero over the delivery months present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if fewer than two distinct delivery months
+    /// are quoted, and `CalculationError` if the trend cannot be separated
+    /// from seasonality (every month quoted only once).
+    pub fn seasonality(&self) -> Result<SeasonalDecomposition, PricingError> {
+        // Within-month regression: demeaning by month removes the seasonal
+        // terms, leaving the slope identified by repeated months.
+        let mut sums = [(0usize, 0.0, 0.0); 12];
+        for quote in &self.quotes {
+            let entry = &mut sums[quote.delivery_month as usize - 1];
+            entry.0 += 1;
+            entry.1 += quote.expiry;
+            entry.2 += quote.price.ln();
+        }
+        let months = sums.iter().filter(|s| s.0 > 0).count();
+        if months < 2 {
+            return Err(PricingError::InvalidParameter {
+                field: "delivery_month",
+                value: months as f64,
+                constraint: "at least 2 distinct months",
+            });
+        }
+
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for quote in &self.quotes {
+            let (n, sum_t, sum_y) = sums[quote.delivery_month as usize - 1];
+            let dt = quote.expiry - sum_t / n as f64;
+            sxy += dt * (quote.price.ln() - sum_y / n as f64);
+            sxx += dt * dt;
+        }
+        if sxx <= 0.0 {
+            return Err(PricingError::CalculationError {
+                operation: "seasonal decomposition",
+                reason: "each delivery month needs more than one contract to separate trend from seasonality".to_string(),
+            });
+        }
+        let slope = sxy / sxx;
+
+        let mut factors = [0.0; 12];
+        for (factor, &(n, sum_t, sum_y)) in factors.iter_mut().zip(&sums) {
+            if n > 0 {
+                *factor = (sum_y - slope * sum_t) / n as f64;
+            }
+        }
+        let intercept = factors.iter().sum::<f64>() / months as f64;
+        for (factor, &(n, _, _)) in factors.iter_mut().zip(&sums) {
+            if n > 0 {
+                *factor -= intercept;
+            }
+        }
+
+        Ok(SeasonalDecomposition {
+            intercept,
+            slope,
+            factors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(expiry: f64, delivery_month: u32, price: f64) -> FuturesQuote {
+        FuturesQuote {
+            expiry,
+            delivery_month,
+            price,
+        }
+    }
+
+    #[test]
+    fn test_shape_and_roll_yield() {
+        let curve = FuturesCurve::new(vec![
+            quote(0.5, 6, 102.0),
+            quote(0.25, 3, 100.0),
+            quote(0.75, 9, 104.0),
+        ])
+        .unwrap();
+
+        assert_eq!(curve.shape(), CurveShape::Contango);
+        assert_eq!(curve.quotes()[0].delivery_month, 3);
+        let rolls = curve.roll_yields();
+        assert!((rolls[0] - 4.0 * (100.0_f64 / 102.0).ln()).abs() < 1e-12);
+        assert!(rolls.iter().all(|&r| r < 0.0));
+        assert!(curve.slope() > 0.0);
+    }
+
+    #[test]
+    fn test_mixed_and_flat_shapes() {
+        let mixed = FuturesCurve::new(vec![
+            quote(0.1, 1, 10.0),
+            quote(0.2, 2, 11.0),
+            quote(0.3, 3, 10.5),
+        ])
+        .unwrap();
+        assert_eq!(mixed.shape(), CurveShape::Mixed);
+
+        let flat = FuturesCurve::new(vec![quote(0.1, 1, 10.0), quote(0.2, 2, 10.0)]).unwrap();
+        assert_eq!(flat.shape(), CurveShape::Flat);
+    }
+
+    #[test]
+    fn test_price_interpolation() {
+        let curve = FuturesCurve::new(vec![quote(1.0, 1, 100.0), quote(2.0, 1, 121.0)]).unwrap();
+
+        assert!((curve.price(1.5) - 110.0).abs() < 1e-9);
+        assert_eq!(curve.price(0.5), 100.0);
+        assert_eq!(curve.price(3.0), 121.0);
+    }
+
+    #[test]
+    fn test_calendar_spread() {
+        let curve = FuturesCurve::new(vec![quote(0.25, 3, 50.0), quote(0.75, 9, 55.0)]).unwrap();
+        let spread = curve.calendar_spread(0, 1).unwrap();
+
+        assert_eq!(spread.spread, 5.0);
+        assert!((spread.spread_ratio - 0.1).abs() < 1e-12);
+        assert!((spread.annualized_carry - 2.0 * 1.1_f64.ln()).abs() < 1e-12);
+        assert!(curve.calendar_spread(1, 0).is_err());
+        assert!(curve.calendar_spread(0, 2).is_err());
+    }
+
+    #[test]
+    fn test_seasonality_recovers_factors() {
+        // Winter premium on a 2%/year trend, three years of monthly contracts
+        let winter = |month: u32| {
+            if month == 12 || month <= 2 {
+                0.15
+            } else {
+                -0.05
+            }
+        };
+        let quotes: Vec<_> = (0..36)
+            .map(|i| {
+                let month = i % 12 + 1;
+                let expiry = (i + 1) as f64 / 12.0;
+                quote(expiry, month, (1.0 + 0.02 * expiry + winter(month)).exp())
+            })
+            .collect();
+        let decomposition = FuturesCurve::new(quotes).unwrap().seasonality().unwrap();
+
+        assert!((decomposition.slope - 0.02).abs() < 1e-10);
+        assert!((decomposition.factors[0] - 0.15).abs() < 1e-10);
+        assert!((decomposition.factors[6] + 0.05).abs() < 1e-10);
+        assert!((decomposition.trend(0.0) - 1.0_f64.exp()).abs() < 1e-9);
+        assert!((decomposition.seasonal_factor(12) - 0.15_f64.exp()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(FuturesCurve::new(vec![]).is_err());
+        assert!(FuturesCurve::new(vec![quote(0.5, 13, 10.0)]).is_err());
+        assert!(FuturesCurve::new(vec![quote(0.5, 1, 0.0)]).is_err());
+        assert!(FuturesCurve::new(vec![quote(0.5, 1, 10.0), quote(0.5, 2, 11.0)]).is_err());
+
+        let single_months =
+            FuturesCurve::new(vec![quote(0.1, 1, 10.0), quote(0.2, 2, 11.0)]).unwrap();
+        assert!(single_months.seasonality().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//! Implied volatility solving and streaming implied-volatility tracking
+
+use std::collections::VecDeque;
+
+use crate::{BlackScholes, OptionParams, OptionType, PricingError};
+
+/// Lower bound of the volatility search interval
+const MIN_VOLATILITY: f64 = 1e-6;
+/// Upper bound of the volatility search interval (500%)
+const MAX_VOLATILITY: f64 = 5.0;
+/// Price tolerance for convergence
+const PRICE_TOLERANCE: f64 = 1e-10;
+/// Width of the volatility bracket at which bisection stops
+const VOLATILITY_TOLERANCE: f64 = 1e-12;
+/// Maximum solver iterations
+const MAX_ITERATIONS: usize = 100;
+
+/// Solves for the Black-Scholes volatility that reproduces a market price
+///
+/// Uses Newton-Raphson on vega, falling back to bisection whenever a Newton
+/// step would leave the current bracket. The `volatility` field of `params`
+/// is ignored.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if the parameters are invalid, the option has
+/// already expired, or `market_price` lies outside the no-arbitrage bounds,
+/// and `CalculationError` if the solver does not converge.
+///
+/// # Example
+///
+/// ```
+/// use pricing::implied_vol::implied_volatility;
+/// use pricing::{BlackScholes, OptionParams, OptionType};
+///
+/// let params = OptionParams {
+///     spot_price: 100.0,
+///     strike_price: 105.0,
+///     time_to_expiry: 0.5,
+///     risk_free_rate: 0.03,
+///     volatility: 0.25,
+///     dividend_yield: 0.0,
+/// };
+/// let price = BlackScholes::price(&params, OptionType::Call)?.price;
+///
+/// let iv = implied_volatility(price, &params, OptionType::Call)?;
+/// assert!((iv - 0.25).abs() < 1e-8);
+/// # Ok::<(), pricing::PricingError>(())
+/// ```
+pub fn implied_volatility(
+    market_price: f64,
+    params: &OptionParams,
+    option_type: OptionType,
+) -> Result<f64, PricingError> {
+    params.validate()?;
+    if params.time_to_expiry <= 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "time_to_expiry",
+            value: params.time_to_expiry,
+            constraint: "> 0",
+        });
+    }
+
+    let discounted_spot =
+        params.spot_price * (-params.dividend_yield * params.time_to_expiry).exp();
+    let discounted_strike =
+        params.strike_price * (-params.risk_free_rate * params.time_to_expiry).exp();
+    let (lower_bound, upper_bound) = match option_type {
+        OptionType::Call => (
+            (discounted_spot - discounted_strike).max(0.0),
+            discounted_spot,
+        ),
+        OptionType::Put => (
+            (discounted_strike - discounted_spot).max(0.0),
+            discounted_strike,
+        ),
+    };
+    if market_price.is_nan() || market_price <= lower_bound || market_price >= upper_bound {
+        return Err(PricingError::InvalidParameter {
+            field: "market_price",
+            value: market_price,
+            constraint: "within the no-arbitrage bounds",
+        });
+    }
+
+    let mut trial = params.clone();
+    let mut low = MIN_VOLATILITY;
+    let mut high = MAX_VOLATILITY;
+    let mut volatility = 0.2;
+
+    for _ in 0..MAX_ITERATIONS {
+        trial.volatility = volatility;
+        let result = BlackScholes::price(&trial, option_type)?;
+        let diff = result.price - market_price;
+        if diff.abs() < PRICE_TOLERANCE {
+            return Ok(volatility);
+        }
+
+        // Price is increasing in volatility, so the sign of `diff` narrows the bracket
+        if diff > 0.0 {
+            high = volatility;
+        } else {
+            low = volatility;
+        }
+
+        // `vega` is expressed per 1% volatility change
+        let vega = result.vega * 100.0;
+        let newton = volatility - diff / vega;
+        volatility = if vega > 0.0 && newton > low && newton < high {
+            newton
+        } else {
+            0.5 * (low + high)
+        };
+
+        if high - low < VOLATILITY_TOLERANCE {
+            return Ok(volatility);
+        }
+    }
+
+    Err(PricingError::CalculationError {
+        operation: "implied volatility",
+        reason: format!("no convergence after {} iterations", MAX_ITERATIONS),
+    })
+}
+
+/// Implied volatility of the latest quote with its rank over the lookback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IvSnapshot {
+    /// Implied volatility of the latest quote
+    pub implied_volatility: f64,
+    /// IV rank in percent: position of the current IV between the lookback
+    /// minimum (0) and maximum (100); `None` until the lookback is full
+    pub iv_rank: Option<f64>,
+    /// IV percentile in percent: share of lookback observations strictly
+    /// below the current IV; `None` until the lookback is full
+    pub iv_percentile: Option<f64>,
+}
+
+/// Streaming implied-volatility tracker for a single option contract
+///
+/// Each update solves for the implied volatility of the latest option quote
+/// given the current underlying price and time to expiry, and maintains the
+/// IV rank and percentile over a rolling lookback of solved values.
+///
+/// # Example
+///
+/// ```
+/// use pricing::implied_vol::IvTracker;
+/// use pricing::OptionType;
+///
+/// let mut tracker = IvTracker::new(OptionType::Call, 100.0, 0.03, 0.0, 3)?;
+///
+/// tracker.update(100.0, 4.0, 0.25)?;
+/// tracker.update(101.0, 4.4, 0.249)?;
+/// let snapshot = tracker.update(99.0, 4.6, 0.248)?;
+///
+/// assert!(snapshot.implied_volatility > 0.0);
+/// assert!(snapshot.iv_rank.is_some());
+/// # Ok::<(), pricing::PricingError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IvTracker {
+    option_type: OptionType,
+    strike_price: f64,
+    risk_free_rate: f64,
+    dividend_yield: f64,
+    /// Number of solved IVs kept for rank/percentile
+    lookback: usize,
+    /// Solved IVs, oldest first
+    history: VecDeque<f64>,
+}
+
+impl IvTracker {
+    /// Creates a tracker for one option contract
+    ///
+    /// # Arguments
+    ///
+    /// * `option_type` - Call or put
+    /// * `strike_price` - Strike of the contract (must be > 0)
+    /// * `risk_free_rate` - Risk-free rate used when solving
+    /// * `dividend_yield` - Dividend yield used when solving
+    /// * `lookback` - Number of observations for IV rank/percentile (must be > 0)
+    pub fn new(
+        option_type: OptionType,
+        strike_price: f64,
+        risk_free_rate: f64,
+        dividend_yield: f64,
+        lookback: usize,
+    ) -> Result<Self, PricingError> {
+        if strike_price.is_nan() || strike_price <= 0.0 {
+            return Err(PricingError::InvalidParameter {
+                field: "strike_price",
+                value: strike_price,
+                constraint: "> 0",
+            });
+        }
+        if lookback == 0 {
+            return Err(PricingError::InvalidParameter {
+                field: "lookback",
+                value: lookback as f64,
+                constraint: "> 0",
+            });
+        }
+
+        Ok(Self {
+            option_type,
+            strike_price,
+            risk_free_rate,
+            dividend_yield,
+            lookback,
+            history: VecDeque::with_capacity(lookback),
+        })
+    }
+
+    /// Processes a new underlying price and option quote
+    ///
+    /// A quote that cannot be inverted (e.g. outside the no-arbitrage bounds)
+    /// returns the solver error and leaves the tracker's history unchanged.
+    pub fn update(
+        &mut self,
+        spot_price: f64,
+        option_price: f64,
+        time_to_expiry: f64,
+    ) -> Result<IvSnapshot, PricingError> {
+        let params = OptionParams {
+            spot_price,
+            strike_price: self.strike_price,
+            time_to_expiry,
+            risk_free_rate: self.risk_free_rate,
+            volatility: 0.0,
+            dividend_yield: self.dividend_yield,
+        };
+        let iv = implied_volatility(option_price, &params, self.option_type)?;
+
+        if self.history.len() == self.lookback {
+            self.history.pop_front();
+        }
+        self.history.push_back(iv);
+
+        Ok(self.snapshot(iv))
+    }
+
+    /// Returns the most recently solved implied volatility
+    pub fn current(&self) -> Option<f64> {
+        self.history.back().copied()
+    }
+
+    /// Clears the lookback history
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+
+    /// Returns the lookback length
+    pub fn lookback(&self) -> usize {
+        self.lookback
+    }
+
+    fn snapshot(&self, iv: f64) -> IvSnapshot {
+        if self.history.len() < self.lookback {
+            return IvSnapshot {
+                implied_volatility: iv,
+                iv_rank: None,
+                iv_percentile: None,
+            };
+        }
+
+        let (min, max) = self
+            .history
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let iv_rank = if max > min {
+            100.0 * (iv - min) / (max - min)
+        } else {
+            50.0
+        };
+        let below = self.history.iter().filter(|&&v| v < iv).count();
+        let iv_percentile = 100.0 * below as f64 / self.history.len() as f64;
+
+        IvSnapshot {
+            implied_volatility: iv,
+            iv_rank: Some(iv_rank),
+            iv_percentile: Some(iv_percentile),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(volatility: f64) -> OptionParams {
+        OptionParams {
+            spot_price: 100.0,
+            strike_price: 100.0,
+            time_to_expiry: 0.5,
+            risk_free_rate: 0.05,
+            volatility,
+            dividend_yield: 0.01,
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility_round_trip() {
+        for &vol in &[0.05, 0.2, 0.8, 2.0] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let price = BlackScholes::price(&params(vol), option_type)
+                    .unwrap()
+                    .price;
+                let iv = implied_volatility(price, &params(0.0), option_type).unwrap();
+                assert!((iv - vol).abs() < 1e-6, "vol {} solved as {}", vol, iv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility_rejects_arbitrage_prices() {
+        // A call cannot be worth more than the (dividend-discounted) spot
+        let err = implied_volatility(150.0, &params(0.0), OptionType::Call).unwrap_err();
+        assert!(matches!(
+            err,
+            PricingError::InvalidParameter {
+                field: "market_price",
+                ..
+            }
+        ));
+        assert!(implied_volatility(0.0, &params(0.0), OptionType::Put).is_err());
+
+        let mut expired = params(0.0);
+        expired.time_to_expiry = 0.0;
+        assert!(implied_volatility(5.0, &expired, OptionType::Call).is_err());
+    }
+
+    #[test]
+    fn test_iv_tracker_rank_and_percentile() {
+        let mut tracker = IvTracker::new(OptionType::Call, 100.0, 0.05, 0.01, 3).unwrap();
+        let quote = |vol: f64| {
+            BlackScholes::price(&params(vol), OptionType::Call)
+                .unwrap()
+                .price
+        };
+
+        let first = tracker.update(100.0, quote(0.20), 0.5).unwrap();
+        assert!((first.implied_volatility - 0.20).abs() < 1e-6);
+        assert_eq!(first.iv_rank, None);
+
+        tracker.update(100.0, quote(0.30), 0.5).unwrap();
+        let third = tracker.update(100.0, quote(0.25), 0.5).unwrap();
+        assert!((third.iv_rank.unwrap() - 50.0).abs() < 1e-4);
+        assert!((third.iv_percentile.unwrap() - 100.0 / 3.0).abs() < 1e-9);
+
+        // The 0.20 observation rolls out of the lookback
+        let fourth = tracker.update(100.0, quote(0.40), 0.5).unwrap();
+        assert!((fourth.iv_rank.unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_iv_tracker_skips_unsolvable_quotes() {
+        let mut tracker = IvTracker::new(OptionType::Put, 100.0, 0.05, 0.0, 5).unwrap();
+        assert!(tracker.update(100.0, 500.0, 0.5).is_err());
+        assert_eq!(tracker.current(), None);
+
+        assert!(IvTracker::new(OptionType::Put, 100.0, 0.05, 0.0, 0).is_err());
+    }
+}
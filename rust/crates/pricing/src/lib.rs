@@ -24,20 +24,23 @@
 //! ```
 
 use statrs::distribution::{ContinuousCDF, Normal};
-use thiserror::Error;
 
-/// Errors that can occur during option pricing calculations
-#[derive(Debug, Error, Clone, PartialEq)]
-pub enum PricingError {
-    #[error("Invalid parameter: {0}")]
-    InvalidParameter(String),
+pub mod credit;
+pub mod crypto;
+pub mod curve;
+mod error;
+pub mod futures;
+pub mod implied_vol;
+pub mod portfolio;
+pub mod scenario;
+pub mod settlement;
+pub mod uncertainty;
 
-    #[error("Calculation error: {0}")]
-    CalculationError(String),
-}
+pub use error::PricingError;
 
 /// Type of option
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptionType {
     /// Call option - right to buy
     Call,
@@ -69,24 +72,32 @@ impl OptionParams {
     /// or values that would make the calculation undefined).
     pub fn validate(&self) -> Result<(), PricingError> {
         if self.spot_price <= 0.0 {
-            return Err(PricingError::InvalidParameter(
-                "Spot price must be positive".to_string(),
-            ));
+            return Err(PricingError::InvalidParameter {
+                field: "spot_price",
+                value: self.spot_price,
+                constraint: "> 0",
+            });
         }
         if self.strike_price <= 0.0 {
-            return Err(PricingError::InvalidParameter(
-                "Strike price must be positive".to_string(),
-            ));
+            return Err(PricingError::InvalidParameter {
+                field: "strike_price",
+                value: self.strike_price,
+                constraint: "> 0",
+            });
         }
         if self.time_to_expiry < 0.0 {
-            return Err(PricingError::InvalidParameter(
-                "Time to expiry cannot be negative".to_string(),
-            ));
+            return Err(PricingError::InvalidParameter {
+                field: "time_to_expiry",
+                value: self.time_to_expiry,
+                constraint: ">= 0",
+            });
         }
         if self.volatility < 0.0 {
-            return Err(PricingError::InvalidParameter(
-                "Volatility cannot be negative".to_string(),
-            ));
+            return Err(PricingError::InvalidParameter {
+                field: "volatility",
+                value: self.volatility,
+                constraint: ">= 0",
+            });
         }
         Ok(())
     }
@@ -94,6 +105,7 @@ impl OptionParams {
 
 /// Result of option pricing calculation including Greeks
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PricingResult {
     /// Option price
     pub price: f64,
@@ -145,7 +157,10 @@ impl BlackScholes {
     /// assert!(result.price > 0.0);
     /// # Ok::<(), pricing::PricingError>(())
     /// ```
-    pub fn price(params: &OptionParams, option_type: OptionType) -> Result<PricingResult, PricingError> {
+    pub fn price(
+        params: &OptionParams,
+        option_type: OptionType,
+    ) -> Result<PricingResult, PricingError> {
         params.validate()?;
 
         // Handle edge case: at expiry
@@ -153,16 +168,17 @@ impl BlackScholes {
             return Self::price_at_expiry(params, option_type);
         }
 
-        let normal = Normal::new(0.0, 1.0)
-            .map_err(|e| PricingError::CalculationError(format!("Failed to create normal distribution: {}", e)))?;
+        let normal = Normal::new(0.0, 1.0).map_err(|e| PricingError::CalculationError {
+            operation: "normal distribution construction",
+            reason: e.to_string(),
+        })?;
 
         // Calculate d1 and d2
         let sqrt_t = params.time_to_expiry.sqrt();
-        let d1 = (
-            (params.spot_price / params.strike_price).ln()
-                + (params.risk_free_rate - params.dividend_yield + 0.5 * params.volatility.powi(2))
-                    * params.time_to_expiry
-        ) / (params.volatility * sqrt_t);
+        let d1 = ((params.spot_price / params.strike_price).ln()
+            + (params.risk_free_rate - params.dividend_yield + 0.5 * params.volatility.powi(2))
+                * params.time_to_expiry)
+            / (params.volatility * sqrt_t);
 
         let d2 = d1 - params.volatility * sqrt_t;
 
@@ -171,16 +187,24 @@ impl BlackScholes {
             OptionType::Call => {
                 let nd1 = normal.cdf(d1);
                 let nd2 = normal.cdf(d2);
-                let price = params.spot_price * (-params.dividend_yield * params.time_to_expiry).exp() * nd1
-                    - params.strike_price * (-params.risk_free_rate * params.time_to_expiry).exp() * nd2;
+                let price = params.spot_price
+                    * (-params.dividend_yield * params.time_to_expiry).exp()
+                    * nd1
+                    - params.strike_price
+                        * (-params.risk_free_rate * params.time_to_expiry).exp()
+                        * nd2;
                 let delta = (-params.dividend_yield * params.time_to_expiry).exp() * nd1;
                 (price, delta)
             }
             OptionType::Put => {
                 let n_neg_d1 = normal.cdf(-d1);
                 let n_neg_d2 = normal.cdf(-d2);
-                let price = params.strike_price * (-params.risk_free_rate * params.time_to_expiry).exp() * n_neg_d2
-                    - params.spot_price * (-params.dividend_yield * params.time_to_expiry).exp() * n_neg_d1;
+                let price = params.strike_price
+                    * (-params.risk_free_rate * params.time_to_expiry).exp()
+                    * n_neg_d2
+                    - params.spot_price
+                        * (-params.dividend_yield * params.time_to_expiry).exp()
+                        * n_neg_d1;
                 let delta = -(-params.dividend_yield * params.time_to_expiry).exp() * n_neg_d1;
                 (price, delta)
             }
@@ -203,7 +227,10 @@ impl BlackScholes {
     }
 
     /// Calculates option price at expiry (intrinsic value)
-    fn price_at_expiry(params: &OptionParams, option_type: OptionType) -> Result<PricingResult, PricingError> {
+    fn price_at_expiry(
+        params: &OptionParams,
+        option_type: OptionType,
+    ) -> Result<PricingResult, PricingError> {
         let intrinsic_value = match option_type {
             OptionType::Call => (params.spot_price - params.strike_price).max(0.0),
             OptionType::Put => (params.strike_price - params.spot_price).max(0.0),
@@ -244,25 +271,31 @@ impl BlackScholes {
         let pdf_d1 = (-0.5 * d1.powi(2)).exp() / (2.0 * std::f64::consts::PI).sqrt();
         let sqrt_t = params.time_to_expiry.sqrt();
 
-        let term1 = -params.spot_price * pdf_d1 * params.volatility
+        let term1 = -params.spot_price
+            * pdf_d1
+            * params.volatility
             * (-params.dividend_yield * params.time_to_expiry).exp()
             / (2.0 * sqrt_t);
 
         match option_type {
             OptionType::Call => {
-                let term2 = params.dividend_yield * params.spot_price
+                let term2 = params.dividend_yield
+                    * params.spot_price
                     * normal.cdf(d1)
                     * (-params.dividend_yield * params.time_to_expiry).exp();
-                let term3 = params.risk_free_rate * params.strike_price
+                let term3 = params.risk_free_rate
+                    * params.strike_price
                     * (-params.risk_free_rate * params.time_to_expiry).exp()
                     * normal.cdf(d2);
                 term1 + term2 - term3
             }
             OptionType::Put => {
-                let term2 = params.dividend_yield * params.spot_price
+                let term2 = params.dividend_yield
+                    * params.spot_price
                     * normal.cdf(-d1)
                     * (-params.dividend_yield * params.time_to_expiry).exp();
-                let term3 = params.risk_free_rate * params.strike_price
+                let term3 = params.risk_free_rate
+                    * params.strike_price
                     * (-params.risk_free_rate * params.time_to_expiry).exp()
                     * normal.cdf(-d2);
                 term1 - term2 + term3
@@ -274,8 +307,8 @@ impl BlackScholes {
         let pdf_d1 = (-0.5 * d1.powi(2)).exp() / (2.0 * std::f64::consts::PI).sqrt();
         let sqrt_t = params.time_to_expiry.sqrt();
 
-        params.spot_price * (-params.dividend_yield * params.time_to_expiry).exp()
-            * pdf_d1 * sqrt_t / 100.0  // Divide by 100 to express per 1% change
+        params.spot_price * (-params.dividend_yield * params.time_to_expiry).exp() * pdf_d1 * sqrt_t
+            / 100.0 // Divide by 100 to express per 1% change
     }
 
     fn calculate_rho(
@@ -286,14 +319,18 @@ impl BlackScholes {
     ) -> f64 {
         match option_type {
             OptionType::Call => {
-                params.strike_price * params.time_to_expiry
+                params.strike_price
+                    * params.time_to_expiry
                     * (-params.risk_free_rate * params.time_to_expiry).exp()
-                    * normal.cdf(d2) / 100.0  // Divide by 100 to express per 1% change
+                    * normal.cdf(d2)
+                    / 100.0 // Divide by 100 to express per 1% change
             }
             OptionType::Put => {
-                -params.strike_price * params.time_to_expiry
+                -params.strike_price
+                    * params.time_to_expiry
                     * (-params.risk_free_rate * params.time_to_expiry).exp()
-                    * normal.cdf(-d2) / 100.0
+                    * normal.cdf(-d2)
+                    / 100.0
             }
         }
     }
@@ -348,7 +385,7 @@ mod tests {
     #[test]
     fn test_invalid_parameters() {
         let params = OptionParams {
-            spot_price: -100.0,  // Invalid: negative spot price
+            spot_price: -100.0, // Invalid: negative spot price
             strike_price: 100.0,
             time_to_expiry: 1.0,
             risk_free_rate: 0.05,
@@ -360,6 +397,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_invalid_parameter_context() {
+        let params = OptionParams {
+            spot_price: 100.0,
+            strike_price: 100.0,
+            time_to_expiry: 1.0,
+            risk_free_rate: 0.05,
+            volatility: -0.2,
+            dividend_yield: 0.0,
+        };
+
+        let err = params.validate().unwrap_err();
+        assert_eq!(
+            err,
+            PricingError::InvalidParameter {
+                field: "volatility",
+                value: -0.2,
+                constraint: ">= 0",
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid parameter `volatility` = -0.2: must be >= 0"
+        );
+    }
+
     #[test]
     fn test_option_at_expiry() {
         let params = OptionParams {
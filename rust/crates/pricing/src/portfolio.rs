@@ -0,0 +1,302 @@
+//! Portfolio-level Greeks aggregation and exposure netting
+//!
+//! Per-position Greeks from [`BlackScholes`](crate::BlackScholes) are scaled by
+//! position size and summed, either per underlying or across the whole book.
+//! Books spanning several underlyings are monitored in index terms by
+//! beta-weighting each position's delta and gamma to a reference index.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use pricing::portfolio::{beta_weighted_exposure, Position};
+//! use pricing::{BlackScholes, OptionParams, OptionType};
+//!
+//! let params = OptionParams {
+//!     spot_price: 200.0,
+//!     strike_price: 210.0,
+//!     time_to_expiry: 0.25,
+//!     risk_free_rate: 0.04,
+//!     volatility: 0.3,
+//!     dividend_yield: 0.0,
+//! };
+//! let call = BlackScholes::price(&params, OptionType::Call)?;
+//!
+//! let positions = vec![
+//!     Position::new("AAPL", 10.0, 100.0, 200.0, call),
+//!     Position::stock("MSFT", -50.0, 400.0),
+//! ];
+//! let betas = HashMap::from([("AAPL".to_string(), 1.2), ("MSFT".to_string(), 0.9)]);
+//!
+//! let exposure = beta_weighted_exposure(&positions, &betas, 500.0)?;
+//! println!("SPX-equivalent delta: {:.1} units", exposure.delta);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{PricingError, PricingResult};
+
+/// A position in an option (or the underlying itself) with its per-unit Greeks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    /// Identifier of the underlying asset (e.g. a ticker symbol)
+    pub underlying: String,
+    /// Number of contracts held; negative for short positions
+    pub quantity: f64,
+    /// Units of the underlying per contract (e.g. 100 for US equity options)
+    pub multiplier: f64,
+    /// Current price of the underlying asset
+    pub spot_price: f64,
+    /// Per-unit price and Greeks of the instrument
+    pub greeks: PricingResult,
+}
+
+impl Position {
+    /// Creates a position from per-unit Greeks
+    pub fn new(
+        underlying: &str,
+        quantity: f64,
+        multiplier: f64,
+        spot_price: f64,
+        greeks: PricingResult,
+    ) -> Self {
+        Self {
+            underlying: underlying.to_string(),
+            quantity,
+            multiplier,
+            spot_price,
+            greeks,
+        }
+    }
+
+    /// Creates a position in the underlying itself (delta 1, all other Greeks 0)
+    pub fn stock(underlying: &str, quantity: f64, spot_price: f64) -> Self {
+        let greeks = PricingResult {
+            price: spot_price,
+            delta: 1.0,
+            gamma: 0.0,
+            theta: 0.0,
+            vega: 0.0,
+            rho: 0.0,
+        };
+        Self::new(underlying, quantity, 1.0, spot_price, greeks)
+    }
+
+    /// Number of underlying units the Greeks are scaled by
+    fn units(&self) -> f64 {
+        self.quantity * self.multiplier
+    }
+}
+
+/// Position-weighted Greeks summed over a set of positions
+///
+/// Delta and gamma are expressed in units of the underlying (share-equivalent),
+/// the remaining Greeks in currency.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortfolioGreeks {
+    /// Market value of the positions
+    pub value: f64,
+    /// Share-equivalent delta
+    pub delta: f64,
+    /// Share-equivalent gamma
+    pub gamma: f64,
+    /// Theta in currency per year
+    pub theta: f64,
+    /// Vega in currency per 1% volatility change
+    pub vega: f64,
+    /// Rho in currency per 1% rate change
+    pub rho: f64,
+}
+
+impl PortfolioGreeks {
+    fn add(&mut self, position: &Position) {
+        let units = position.units();
+        let greeks = &position.greeks;
+        self.value += units * greeks.price;
+        self.delta += units * greeks.delta;
+        self.gamma += units * greeks.gamma;
+        self.theta += units * greeks.theta;
+        self.vega += units * greeks.vega;
+        self.rho += units * greeks.rho;
+    }
+}
+
+/// Index-equivalent exposure of a multi-underlying book
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BetaWeightedExposure {
+    /// Delta in units of the reference index
+    pub delta: f64,
+    /// Gamma in units of the reference index per index point
+    pub gamma: f64,
+    /// Currency P&L per 1% move in the reference index (delta only)
+    pub dollar_delta_per_percent: f64,
+}
+
+/// Sums position-weighted Greeks across all positions
+pub fn aggregate(positions: &[Position]) -> PortfolioGreeks {
+    positions
+        .iter()
+        .fold(PortfolioGreeks::default(), |mut total, position| {
+            total.add(position);
+            total
+        })
+}
+
+/// Nets position-weighted Greeks per underlying
+///
+/// Long and short positions on the same underlying offset each other, which
+/// is the exposure a hedger actually has to manage per name.
+pub fn net_by_underlying(positions: &[Position]) -> BTreeMap<String, PortfolioGreeks> {
+    let mut netted: BTreeMap<String, PortfolioGreeks> = BTreeMap::new();
+    for position in positions {
+        netted
+            .entry(position.underlying.clone())
+            .or_default()
+            .add(position);
+    }
+    netted
+}
+
+/// Beta-weights delta and gamma to a reference index
+///
+/// A 1-point move in the index is assumed to move underlying `i` by
+/// `beta_i × S_i / S_index` points, so:
+///
+/// * index delta = Σ Δᵢ × unitsᵢ × βᵢ × Sᵢ / S_index
+/// * index gamma = Σ Γᵢ × unitsᵢ × (βᵢ × Sᵢ / S_index)²
+///
+/// # Arguments
+///
+/// * `positions` - Positions with per-unit Greeks
+/// * `betas` - Beta of each underlying to the reference index
+/// * `index_price` - Current level of the reference index (must be > 0)
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for a non-positive index price and
+/// `MissingInput` if an underlying has no beta.
+pub fn beta_weighted_exposure(
+    positions: &[Position],
+    betas: &HashMap<String, f64>,
+    index_price: f64,
+) -> Result<BetaWeightedExposure, PricingError> {
+    if index_price.is_nan() || index_price <= 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "index_price",
+            value: index_price,
+            constraint: "> 0",
+        });
+    }
+
+    let mut exposure = BetaWeightedExposure::default();
+    for position in positions {
+        let beta = betas
+            .get(&position.underlying)
+            .ok_or_else(|| PricingError::MissingInput {
+                field: "beta",
+                key: position.underlying.clone(),
+            })?;
+        let ratio = beta * position.spot_price / index_price;
+        let units = position.units();
+
+        exposure.delta += position.greeks.delta * units * ratio;
+        exposure.gamma += position.greeks.gamma * units * ratio * ratio;
+    }
+    exposure.dollar_delta_per_percent = exposure.delta * index_price / 100.0;
+
+    Ok(exposure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option_greeks(delta: f64, gamma: f64) -> PricingResult {
+        PricingResult {
+            price: 5.0,
+            delta,
+            gamma,
+            theta: -2.0,
+            vega: 0.3,
+            rho: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_scales_by_position_size() {
+        let positions = vec![
+            Position::new("AAPL", 2.0, 100.0, 150.0, option_greeks(0.5, 0.02)),
+            Position::new("AAPL", -1.0, 100.0, 150.0, option_greeks(0.3, 0.01)),
+        ];
+        let total = aggregate(&positions);
+
+        assert!((total.delta - 70.0).abs() < 1e-10);
+        assert!((total.gamma - 3.0).abs() < 1e-10);
+        assert!((total.value - 500.0).abs() < 1e-10);
+        assert!((total.theta + 200.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_net_by_underlying() {
+        let positions = vec![
+            Position::stock("AAPL", 100.0, 150.0),
+            Position::new("AAPL", -2.0, 100.0, 150.0, option_greeks(0.5, 0.02)),
+            Position::stock("MSFT", 10.0, 400.0),
+        ];
+        let netted = net_by_underlying(&positions);
+
+        assert_eq!(netted.len(), 2);
+        assert!(netted["AAPL"].delta.abs() < 1e-10);
+        assert!((netted["MSFT"].delta - 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_beta_weighted_stock_position() {
+        // 100 shares of a $200 stock with beta 1.5 vs a $400 index
+        // behave like 100 × 1.5 × 200 / 400 = 75 index units
+        let positions = vec![Position::stock("XYZ", 100.0, 200.0)];
+        let betas = HashMap::from([("XYZ".to_string(), 1.5)]);
+        let exposure = beta_weighted_exposure(&positions, &betas, 400.0).unwrap();
+
+        assert!((exposure.delta - 75.0).abs() < 1e-10);
+        assert_eq!(exposure.gamma, 0.0);
+        assert!((exposure.dollar_delta_per_percent - 300.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_beta_weighted_gamma_uses_squared_ratio() {
+        let positions = vec![Position::new(
+            "XYZ",
+            1.0,
+            100.0,
+            50.0,
+            option_greeks(0.5, 0.04),
+        )];
+        let betas = HashMap::from([("XYZ".to_string(), 2.0)]);
+        let exposure = beta_weighted_exposure(&positions, &betas, 100.0).unwrap();
+
+        // ratio = 2 × 50 / 100 = 1
+        assert!((exposure.delta - 50.0).abs() < 1e-10);
+        assert!((exposure.gamma - 4.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_beta_weighted_errors() {
+        let positions = vec![Position::stock("XYZ", 100.0, 200.0)];
+
+        let err = beta_weighted_exposure(&positions, &HashMap::new(), 400.0).unwrap_err();
+        assert_eq!(
+            err,
+            PricingError::MissingInput {
+                field: "beta",
+                key: "XYZ".to_string()
+            }
+        );
+        assert_eq!(err.to_string(), "Missing beta for `XYZ`");
+
+        let betas = HashMap::from([("XYZ".to_string(), 1.0)]);
+        assert!(beta_weighted_exposure(&positions, &betas, 0.0).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Scenario definitions and a batch scenario runner
+//!
+//! A [`Scenario`] bundles a book of holdings, the market data for each
+//! underlying and a set of named [`Shock`]s. [`run`] prices the book under
+//! the base market and under every shock and returns a [`ScenarioReport`]
+//! with position-weighted Greeks and P&L per shock.
+//!
+//! With the `serde` feature enabled, scenarios and reports implement
+//! `Serialize`/`Deserialize`, so they can be stored as JSON or YAML with
+//! the caller's choice of format crate:
+//!
+//! ```json
+//! {
+//!   "name": "desk-eod",
+//!   "holdings": [
+//!     { "underlying": "AAPL", "quantity": 10, "multiplier": 100,
+//!       "kind": { "Option": { "option_type": "Call", "strike_price": 200, "time_to_expiry": 0.25 } } }
+//!   ],
+//!   "market": { "AAPL": { "spot_price": 195, "volatility": 0.28, "risk_free_rate": 0.04 } },
+//!   "shocks": [ { "name": "crash", "spot_change": -0.2, "volatility_shift": 0.15 } ]
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use pricing::scenario::{run, Holding, HoldingKind, MarketData, Scenario, Shock};
+//! use pricing::OptionType;
+//!
+//! let scenario = Scenario {
+//!     name: "spx-puts".to_string(),
+//!     holdings: vec![Holding {
+//!         underlying: "SPX".to_string(),
+//!         quantity: 5.0,
+//!         multiplier: 100.0,
+//!         kind: HoldingKind::Option {
+//!             option_type: OptionType::Put,
+//!             strike_price: 4_800.0,
+//!             time_to_expiry: 0.5,
+//!         },
+//!     }],
+//!     market: BTreeMap::from([("SPX".to_string(), MarketData::new(5_000.0, 0.18, 0.04))]),
+//!     shocks: vec![Shock {
+//!         spot_change: -0.1,
+//!         volatility_shift: 0.1,
+//!         ..Shock::new("crash")
+//!     }],
+//! };
+//!
+//! let report = run(&scenario)?;
+//! assert!(report.shocks[0].pnl > 0.0);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::portfolio::{aggregate, PortfolioGreeks, Position};
+use crate::{BlackScholes, OptionParams, OptionType, PricingError};
+
+/// What a holding is
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HoldingKind {
+    /// The underlying itself
+    Stock,
+    /// A European option on the underlying
+    Option {
+        /// Call or put
+        option_type: OptionType,
+        /// Strike price
+        strike_price: f64,
+        /// Time to expiry in years
+        time_to_expiry: f64,
+    },
+}
+
+/// A position in a scenario book
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Holding {
+    /// Identifier of the underlying, used to look up its market data
+    pub underlying: String,
+    /// Number of contracts held; negative for short positions
+    pub quantity: f64,
+    /// Units of the underlying per contract
+    #[cfg_attr(feature = "serde", serde(default = "default_multiplier"))]
+    pub multiplier: f64,
+    /// Instrument held
+    pub kind: HoldingKind,
+}
+
+/// Market data for one underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketData {
+    /// Current price of the underlying
+    pub spot_price: f64,
+    /// Annualized volatility used for all options on the underlying
+    pub volatility: f64,
+    /// Annualized risk-free rate
+    pub risk_free_rate: f64,
+    /// Annualized dividend yield
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dividend_yield: f64,
+}
+
+impl MarketData {
+    /// Creates market data with no dividend yield
+    pub fn new(spot_price: f64, volatility: f64, risk_free_rate: f64) -> Self {
+        Self {
+            spot_price,
+            volatility,
+            risk_free_rate,
+            dividend_yield: 0.0,
+        }
+    }
+}
+
+/// A named market move applied to every underlying (or a subset)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Shock {
+    /// Name reported with the result
+    pub name: String,
+    /// Relative spot move (e.g. `-0.1` for −10%)
+    pub spot_change: f64,
+    /// Absolute volatility shift (e.g. `0.05` for +5 vol points)
+    pub volatility_shift: f64,
+    /// Absolute risk-free rate shift
+    pub rate_shift: f64,
+    /// Time elapsed in years, reducing every option's time to expiry
+    pub time_elapsed: f64,
+    /// Underlyings the shock applies to; empty applies it to all
+    pub underlyings: Vec<String>,
+}
+
+impl Shock {
+    /// Creates a shock that leaves the market unchanged
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn applies_to(&self, underlying: &str) -> bool {
+        self.underlyings.is_empty() || self.underlyings.iter().any(|u| u == underlying)
+    }
+}
+
+/// A book, its market data and the shocks to run it through
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario {
+    /// Scenario name
+    pub name: String,
+    /// Positions in the book
+    pub holdings: Vec<Holding>,
+    /// Market data keyed by underlying
+    pub market: BTreeMap<String, MarketData>,
+    /// Shocks to evaluate
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shocks: Vec<Shock>,
+}
+
+/// Result of one shock
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShockResult {
+    /// Shock name
+    pub name: String,
+    /// Position-weighted Greeks under the shocked market
+    pub greeks: PortfolioGreeks,
+    /// Change in book value from the base market
+    pub pnl: f64,
+}
+
+/// Results of running a scenario
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioReport {
+    /// Scenario name
+    pub name: String,
+    /// Position-weighted Greeks under the base market
+    pub base: PortfolioGreeks,
+    /// One result per shock, in scenario order
+    pub shocks: Vec<ShockResult>,
+}
+
+/// Prices a scenario's book under the base market and every shock
+///
+/// Options whose expiry falls inside a shock's `time_elapsed` are valued at
+/// intrinsic value.
+///
+/// # Errors
+///
+/// Returns `MissingInput` if a holding's underlying has no market data, and
+/// `InvalidParameter` if base or shocked inputs fail
+/// [`OptionParams::validate`] (e.g. a shock pushing volatility below zero).
+pub fn run(scenario: &Scenario) -> Result<ScenarioReport, PricingError> {
+    let base = value_book(scenario, &Shock::default())?;
+    let shocks = scenario
+        .shocks
+        .iter()
+        .map(|shock| {
+            let greeks = value_book(scenario, shock)?;
+            Ok(ShockResult {
+                name: shock.name.clone(),
+                greeks,
+                pnl: greeks.value - base.value,
+            })
+        })
+        .collect::<Result<Vec<_>, PricingError>>()?;
+
+    Ok(ScenarioReport {
+        name: scenario.name.clone(),
+        base,
+        shocks,
+    })
+}
+
+fn value_book(scenario: &Scenario, shock: &Shock) -> Result<PortfolioGreeks, PricingError> {
+    let positions = scenario
+        .holdings
+        .iter()
+        .map(|holding| {
+            let mut market = *scenario.market.get(&holding.underlying).ok_or_else(|| {
+                PricingError::MissingInput {
+                    field: "market data",
+                    key: holding.underlying.clone(),
+                }
+            })?;
+            let mut elapsed = 0.0;
+            if shock.applies_to(&holding.underlying) {
+                market.spot_price *= 1.0 + shock.spot_change;
+                market.volatility += shock.volatility_shift;
+                market.risk_free_rate += shock.rate_shift;
+                elapsed = shock.time_elapsed;
+            }
+            position(holding, &market, elapsed)
+        })
+        .collect::<Result<Vec<_>, PricingError>>()?;
+    Ok(aggregate(&positions))
+}
+
+fn position(
+    holding: &Holding,
+    market: &MarketData,
+    elapsed: f64,
+) -> Result<Position, PricingError> {
+    match holding.kind {
+        HoldingKind::Stock => Ok(Position::stock(
+            &holding.underlying,
+            holding.quantity * holding.multiplier,
+            market.spot_price,
+        )),
+        HoldingKind::Option {
+            option_type,
+            strike_price,
+            time_to_expiry,
+        } => {
+            let params = OptionParams {
+                spot_price: market.spot_price,
+                strike_price,
+                time_to_expiry: (time_to_expiry - elapsed).max(0.0),
+                risk_free_rate: market.risk_free_rate,
+                volatility: market.volatility,
+                dividend_yield: market.dividend_yield,
+            };
+            Ok(Position::new(
+                &holding.underlying,
+                holding.quantity,
+                holding.multiplier,
+                market.spot_price,
+                BlackScholes::price(&params, option_type)?,
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(shocks: Vec<Shock>) -> Scenario {
+        Scenario {
+            name: "test".to_string(),
+            holdings: vec![
+                Holding {
+                    underlying: "AAA".to_string(),
+                    quantity: 2.0,
+                    multiplier: 100.0,
+                    kind: HoldingKind::Option {
+                        option_type: OptionType::Call,
+                        strike_price: 100.0,
+                        time_to_expiry: 0.5,
+                    },
+                },
+                Holding {
+                    underlying: "BBB".to_string(),
+                    quantity: -50.0,
+                    multiplier: 1.0,
+                    kind: HoldingKind::Stock,
+                },
+            ],
+            market: BTreeMap::from([
+                ("AAA".to_string(), MarketData::new(100.0, 0.25, 0.03)),
+                ("BBB".to_string(), MarketData::new(40.0, 0.3, 0.03)),
+            ]),
+            shocks,
+        }
+    }
+
+    #[test]
+    fn test_base_valuation() {
+        let report = run(&scenario(vec![])).unwrap();
+        let call = BlackScholes::price(
+            &OptionParams {
+                spot_price: 100.0,
+                strike_price: 100.0,
+                time_to_expiry: 0.5,
+                risk_free_rate: 0.03,
+                volatility: 0.25,
+                dividend_yield: 0.0,
+            },
+            OptionType::Call,
+        )
+        .unwrap();
+
+        assert!((report.base.value - (200.0 * call.price - 2_000.0)).abs() < 1e-9);
+        assert!((report.base.delta - (200.0 * call.delta - 50.0)).abs() < 1e-9);
+        assert!(report.shocks.is_empty());
+    }
+
+    #[test]
+    fn test_shocks_report_pnl() {
+        let report = run(&scenario(vec![
+            Shock::new("unchanged"),
+            Shock {
+                spot_change: -0.1,
+                underlyings: vec!["BBB".to_string()],
+                ..Shock::new("bbb down")
+            },
+            Shock {
+                volatility_shift: 0.05,
+                ..Shock::new("vol up")
+            },
+        ]))
+        .unwrap();
+
+        assert_eq!(report.shocks[0].pnl, 0.0);
+        // Short 50 BBB gains 50 × 4 when BBB falls 10%
+        assert!((report.shocks[1].pnl - 200.0).abs() < 1e-9);
+        assert!(report.shocks[2].pnl > 0.0);
+        assert_eq!(report.shocks[2].name, "vol up");
+    }
+
+    #[test]
+    fn test_time_elapsed_past_expiry_uses_intrinsic() {
+        let report = run(&scenario(vec![Shock {
+            spot_change: 0.2,
+            time_elapsed: 1.0,
+            underlyings: vec!["AAA".to_string()],
+            ..Shock::new("expired")
+        }]))
+        .unwrap();
+
+        // Call is worth 20 intrinsic on 200 units
+        assert!((report.shocks[0].greeks.value - (4_000.0 - 2_000.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_market_data() {
+        let mut incomplete = scenario(vec![]);
+        incomplete.market.remove("BBB");
+        assert_eq!(
+            run(&incomplete).unwrap_err(),
+            PricingError::MissingInput {
+                field: "market data",
+                key: "BBB".to_string(),
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_scenario_from_json() {
+        let json = r#"{
+            "name": "desk",
+            "holdings": [
+                { "underlying": "AAA", "quantity": 1,
+                  "kind": { "Option": { "option_type": "Put", "strike_price": 90, "time_to_expiry": 0.25 } } }
+            ],
+            "market": { "AAA": { "spot_price": 100, "volatility": 0.3, "risk_free_rate": 0.02 } },
+            "shocks": [ { "name": "crash", "spot_change": -0.2 } ]
+        }"#;
+        let scenario: Scenario = serde_json::from_str(json).unwrap();
+        assert_eq!(scenario.holdings[0].multiplier, 1.0);
+
+        let report = run(&scenario).unwrap();
+        assert!(report.shocks[0].pnl > 0.0);
+        assert!(serde_json::to_string(&report)
+            .unwrap()
+            .contains("\"crash\""));
+    }
+}
@@ -0,0 +1,283 @@
+//! Exercise, assignment and expiry settlement
+//!
+//! Given option positions on one underlying and its settlement price at
+//! expiry, these utilities decide which legs are exercised, assigned or
+//! expire worthless, and compute the resulting stock position and cash flow.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::settlement::{settle, ExpiringOption, ExpiryAction, ExpiryRules};
+//! use pricing::OptionType;
+//!
+//! // Bull call spread: long 100 call, short 110 call
+//! let legs = vec![
+//!     ExpiringOption { option_type: OptionType::Call, strike: 100.0, quantity: 1.0, multiplier: 100.0 },
+//!     ExpiringOption { option_type: OptionType::Call, strike: 110.0, quantity: -1.0, multiplier: 100.0 },
+//! ];
+//!
+//! let outcome = settle(&legs, 115.0, &ExpiryRules::default())?;
+//!
+//! assert_eq!(outcome.legs[0].action, ExpiryAction::Exercised);
+//! assert_eq!(outcome.legs[1].action, ExpiryAction::Assigned);
+//! // Shares bought at 100 are delivered at 110: flat stock, $1,000 cash
+//! assert_eq!(outcome.stock_quantity, 0.0);
+//! assert_eq!(outcome.cash, 1_000.0);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use crate::{OptionType, PricingError};
+
+/// Tolerance for comparing intrinsic value against the exercise threshold,
+/// so that e.g. 50.01 − 50.00 counts as one cent in the money
+const PRICE_TOLERANCE: f64 = 1e-9;
+
+/// An option position held into expiry
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringOption {
+    /// Call or put
+    pub option_type: OptionType,
+    /// Strike price
+    pub strike: f64,
+    /// Number of contracts; negative for short positions
+    pub quantity: f64,
+    /// Units of the underlying per contract
+    pub multiplier: f64,
+}
+
+/// How exercised options are settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementStyle {
+    /// Exercise delivers the underlying at the strike price
+    Physical,
+    /// Exercise pays the intrinsic value in cash
+    Cash,
+}
+
+/// Rules applied at expiry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpiryRules {
+    /// Minimum in-the-money amount for automatic exercise (OCC uses $0.01)
+    pub auto_exercise_threshold: f64,
+    /// Physical delivery or cash settlement
+    pub style: SettlementStyle,
+}
+
+impl Default for ExpiryRules {
+    fn default() -> Self {
+        Self {
+            auto_exercise_threshold: 0.01,
+            style: SettlementStyle::Physical,
+        }
+    }
+}
+
+/// What happened to a leg at expiry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpiryAction {
+    /// Long position exercised
+    Exercised,
+    /// Short position assigned
+    Assigned,
+    /// Position expired worthless
+    Expired,
+}
+
+/// Settlement of a single leg
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegSettlement {
+    /// Exercise, assignment or expiry
+    pub action: ExpiryAction,
+    /// Change in the stock position (units of the underlying)
+    pub stock_quantity: f64,
+    /// Cash received (positive) or paid (negative)
+    pub cash: f64,
+}
+
+/// Combined settlement of all legs
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementOutcome {
+    /// Per-leg results in input order
+    pub legs: Vec<LegSettlement>,
+    /// Net change in the stock position
+    pub stock_quantity: f64,
+    /// Net cash flow
+    pub cash: f64,
+}
+
+/// Settles option positions on one underlying at expiry
+///
+/// A leg is exercised (long) or assigned (short) when its intrinsic value
+/// reaches `rules.auto_exercise_threshold`. Short legs are assumed to be
+/// assigned whenever the holder would be auto-exercised.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` for a non-positive settlement price or strike,
+/// or a negative exercise threshold.
+pub fn settle(
+    options: &[ExpiringOption],
+    settlement_price: f64,
+    rules: &ExpiryRules,
+) -> Result<SettlementOutcome, PricingError> {
+    if settlement_price.is_nan() || settlement_price <= 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "settlement_price",
+            value: settlement_price,
+            constraint: "> 0",
+        });
+    }
+    if rules.auto_exercise_threshold.is_nan() || rules.auto_exercise_threshold < 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "auto_exercise_threshold",
+            value: rules.auto_exercise_threshold,
+            constraint: ">= 0",
+        });
+    }
+
+    let legs = options
+        .iter()
+        .map(|option| settle_leg(option, settlement_price, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SettlementOutcome {
+        stock_quantity: legs.iter().map(|leg| leg.stock_quantity).sum(),
+        cash: legs.iter().map(|leg| leg.cash).sum(),
+        legs,
+    })
+}
+
+fn settle_leg(
+    option: &ExpiringOption,
+    settlement_price: f64,
+    rules: &ExpiryRules,
+) -> Result<LegSettlement, PricingError> {
+    if option.strike.is_nan() || option.strike <= 0.0 {
+        return Err(PricingError::InvalidParameter {
+            field: "strike",
+            value: option.strike,
+            constraint: "> 0",
+        });
+    }
+
+    let intrinsic = match option.option_type {
+        OptionType::Call => settlement_price - option.strike,
+        OptionType::Put => option.strike - settlement_price,
+    };
+    if intrinsic < rules.auto_exercise_threshold - PRICE_TOLERANCE || option.quantity == 0.0 {
+        return Ok(LegSettlement {
+            action: ExpiryAction::Expired,
+            stock_quantity: 0.0,
+            cash: 0.0,
+        });
+    }
+
+    let action = if option.quantity > 0.0 {
+        ExpiryAction::Exercised
+    } else {
+        ExpiryAction::Assigned
+    };
+    let units = option.quantity * option.multiplier;
+
+    let (stock_quantity, cash) = match (rules.style, option.option_type) {
+        // Calls buy the underlying at the strike, puts sell it; short legs
+        // take the other side through the sign of `units`
+        (SettlementStyle::Physical, OptionType::Call) => (units, -units * option.strike),
+        (SettlementStyle::Physical, OptionType::Put) => (-units, units * option.strike),
+        (SettlementStyle::Cash, _) => (0.0, units * intrinsic),
+    };
+
+    Ok(LegSettlement {
+        action,
+        stock_quantity,
+        cash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(option_type: OptionType, strike: f64, quantity: f64) -> ExpiringOption {
+        ExpiringOption {
+            option_type,
+            strike,
+            quantity,
+            multiplier: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_long_call_exercised() {
+        let outcome = settle(
+            &[leg(OptionType::Call, 50.0, 2.0)],
+            55.0,
+            &ExpiryRules::default(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.legs[0].action, ExpiryAction::Exercised);
+        assert_eq!(outcome.stock_quantity, 200.0);
+        assert_eq!(outcome.cash, -10_000.0);
+    }
+
+    #[test]
+    fn test_short_put_assigned() {
+        let outcome = settle(
+            &[leg(OptionType::Put, 50.0, -1.0)],
+            45.0,
+            &ExpiryRules::default(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.legs[0].action, ExpiryAction::Assigned);
+        assert_eq!(outcome.stock_quantity, 100.0);
+        assert_eq!(outcome.cash, -5_000.0);
+    }
+
+    #[test]
+    fn test_auto_exercise_threshold() {
+        let options = [leg(OptionType::Call, 50.0, 1.0)];
+        let rules = ExpiryRules::default();
+
+        let barely_itm = settle(&options, 50.005, &rules).unwrap();
+        assert_eq!(barely_itm.legs[0].action, ExpiryAction::Expired);
+        assert_eq!(barely_itm.cash, 0.0);
+
+        let itm = settle(&options, 50.01, &rules).unwrap();
+        assert_eq!(itm.legs[0].action, ExpiryAction::Exercised);
+
+        let otm = settle(&[leg(OptionType::Put, 50.0, -3.0)], 60.0, &rules).unwrap();
+        assert_eq!(otm.legs[0].action, ExpiryAction::Expired);
+    }
+
+    #[test]
+    fn test_cash_settlement() {
+        let rules = ExpiryRules {
+            style: SettlementStyle::Cash,
+            ..ExpiryRules::default()
+        };
+        let options = [
+            leg(OptionType::Put, 4000.0, 1.0),
+            leg(OptionType::Put, 3900.0, -1.0),
+        ];
+        let outcome = settle(&options, 3850.0, &rules).unwrap();
+
+        assert_eq!(outcome.stock_quantity, 0.0);
+        // 150 × 100 received on the long put, 50 × 100 paid on the short put
+        assert!((outcome.cash - 10_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settle_invalid_inputs() {
+        let rules = ExpiryRules::default();
+        assert!(settle(&[leg(OptionType::Call, 50.0, 1.0)], 0.0, &rules).is_err());
+        assert!(settle(&[leg(OptionType::Call, -5.0, 1.0)], 50.0, &rules).is_err());
+
+        let negative_threshold = ExpiryRules {
+            auto_exercise_threshold: -1.0,
+            ..rules
+        };
+        assert!(settle(&[], 50.0, &negative_threshold).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+//! Propagating input uncertainty through option pricing
+//!
+//! Market inputs are rarely known exactly: the implied volatility of a
+//! quote lies somewhere between its bid and ask IV, and spot moves within
+//! the bid/ask spread. [`UncertainParams`] carries each input as an
+//! [`Interval`], and [`price_range`] reports the resulting range of the
+//! price and every Greek, so quote noise shows up explicitly in risk
+//! numbers instead of being hidden behind a single mid value.
+//!
+//! # Example
+//!
+//! ```
+//! use pricing::uncertainty::{price_range, Interval, UncertainParams};
+//! use pricing::OptionType;
+//!
+//! let params = UncertainParams {
+//!     spot_price: Interval::new(99.95, 100.05)?,
+//!     strike_price: 105.0,
+//!     time_to_expiry: 0.5,
+//!     risk_free_rate: Interval::point(0.04),
+//!     // Bid/ask implied volatilities
+//!     volatility: Interval::new(0.21, 0.23)?,
+//!     dividend_yield: Interval::point(0.0),
+//! };
+//!
+//! let range = price_range(&params, OptionType::Call)?;
+//! assert!(range.price.contains(range.mid.price));
+//! println!("Price {:.3} - {:.3}", range.price.low, range.price.high);
+//! # Ok::<(), pricing::PricingError>(())
+//! ```
+
+use crate::{BlackScholes, OptionParams, OptionType, PricingError, PricingResult};
+
+/// Closed interval `[low, high]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    /// Lower bound
+    pub low: f64,
+    /// Upper bound
+    pub high: f64,
+}
+
+impl Interval {
+    /// Creates an interval
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if a bound is NaN or `low > high`.
+    pub fn new(low: f64, high: f64) -> Result<Self, PricingError> {
+        if low.is_nan() || high.is_nan() || low > high {
+            return Err(PricingError::InvalidParameter {
+                field: "low",
+                value: low,
+                constraint: "<= high",
+            });
+        }
+        Ok(Self { low, high })
+    }
+
+    /// Degenerate interval containing a single value
+    pub fn point(value: f64) -> Self {
+        Self {
+            low: value,
+            high: value,
+        }
+    }
+
+    /// Interval centered on `mid` with the given half-width
+    pub fn around(mid: f64, half_width: f64) -> Result<Self, PricingError> {
+        Self::new(mid - half_width, mid + half_width)
+    }
+
+    /// Midpoint of the interval
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.low + self.high)
+    }
+
+    /// Width of the interval
+    pub fn width(&self) -> f64 {
+        self.high - self.low
+    }
+
+    /// Whether `value` lies within the interval
+    pub fn contains(&self, value: f64) -> bool {
+        (self.low..=self.high).contains(&value)
+    }
+
+    /// Low, mid and high values, or just the value for a point interval
+    fn samples(&self) -> Vec<f64> {
+        if self.width() == 0.0 {
+            vec![self.low]
+        } else {
+            vec![self.low, self.mid(), self.high]
+        }
+    }
+
+    fn widen(&mut self, value: f64) {
+        self.low = self.low.min(value);
+        self.high = self.high.max(value);
+    }
+}
+
+/// Option parameters whose market inputs carry uncertainty
+///
+/// Contract terms (strike and expiry) are exact; the market inputs are
+/// intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncertainParams {
+    /// Current price of the underlying asset
+    pub spot_price: Interval,
+    /// Strike price of the option
+    pub strike_price: f64,
+    /// Time to expiry in years
+    pub time_to_expiry: f64,
+    /// Risk-free interest rate (annualized)
+    pub risk_free_rate: Interval,
+    /// Volatility of the underlying asset (annualized)
+    pub volatility: Interval,
+    /// Dividend yield (annualized)
+    pub dividend_yield: Interval,
+}
+
+impl UncertainParams {
+    /// Parameters at the midpoint of every interval
+    pub fn mid(&self) -> OptionParams {
+        OptionParams {
+            spot_price: self.spot_price.mid(),
+            strike_price: self.strike_price,
+            time_to_expiry: self.time_to_expiry,
+            risk_free_rate: self.risk_free_rate.mid(),
+            volatility: self.volatility.mid(),
+            dividend_yield: self.dividend_yield.mid(),
+        }
+    }
+}
+
+/// Range of the price and Greeks over the uncertain inputs
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingRange {
+    /// Result at the midpoint inputs
+    pub mid: PricingResult,
+    /// Option price range
+    pub price: Interval,
+    /// Delta range
+    pub delta: Interval,
+    /// Gamma range
+    pub gamma: Interval,
+    /// Theta range
+    pub theta: Interval,
+    /// Vega range
+    pub vega: Interval,
+    /// Rho range
+    pub rho: Interval,
+}
+
+/// Prices an option over the box of uncertain inputs
+///
+/// Bump-based propagation: Black-Scholes is evaluated at the low, mid and
+/// high value of every uncertain input (up to 81 evaluations) and each
+/// output's range is the envelope of the results. The price is monotone in
+/// each input, so its range is exact; Greeks that peak inside the box (gamma
+/// and vega near the money) are bounded at the grid resolution.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if any evaluated corner fails
+/// [`OptionParams::validate`], e.g. a volatility interval reaching below zero.
+pub fn price_range(
+    params: &UncertainParams,
+    option_type: OptionType,
+) -> Result<PricingRange, PricingError> {
+    let mid = BlackScholes::price(&params.mid(), option_type)?;
+    let mut range = PricingRange {
+        price: Interval::point(mid.price),
+        delta: Interval::point(mid.delta),
+        gamma: Interval::point(mid.gamma),
+        theta: Interval::point(mid.theta),
+        vega: Interval::point(mid.vega),
+        rho: Interval::point(mid.rho),
+        mid,
+    };
+
+    for &spot_price in &params.spot_price.samples() {
+        for &risk_free_rate in &params.risk_free_rate.samples() {
+            for &volatility in &params.volatility.samples() {
+                for &dividend_yield in &params.dividend_yield.samples() {
+                    let result = BlackScholes::price(
+                        &OptionParams {
+                            spot_price,
+                            strike_price: params.strike_price,
+                            time_to_expiry: params.time_to_expiry,
+                            risk_free_rate,
+                            volatility,
+                            dividend_yield,
+                        },
+                        option_type,
+                    )?;
+                    range.price.widen(result.price);
+                    range.delta.widen(result.delta);
+                    range.gamma.widen(result.gamma);
+                    range.theta.widen(result.theta);
+                    range.vega.widen(result.vega);
+                    range.rho.widen(result.rho);
+                }
+            }
+        }
+    }
+
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(volatility: Interval) -> UncertainParams {
+        UncertainParams {
+            spot_price: Interval::point(100.0),
+            strike_price: 100.0,
+            time_to_expiry: 1.0,
+            risk_free_rate: Interval::point(0.05),
+            volatility,
+            dividend_yield: Interval::point(0.0),
+        }
+    }
+
+    fn price_at(volatility: f64) -> f64 {
+        BlackScholes::price(&params(Interval::point(volatility)).mid(), OptionType::Call)
+            .unwrap()
+            .price
+    }
+
+    #[test]
+    fn test_point_inputs_give_point_ranges() {
+        let range = price_range(&params(Interval::point(0.2)), OptionType::Call).unwrap();
+
+        assert_eq!(range.price.width(), 0.0);
+        assert_eq!(range.vega.width(), 0.0);
+        assert_eq!(range.price.low, range.mid.price);
+    }
+
+    #[test]
+    fn test_volatility_spread_bounds_price() {
+        let range = price_range(
+            &params(Interval::new(0.18, 0.22).unwrap()),
+            OptionType::Call,
+        )
+        .unwrap();
+
+        // Price is increasing in volatility, so the bounds are the bid/ask IV prices
+        assert!((range.price.low - price_at(0.18)).abs() < 1e-12);
+        assert!((range.price.high - price_at(0.22)).abs() < 1e-12);
+        assert!(range.delta.width() > 0.0);
+    }
+
+    #[test]
+    fn test_spot_uncertainty_moves_delta_range() {
+        let mut uncertain = params(Interval::point(0.2));
+        uncertain.spot_price = Interval::around(100.0, 5.0).unwrap();
+        let range = price_range(&uncertain, OptionType::Put).unwrap();
+
+        assert!(range.delta.high < 0.0);
+        assert!(range.delta.contains(range.mid.delta));
+        // A ±5 spot move is roughly ±5 × |delta| in price
+        let approx = 10.0 * range.mid.delta.abs();
+        assert!((range.price.width() - approx).abs() < 0.1 * approx);
+    }
+
+    #[test]
+    fn test_invalid_intervals() {
+        assert!(Interval::new(0.3, 0.2).is_err());
+        assert!(Interval::new(f64::NAN, 0.2).is_err());
+        let negative_vol = params(Interval::new(-0.05, 0.1).unwrap());
+        assert!(price_range(&negative_vol, OptionType::Call).is_err());
+    }
+}
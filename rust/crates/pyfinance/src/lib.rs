@@ -35,8 +35,8 @@
 //! print(f"EMA values: {result}")
 //! ```
 
-use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 /// Python wrapper for option pricing
@@ -61,6 +61,7 @@ use pyo3::types::PyDict;
 /// - `vega`: Vega Greek
 /// - `rho`: Rho Greek
 #[pyfunction]
+#[allow(clippy::too_many_arguments)]
 #[pyo3(signature = (spot_price, strike_price, time_to_expiry, risk_free_rate, volatility, dividend_yield, option_type))]
 fn price_option(
     py: Python,
@@ -94,7 +95,7 @@ fn price_option(
         .map_err(|e| PyValueError::new_err(format!("Pricing error: {}", e)))?;
 
     // Convert to Python dictionary
-    let dict = PyDict::new_bound(py);
+    let dict = PyDict::new(py);
     dict.set_item("price", result.price)?;
     dict.set_item("delta", result.delta)?;
     dict.set_item("gamma", result.gamma)?;
@@ -102,7 +103,7 @@ fn price_option(
     dict.set_item("vega", result.vega)?;
     dict.set_item("rho", result.rho)?;
 
-    Ok(dict.into())
+    Ok(dict.into_any().unbind())
 }
 
 /// Python wrapper for EMA (Exponential Moving Average) indicator
@@ -180,6 +181,28 @@ impl EMA {
         self.inner.update(current_ema, new_price)
     }
 
+    /// Add a new price to the internal EMA state (streaming mode)
+    ///
+    /// Returns None until `period` prices have been seen, then the same
+    /// values as `calculate` on the full series.
+    ///
+    /// # Example
+    ///
+    /// ```python
+    /// ema = pyfinance.EMA(period=3)
+    /// ema.next(10.0)  # None
+    /// ema.next(11.0)  # None
+    /// ema.next(12.0)  # 11.0
+    /// ```
+    fn next(&mut self, price: f64) -> Option<f64> {
+        self.inner.next(price)
+    }
+
+    /// Clear the internal EMA state
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+
     /// Get the period used for EMA calculation
     #[pyo3(name = "period")]
     fn get_period(&self) -> usize {
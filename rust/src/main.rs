@@ -1,3 +1,87 @@
-fn main() {
-    println!("Hello, world!");
+//! Command-line entry point for the pricing and risk engines
+//!
+//! ```text
+//! rust scenario <file.json>
+//! ```
+//!
+//! `scenario` reads a [`Scenario`] in the JSON format described in
+//! [`pricing::scenario`], runs it and prints the [`ScenarioReport`] as JSON.
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+use pricing::scenario::{self, Scenario, ScenarioReport};
+
+const USAGE: &str = "usage: rust scenario <file.json>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = match args.as_slice() {
+        [command, path] if command == "scenario" => path,
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run_scenario_file(path) {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads, runs and serialises the scenario at `path`
+fn run_scenario_file(path: &str) -> Result<String, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
+    let report = run_scenario_json(&contents).map_err(|e| format!("{path}: {e}"))?;
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+fn run_scenario_json(json: &str) -> Result<ScenarioReport, String> {
+    let scenario: Scenario =
+        serde_json::from_str(json).map_err(|e| format!("invalid scenario: {e}"))?;
+    scenario::run(&scenario).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_scenario_json() {
+        let json = r#"{
+            "name": "desk-eod",
+            "holdings": [
+                { "underlying": "AAPL", "quantity": 10, "multiplier": 100,
+                  "kind": { "Option": { "option_type": "Call", "strike_price": 200, "time_to_expiry": 0.25 } } }
+            ],
+            "market": { "AAPL": { "spot_price": 195, "volatility": 0.28, "risk_free_rate": 0.04 } },
+            "shocks": [ { "name": "crash", "spot_change": -0.2, "volatility_shift": 0.15 } ]
+        }"#;
+        let report = run_scenario_json(json).unwrap();
+        assert_eq!(report.name, "desk-eod");
+        assert_eq!(report.shocks[0].name, "crash");
+        assert!(report.shocks[0].pnl < 0.0);
+    }
+
+    #[test]
+    fn test_reports_bad_input() {
+        assert!(run_scenario_json("{")
+            .unwrap_err()
+            .starts_with("invalid scenario"));
+
+        let missing_market = r#"{ "name": "x", "holdings": [
+            { "underlying": "MSFT", "quantity": 1, "kind": "Stock" } ], "market": {} }"#;
+        assert!(run_scenario_json(missing_market).is_err());
+        assert!(run_scenario_file("/nonexistent/scenario.json")
+            .unwrap_err()
+            .starts_with("cannot read"));
+    }
 }